@@ -8,7 +8,10 @@ use std::process::Command;
 
 use crate::discovery::MarketplaceConfig;
 use crate::error::{SoukError, ValidationDiagnostic, ValidationResult};
-use crate::validation::{validate_marketplace, validate_plugin};
+use crate::types::Marketplace;
+use crate::validation::{
+    validate_marketplace, validate_marketplace_with_jobs, validate_plugin_with,
+};
 
 /// Detect which plugins have changes staged for commit.
 ///
@@ -87,12 +90,66 @@ pub fn is_marketplace_staged(config: &MarketplaceConfig) -> Result<bool, SoukErr
     Ok(stdout.lines().any(|line| line.contains("marketplace.json")))
 }
 
+/// Read the marketplace version recorded at `revision` (e.g. `HEAD`, or an
+/// empty string for the index).
+///
+/// Returns `None` if git cannot show the file at that revision, such as in
+/// a repository with no commits yet.
+fn marketplace_version_at(config: &MarketplaceConfig, revision: &str) -> Option<String> {
+    let rel = config
+        .marketplace_path
+        .strip_prefix(&config.project_root)
+        .ok()?;
+    let spec = format!("{revision}:./{}", rel.to_string_lossy().replace('\\', "/"));
+    let output = Command::new("git")
+        .args(["show", &spec])
+        .current_dir(&config.project_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let marketplace: Marketplace = serde_json::from_slice(&output.stdout).ok()?;
+    Some(marketplace.version)
+}
+
+/// Warn when plugins have staged changes but the marketplace version that
+/// will be committed is the same as at `HEAD`.
+///
+/// Returns `None` when there is nothing to report, including when `HEAD`
+/// does not exist yet.
+pub fn check_version_bumped(
+    config: &MarketplaceConfig,
+    changed_plugins: &[String],
+) -> Option<ValidationDiagnostic> {
+    if changed_plugins.is_empty() {
+        return None;
+    }
+    let head = marketplace_version_at(config, "HEAD")?;
+    let staged =
+        marketplace_version_at(config, "").unwrap_or_else(|| config.marketplace.version.clone());
+    if head != staged {
+        return None;
+    }
+    Some(
+        ValidationDiagnostic::warning(format!(
+            "Plugins changed ({}) but marketplace version not bumped (still {head}); \
+             run `souk update <plugin>` to bump it",
+            changed_plugins.join(", ")
+        ))
+        .with_path(&config.marketplace_path)
+        .with_field("version"),
+    )
+}
+
 /// Run pre-commit validation.
 ///
 /// This validates only the plugins that have staged changes (detected via
 /// `git diff --cached`). If `marketplace.json` itself is staged, the
 /// marketplace structure is also validated (skipping individual plugin
-/// validation to avoid redundancy).
+/// validation to avoid redundancy). Unless disabled with
+/// `check_version_bump = false` in `souk.toml`, a warning is added when
+/// plugins changed but the marketplace version did not.
 ///
 /// Returns a [`ValidationResult`] that the caller can inspect to decide
 /// whether to allow or block the commit.
@@ -114,7 +171,8 @@ pub fn run_pre_commit(config: &MarketplaceConfig) -> ValidationResult {
     for name in &changed {
         let plugin_path = config.plugin_root_abs.join(name);
         if plugin_path.is_dir() {
-            let plugin_result = validate_plugin(&plugin_path);
+            let mut plugin_result = validate_plugin_with(&plugin_path, &config.settings);
+            plugin_result.tag_plugin(name);
             result.merge(plugin_result);
         }
     }
@@ -125,6 +183,12 @@ pub fn run_pre_commit(config: &MarketplaceConfig) -> ValidationResult {
         result.merge(mp_result);
     }
 
+    if config.settings.check_version_bump {
+        if let Some(warning) = check_version_bumped(config, &changed) {
+            result.push(warning);
+        }
+    }
+
     result
 }
 
@@ -133,8 +197,16 @@ pub fn run_pre_commit(config: &MarketplaceConfig) -> ValidationResult {
 /// This performs a full marketplace validation including all plugins,
 /// equivalent to `souk validate marketplace`. Use this in a `pre-push`
 /// git hook to ensure only valid marketplaces are pushed to remote.
+///
+/// Plugins are validated in parallel on [`pre_push_jobs`] threads; the
+/// result is identical to a serial run.
 pub fn run_pre_push(config: &MarketplaceConfig) -> ValidationResult {
-    validate_marketplace(config, false)
+    validate_marketplace_with_jobs(config, false, pre_push_jobs())
+}
+
+/// Number of threads pre-push validation uses: one per available core.
+pub fn pre_push_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
 }
 
 #[cfg(test)]
@@ -216,6 +288,38 @@ mod tests {
         assert!(result.has_errors());
     }
 
+    #[test]
+    fn pre_push_parallel_result_matches_serial() {
+        let names: Vec<String> = (0..16).map(|i| format!("plugin-{i:02}")).collect();
+        let dirs: Vec<&str> = names.iter().map(String::as_str).collect();
+        let entries: Vec<(&str, &str)> = dirs.iter().map(|n| (*n, *n)).collect();
+        let tmp = TempDir::new().unwrap();
+        let config = setup_git_marketplace(&tmp, &dirs, &entries);
+
+        for broken in ["plugin-02", "plugin-09", "plugin-15"] {
+            let plugin_json = tmp
+                .path()
+                .join("plugins")
+                .join(broken)
+                .join(".claude-plugin/plugin.json");
+            std::fs::write(&plugin_json, "not valid json").unwrap();
+        }
+
+        let result = run_pre_push(&config);
+        let tagged: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter_map(|d| d.plugin.as_deref())
+            .collect();
+        assert_eq!(tagged, vec!["plugin-02", "plugin-09", "plugin-15"]);
+
+        let serial = validate_marketplace(&config, false);
+        let messages = |r: &ValidationResult| -> Vec<String> {
+            r.diagnostics.iter().map(|d| d.message.clone()).collect()
+        };
+        assert_eq!(messages(&result), messages(&serial));
+    }
+
     #[test]
     fn pre_commit_returns_empty_when_no_staged_changes() {
         let tmp = TempDir::new().unwrap();
@@ -374,4 +478,89 @@ mod tests {
             result.diagnostics
         );
     }
+
+    /// Commit everything currently in the work tree.
+    fn commit_all(dir: &std::path::Path) {
+        for args in [
+            &["add", "-A"][..],
+            &[
+                "-c",
+                "user.name=souk",
+                "-c",
+                "user.email=souk@example.com",
+                "commit",
+                "-qm",
+                "init",
+            ][..],
+        ] {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .status()
+                .expect("git failed");
+            assert!(status.success());
+        }
+    }
+
+    fn stage_plugin_edit(dir: &std::path::Path) {
+        std::fs::write(dir.join("plugins/alpha/README.md"), "# Alpha\n").unwrap();
+        Command::new("git")
+            .args(["add", "plugins/alpha/README.md"])
+            .current_dir(dir)
+            .output()
+            .expect("git add failed");
+    }
+
+    #[test]
+    fn pre_commit_warns_when_marketplace_version_not_bumped() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_git_marketplace(&tmp, &["alpha"], &[("alpha", "alpha")]);
+        commit_all(tmp.path());
+        stage_plugin_edit(tmp.path());
+
+        let result = run_pre_commit(&config);
+        assert!(!result.has_errors(), "{:?}", result.diagnostics);
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .any(|d| d.message.contains("marketplace version not bumped")),
+            "{:?}",
+            result.diagnostics
+        );
+    }
+
+    #[test]
+    fn pre_commit_no_warning_when_marketplace_version_bumped() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_git_marketplace(&tmp, &["alpha"], &[("alpha", "alpha")]);
+        commit_all(tmp.path());
+        stage_plugin_edit(tmp.path());
+
+        let mp = tmp.path().join(".claude-plugin/marketplace.json");
+        let bumped = std::fs::read_to_string(&mp)
+            .unwrap()
+            .replace("0.1.0", "0.1.1");
+        std::fs::write(&mp, bumped).unwrap();
+        Command::new("git")
+            .args(["add", ".claude-plugin/marketplace.json"])
+            .current_dir(tmp.path())
+            .output()
+            .expect("git add failed");
+
+        let result = run_pre_commit(&config);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    }
+
+    #[test]
+    fn version_bump_check_can_be_disabled() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = setup_git_marketplace(&tmp, &["alpha"], &[("alpha", "alpha")]);
+        commit_all(tmp.path());
+        stage_plugin_edit(tmp.path());
+        config.settings.check_version_bump = false;
+
+        let result = run_pre_commit(&config);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    }
 }
@@ -27,6 +27,16 @@ pub enum HookManager {
 }
 
 impl HookManager {
+    /// Every supported hook manager.
+    pub const ALL: [HookManager; 6] = [
+        HookManager::Native,
+        HookManager::Lefthook,
+        HookManager::Husky,
+        HookManager::Overcommit,
+        HookManager::Hk,
+        HookManager::SimpleGitHooks,
+    ];
+
     /// Returns the lowercase name of the hook manager.
     pub fn name(&self) -> &str {
         match self {
@@ -27,6 +27,16 @@ pub enum CiProvider {
 }
 
 impl CiProvider {
+    /// Every supported CI provider.
+    pub const ALL: [CiProvider; 6] = [
+        CiProvider::GitHub,
+        CiProvider::Blacksmith,
+        CiProvider::Northflank,
+        CiProvider::CircleCi,
+        CiProvider::GitLab,
+        CiProvider::Buildkite,
+    ];
+
     /// Returns the lowercase name of the CI provider.
     pub fn name(&self) -> &str {
         match self {
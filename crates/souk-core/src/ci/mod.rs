@@ -8,4 +8,7 @@ pub mod hooks;
 pub mod install_hooks;
 pub mod install_workflows;
 
-pub use hooks::{detect_changed_plugins, is_marketplace_staged, run_pre_commit, run_pre_push};
+pub use hooks::{
+    check_version_bumped, detect_changed_plugins, is_marketplace_staged, pre_push_jobs,
+    run_pre_commit, run_pre_push,
+};
@@ -0,0 +1,304 @@
+//! Project-level settings loaded from `souk.toml`.
+//!
+//! The file lives in the project root, next to `.claude-plugin/`. Every
+//! setting is optional; a missing file yields [`SoukConfig::default`].
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::SoukError;
+use crate::ops::add::CONFLICT_STRATEGIES;
+use crate::ops::ignore::DEFAULT_COPY_IGNORE;
+use crate::review::ReportFormat;
+use crate::validation::plugin::CompiledSchema;
+
+/// Name of the settings file in the project root.
+pub const CONFIG_FILE: &str = "souk.toml";
+
+/// plugin.json fields that are always required.
+pub const BUILTIN_REQUIRED_FIELDS: [&str; 3] = ["name", "version", "description"];
+
+/// Settings read from `souk.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SoukConfig {
+    /// Additional plugin.json fields that must be present and non-null.
+    ///
+    /// These augment [`BUILTIN_REQUIRED_FIELDS`]; listing a built-in field
+    /// here has no extra effect.
+    pub required_fields: Vec<String>,
+    /// Warn in pre-commit when plugins are staged but the marketplace
+    /// version is unchanged from `HEAD`. Enabled by default.
+    pub check_version_bump: bool,
+    /// Allow marketplace sources that are absolute paths or escape
+    /// pluginRoot. Enabled by default; disable for portable marketplaces.
+    pub allow_external_sources: bool,
+    /// JSON Schema that every plugin.json must also satisfy. Relative paths
+    /// are resolved against the project root.
+    pub plugin_schema: Option<PathBuf>,
+    /// Rubric file appended to every review prompt. Relative paths are
+    /// resolved against the project root.
+    pub review_rubric: Option<PathBuf>,
+    /// Default review provider, used when `--provider` is not given.
+    pub provider: Option<String>,
+    /// Default review model, used when neither `--model` nor a
+    /// `--model-map` entry applies.
+    pub model: Option<String>,
+    /// Default review report format, used when `--format` is not given.
+    pub report_format: Option<ReportFormat>,
+    /// Default `souk add` conflict strategy, used when `--on-conflict` is
+    /// not given. One of "abort", "skip", "replace", or "rename".
+    pub on_conflict: Option<String>,
+    /// Default number of skills, or plugins with `review marketplace
+    /// --recursive`, reviewed at once, used when `--jobs` is not given.
+    pub jobs: Option<u16>,
+    /// Follow symlinks in pluginRoot when looking for orphaned directories,
+    /// as with `--dereference`. Disabled by default, so symlinks are never
+    /// reported as orphans or pruned.
+    pub dereference: bool,
+    /// Abort a scan of pluginRoot after this many directory entries, as
+    /// with `--max-scan-entries`. Unlimited by default.
+    pub max_scan_entries: Option<usize>,
+    /// gitignore-style patterns left out when `souk add` copies an external
+    /// plugin, extended by the plugin's own `.soukignore`. Defaults to
+    /// [`DEFAULT_COPY_IGNORE`].
+    pub copy_ignore: Vec<String>,
+    /// File that mutating commands append a JSON line to once they finish;
+    /// see [`crate::ops::audit`]. Relative paths are resolved against the
+    /// project root. Disabled when unset.
+    pub audit_log: Option<PathBuf>,
+    /// Warn when marketplace.json or a plugin.json is not in souk's
+    /// canonical formatting, as with `--check-formatting`. Disabled by
+    /// default.
+    pub check_formatting: bool,
+    /// `plugin_schema`, compiled once per run.
+    #[serde(skip)]
+    pub(crate) compiled_schema: CompiledSchema,
+}
+
+impl Default for SoukConfig {
+    fn default() -> Self {
+        Self {
+            required_fields: Vec::new(),
+            check_version_bump: true,
+            allow_external_sources: true,
+            plugin_schema: None,
+            review_rubric: None,
+            provider: None,
+            model: None,
+            report_format: None,
+            on_conflict: None,
+            jobs: None,
+            dereference: false,
+            max_scan_entries: None,
+            copy_ignore: DEFAULT_COPY_IGNORE.map(String::from).to_vec(),
+            audit_log: None,
+            check_formatting: false,
+            compiled_schema: CompiledSchema::default(),
+        }
+    }
+}
+
+impl SoukConfig {
+    /// Load `souk.toml` from `project_root`, or return the defaults if the
+    /// file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoukError::Io`] if the file exists but cannot be read,
+    /// [`SoukError::Toml`] if it is not valid, or [`SoukError::Other`] if
+    /// `on_conflict` or `jobs` is out of range.
+    pub fn load(project_root: &Path) -> Result<Self, SoukError> {
+        let path = project_root.join(CONFIG_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let mut config: Self = toml::from_str(&content)?;
+        config.plugin_schema = config.plugin_schema.map(|p| project_root.join(p));
+        config.review_rubric = config.review_rubric.map(|p| project_root.join(p));
+        config.audit_log = config.audit_log.map(|p| project_root.join(p));
+        if let Some(strategy) = &config.on_conflict {
+            if !CONFLICT_STRATEGIES.contains(&strategy.as_str()) {
+                return Err(SoukError::Other(format!(
+                    "Invalid on_conflict in {CONFIG_FILE}: {strategy} (expected {})",
+                    CONFLICT_STRATEGIES.join(", ")
+                )));
+            }
+        }
+        if config.jobs == Some(0) {
+            return Err(SoukError::Other(format!(
+                "Invalid jobs in {CONFIG_FILE}: must be at least 1"
+            )));
+        }
+        Ok(config)
+    }
+
+    /// Configured required fields that are not already built in.
+    pub fn extra_required_fields(&self) -> impl Iterator<Item = &str> {
+        self.required_fields
+            .iter()
+            .map(String::as_str)
+            .filter(|f| !BUILTIN_REQUIRED_FIELDS.contains(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn missing_file_uses_defaults() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(SoukConfig::load(tmp.path()).unwrap(), SoukConfig::default());
+    }
+
+    #[test]
+    fn loads_required_fields() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join(CONFIG_FILE),
+            r#"required_fields = ["name", "version", "description", "license"]"#,
+        )
+        .unwrap();
+
+        let config = SoukConfig::load(tmp.path()).unwrap();
+        assert_eq!(config.required_fields.len(), 4);
+        assert_eq!(
+            config.extra_required_fields().collect::<Vec<_>>(),
+            vec!["license"]
+        );
+    }
+
+    #[test]
+    fn loads_allow_external_sources() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join(CONFIG_FILE),
+            "allow_external_sources = false",
+        )
+        .unwrap();
+
+        let config = SoukConfig::load(tmp.path()).unwrap();
+        assert!(!config.allow_external_sources);
+        assert!(config.check_version_bump);
+    }
+
+    #[test]
+    fn plugin_schema_is_relative_to_project_root() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join(CONFIG_FILE),
+            r#"plugin_schema = "schemas/plugin.json""#,
+        )
+        .unwrap();
+
+        let config = SoukConfig::load(tmp.path()).unwrap();
+        assert_eq!(
+            config.plugin_schema,
+            Some(tmp.path().join("schemas").join("plugin.json"))
+        );
+    }
+
+    #[test]
+    fn review_rubric_is_relative_to_project_root() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join(CONFIG_FILE),
+            r#"review_rubric = "RUBRIC.md""#,
+        )
+        .unwrap();
+
+        let config = SoukConfig::load(tmp.path()).unwrap();
+        assert_eq!(config.review_rubric, Some(tmp.path().join("RUBRIC.md")));
+    }
+
+    #[test]
+    fn audit_log_is_relative_to_project_root() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join(CONFIG_FILE),
+            r#"audit_log = ".souk/audit.log""#,
+        )
+        .unwrap();
+
+        let config = SoukConfig::load(tmp.path()).unwrap();
+        assert_eq!(
+            config.audit_log,
+            Some(tmp.path().join(".souk").join("audit.log"))
+        );
+        assert_eq!(SoukConfig::default().audit_log, None);
+    }
+
+    #[test]
+    fn loads_command_defaults() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join(CONFIG_FILE),
+            r#"
+provider = "anthropic"
+model = "claude-opus-4"
+report_format = "json"
+on_conflict = "rename"
+jobs = 8
+copy_ignore = ["dist/"]
+"#,
+        )
+        .unwrap();
+
+        let config = SoukConfig::load(tmp.path()).unwrap();
+        assert_eq!(config.provider.as_deref(), Some("anthropic"));
+        assert_eq!(config.model.as_deref(), Some("claude-opus-4"));
+        assert_eq!(config.report_format, Some(ReportFormat::Json));
+        assert_eq!(config.on_conflict.as_deref(), Some("rename"));
+        assert_eq!(config.jobs, Some(8));
+        assert_eq!(config.copy_ignore, ["dist/"]);
+    }
+
+    #[test]
+    fn partial_file_leaves_other_defaults_unset() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join(CONFIG_FILE), r#"model = "gpt-4o""#).unwrap();
+
+        let config = SoukConfig::load(tmp.path()).unwrap();
+        assert_eq!(
+            config,
+            SoukConfig {
+                model: Some("gpt-4o".to_string()),
+                ..SoukConfig::default()
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_command_defaults_are_errors() {
+        let tmp = TempDir::new().unwrap();
+        for content in [
+            r#"on_conflict = "merge""#,
+            r#"report_format = "yaml""#,
+            "jobs = 0",
+        ] {
+            std::fs::write(tmp.path().join(CONFIG_FILE), content).unwrap();
+            assert!(SoukConfig::load(tmp.path()).is_err(), "{content}");
+        }
+    }
+
+    #[test]
+    fn invalid_toml_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join(CONFIG_FILE), "required_fields = [").unwrap();
+        assert!(matches!(
+            SoukConfig::load(tmp.path()),
+            Err(SoukError::Toml(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_key_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join(CONFIG_FILE), "requried_fields = []").unwrap();
+        assert!(SoukConfig::load(tmp.path()).is_err());
+    }
+}
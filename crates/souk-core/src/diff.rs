@@ -0,0 +1,186 @@
+//! Marketplace drift report.
+//!
+//! Compares marketplace.json against the plugin directories on disk and the
+//! plugin.json manifests inside them, producing a [`MarketplaceDiff`] that
+//! CI can fail on when any category is non-empty.
+
+use serde::Serialize;
+
+use crate::discovery::MarketplaceConfig;
+use crate::error::SoukError;
+use crate::resolution::resolve_source;
+use crate::types::plugin::read_manifest_text;
+use crate::types::PluginManifest;
+use crate::validation::find_orphaned_dirs;
+
+/// Differences between marketplace.json and the filesystem.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MarketplaceDiff {
+    /// Directory names under pluginRoot with no marketplace entry.
+    pub orphaned_dirs: Vec<String>,
+    /// Names of marketplace entries whose source directory does not exist.
+    pub missing_dirs: Vec<String>,
+    /// Entries whose name differs from their plugin.json `name`.
+    pub name_drift: Vec<NameDrift>,
+    /// Entries whose tags differ from their plugin.json `keywords`.
+    pub tag_drift: Vec<TagDrift>,
+}
+
+impl MarketplaceDiff {
+    /// Returns `true` if no category has any differences.
+    pub fn is_empty(&self) -> bool {
+        self.orphaned_dirs.is_empty()
+            && self.missing_dirs.is_empty()
+            && self.name_drift.is_empty()
+            && self.tag_drift.is_empty()
+    }
+}
+
+/// A marketplace entry whose name disagrees with its manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NameDrift {
+    /// Name recorded in marketplace.json.
+    pub entry: String,
+    /// Name declared in plugin.json.
+    pub manifest: String,
+}
+
+/// A marketplace entry whose tags disagree with its manifest keywords.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagDrift {
+    /// Name recorded in marketplace.json.
+    pub name: String,
+    /// Tags recorded in marketplace.json.
+    pub entry: Vec<String>,
+    /// Keywords declared in plugin.json.
+    pub manifest: Vec<String>,
+}
+
+/// Compute the drift between marketplace.json and the plugins on disk.
+///
+/// Entries whose plugin.json is missing or unparseable are skipped for the
+/// name and tag checks; a missing directory is reported in `missing_dirs`.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Io`] if the plugin root cannot be read.
+pub fn diff_marketplace(config: &MarketplaceConfig) -> Result<MarketplaceDiff, SoukError> {
+    let mut diff = MarketplaceDiff::default();
+
+    let mut orphaned_dirs: Vec<String> = find_orphaned_dirs(config)?
+        .iter()
+        .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect();
+    orphaned_dirs.sort();
+    diff.orphaned_dirs = orphaned_dirs;
+
+    for entry in &config.marketplace.plugins {
+        let plugin_path = resolve_source(&entry.source, config)?;
+        if !plugin_path.is_dir() {
+            diff.missing_dirs.push(entry.name.clone());
+            continue;
+        }
+
+        let manifest: Option<PluginManifest> =
+            read_manifest_text(&plugin_path.join(".claude-plugin").join("plugin.json"))
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok());
+        let Some(manifest) = manifest else {
+            continue;
+        };
+
+        if let Some(name) = manifest.name_str() {
+            if name != entry.name {
+                diff.name_drift.push(NameDrift {
+                    entry: entry.name.clone(),
+                    manifest: name.to_string(),
+                });
+            }
+        }
+
+        if manifest.keywords != entry.tags {
+            diff.tag_drift.push(TagDrift {
+                name: entry.name.clone(),
+                entry: entry.tags.clone(),
+                manifest: manifest.keywords,
+            });
+        }
+    }
+
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::load_marketplace_config;
+    use tempfile::TempDir;
+
+    fn write_plugin(tmp: &TempDir, dir: &str, manifest: &str) {
+        let p = tmp.path().join("plugins").join(dir).join(".claude-plugin");
+        std::fs::create_dir_all(&p).unwrap();
+        std::fs::write(p.join("plugin.json"), manifest).unwrap();
+    }
+
+    fn load(tmp: &TempDir, plugins_json: &str) -> MarketplaceConfig {
+        let claude = tmp.path().join(".claude-plugin");
+        std::fs::create_dir_all(&claude).unwrap();
+        std::fs::create_dir_all(tmp.path().join("plugins")).unwrap();
+        std::fs::write(
+            claude.join("marketplace.json"),
+            format!(r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{plugins_json}]}}"#),
+        )
+        .unwrap();
+        load_marketplace_config(&claude.join("marketplace.json")).unwrap()
+    }
+
+    #[test]
+    fn reports_each_category() {
+        let tmp = TempDir::new().unwrap();
+        write_plugin(&tmp, "ok", r#"{"name":"ok","keywords":["a"]}"#);
+        write_plugin(&tmp, "renamed", r#"{"name":"new-name"}"#);
+        write_plugin(
+            &tmp,
+            "retagged",
+            r#"{"name":"retagged","keywords":["x","y"]}"#,
+        );
+        write_plugin(&tmp, "stray", r#"{"name":"stray"}"#);
+        let config = load(
+            &tmp,
+            r#"{"name":"ok","source":"ok","tags":["a"]},
+               {"name":"renamed","source":"renamed"},
+               {"name":"retagged","source":"retagged","tags":["x"]},
+               {"name":"ghost","source":"ghost"}"#,
+        );
+
+        let diff = diff_marketplace(&config).unwrap();
+
+        assert_eq!(diff.orphaned_dirs, vec!["stray"]);
+        assert_eq!(diff.missing_dirs, vec!["ghost"]);
+        assert_eq!(
+            diff.name_drift,
+            vec![NameDrift {
+                entry: "renamed".into(),
+                manifest: "new-name".into(),
+            }]
+        );
+        assert_eq!(
+            diff.tag_drift,
+            vec![TagDrift {
+                name: "retagged".into(),
+                entry: vec!["x".into()],
+                manifest: vec!["x".into(), "y".into()],
+            }]
+        );
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn in_sync_marketplace_is_empty() {
+        let tmp = TempDir::new().unwrap();
+        write_plugin(&tmp, "ok", r#"{"name":"ok"}"#);
+        let config = load(&tmp, r#"{"name":"ok","source":"ok"}"#);
+
+        assert!(diff_marketplace(&config).unwrap().is_empty());
+    }
+}
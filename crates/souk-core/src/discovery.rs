@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use crate::config::SoukConfig;
 use crate::error::SoukError;
 use crate::types::marketplace::Marketplace;
 
@@ -9,6 +10,20 @@ pub struct MarketplaceConfig {
     pub project_root: PathBuf,
     pub plugin_root_abs: PathBuf,
     pub marketplace: Marketplace,
+    /// Project settings from `souk.toml` (defaults if absent).
+    pub settings: SoukConfig,
+}
+
+impl MarketplaceConfig {
+    /// Render `path` for display, relative to `project_root` when it lies
+    /// inside the project and as an absolute path otherwise.
+    pub fn display_path(&self, path: &Path) -> String {
+        match path.strip_prefix(&self.project_root) {
+            Ok(rel) if rel.as_os_str().is_empty() => ".".to_string(),
+            Ok(rel) => rel.display().to_string(),
+            Err(_) => path.display().to_string(),
+        }
+    }
 }
 
 pub fn discover_marketplace(start_dir: &Path) -> Result<PathBuf, SoukError> {
@@ -35,6 +50,39 @@ pub fn discover_marketplace(start_dir: &Path) -> Result<PathBuf, SoukError> {
     Err(SoukError::MarketplaceNotFound(start_dir.to_path_buf()))
 }
 
+/// Directories never searched by [`discover_all_marketplaces`].
+const SKIPPED_DIRS: [&str; 3] = [".git", "node_modules", "target"];
+
+/// Find every `.claude-plugin/marketplace.json` under `root`, for monorepos
+/// with one marketplace per package.
+///
+/// Skips `.git`, `node_modules`, and `target`, does not follow symlinks,
+/// and ignores directories it cannot read. Paths are canonical and sorted.
+pub fn discover_all_marketplaces(root: &Path) -> Result<Vec<PathBuf>, SoukError> {
+    let root = root.canonicalize().map_err(SoukError::Io)?;
+    let mut found = Vec::new();
+    collect_marketplaces(&root, &mut found);
+    found.sort();
+    Ok(found)
+}
+
+fn collect_marketplaces(dir: &Path, found: &mut Vec<PathBuf>) {
+    let candidate = dir.join(".claude-plugin").join("marketplace.json");
+    if candidate.is_file() {
+        found.push(candidate);
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        let name = entry.file_name();
+        if is_dir && !SKIPPED_DIRS.iter().any(|skip| name == *skip) {
+            collect_marketplaces(&entry.path(), found);
+        }
+    }
+}
+
 pub fn load_marketplace_config(marketplace_path: &Path) -> Result<MarketplaceConfig, SoukError> {
     let marketplace_path = marketplace_path.canonicalize().map_err(SoukError::Io)?;
 
@@ -60,11 +108,14 @@ pub fn load_marketplace_config(marketplace_path: &Path) -> Result<MarketplaceCon
             ))
         })?;
 
+    let settings = SoukConfig::load(&project_root)?;
+
     Ok(MarketplaceConfig {
         marketplace_path,
         project_root,
         plugin_root_abs,
         marketplace,
+        settings,
     })
 }
 
@@ -115,6 +166,40 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn discover_all_finds_sibling_marketplaces() {
+        let tmp = TempDir::new().unwrap();
+        for package in ["packages/a", "packages/b"] {
+            let dir = tmp.path().join(package).join(".claude-plugin");
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("marketplace.json"), "{}").unwrap();
+        }
+
+        let found = discover_all_marketplaces(tmp.path()).unwrap();
+        let root = tmp.path().canonicalize().unwrap();
+        assert_eq!(
+            found,
+            vec![
+                root.join("packages/a/.claude-plugin/marketplace.json"),
+                root.join("packages/b/.claude-plugin/marketplace.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn discover_all_skips_git_and_node_modules() {
+        let tmp = TempDir::new().unwrap();
+        let mp_path = setup_marketplace(&tmp);
+        for skipped in [".git/vendored", "node_modules/some-package"] {
+            let dir = tmp.path().join(skipped).join(".claude-plugin");
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("marketplace.json"), "{}").unwrap();
+        }
+
+        let found = discover_all_marketplaces(tmp.path()).unwrap();
+        assert_eq!(found, vec![mp_path.canonicalize().unwrap()]);
+    }
+
     #[test]
     fn load_marketplace_config_resolves_paths() {
         let tmp = TempDir::new().unwrap();
@@ -137,4 +222,41 @@ mod tests {
         let config = load_marketplace_config(&mp_path).unwrap();
         assert!(config.plugin_root_abs.ends_with("plugins"));
     }
+
+    #[test]
+    fn display_path_inside_project_is_relative() {
+        let tmp = TempDir::new().unwrap();
+        let config = load_marketplace_config(&setup_marketplace(&tmp)).unwrap();
+
+        let inside = config.plugin_root_abs.join("my-plugin");
+        assert_eq!(
+            config.display_path(&inside),
+            Path::new("plugins").join("my-plugin").display().to_string()
+        );
+        assert_eq!(config.display_path(&config.project_root), ".");
+    }
+
+    #[test]
+    fn display_path_outside_project_is_absolute() {
+        let tmp = TempDir::new().unwrap();
+        let config = load_marketplace_config(&setup_marketplace(&tmp)).unwrap();
+
+        let other = TempDir::new().unwrap();
+        let outside = other.path().join("elsewhere");
+        assert_eq!(config.display_path(&outside), outside.display().to_string());
+    }
+
+    #[test]
+    fn load_marketplace_config_reads_souk_toml() {
+        let tmp = TempDir::new().unwrap();
+        let mp_path = setup_marketplace(&tmp);
+        std::fs::write(
+            tmp.path().join("souk.toml"),
+            r#"required_fields = ["license"]"#,
+        )
+        .unwrap();
+
+        let config = load_marketplace_config(&mp_path).unwrap();
+        assert_eq!(config.settings.required_fields, vec!["license"]);
+    }
 }
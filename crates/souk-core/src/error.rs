@@ -1,20 +1,42 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
 use thiserror::Error;
 
 /// Severity of a validation diagnostic.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
     Warning,
 }
 
 /// A single validation finding.
-#[derive(Debug, Clone)]
+///
+/// Serializes with a stable layout for `--json` consumers: `severity`,
+/// `message`, `path` (a string), and `field` are always present, with
+/// `null` for a missing path or field; `plugin` is added when known.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValidationDiagnostic {
     pub severity: Severity,
     pub message: String,
+    #[serde(serialize_with = "serialize_path")]
     pub path: Option<PathBuf>,
     pub field: Option<String>,
+    /// Marketplace name of the plugin this finding belongs to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+}
+
+/// Serialize a path as a string, replacing invalid UTF-8 rather than
+/// failing.
+fn serialize_path<S: Serializer>(path: &Option<PathBuf>, serializer: S) -> Result<S::Ok, S::Error> {
+    match path.as_deref().map(Path::to_string_lossy) {
+        Some(path) => serializer.serialize_some(&path),
+        None => serializer.serialize_none(),
+    }
 }
 
 impl ValidationDiagnostic {
@@ -24,6 +46,7 @@ impl ValidationDiagnostic {
             message: message.into(),
             path: None,
             field: None,
+            plugin: None,
         }
     }
 
@@ -33,6 +56,7 @@ impl ValidationDiagnostic {
             message: message.into(),
             path: None,
             field: None,
+            plugin: None,
         }
     }
 
@@ -46,17 +70,37 @@ impl ValidationDiagnostic {
         self
     }
 
+    pub fn with_plugin(mut self, plugin: impl Into<String>) -> Self {
+        self.plugin = Some(plugin.into());
+        self
+    }
+
     pub fn is_error(&self) -> bool {
         self.severity == Severity::Error
     }
 }
 
 /// The result of validating a plugin or marketplace.
-#[derive(Debug)]
+///
+/// Serializes as `{"ok", "error_count", "warning_count", "diagnostics"}`,
+/// where `ok` means there are no errors. Deserializing reads
+/// `diagnostics` and recomputes the rest.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
 pub struct ValidationResult {
     pub diagnostics: Vec<ValidationDiagnostic>,
 }
 
+impl Serialize for ValidationResult {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ValidationResult", 4)?;
+        state.serialize_field("ok", &!self.has_errors())?;
+        state.serialize_field("error_count", &self.error_count())?;
+        state.serialize_field("warning_count", &self.warning_count())?;
+        state.serialize_field("diagnostics", &self.diagnostics)?;
+        state.end()
+    }
+}
+
 impl ValidationResult {
     pub fn new() -> Self {
         Self {
@@ -83,9 +127,119 @@ impl ValidationResult {
             .count()
     }
 
+    /// Decide how a command reporting this result should exit.
+    ///
+    /// Errors always fail. Warnings fail only when `strict` is set.
+    pub fn outcome(&self, strict: bool) -> CommandOutcome {
+        if self.has_errors() || (strict && self.warning_count() > 0) {
+            CommandOutcome::Failed
+        } else if self.warning_count() > 0 {
+            CommandOutcome::Warnings
+        } else {
+            CommandOutcome::Clean
+        }
+    }
+
     pub fn merge(&mut self, other: ValidationResult) {
         self.diagnostics.extend(other.diagnostics);
     }
+
+    /// Attribute every diagnostic that has no plugin yet to `plugin`.
+    pub fn tag_plugin(&mut self, plugin: &str) {
+        for d in &mut self.diagnostics {
+            if d.plugin.is_none() {
+                d.plugin = Some(plugin.to_string());
+            }
+        }
+    }
+
+    /// Split diagnostics into marketplace-level findings and per-plugin
+    /// findings keyed by the diagnostic's `plugin` field.
+    pub fn group_by_plugin(&self) -> GroupedDiagnostics<'_> {
+        let mut grouped = GroupedDiagnostics {
+            marketplace: Vec::new(),
+            plugins: BTreeMap::new(),
+        };
+        for d in &self.diagnostics {
+            match &d.plugin {
+                Some(name) => grouped.plugins.entry(name.clone()).or_default().push(d),
+                None => grouped.marketplace.push(d),
+            }
+        }
+        grouped
+    }
+}
+
+/// How a validating command should finish, from [`ValidationResult::outcome`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// No diagnostics.
+    Clean,
+    /// Only warnings, and not running in strict mode.
+    Warnings,
+    /// Errors, or warnings in strict mode.
+    Failed,
+}
+
+impl CommandOutcome {
+    /// Whether the command should exit successfully.
+    pub fn is_success(self) -> bool {
+        self != CommandOutcome::Failed
+    }
+}
+
+/// Process exit status of a souk command, so scripts can tell invalid input
+/// apart from souk itself failing.
+///
+/// | Code | Status | Meaning |
+/// |------|--------|---------|
+/// | 0 | [`Success`](ExitStatus::Success) | The command succeeded; warnings are allowed unless `--strict` |
+/// | 1 | [`Findings`](ExitStatus::Findings) | Validation found problems, or a change was refused or rolled back because of them |
+/// | 2 | [`Usage`](ExitStatus::Usage) | Bad arguments or configuration, such as no marketplace found or an unknown plugin |
+/// | 3 | [`Internal`](ExitStatus::Internal) | souk could not finish: an I/O, network, or provider error, or a bug |
+///
+/// [`SoukError::exit_status`] maps each error to one of these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    Success,
+    Findings,
+    Usage,
+    Internal,
+}
+
+impl ExitStatus {
+    /// The process exit code.
+    pub fn code(self) -> i32 {
+        match self {
+            ExitStatus::Success => 0,
+            ExitStatus::Findings => 1,
+            ExitStatus::Usage => 2,
+            ExitStatus::Internal => 3,
+        }
+    }
+
+    pub fn is_success(self) -> bool {
+        self == ExitStatus::Success
+    }
+}
+
+impl From<CommandOutcome> for ExitStatus {
+    fn from(outcome: CommandOutcome) -> Self {
+        if outcome.is_success() {
+            ExitStatus::Success
+        } else {
+            ExitStatus::Findings
+        }
+    }
+}
+
+/// Diagnostics grouped by the plugin they belong to.
+///
+/// Serializes as `{ "marketplace": [...], "plugins": { "<name>": [...] } }`.
+#[derive(Debug, Serialize)]
+pub struct GroupedDiagnostics<'a> {
+    pub marketplace: Vec<&'a ValidationDiagnostic>,
+    pub plugins: BTreeMap<String, Vec<&'a ValidationDiagnostic>>,
 }
 
 impl Default for ValidationResult {
@@ -117,7 +271,14 @@ pub enum SoukError {
     #[error("Atomic operation failed, backup restored: {0}")]
     AtomicRollback(String),
 
-    #[error("No LLM API key found. Set one of: ANTHROPIC_API_KEY, OPENAI_API_KEY, GEMINI_API_KEY")]
+    #[error("Directory scan aborted: {0}")]
+    ScanAborted(String),
+
+    #[error(
+        "No LLM API key found. Set one of: ANTHROPIC_API_KEY, OPENAI_API_KEY, \
+         AZURE_OPENAI_API_KEY (with AZURE_OPENAI_ENDPOINT and AZURE_OPENAI_DEPLOYMENT), \
+         GEMINI_API_KEY, or OLLAMA_HOST"
+    )]
     NoApiKey,
 
     #[error("LLM API error: {0}")]
@@ -132,14 +293,107 @@ pub enum SoukError {
     #[error("Semver error: {0}")]
     Semver(#[from] semver::Error),
 
+    #[error("TOML error: {0}")]
+    Toml(#[from] toml::de::Error),
+
     #[error("{0}")]
     Other(String),
 }
 
+impl SoukError {
+    /// The exit status a command failing with this error should use.
+    ///
+    /// Missing or conflicting names and configuration are usage errors;
+    /// invalid marketplace or plugin content, including malformed JSON or
+    /// versions and a symlink loop or oversized tree under pluginRoot, is a
+    /// finding; I/O, provider, and other failures are
+    /// internal.
+    pub fn exit_status(&self) -> ExitStatus {
+        match self {
+            SoukError::PluginNotFound(_)
+            | SoukError::SkillNotFound { .. }
+            | SoukError::MarketplaceNotFound(_)
+            | SoukError::MarketplaceAlreadyExists(_)
+            | SoukError::PluginAlreadyExists(_)
+            | SoukError::NoApiKey
+            | SoukError::Toml(_) => ExitStatus::Usage,
+            SoukError::ValidationFailed(_)
+            | SoukError::AtomicRollback(_)
+            | SoukError::ScanAborted(_)
+            | SoukError::Json(_)
+            | SoukError::Semver(_) => ExitStatus::Findings,
+            SoukError::LlmApiError(_) | SoukError::Io(_) | SoukError::Other(_) => {
+                ExitStatus::Internal
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn exit_status_codes() {
+        let codes: Vec<i32> = [
+            ExitStatus::Success,
+            ExitStatus::Findings,
+            ExitStatus::Usage,
+            ExitStatus::Internal,
+        ]
+        .iter()
+        .map(|s| s.code())
+        .collect();
+        assert_eq!(codes, [0, 1, 2, 3]);
+        assert_eq!(
+            ExitStatus::from(CommandOutcome::Warnings),
+            ExitStatus::Success
+        );
+        assert_eq!(
+            ExitStatus::from(CommandOutcome::Failed),
+            ExitStatus::Findings
+        );
+    }
+
+    #[test]
+    fn errors_map_to_exit_status() {
+        assert_eq!(
+            SoukError::MarketplaceNotFound(PathBuf::from("/tmp")).exit_status(),
+            ExitStatus::Usage
+        );
+        assert_eq!(
+            SoukError::AtomicRollback("invalid".into()).exit_status(),
+            ExitStatus::Findings
+        );
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        assert_eq!(SoukError::Io(io).exit_status(), ExitStatus::Internal);
+    }
+
+    #[test]
+    fn outcome_clean() {
+        let result = ValidationResult::new();
+        assert_eq!(result.outcome(false), CommandOutcome::Clean);
+        assert_eq!(result.outcome(true), CommandOutcome::Clean);
+    }
+
+    #[test]
+    fn outcome_errors_only_fails_regardless_of_strict() {
+        let mut result = ValidationResult::new();
+        result.push(ValidationDiagnostic::error("bad"));
+        assert_eq!(result.outcome(false), CommandOutcome::Failed);
+        assert_eq!(result.outcome(true), CommandOutcome::Failed);
+    }
+
+    #[test]
+    fn outcome_warnings_only_fails_only_when_strict() {
+        let mut result = ValidationResult::new();
+        result.push(ValidationDiagnostic::warning("meh"));
+        assert_eq!(result.outcome(false), CommandOutcome::Warnings);
+        assert!(result.outcome(false).is_success());
+        assert_eq!(result.outcome(true), CommandOutcome::Failed);
+        assert!(!result.outcome(true).is_success());
+    }
+
     #[test]
     fn validation_result_tracks_errors_and_warnings() {
         let mut result = ValidationResult::new();
@@ -174,4 +428,88 @@ mod tests {
         assert_eq!(d.path.unwrap().to_str().unwrap(), "/tmp/plugin");
         assert_eq!(d.field.unwrap(), "name");
     }
+
+    #[test]
+    fn group_by_plugin_separates_marketplace_and_plugin_diagnostics() {
+        let mut result = ValidationResult::new();
+        result.push(ValidationDiagnostic::error("duplicate name"));
+
+        let mut foo = ValidationResult::new();
+        foo.push(ValidationDiagnostic::error("foo missing version"));
+        foo.push(ValidationDiagnostic::warning("foo no description"));
+        foo.tag_plugin("foo");
+        result.merge(foo);
+
+        result.push(ValidationDiagnostic::warning("bar warning").with_plugin("bar"));
+
+        let json = serde_json::to_value(result.group_by_plugin()).unwrap();
+
+        assert_eq!(json["marketplace"].as_array().unwrap().len(), 1);
+        assert_eq!(json["marketplace"][0]["message"], "duplicate name");
+        assert_eq!(json["plugins"]["foo"].as_array().unwrap().len(), 2);
+        assert_eq!(json["plugins"]["foo"][0]["severity"], "error");
+        assert_eq!(json["plugins"]["foo"][1]["severity"], "warning");
+        assert_eq!(json["plugins"]["bar"][0]["message"], "bar warning");
+    }
+
+    #[test]
+    fn validation_result_json_layout_round_trips() {
+        let mut result = ValidationResult::new();
+        result.push(
+            ValidationDiagnostic::error("Missing or null required field: name")
+                .with_path("/tmp/plugin/.claude-plugin/plugin.json")
+                .with_field("name")
+                .with_plugin("plugin"),
+        );
+        result.push(ValidationDiagnostic::warning("hooks.json defines no hooks"));
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "ok": false,
+                "error_count": 1,
+                "warning_count": 1,
+                "diagnostics": [
+                    {
+                        "severity": "error",
+                        "message": "Missing or null required field: name",
+                        "path": "/tmp/plugin/.claude-plugin/plugin.json",
+                        "field": "name",
+                        "plugin": "plugin",
+                    },
+                    {
+                        "severity": "warning",
+                        "message": "hooks.json defines no hooks",
+                        "path": null,
+                        "field": null,
+                    },
+                ],
+            })
+        );
+
+        let parsed: ValidationResult = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, result);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_paths_serialize_as_strings() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = PathBuf::from(std::ffi::OsStr::from_bytes(b"/tmp/bad-\xff"));
+        let json = serde_json::to_value(ValidationDiagnostic::error("x").with_path(path)).unwrap();
+        assert_eq!(json["path"], "/tmp/bad-\u{FFFD}");
+    }
+
+    #[test]
+    fn tag_plugin_keeps_existing_attribution() {
+        let mut result = ValidationResult::new();
+        result.push(ValidationDiagnostic::error("a").with_plugin("dep"));
+        result.push(ValidationDiagnostic::error("b"));
+        result.tag_plugin("main");
+
+        assert_eq!(result.diagnostics[0].plugin.as_deref(), Some("dep"));
+        assert_eq!(result.diagnostics[1].plugin.as_deref(), Some("main"));
+    }
 }
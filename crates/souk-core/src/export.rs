@@ -0,0 +1,197 @@
+//! Marketplace export.
+//!
+//! Assembles a single JSON-serializable bundle describing the marketplace and
+//! every plugin it lists (marketplace entry, plugin.json, skills, and
+//! extends-plugin.json), suitable for ingestion by a web catalog.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::discovery::MarketplaceConfig;
+use crate::error::SoukError;
+use crate::resolution::{enumerate_skills, resolve_source};
+use crate::types::{Marketplace, PluginEntry};
+
+/// A complete description of a marketplace and its plugins.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportBundle {
+    /// The marketplace.json document.
+    pub marketplace: Marketplace,
+    /// One record per marketplace entry, in marketplace order.
+    pub plugins: Vec<ExportedPlugin>,
+}
+
+/// Everything souk knows about a single plugin.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedPlugin {
+    /// The plugin's entry in marketplace.json.
+    pub entry: PluginEntry,
+    /// The raw plugin.json document, or `None` if it could not be read.
+    pub manifest: Option<serde_json::Value>,
+    /// Skills found under the plugin's `skills/` directory.
+    pub skills: Vec<ExportedSkill>,
+    /// The raw extends-plugin.json document, if present.
+    pub extends: Option<serde_json::Value>,
+}
+
+/// A skill within an exported plugin.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedSkill {
+    /// Display name from SKILL.md frontmatter (or the directory name).
+    pub name: String,
+    /// Directory name under `skills/`.
+    pub dir_name: String,
+}
+
+/// Build an [`ExportBundle`] for the marketplace described by `config`.
+///
+/// Each plugin is located via [`resolve_source`]. Plugins whose directory or
+/// manifest cannot be read are still included, with `manifest` set to `None`
+/// and no skills, so the bundle always mirrors marketplace.json.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Json`] if a plugin.json or extends-plugin.json file
+/// exists but contains invalid JSON.
+pub fn export_bundle(config: &MarketplaceConfig) -> Result<ExportBundle, SoukError> {
+    let mut plugins = Vec::with_capacity(config.marketplace.plugins.len());
+
+    for entry in &config.marketplace.plugins {
+        let plugin_path = resolve_source(&entry.source, config)?;
+        let claude_dir = plugin_path.join(".claude-plugin");
+
+        let manifest = read_json_if_present(&claude_dir.join("plugin.json"))?;
+        let extends = read_json_if_present(&claude_dir.join("extends-plugin.json"))?;
+        let skills = enumerate_skills(&plugin_path)
+            .into_iter()
+            .map(|s| ExportedSkill {
+                name: s.display_name,
+                dir_name: s.dir_name,
+            })
+            .collect();
+
+        plugins.push(ExportedPlugin {
+            entry: entry.clone(),
+            manifest,
+            skills,
+            extends,
+        });
+    }
+
+    Ok(ExportBundle {
+        marketplace: config.marketplace.clone(),
+        plugins,
+    })
+}
+
+/// Reads and parses a JSON file, returning `None` if it cannot be read.
+fn read_json_if_present(path: &Path) -> Result<Option<serde_json::Value>, SoukError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::load_marketplace_config;
+    use tempfile::TempDir;
+
+    fn setup_marketplace(tmp: &TempDir) -> MarketplaceConfig {
+        let claude = tmp.path().join(".claude-plugin");
+        std::fs::create_dir_all(&claude).unwrap();
+        let plugins = tmp.path().join("plugins");
+
+        for name in ["alpha", "beta"] {
+            let p = plugins.join(name).join(".claude-plugin");
+            std::fs::create_dir_all(&p).unwrap();
+            std::fs::write(
+                p.join("plugin.json"),
+                format!(r#"{{"name":"{name}","version":"1.0.0","description":"test"}}"#),
+            )
+            .unwrap();
+        }
+
+        let skill = plugins.join("alpha").join("skills").join("git-commit");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(
+            skill.join("SKILL.md"),
+            "---\nname: commit-message\n---\n# Commit",
+        )
+        .unwrap();
+        std::fs::write(
+            plugins
+                .join("alpha")
+                .join(".claude-plugin")
+                .join("extends-plugin.json"),
+            r#"{"dependencies": {"beta": "^1.0.0"}}"#,
+        )
+        .unwrap();
+
+        std::fs::write(
+            claude.join("marketplace.json"),
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[
+                {"name":"alpha","source":"alpha","tags":["dev"]},
+                {"name":"beta","source":"beta"}
+            ]}"#,
+        )
+        .unwrap();
+        load_marketplace_config(&claude.join("marketplace.json")).unwrap()
+    }
+
+    #[test]
+    fn bundle_includes_manifest_and_skills_for_each_plugin() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp);
+
+        let bundle = export_bundle(&config).unwrap();
+
+        assert_eq!(bundle.marketplace.version, "0.1.0");
+        assert_eq!(bundle.plugins.len(), 2);
+
+        let alpha = &bundle.plugins[0];
+        assert_eq!(alpha.entry.name, "alpha");
+        assert_eq!(alpha.entry.tags, vec!["dev"]);
+        assert_eq!(alpha.manifest.as_ref().unwrap()["version"], "1.0.0");
+        assert_eq!(alpha.skills.len(), 1);
+        assert_eq!(alpha.skills[0].name, "commit-message");
+        assert_eq!(alpha.skills[0].dir_name, "git-commit");
+        assert_eq!(
+            alpha.extends.as_ref().unwrap()["dependencies"]["beta"],
+            "^1.0.0"
+        );
+
+        let beta = &bundle.plugins[1];
+        assert_eq!(beta.manifest.as_ref().unwrap()["name"], "beta");
+        assert!(beta.skills.is_empty());
+        assert!(beta.extends.is_none());
+    }
+
+    #[test]
+    fn bundle_serializes_expected_shape() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp);
+
+        let json = serde_json::to_value(export_bundle(&config).unwrap()).unwrap();
+
+        assert_eq!(json["marketplace"]["pluginRoot"], "./plugins");
+        assert_eq!(json["plugins"][0]["entry"]["source"], "alpha");
+        assert_eq!(json["plugins"][0]["skills"][0]["dirName"], "git-commit");
+        assert!(json["plugins"][1]["extends"].is_null());
+    }
+
+    #[test]
+    fn missing_plugin_directory_is_exported_without_manifest() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp);
+        std::fs::remove_dir_all(config.plugin_root_abs.join("beta")).unwrap();
+
+        let bundle = export_bundle(&config).unwrap();
+
+        assert_eq!(bundle.plugins.len(), 2);
+        assert!(bundle.plugins[1].manifest.is_none());
+    }
+}
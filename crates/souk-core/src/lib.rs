@@ -1,12 +1,21 @@
 pub mod ci;
+pub mod config;
+pub mod diff;
 pub mod discovery;
 pub mod error;
+pub mod export;
 pub mod ops;
+pub mod remote;
+pub mod report;
 pub mod resolution;
 pub mod review;
+pub mod stats;
 pub mod types;
+pub mod util;
 pub mod validation;
 pub mod version;
 
-pub use error::{Severity, SoukError, ValidationDiagnostic, ValidationResult};
+pub use error::{
+    CommandOutcome, ExitStatus, Severity, SoukError, ValidationDiagnostic, ValidationResult,
+};
 pub use types::*;
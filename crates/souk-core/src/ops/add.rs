@@ -1,7 +1,9 @@
 //! Add plugins to the marketplace.
 //!
 //! Implements the 7-phase pipeline for adding plugins:
-//! 1. Preflight: Resolve each plugin path, validate it
+//! 1. Preflight: Resolve each plugin path (cloning git URLs and extracting
+//!    archives), validate it, and note missing recommended structure
+//!    (README, skills/commands/agents) as warnings
 //! 2. Plan: Determine if internal or external, check for conflicts
 //! 3. Dry-run gate: If dry run, report planned actions and stop
 //! 4. Copy: For external plugins, copy to pluginRoot
@@ -11,16 +13,87 @@
 
 use std::collections::HashSet;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
 
+use serde::Deserialize;
+use tempfile::TempDir;
+
+use crate::config::SoukConfig;
 use crate::discovery::{load_marketplace_config, MarketplaceConfig};
 use crate::error::SoukError;
+use crate::ops::ignore::CopyFilter;
 use crate::ops::AtomicGuard;
+use crate::resolution::skill::enumerate_skills;
 use crate::resolution::{plugin_path_to_source, resolve_plugin};
+use crate::review::provider::http_client;
+use crate::types::plugin::read_manifest_text;
 use crate::types::{Marketplace, PluginEntry, PluginManifest};
-use crate::validation::{validate_marketplace, validate_plugin};
+use crate::util::{tree_hash, tree_hash_where};
+use crate::validation::{validate_marketplace, validate_plugin_with};
 use crate::version::{bump_patch, generate_unique_name};
 
+/// Conflict strategies accepted by [`plan_add`].
+pub const CONFLICT_STRATEGIES: &[&str] = &["abort", "skip", "replace", "rename"];
+
+/// Prefixes of the temporary directories that git clones, downloads, and
+/// extracted archives are staged in; see [`find_stale_temp_dirs`].
+pub const TEMP_DIR_PREFIXES: [&str; 2] = ["souk-add-", "souk-download-"];
+
+/// An add operation described as JSON, for tools that drive souk
+/// programmatically (`souk add --stdin`).
+///
+/// ```json
+/// {"plugins": ["./a", "./b"], "on_conflict": "rename"}
+/// ```
+///
+/// Only `plugins` is required. Omitted fields fall back to the caller's
+/// defaults.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AddRequest {
+    /// Plugin paths or names to add.
+    pub plugins: Vec<String>,
+    /// One of "abort", "skip", "replace", or "rename".
+    #[serde(default)]
+    pub on_conflict: Option<String>,
+    /// Preview changes without executing.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Reference external plugins by absolute path instead of copying them.
+    #[serde(default)]
+    pub no_copy: bool,
+}
+
+impl AddRequest {
+    /// Parse and check a JSON add request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoukError::Other`] if the JSON is malformed, has unknown
+    /// fields, lists no plugins, or names an unknown conflict strategy.
+    pub fn from_json(json: &str) -> Result<Self, SoukError> {
+        let request: Self = serde_json::from_str(json)
+            .map_err(|e| SoukError::Other(format!("Invalid add request: {e}")))?;
+        if request.plugins.is_empty() {
+            return Err(SoukError::Other(
+                "Add request must list at least one plugin".to_string(),
+            ));
+        }
+        if let Some(strategy) = request.on_conflict.as_deref() {
+            if !CONFLICT_STRATEGIES.contains(&strategy) {
+                return Err(SoukError::Other(format!(
+                    "Invalid conflict strategy: {strategy} (expected {})",
+                    CONFLICT_STRATEGIES.join(", ")
+                )));
+            }
+        }
+        Ok(request)
+    }
+}
+
 /// A planned action for adding a single plugin.
 #[derive(Debug, Clone)]
 pub struct AddAction {
@@ -34,6 +107,10 @@ pub struct AddAction {
     pub is_external: bool,
     /// How to resolve a name conflict, if one exists.
     pub conflict: Option<ConflictResolution>,
+    /// The git source the plugin was cloned from, recorded on its entry.
+    pub origin: Option<String>,
+    /// The commit the clone of `origin` was at.
+    pub sha: Option<String>,
 }
 
 /// How a name conflict should be resolved for a single plugin.
@@ -51,6 +128,23 @@ pub enum ConflictResolution {
 #[derive(Debug, Clone)]
 pub struct AddPlan {
     pub actions: Vec<AddAction>,
+    /// Non-fatal preflight findings, such as a plugin with no README, to
+    /// show before executing the plan.
+    pub warnings: Vec<String>,
+    /// Clones of git sources and extracted archives that actions copy from,
+    /// removed once the plan is dropped.
+    _checkouts: Vec<Arc<TempDir>>,
+}
+
+impl AddPlan {
+    /// Actions that would record a machine-specific absolute path as the
+    /// marketplace source (external plugins added with `no_copy`).
+    pub fn absolute_sources(&self) -> impl Iterator<Item = &AddAction> {
+        self.actions.iter().filter(|a| {
+            !matches!(a.conflict, Some(ConflictResolution::Skip))
+                && Path::new(&a.source).is_absolute()
+        })
+    }
 }
 
 /// Plans the add operation without modifying the filesystem.
@@ -58,6 +152,16 @@ pub struct AddPlan {
 /// Resolves each input to a plugin path, reads its plugin.json, determines
 /// internal vs external, and applies the conflict resolution strategy.
 ///
+/// An input that is a git URL (`https://…`, `git@…`, or anything ending in
+/// `.git`), optionally suffixed with `#ref` to check out a branch, tag, or
+/// commit, is cloned into a temporary directory. The plugin is the
+/// repository root if it has a `.claude-plugin/plugin.json`, else the one
+/// directory inside that does. A `.tar.gz`, `.tgz`, or `.zip` file is
+/// extracted and searched the same way, as is one named by a `file://` URL
+/// or downloaded from an `http(s)://` URL ending in one of those
+/// extensions. Plugins from git or an archive are always copied into
+/// pluginRoot, even with `no_copy`.
+///
 /// # Arguments
 ///
 /// * `inputs` - Plugin paths or names to add.
@@ -68,7 +172,11 @@ pub struct AddPlan {
 ///
 /// # Errors
 ///
-/// Returns [`SoukError::PluginNotFound`] if a plugin cannot be resolved.
+/// Returns [`SoukError::PluginNotFound`] if a plugin cannot be resolved,
+/// or [`SoukError::Other`] if a git source cannot be cloned, an archive
+/// cannot be downloaded or extracted, is not the format its extension
+/// says, or has an entry outside its root, or either holds no single
+/// plugin.
 /// Returns [`SoukError::PluginAlreadyExists`] if the strategy is "abort" and a
 /// conflict is detected.
 /// Returns [`SoukError::ValidationFailed`] if preflight validation fails.
@@ -86,17 +194,25 @@ pub fn plan_add(
         .collect();
 
     let mut actions = Vec::new();
+    let mut warnings = Vec::new();
+    let mut checkouts = Vec::new();
     let mut errors: Vec<String> = Vec::new();
 
     for input in inputs {
         // Phase 1: Resolve plugin path
-        let plugin_path = match resolve_plugin_input(input, config) {
-            Ok(p) => p,
+        let (plugin_path, checkout, sha) = match resolve_plugin_input(input, config) {
+            Ok(resolved) => resolved,
+            Err(e) if git_source(input).is_some() || archive_kind(input).is_some() => {
+                errors.push(e.to_string());
+                continue;
+            }
             Err(e) => {
                 errors.push(format!("Plugin not found: {input} ({e})"));
                 continue;
             }
         };
+        let from_checkout = checkout.is_some();
+        checkouts.extend(checkout.map(Arc::new));
 
         // Read plugin.json to get the name
         let manifest = read_plugin_manifest(&plugin_path)?;
@@ -111,7 +227,7 @@ pub fn plan_add(
             .to_string();
 
         // Validate the plugin
-        let validation = validate_plugin(&plugin_path);
+        let validation = validate_plugin_with(&plugin_path, &config.settings);
         if validation.has_errors() {
             errors.push(format!(
                 "Plugin validation failed: {plugin_name} ({})",
@@ -120,12 +236,14 @@ pub fn plan_add(
             continue;
         }
 
+        warnings.extend(missing_recommended_structure(&plugin_name, &plugin_path));
+
         // Phase 2: Determine internal vs external
         let (source, is_internal) = plugin_path_to_source(&plugin_path, config);
         let is_external = !is_internal;
 
         // Determine the final source for the marketplace entry
-        let final_source = if is_external && !no_copy {
+        let final_source = if is_external && (!no_copy || from_checkout) {
             // Will be copied to pluginRoot; source = the plugin name (directory name)
             plugin_name.clone()
         } else {
@@ -160,6 +278,8 @@ pub fn plan_add(
             source: final_source,
             is_external,
             conflict,
+            origin: sha.is_some().then(|| input.clone()),
+            sha,
         });
     }
 
@@ -167,13 +287,44 @@ pub fn plan_add(
         return Err(SoukError::Other(errors.join("; ")));
     }
 
-    Ok(AddPlan { actions })
+    Ok(AddPlan {
+        actions,
+        warnings,
+        _checkouts: checkouts,
+    })
+}
+
+/// Describe recommended plugin structure that is missing from `plugin_path`:
+/// a README.md, and at least one skill, command, or agent.
+fn missing_recommended_structure(plugin_name: &str, plugin_path: &Path) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if !plugin_path.join("README.md").is_file() {
+        warnings.push(format!("{plugin_name}: no README.md"));
+    }
+
+    let has_markdown = |dir: &str| {
+        fs::read_dir(plugin_path.join(dir)).is_ok_and(|entries| {
+            entries
+                .flatten()
+                .any(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+        })
+    };
+    if enumerate_skills(plugin_path).is_empty()
+        && !has_markdown("commands")
+        && !has_markdown("agents")
+    {
+        warnings.push(format!("{plugin_name}: no skills, commands, or agents"));
+    }
+
+    warnings
 }
 
 /// Inner marketplace mutation, separated for cleanup-on-failure in execute_add.
 fn execute_add_marketplace(
     effective_actions: &[&AddAction],
     config: &MarketplaceConfig,
+    validate_after: bool,
 ) -> Result<Vec<String>, SoukError> {
     let guard = AtomicGuard::new(&config.marketplace_path)?;
 
@@ -200,6 +351,8 @@ fn execute_add_marketplace(
             name: final_name.clone(),
             source: final_source,
             tags,
+            origin: action.origin.clone(),
+            sha: action.sha.clone(),
         });
 
         added_names.push(final_name);
@@ -210,13 +363,15 @@ fn execute_add_marketplace(
     let json = serde_json::to_string_pretty(&marketplace)?;
     fs::write(&config.marketplace_path, format!("{json}\n"))?;
 
-    let updated_config = load_marketplace_config(&config.marketplace_path)?;
-    let validation = validate_marketplace(&updated_config, true);
-    if validation.has_errors() {
-        drop(guard);
-        return Err(SoukError::AtomicRollback(
-            "Final validation failed after add".to_string(),
-        ));
+    if validate_after {
+        let updated_config = load_marketplace_config(&config.marketplace_path)?;
+        let validation = validate_marketplace(&updated_config, true);
+        if validation.has_errors() {
+            drop(guard);
+            return Err(SoukError::AtomicRollback(
+                "Final validation failed after add".to_string(),
+            ));
+        }
     }
 
     guard.commit()?;
@@ -229,16 +384,41 @@ fn execute_add_marketplace(
 /// If `dry_run` is true, no changes are made and the function returns early
 /// after the planning phase.
 ///
+/// If `validate_after` is false, the final marketplace validation is
+/// skipped. This saves a full re-scan on very large marketplaces, at the
+/// cost of not re-checking integrity after the write.
+///
+/// Absolute sources only resolve on the machine that added them, so a plan
+/// containing any (see [`AddPlan::absolute_sources`]) is refused unless
+/// `confirm_external` is true.
+///
 /// # Errors
 ///
+/// Returns [`SoukError::Other`] if the plan records an absolute source and
+/// `confirm_external` is false.
+///
 /// Returns an error if copying, atomic update, version bump, or final
 /// validation fails. On atomic update failure, the AtomicGuard restores
 /// the original marketplace.json. Copied directories are cleaned up on failure.
+/// Each copy is validated and compared with its source before
+/// marketplace.json is touched, so an incomplete copy is never recorded.
 pub fn execute_add(
     plan: &AddPlan,
     config: &MarketplaceConfig,
     dry_run: bool,
+    validate_after: bool,
+    confirm_external: bool,
 ) -> Result<Vec<String>, SoukError> {
+    if !dry_run && !confirm_external {
+        if let Some(action) = plan.absolute_sources().next() {
+            return Err(SoukError::Other(format!(
+                "Refusing to record machine-specific absolute source for '{}': {}. \
+                 Use --confirm-external to allow it.",
+                action.plugin_name, action.source
+            )));
+        }
+    }
+
     // Collect the effective actions (skip those marked Skip)
     let effective_actions: Vec<&AddAction> = plan
         .actions
@@ -263,10 +443,39 @@ pub fn execute_add(
     }
 
     // Phase 4: Copy external plugins
+    let copied_dirs = copy_external_plugins(&effective_actions, config, |src, dst| {
+        copy_plugin(src, dst, &config.settings)
+    })?;
+
+    // Phase 5-7: Atomic update, version bump, validation
+    let result = execute_add_marketplace(&effective_actions, config, validate_after);
+
+    if result.is_err() {
+        // Clean up copied directories on failure
+        for dir in &copied_dirs {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+
+    result
+}
+
+/// Copies each external plugin in `actions` into pluginRoot with `copy`,
+/// returning the directories it created.
+///
+/// Every copy is checked before anything is recorded: it must validate
+/// cleanly and hash the same as the files it should hold (see
+/// [`tree_hash`]), so a copy cut short mid-stream is caught. On any
+/// failure, all directories copied so far are removed.
+fn copy_external_plugins(
+    actions: &[&AddAction],
+    config: &MarketplaceConfig,
+    copy: impl Fn(&Path, &Path) -> Result<(), SoukError>,
+) -> Result<Vec<PathBuf>, SoukError> {
     // Track directories we copy so we can clean up on failure
     let mut copied_dirs: Vec<PathBuf> = Vec::new();
 
-    for action in &effective_actions {
+    for action in actions {
         if action.is_external && !action.source.starts_with('/') {
             let target_name = match &action.conflict {
                 Some(ConflictResolution::Rename(new_name)) => new_name.as_str(),
@@ -287,7 +496,9 @@ pub fn execute_add(
             }
 
             copied_dirs.push(target_dir.clone());
-            if let Err(e) = copy_dir_recursive(&action.plugin_path, &target_dir) {
+            let copied = copy(&action.plugin_path, &target_dir)
+                .and_then(|()| verify_copy(&action.plugin_path, &target_dir, config));
+            if let Err(e) = copied {
                 // Clean up all previously copied dirs plus the partial one
                 for dir in &copied_dirs {
                     let _ = fs::remove_dir_all(dir);
@@ -297,79 +508,592 @@ pub fn execute_add(
         }
     }
 
-    // Phase 5-7: Atomic update, version bump, validation
-    let result = execute_add_marketplace(&effective_actions, config);
+    Ok(copied_dirs)
+}
 
-    if result.is_err() {
-        // Clean up copied directories on failure
-        for dir in &copied_dirs {
-            let _ = fs::remove_dir_all(dir);
-        }
+/// Checks that `copy` is a complete, valid copy of the plugin at `source`.
+fn verify_copy(source: &Path, copy: &Path, config: &MarketplaceConfig) -> Result<(), SoukError> {
+    let validation = validate_plugin_with(copy, &config.settings);
+    if validation.has_errors() {
+        let problems: Vec<_> = validation
+            .diagnostics
+            .iter()
+            .filter(|d| d.is_error())
+            .map(|d| d.message.as_str())
+            .collect();
+        return Err(SoukError::Other(format!(
+            "Copied plugin at {} failed validation: {}",
+            copy.display(),
+            problems.join("; ")
+        )));
     }
-
-    result
+    let filter = CopyFilter::for_plugin(source, &config.settings.copy_ignore)?;
+    if tree_hash_where(source, |rel, is_dir| !filter.is_ignored(rel, is_dir)) != tree_hash(copy) {
+        return Err(SoukError::Other(format!(
+            "Copy of {} is incomplete: {} does not match the source",
+            source.display(),
+            copy.display()
+        )));
+    }
+    Ok(())
 }
 
-/// Resolves a plugin input (path or name) to an absolute path.
-fn resolve_plugin_input(input: &str, config: &MarketplaceConfig) -> Result<PathBuf, SoukError> {
+/// Resolves a plugin input (path, name, git URL, or archive) to an
+/// absolute path.
+///
+/// A git URL is cloned and an archive extracted into a temporary directory,
+/// which is returned alongside the plugin path inside it so the caller can
+/// keep it alive. A clone also returns the commit it checked out.
+fn resolve_plugin_input(
+    input: &str,
+    config: &MarketplaceConfig,
+) -> Result<(PathBuf, Option<TempDir>, Option<String>), SoukError> {
+    // Before git sources, which also claim http(s):// and file:// URLs
+    if let Some(kind) = archive_url_kind(input) {
+        let (path, extracted) = fetch_archive(input, kind)?;
+        return Ok((path, Some(extracted), None));
+    }
+    if let Some((url, git_ref)) = git_source(input) {
+        let (path, checkout, sha) = clone_plugin(url, git_ref)?;
+        return Ok((path, Some(checkout), Some(sha)));
+    }
+    if let Some(kind) = archive_kind(input) {
+        let (path, extracted) = extract_plugin(Path::new(input), kind)?;
+        return Ok((path, Some(extracted), None));
+    }
+
     let input_path = PathBuf::from(input);
 
     // Try as a direct path first
     if input_path.is_dir() {
-        return input_path.canonicalize().map_err(SoukError::Io);
+        return Ok((input_path.canonicalize()?, None, None));
     }
 
     // Try resolving via plugin resolution
-    resolve_plugin(input, Some(config))
+    Ok((resolve_plugin(input, Some(config))?, None, None))
+}
+
+/// Whether `input` is a git or archive URL that [`plan_add`] fetches, as
+/// opposed to a local path or plugin name.
+pub fn is_fetchable_source(input: &str) -> bool {
+    archive_url_kind(input).is_some()
+        || git_source(input).is_some_and(|(url, _)| !Path::new(url).exists())
+}
+
+/// Splits a git source into its URL and optional `#ref`, or returns `None`
+/// if `input` is not a git source.
+///
+/// A local directory ending in `.git` is only a git source if it is not
+/// itself a plugin.
+fn git_source(input: &str) -> Option<(&str, Option<&str>)> {
+    const SCHEMES: &[&str] = &["http://", "https://", "ssh://", "git://", "file://", "git@"];
+
+    let (url, git_ref) = match input.rsplit_once('#') {
+        Some((url, git_ref)) if !git_ref.is_empty() => (url, Some(git_ref)),
+        _ => (input.trim_end_matches('#'), None),
+    };
+    let is_git = SCHEMES.iter().any(|scheme| url.starts_with(scheme))
+        || (url.trim_end_matches('/').ends_with(".git")
+            && !Path::new(url).join(".claude-plugin").is_dir());
+    is_git.then_some((url, git_ref))
+}
+
+/// Clones `url` into a temporary directory, checks out `git_ref` if given,
+/// and returns the plugin directory inside it with the directory itself
+/// and the commit checked out.
+///
+/// The clone's `.git` directory is removed so it is not copied into
+/// pluginRoot. A ref starting with `-` is rejected rather than passed to
+/// git, where it would be read as an option.
+fn clone_plugin(url: &str, git_ref: Option<&str>) -> Result<(PathBuf, TempDir, String), SoukError> {
+    check_git_ref(url, git_ref)?;
+    let checkout = tempfile::Builder::new()
+        .prefix(TEMP_DIR_PREFIXES[0])
+        .tempdir()?;
+    let repo_name = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .map(|name| name.trim_end_matches(".git"))
+        .filter(|name| !name.is_empty())
+        .unwrap_or("plugin");
+    let repo = checkout.path().join(repo_name);
+
+    run_git(
+        Command::new("git")
+            .args(["clone", "--quiet", "--", url])
+            .arg(&repo),
+        &format!("git clone of {url}"),
+    )?;
+    if let Some(git_ref) = git_ref {
+        run_git(
+            Command::new("git")
+                .args(["checkout", "--quiet", git_ref, "--"])
+                .current_dir(&repo),
+            &format!("git checkout of '{git_ref}' in {url}"),
+        )?;
+    }
+    let sha = run_git(
+        Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(&repo),
+        &format!("git rev-parse in {url}"),
+    )?;
+    fs::remove_dir_all(repo.join(".git"))?;
+
+    let plugin = find_plugin_dir(&repo).map_err(|e| SoukError::Other(format!("{e} in {url}")))?;
+    Ok((plugin, checkout, sha))
+}
+
+/// Resolves the commit that `origin`, a git source as recorded by
+/// [`execute_add`], currently points at, without cloning it. The `#ref`
+/// is looked up as a branch or tag, defaulting to the remote's `HEAD`.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Other`] if `origin` is not a git source, git
+/// fails, or the ref does not exist at the origin.
+pub(crate) fn origin_sha(origin: &str) -> Result<String, SoukError> {
+    let (url, git_ref) = git_source(origin)
+        .ok_or_else(|| SoukError::Other(format!("Not a git source: {origin}")))?;
+    check_git_ref(url, git_ref)?;
+    let git_ref = git_ref.unwrap_or("HEAD");
+    let listing = run_git(
+        Command::new("git").args(["ls-remote", "--", url, git_ref]),
+        &format!("git ls-remote of {url}"),
+    )?;
+    let refs: Vec<(&str, &str)> = listing
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .collect();
+    // A peeled tag (`^{}`) names the commit rather than the tag object
+    let candidates = [
+        git_ref.to_string(),
+        format!("refs/heads/{git_ref}"),
+        format!("refs/tags/{git_ref}^{{}}"),
+        format!("refs/tags/{git_ref}"),
+    ];
+    candidates
+        .iter()
+        .find_map(|wanted| refs.iter().find(|(_, name)| name == wanted))
+        .map(|(sha, _)| sha.to_string())
+        .ok_or_else(|| SoukError::Other(format!("No ref '{git_ref}' at {url}")))
+}
+
+/// Rejects a ref starting with `-`, which git would read as an option.
+fn check_git_ref(url: &str, git_ref: Option<&str>) -> Result<(), SoukError> {
+    match git_ref.filter(|r| r.starts_with('-')) {
+        Some(git_ref) => Err(SoukError::Other(format!(
+            "Invalid git ref '{git_ref}' in {url}: refs cannot start with '-'"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Runs a git command, turning a failure into an error naming `what`, and
+/// returns its trimmed standard output.
+fn run_git(command: &mut Command, what: &str) -> Result<String, SoukError> {
+    let output = command
+        .output()
+        .map_err(|e| SoukError::Other(format!("Failed to run git: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(SoukError::Other(format!(
+            "{what} failed: {}",
+            stderr.trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Archive formats `souk add` can extract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    TarGz,
+    Zip,
+}
+
+impl ArchiveKind {
+    /// The format named by `name`'s `.tar.gz`, `.tgz`, or `.zip` extension.
+    fn from_name(name: &str) -> Option<Self> {
+        let lower = name.to_lowercase();
+        if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else if lower.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `header`, the start of a file, matches this format.
+    fn matches(self, header: &[u8]) -> bool {
+        match self {
+            ArchiveKind::TarGz => header.starts_with(&[0x1f, 0x8b]),
+            ArchiveKind::Zip => {
+                header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06")
+            }
+        }
+    }
+}
+
+/// The archive format of `input`, if it is a file with a `.tar.gz`, `.tgz`,
+/// or `.zip` extension.
+fn archive_kind(input: &str) -> Option<ArchiveKind> {
+    let kind = ArchiveKind::from_name(input)?;
+    Path::new(input).is_file().then_some(kind)
+}
+
+/// The archive format of `input`, if it is an `http://`, `https://`, or
+/// `file://` URL whose path has an archive extension. Any query string or
+/// fragment is ignored.
+fn archive_url_kind(input: &str) -> Option<ArchiveKind> {
+    let rest = ["http://", "https://", "file://"]
+        .iter()
+        .find_map(|scheme| input.strip_prefix(scheme))?;
+    ArchiveKind::from_name(url_path(rest))
+}
+
+/// `url` without its query string or fragment.
+fn url_path(url: &str) -> &str {
+    url.split(['?', '#']).next().unwrap_or(url)
+}
+
+/// Opens the `file://` archive or downloads the `http(s)://` one at `url`,
+/// then extracts the plugin inside it as [`extract_plugin`] does.
+fn fetch_archive(url: &str, kind: ArchiveKind) -> Result<(PathBuf, TempDir), SoukError> {
+    if let Some(path) = url.strip_prefix("file://") {
+        let path = Path::new(url_path(path));
+        if !path.is_file() {
+            return Err(SoukError::Other(format!("Archive not found: {url}")));
+        }
+        return extract_plugin(path, kind);
+    }
+
+    let fail =
+        |e: &dyn std::fmt::Display| SoukError::Other(format!("Failed to download {url}: {e}"));
+    let response = http_client(None).get(url).send().map_err(|e| fail(&e))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(fail(&format!("HTTP {status}")));
+    }
+    let bytes = response.bytes().map_err(|e| fail(&e))?;
+
+    // Keep the URL's file name, which names the extracted directory
+    let download = tempfile::Builder::new()
+        .prefix(TEMP_DIR_PREFIXES[1])
+        .tempdir()?;
+    let file_name = url_path(url)
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or("plugin");
+    let archive = download.path().join(file_name);
+    fs::write(&archive, &bytes)?;
+    extract_plugin(&archive, kind)
+}
+
+/// Extracts `archive` into a temporary directory and returns the plugin
+/// directory inside it with the directory itself.
+///
+/// The file must start like an archive of `kind`, so a download that
+/// returned an error page is reported as such. Entries with absolute paths
+/// or `..` components, links, and devices are refused before anything is
+/// written outside the temporary directory; pax and GNU metadata entries are
+/// skipped.
+fn extract_plugin(archive: &Path, kind: ArchiveKind) -> Result<(PathBuf, TempDir), SoukError> {
+    let mut header = Vec::new();
+    fs::File::open(archive)?.take(4).read_to_end(&mut header)?;
+    if !kind.matches(&header) {
+        return Err(SoukError::Other(format!(
+            "{} is not a valid {} archive",
+            archive.display(),
+            match kind {
+                ArchiveKind::TarGz => "gzip-compressed tar",
+                ArchiveKind::Zip => "zip",
+            }
+        )));
+    }
+
+    let extracted = tempfile::Builder::new()
+        .prefix(TEMP_DIR_PREFIXES[0])
+        .tempdir()?;
+    let file_name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stem = [".tar.gz", ".tgz", ".zip"]
+        .iter()
+        .find_map(|ext| file_name.strip_suffix(ext))
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or("plugin");
+    let root = extracted.path().join(stem);
+    fs::create_dir_all(&root)?;
+
+    let fail = |e: &dyn std::fmt::Display| {
+        SoukError::Other(format!("Cannot extract {}: {e}", archive.display()))
+    };
+    match kind {
+        ArchiveKind::TarGz => {
+            let decoder = flate2::read::GzDecoder::new(fs::File::open(archive)?);
+            let mut tar = tar::Archive::new(decoder);
+            for entry in tar.entries().map_err(|e| fail(&e))? {
+                let mut entry = entry.map_err(|e| fail(&e))?;
+                let kind = entry.header().entry_type();
+                // Pax and GNU metadata, such as the commit id `git archive`
+                // stores in a global header, describe other entries.
+                if matches!(
+                    kind,
+                    tar::EntryType::XGlobalHeader
+                        | tar::EntryType::XHeader
+                        | tar::EntryType::GNULongName
+                        | tar::EntryType::GNULongLink
+                ) {
+                    continue;
+                }
+                let path = entry.path().map_err(|e| fail(&e))?.into_owned();
+                let target = root.join(safe_entry_path(&path, archive)?);
+                match kind {
+                    tar::EntryType::Directory => fs::create_dir_all(&target)?,
+                    tar::EntryType::Regular | tar::EntryType::Continuous => {
+                        if let Some(parent) = target.parent() {
+                            fs::create_dir_all(parent)?;
+                        }
+                        entry.unpack(&target).map_err(|e| fail(&e))?;
+                    }
+                    other => {
+                        return Err(fail(&format!(
+                            "unsupported entry type {other:?} at {}",
+                            path.display()
+                        )))
+                    }
+                }
+            }
+        }
+        ArchiveKind::Zip => {
+            let mut zip = zip::ZipArchive::new(fs::File::open(archive)?).map_err(|e| fail(&e))?;
+            for i in 0..zip.len() {
+                let mut file = zip.by_index(i).map_err(|e| fail(&e))?;
+                let path = PathBuf::from(file.name());
+                let target = root.join(safe_entry_path(&path, archive)?);
+                if file.is_symlink() {
+                    return Err(fail(&format!(
+                        "links are not supported: {}",
+                        path.display()
+                    )));
+                }
+                if file.is_dir() {
+                    fs::create_dir_all(&target)?;
+                } else {
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    std::io::copy(&mut file, &mut fs::File::create(&target)?)?;
+                }
+            }
+        }
+    }
+
+    let plugin = find_plugin_dir(&root)
+        .map_err(|e| SoukError::Other(format!("{e} in {}", archive.display())))?;
+    Ok((plugin, extracted))
+}
+
+/// `path` if it stays inside the extraction directory: relative, with no
+/// `..` components.
+fn safe_entry_path<'a>(path: &'a Path, archive: &Path) -> Result<&'a Path, SoukError> {
+    let safe = path
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if safe {
+        Ok(path)
+    } else {
+        Err(SoukError::Other(format!(
+            "Archive {} has an entry outside its root: {}",
+            archive.display(),
+            path.display()
+        )))
+    }
+}
+
+/// The directory holding `.claude-plugin/plugin.json`: `root` itself, or
+/// its only descendant that has one.
+fn find_plugin_dir(root: &Path) -> Result<PathBuf, String> {
+    let is_plugin = |dir: &Path| dir.join(".claude-plugin").join("plugin.json").is_file();
+    if is_plugin(root) {
+        return Ok(root.to_path_buf());
+    }
+
+    let mut found: Vec<PathBuf> = walkdir::WalkDir::new(root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_dir() && is_plugin(e.path()))
+        .map(|e| e.into_path())
+        .collect();
+    match found.len() {
+        0 => Err("No plugin found (no .claude-plugin/plugin.json)".to_string()),
+        1 => Ok(found.remove(0)),
+        _ => {
+            let dirs: Vec<_> = found
+                .iter()
+                .filter_map(|p| p.strip_prefix(root).ok())
+                .map(|p| p.display().to_string())
+                .collect();
+            Err(format!("Multiple plugins found ({})", dirs.join(", ")))
+        }
+    }
 }
 
 /// Reads and parses plugin.json from a plugin directory.
-fn read_plugin_manifest(plugin_path: &Path) -> Result<PluginManifest, SoukError> {
+pub(crate) fn read_plugin_manifest(plugin_path: &Path) -> Result<PluginManifest, SoukError> {
     let plugin_json = plugin_path.join(".claude-plugin").join("plugin.json");
 
-    let content = fs::read_to_string(&plugin_json).map_err(|e| {
-        SoukError::Other(format!(
-            "Cannot read plugin.json at {}: {e}",
-            plugin_json.display()
-        ))
-    })?;
+    let content = read_manifest_text(&plugin_json)
+        .map_err(|e| SoukError::Other(format!("{e} ({})", plugin_json.display())))?;
 
     let manifest: PluginManifest = serde_json::from_str(&content)?;
     Ok(manifest)
 }
 
-/// Recursively copies a directory from `src` to `dst`.
+/// Recursively copies a directory from `src` to `dst`, skipping entries
+/// `filter` ignores.
 ///
-/// Returns an error if any symlinks are encountered.
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), SoukError> {
+/// File permissions are kept and symlinks are recreated as symlinks (see
+/// [`copy_symlink`]).
+fn copy_dir_recursive(src: &Path, dst: &Path, filter: &CopyFilter) -> Result<(), SoukError> {
+    copy_filtered(src, src, dst, filter)
+}
+
+/// Copies the plugin at `src` to `dst`, leaving out entries matched by
+/// `settings.copy_ignore` or the plugin's `.soukignore`.
+fn copy_plugin(src: &Path, dst: &Path, settings: &SoukConfig) -> Result<(), SoukError> {
+    let filter = CopyFilter::for_plugin(src, &settings.copy_ignore)?;
+    copy_dir_recursive(src, dst, &filter)
+}
+
+/// Copies `src`, which lies under the plugin directory `root`, to `dst`.
+fn copy_filtered(
+    root: &Path,
+    src: &Path,
+    dst: &Path,
+    filter: &CopyFilter,
+) -> Result<(), SoukError> {
     fs::create_dir_all(dst)?;
     for entry in fs::read_dir(src)? {
         let entry = entry?;
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
-        // Check for symlinks before processing
         let meta = fs::symlink_metadata(&src_path)?;
-        if meta.file_type().is_symlink() {
-            return Err(SoukError::Other(format!(
-                "Symlink detected at '{}': symlinks are not supported in plugin directories",
-                src_path.display()
-            )));
+        let rel = src_path.strip_prefix(root).unwrap_or(&src_path);
+        if filter.is_ignored(rel, meta.is_dir()) {
+            continue;
         }
 
-        if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
+        if meta.file_type().is_symlink() {
+            copy_symlink(root, &src_path, &dst_path)?;
+        } else if meta.is_dir() {
+            copy_filtered(root, &src_path, &dst_path, filter)?;
         } else {
+            // fs::copy carries the permission bits over, so hook scripts
+            // stay executable.
             fs::copy(&src_path, &dst_path)?;
         }
     }
     Ok(())
 }
 
+/// Recreates the symlink at `src`, inside the plugin directory `root`, as
+/// `dst`.
+///
+/// Only relative links that resolve inside the plugin are supported; any
+/// other link would point somewhere else, or nowhere, once the plugin is
+/// copied into pluginRoot.
+#[cfg(unix)]
+fn copy_symlink(root: &Path, src: &Path, dst: &Path) -> Result<(), SoukError> {
+    let target = fs::read_link(src)?;
+    let link_dir = src
+        .parent()
+        .and_then(|p| p.strip_prefix(root).ok())
+        .unwrap_or(Path::new(""));
+    if target.is_absolute() || escapes_root(&link_dir.join(&target)) {
+        return Err(SoukError::Other(format!(
+            "Symlink at '{}' points outside the plugin ({}): only relative links within the plugin directory are supported",
+            src.display(),
+            target.display()
+        )));
+    }
+    std::os::unix::fs::symlink(&target, dst)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(_root: &Path, src: &Path, _dst: &Path) -> Result<(), SoukError> {
+    Err(SoukError::Other(format!(
+        "Symlink detected at '{}': symlinks in plugin directories are only supported on Unix",
+        src.display()
+    )))
+}
+
+/// Whether the relative `path` climbs above its starting directory.
+pub(crate) fn escapes_root(path: &Path) -> bool {
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::ParentDir => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => return true,
+            },
+            Component::Normal(_) => depth += 1,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Find staging directories in `temp_dir` (normally
+/// [`std::env::temp_dir`]) left behind by an add that was killed before it
+/// could clean up.
+///
+/// Only directories whose name starts with one of [`TEMP_DIR_PREFIXES`] and
+/// that were last modified more than `older_than` ago are returned, sorted
+/// by path.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Io`] if `temp_dir` cannot be read.
+pub fn find_stale_temp_dirs(
+    temp_dir: &Path,
+    older_than: std::time::Duration,
+) -> Result<Vec<PathBuf>, SoukError> {
+    let mut stale = Vec::new();
+    for entry in fs::read_dir(temp_dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !TEMP_DIR_PREFIXES.iter().any(|p| name.starts_with(p)) {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.elapsed().ok());
+        if metadata.is_dir() && age.is_some_and(|age| age > older_than) {
+            stale.push(entry.path());
+        }
+    }
+    stale.sort();
+    Ok(stale)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::discovery::load_marketplace_config;
+    use crate::ops::update::{update_plugins, UpdateFields};
     use tempfile::TempDir;
 
     /// Creates a minimal marketplace setup in a temp directory.
@@ -400,6 +1124,31 @@ mod tests {
         plugin_dir
     }
 
+    #[test]
+    fn add_request_from_json() {
+        let request =
+            AddRequest::from_json(r#"{"plugins":["./a","./b"],"on_conflict":"rename"}"#).unwrap();
+        assert_eq!(request.plugins, vec!["./a", "./b"]);
+        assert_eq!(request.on_conflict.as_deref(), Some("rename"));
+        assert!(!request.dry_run);
+        assert!(!request.no_copy);
+
+        let request = AddRequest::from_json(r#"{"plugins":["a"]}"#).unwrap();
+        assert!(request.on_conflict.is_none());
+    }
+
+    #[test]
+    fn add_request_rejects_bad_input() {
+        for json in [
+            "not json",
+            r#"{"plugins":[]}"#,
+            r#"{"plugins":["a"],"on_conflict":"merge"}"#,
+            r#"{"plugins":["a"],"onConflict":"skip"}"#,
+        ] {
+            assert!(AddRequest::from_json(json).is_err(), "{json}");
+        }
+    }
+
     #[test]
     fn add_single_plugin_to_empty_marketplace() {
         let tmp = TempDir::new().unwrap();
@@ -415,7 +1164,7 @@ mod tests {
         assert!(!plan.actions[0].is_external);
         assert!(plan.actions[0].conflict.is_none());
 
-        let added = execute_add(&plan, &config, false).unwrap();
+        let added = execute_add(&plan, &config, false, true, false).unwrap();
         assert_eq!(added, vec!["my-plugin"]);
 
         // Verify marketplace was updated
@@ -460,7 +1209,7 @@ mod tests {
         ));
 
         // Execute should not add anything
-        let added = execute_add(&plan, &config, false).unwrap();
+        let added = execute_add(&plan, &config, false, true, false).unwrap();
         assert!(added.is_empty());
 
         // Marketplace should be unchanged
@@ -487,7 +1236,7 @@ mod tests {
             Some(ConflictResolution::Replace)
         ));
 
-        let added = execute_add(&plan, &config, false).unwrap();
+        let added = execute_add(&plan, &config, false, true, false).unwrap();
         assert_eq!(added, vec!["existing"]);
 
         // Tags should be updated from plugin.json
@@ -524,7 +1273,7 @@ mod tests {
 
         let plan = plan_add(&["my-plugin".to_string()], &config, "abort", false).unwrap();
 
-        let added = execute_add(&plan, &config, true).unwrap();
+        let added = execute_add(&plan, &config, true, true, false).unwrap();
         assert_eq!(added, vec!["my-plugin"]);
 
         // Marketplace should be unchanged
@@ -555,7 +1304,7 @@ mod tests {
         assert_eq!(plan.actions.len(), 1);
         assert!(plan.actions[0].is_external);
 
-        let added = execute_add(&plan, &config, false).unwrap();
+        let added = execute_add(&plan, &config, false, true, false).unwrap();
         assert_eq!(added, vec!["ext-plugin"]);
 
         // Plugin should be copied to pluginRoot
@@ -594,9 +1343,42 @@ mod tests {
         assert!(std::path::Path::new(&plan.actions[0].source).is_absolute());
     }
 
+    #[test]
+    fn no_copy_requires_confirm_external() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let external_dir = TempDir::new().unwrap();
+        let ext_path = create_plugin(external_dir.path(), "ext-plugin");
+
+        let plan = plan_add(
+            &[ext_path.to_string_lossy().to_string()],
+            &config,
+            "abort",
+            true,
+        )
+        .unwrap();
+        assert_eq!(plan.absolute_sources().count(), 1);
+
+        let result = execute_add(&plan, &config, false, true, false);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--confirm-external"));
+        let content = fs::read_to_string(&config.marketplace_path).unwrap();
+        let mp: Marketplace = serde_json::from_str(&content).unwrap();
+        assert!(mp.plugins.is_empty());
+
+        let added = execute_add(&plan, &config, false, true, true).unwrap();
+        assert_eq!(added, vec!["ext-plugin"]);
+        let content = fs::read_to_string(&config.marketplace_path).unwrap();
+        let mp: Marketplace = serde_json::from_str(&content).unwrap();
+        assert_eq!(mp.plugins[0].source, plan.actions[0].source);
+        assert!(Path::new(&mp.plugins[0].source).is_absolute());
+    }
+
     #[cfg(unix)]
     #[test]
-    fn copy_dir_recursive_rejects_symlinks() {
+    fn copy_dir_recursive_rejects_symlinks_outside_plugin() {
         let tmp = TempDir::new().unwrap();
         let src = tmp.path().join("src_plugin");
         let claude_dir = src.join(".claude-plugin");
@@ -607,11 +1389,11 @@ mod tests {
         )
         .unwrap();
 
-        // Create a symlink inside the plugin directory
+        // Absolute and escaping links would dangle once copied
         std::os::unix::fs::symlink("/tmp", src.join("bad-link")).unwrap();
 
         let dst = tmp.path().join("dst_plugin");
-        let result = copy_dir_recursive(&src, &dst);
+        let result = copy_dir_recursive(&src, &dst, &CopyFilter::default());
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -619,6 +1401,88 @@ mod tests {
             err_msg.contains("Symlink"),
             "Error should mention symlink: {err_msg}"
         );
+
+        fs::remove_file(src.join("bad-link")).unwrap();
+        std::os::unix::fs::symlink("../../secrets", claude_dir.join("escape")).unwrap();
+        let result = copy_dir_recursive(&src, &tmp.path().join("dst2"), &CopyFilter::default());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("outside the plugin"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn external_copy_keeps_executable_bits_and_relative_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let external = TempDir::new().unwrap();
+        let src = create_plugin(external.path(), "ext-plugin");
+        fs::create_dir_all(src.join("hooks")).unwrap();
+        let script = src.join("hooks/run.sh");
+        fs::write(&script, "#!/bin/sh\necho hi\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink("run.sh", src.join("hooks/latest.sh")).unwrap();
+        std::os::unix::fs::symlink("../README.md", src.join("hooks/README.md")).unwrap();
+        fs::write(src.join("README.md"), "# Ext").unwrap();
+
+        let plan = plan_add(
+            &[src.to_string_lossy().to_string()],
+            &config,
+            "abort",
+            false,
+        )
+        .unwrap();
+        execute_add(&plan, &config, false, true, false).unwrap();
+
+        let hooks = config.plugin_root_abs.join("ext-plugin/hooks");
+        let mode = fs::metadata(hooks.join("run.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+        assert_eq!(
+            fs::read_link(hooks.join("latest.sh")).unwrap(),
+            Path::new("run.sh")
+        );
+        assert_eq!(
+            fs::read_to_string(hooks.join("README.md")).unwrap(),
+            "# Ext"
+        );
+    }
+
+    #[test]
+    fn external_copy_skips_vcs_and_ignored_files() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let external = TempDir::new().unwrap();
+        let src = create_plugin(external.path(), "ext-plugin");
+        fs::create_dir_all(src.join(".git/objects")).unwrap();
+        fs::write(src.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::create_dir_all(src.join("node_modules/dep")).unwrap();
+        fs::write(src.join(".DS_Store"), "").unwrap();
+        fs::write(src.join("notes.draft"), "wip").unwrap();
+        fs::write(src.join(".soukignore"), "*.draft\n").unwrap();
+        fs::write(src.join("README.md"), "# Ext").unwrap();
+
+        let plan = plan_add(
+            &[src.to_string_lossy().to_string()],
+            &config,
+            "abort",
+            false,
+        )
+        .unwrap();
+        execute_add(&plan, &config, false, true, false).unwrap();
+
+        let copy = config.plugin_root_abs.join("ext-plugin");
+        assert!(copy.join("README.md").is_file());
+        assert!(copy.join(".soukignore").is_file());
+        assert!(!copy.join(".git").exists());
+        assert!(!copy.join("node_modules").exists());
+        assert!(!copy.join(".DS_Store").exists());
+        assert!(!copy.join("notes.draft").exists());
     }
 
     #[test]
@@ -642,7 +1506,7 @@ mod tests {
         // Corrupt marketplace.json so validation will fail after copy
         fs::write(&config.marketplace_path, "not valid json").unwrap();
 
-        let result = execute_add(&plan, &config, false);
+        let result = execute_add(&plan, &config, false, true, false);
         assert!(result.is_err());
 
         // The copied directory should have been cleaned up
@@ -653,6 +1517,78 @@ mod tests {
         );
     }
 
+    /// Plan adding two external plugins, `ext-a` and `ext-b`, from `external`.
+    fn plan_external_pair(config: &MarketplaceConfig, external: &Path) -> AddPlan {
+        for name in ["ext-a", "ext-b"] {
+            let plugin = create_plugin(external, name);
+            fs::write(plugin.join("README.md"), "# Docs\n".repeat(100)).unwrap();
+        }
+        let inputs: Vec<_> = ["ext-a", "ext-b"]
+            .iter()
+            .map(|n| external.join(n).to_string_lossy().to_string())
+            .collect();
+        plan_add(&inputs, config, "abort", false).unwrap()
+    }
+
+    #[test]
+    fn partial_copy_fails_and_rolls_back() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let external = TempDir::new().unwrap();
+        let plan = plan_external_pair(&config, external.path());
+        let actions: Vec<_> = plan.actions.iter().collect();
+
+        // The second copy is cut short, leaving a truncated README.
+        let partial = |src: &Path, dst: &Path| {
+            copy_plugin(src, dst, &config.settings)?;
+            if dst.ends_with("ext-b") {
+                fs::write(dst.join("README.md"), "# Do")?;
+            }
+            Ok(())
+        };
+        let err = copy_external_plugins(&actions, &config, partial).unwrap_err();
+
+        assert!(err.to_string().contains("is incomplete"), "{err}");
+        assert!(!config.plugin_root_abs.join("ext-a").exists());
+        assert!(!config.plugin_root_abs.join("ext-b").exists());
+    }
+
+    #[test]
+    fn invalid_copy_fails_validation() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let external = TempDir::new().unwrap();
+        let plan = plan_external_pair(&config, external.path());
+        let actions: Vec<_> = plan.actions.iter().collect();
+
+        // A copy interrupted before plugin.json was written.
+        let partial = |src: &Path, dst: &Path| {
+            copy_plugin(src, dst, &config.settings)?;
+            fs::remove_file(dst.join(".claude-plugin").join("plugin.json"))?;
+            Ok(())
+        };
+        let err = copy_external_plugins(&actions, &config, partial).unwrap_err();
+
+        assert!(err.to_string().contains("failed validation"), "{err}");
+        assert!(!config.plugin_root_abs.join("ext-a").exists());
+    }
+
+    #[test]
+    fn complete_copies_pass_verification() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let external = TempDir::new().unwrap();
+        let plan = plan_external_pair(&config, external.path());
+        let actions: Vec<_> = plan.actions.iter().collect();
+
+        let copied = copy_external_plugins(&actions, &config, |src, dst| {
+            copy_plugin(src, dst, &config.settings)
+        })
+        .unwrap();
+        assert_eq!(copied.len(), 2);
+        assert!(copied.iter().all(|dir| dir.join("README.md").is_file()));
+    }
+
     #[test]
     fn add_multiple_plugins() {
         let tmp = TempDir::new().unwrap();
@@ -670,11 +1606,591 @@ mod tests {
 
         assert_eq!(plan.actions.len(), 2);
 
-        let added = execute_add(&plan, &config, false).unwrap();
+        let added = execute_add(&plan, &config, false, true, false).unwrap();
         assert_eq!(added.len(), 2);
 
         let content = fs::read_to_string(&config.marketplace_path).unwrap();
         let mp: Marketplace = serde_json::from_str(&content).unwrap();
         assert_eq!(mp.plugins.len(), 2);
     }
+
+    #[test]
+    fn add_with_final_validation_catches_corruption() {
+        let tmp = TempDir::new().unwrap();
+        // "ghost" has no directory, so the marketplace fails validation.
+        let config = setup_marketplace(&tmp, r#"{"name":"ghost","source":"ghost"}"#);
+        create_plugin(&config.plugin_root_abs, "my-plugin");
+        let before = fs::read_to_string(&config.marketplace_path).unwrap();
+
+        let plan = plan_add(&["my-plugin".to_string()], &config, "abort", false).unwrap();
+        let result = execute_add(&plan, &config, false, true, false);
+
+        assert!(matches!(result, Err(SoukError::AtomicRollback(_))));
+        assert_eq!(
+            fs::read_to_string(&config.marketplace_path).unwrap(),
+            before
+        );
+    }
+
+    #[test]
+    fn add_without_final_validation_still_writes() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, r#"{"name":"ghost","source":"ghost"}"#);
+        create_plugin(&config.plugin_root_abs, "my-plugin");
+
+        let plan = plan_add(&["my-plugin".to_string()], &config, "abort", false).unwrap();
+        let added = execute_add(&plan, &config, false, false, false).unwrap();
+        assert_eq!(added, vec!["my-plugin"]);
+
+        let content = fs::read_to_string(&config.marketplace_path).unwrap();
+        let mp: Marketplace = serde_json::from_str(&content).unwrap();
+        assert_eq!(mp.version, "0.1.1");
+        assert!(mp.plugins.iter().any(|p| p.name == "my-plugin"));
+        assert!(mp.plugins.iter().any(|p| p.name == "ghost"));
+    }
+
+    #[test]
+    fn plan_warns_on_bare_plugin() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        create_plugin(&config.plugin_root_abs, "bare");
+
+        let plan = plan_add(&["bare".to_string()], &config, "abort", false).unwrap();
+
+        assert_eq!(plan.actions.len(), 1);
+        assert_eq!(
+            plan.warnings,
+            vec!["bare: no README.md", "bare: no skills, commands, or agents"]
+        );
+    }
+
+    #[test]
+    fn plan_has_no_warnings_for_complete_plugin() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let plugin = create_plugin(&config.plugin_root_abs, "complete");
+        fs::write(plugin.join("README.md"), "# complete").unwrap();
+        fs::create_dir_all(plugin.join("commands")).unwrap();
+        fs::write(
+            plugin.join("commands").join("run.md"),
+            "---\ndescription: Run it\n---\nRun it",
+        )
+        .unwrap();
+
+        let plan = plan_add(&["complete".to_string()], &config, "abort", false).unwrap();
+
+        assert!(plan.warnings.is_empty(), "{:?}", plan.warnings);
+    }
+
+    /// Runs git in `dir` with a throwaway identity, panicking on failure.
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(["-c", "user.name=souk", "-c", "user.email=souk@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("git failed to run");
+        assert!(output.status.success(), "git {args:?}: {output:?}");
+    }
+
+    /// A bare repository whose root is the plugin `git-plugin`, tagged `v1`
+    /// at version 1.0.0 and then bumped to 2.0.0.
+    fn bare_plugin_repo(base: &Path) -> PathBuf {
+        let work = create_plugin(base, "git-plugin");
+        git(&work, &["init", "--quiet"]);
+        git(&work, &["add", "."]);
+        git(&work, &["commit", "--quiet", "-m", "v1"]);
+        git(&work, &["tag", "v1"]);
+        let manifest = work.join(".claude-plugin/plugin.json");
+        let bumped = fs::read_to_string(&manifest)
+            .unwrap()
+            .replace("1.0.0", "2.0.0");
+        fs::write(&manifest, bumped).unwrap();
+        git(&work, &["commit", "--quiet", "-am", "v2"]);
+
+        let bare = base.join("git-plugin.git");
+        git(
+            base,
+            &["clone", "--quiet", "--bare", "git-plugin", "git-plugin.git"],
+        );
+        bare
+    }
+
+    #[test]
+    fn git_source_detection() {
+        assert_eq!(
+            git_source("https://github.com/me/plugin.git#v1.2"),
+            Some(("https://github.com/me/plugin.git", Some("v1.2")))
+        );
+        assert_eq!(
+            git_source("git@github.com:me/plugin"),
+            Some(("git@github.com:me/plugin", None))
+        );
+        assert_eq!(
+            git_source("/srv/repos/plugin.git"),
+            Some(("/srv/repos/plugin.git", None))
+        );
+        assert_eq!(git_source("./plugins/my-plugin"), None);
+        assert_eq!(git_source("my-plugin"), None);
+    }
+
+    #[test]
+    fn add_from_git_repo_at_ref() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let repos = TempDir::new().unwrap();
+        let bare = bare_plugin_repo(repos.path());
+
+        let input = format!("{}#v1", bare.display());
+        let plan = plan_add(std::slice::from_ref(&input), &config, "abort", true).unwrap();
+        assert_eq!(plan.actions[0].source, "git-plugin");
+        execute_add(&plan, &config, false, true, false).unwrap();
+
+        let copied = config.plugin_root_abs.join("git-plugin");
+        let manifest = read_plugin_manifest(&copied).unwrap();
+        assert_eq!(manifest.version_str(), Some("1.0.0"));
+        assert!(!copied.join(".git").exists());
+
+        let updated = load_marketplace_config(&config.marketplace_path).unwrap();
+        let entry = &updated.marketplace.plugins[0];
+        assert_eq!(entry.source, "git-plugin");
+        assert_eq!(entry.origin.as_deref(), Some(input.as_str()));
+        let tagged = head_sha(&repos.path().join("git-plugin"), "v1");
+        assert_eq!(entry.sha.as_deref(), Some(tagged.as_str()));
+    }
+
+    /// The commit `rev` resolves to in the repository at `dir`.
+    fn head_sha(dir: &Path, rev: &str) -> String {
+        run_git(
+            Command::new("git")
+                .args(["rev-parse", rev])
+                .current_dir(dir),
+            "git rev-parse",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn refresh_origin_sha_follows_the_origin() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let repos = TempDir::new().unwrap();
+        bare_plugin_repo(repos.path());
+        let work = repos.path().join("git-plugin");
+        let origin = format!("file://{}", work.display());
+
+        let plan = plan_add(std::slice::from_ref(&origin), &config, "abort", false).unwrap();
+        execute_add(&plan, &config, false, true, false).unwrap();
+        let first = head_sha(&work, "HEAD");
+
+        fs::write(work.join("README.md"), "# git-plugin").unwrap();
+        git(&work, &["add", "."]);
+        git(&work, &["commit", "--quiet", "-m", "readme"]);
+        let second = head_sha(&work, "HEAD");
+        assert_ne!(first, second);
+
+        let config = load_marketplace_config(&config.marketplace_path).unwrap();
+        assert_eq!(
+            config.marketplace.plugins[0].sha.as_deref(),
+            Some(first.as_str())
+        );
+        let names = vec!["git-plugin".to_string()];
+        update_plugins(
+            &names,
+            None,
+            None,
+            false,
+            UpdateFields::ORIGIN_SHA,
+            true,
+            &config,
+        )
+        .unwrap();
+
+        let updated = load_marketplace_config(&config.marketplace_path).unwrap();
+        let entry = &updated.marketplace.plugins[0];
+        assert_eq!(entry.origin.as_deref(), Some(origin.as_str()));
+        assert_eq!(entry.sha.as_deref(), Some(second.as_str()));
+        // The copied plugin is left as it was added
+        assert!(!config.plugin_root_abs.join("git-plugin/README.md").exists());
+    }
+
+    #[test]
+    fn add_from_git_repo_finds_nested_plugin() {
+        let repos = TempDir::new().unwrap();
+        let work = repos.path().join("monorepo");
+        create_plugin(&work.join("plugins"), "nested");
+        git(&work, &["init", "--quiet"]);
+        git(&work, &["add", "."]);
+        git(&work, &["commit", "--quiet", "-m", "init"]);
+
+        let (plugin, _checkout, _) = clone_plugin(&work.to_string_lossy(), None).unwrap();
+        assert!(plugin.ends_with("monorepo/plugins/nested"));
+    }
+
+    #[test]
+    fn git_clone_failure_is_reported() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let missing = tmp.path().join("missing.git");
+
+        let err = plan_add(
+            &[missing.to_string_lossy().to_string()],
+            &config,
+            "abort",
+            false,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("git clone of"), "{err}");
+
+        let repos = TempDir::new().unwrap();
+        let bare = bare_plugin_repo(repos.path());
+        let err = plan_add(
+            &[format!("{}#no-such-ref", bare.display())],
+            &config,
+            "abort",
+            false,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("git checkout of 'no-such-ref'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn option_like_git_input_is_not_read_as_an_option() {
+        let repos = TempDir::new().unwrap();
+        let bare = bare_plugin_repo(repos.path());
+
+        let err = clone_plugin(&bare.to_string_lossy(), Some("--orphan=x")).unwrap_err();
+        assert!(
+            err.to_string().contains("refs cannot start with '-'"),
+            "{err}"
+        );
+
+        // A URL that looks like an option is passed as the repository
+        let err = clone_plugin("--upload-pack=false.git", None).unwrap_err();
+        assert!(err.to_string().contains("git clone of"), "{err}");
+    }
+
+    const ARCHIVED_MANIFEST: &str =
+        r#"{"name":"packed","version":"1.0.0","description":"A test plugin"}"#;
+
+    /// Writes a `.tar.gz` holding `files` as `(path, contents)` pairs.
+    fn write_tarball(path: &Path, files: &[(&str, &str)]) {
+        let encoder =
+            flate2::write::GzEncoder::new(fs::File::create(path).unwrap(), Default::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            builder
+                .append_data(&mut header, name, contents.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    /// Writes a `.zip` holding `files` as `(path, contents)` pairs.
+    fn write_zip(path: &Path, files: &[(&str, &str)]) {
+        use std::io::Write;
+
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, contents) in files {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn add_from_tarball() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let artifacts = TempDir::new().unwrap();
+        let tarball = artifacts.path().join("packed-1.0.0.tar.gz");
+        write_tarball(
+            &tarball,
+            &[
+                (".claude-plugin/plugin.json", ARCHIVED_MANIFEST),
+                ("README.md", "# Packed"),
+            ],
+        );
+
+        let plan = plan_add(
+            &[tarball.to_string_lossy().to_string()],
+            &config,
+            "abort",
+            true,
+        )
+        .unwrap();
+        assert_eq!(plan.actions[0].source, "packed");
+        execute_add(&plan, &config, false, true, false).unwrap();
+
+        let copied = config.plugin_root_abs.join("packed");
+        assert!(copied.join("README.md").is_file());
+        assert!(copied.join(".claude-plugin/plugin.json").is_file());
+    }
+
+    #[test]
+    fn add_from_file_url_tarball() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let artifacts = TempDir::new().unwrap();
+        let tarball = artifacts.path().join("packed-1.0.0.tar.gz");
+        write_tarball(
+            &tarball,
+            &[(".claude-plugin/plugin.json", ARCHIVED_MANIFEST)],
+        );
+        let url = format!("file://{}", tarball.display());
+
+        let plan = plan_add(&[url], &config, "abort", false).unwrap();
+        assert_eq!(plan.actions[0].plugin_name, "packed");
+        execute_add(&plan, &config, false, true, false).unwrap();
+        assert!(config
+            .plugin_root_abs
+            .join("packed/.claude-plugin/plugin.json")
+            .is_file());
+
+        let missing = format!("file://{}", artifacts.path().join("gone.tgz").display());
+        let err = plan_add(&[missing], &config, "abort", false).unwrap_err();
+        assert!(err.to_string().contains("Archive not found"), "{err}");
+    }
+
+    #[test]
+    fn add_downloads_tarball_url() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let artifacts = TempDir::new().unwrap();
+        let tarball = artifacts.path().join("remote.tar.gz");
+        write_tarball(
+            &tarball,
+            &[(".claude-plugin/plugin.json", ARCHIVED_MANIFEST)],
+        );
+        let body = fs::read(&tarball).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(&body).unwrap();
+            request_line
+        });
+
+        let url = format!("http://127.0.0.1:{port}/releases/remote.tar.gz?token=x");
+        assert_eq!(archive_url_kind(&url), Some(ArchiveKind::TarGz));
+        let (plugin, _extracted) = fetch_archive(&url, ArchiveKind::TarGz).unwrap();
+        assert!(plugin.ends_with("remote"));
+        assert_eq!(
+            read_plugin_manifest(&plugin).unwrap().name_str(),
+            Some("packed")
+        );
+        assert!(server
+            .join()
+            .unwrap()
+            .starts_with("GET /releases/remote.tar.gz?token=x "));
+    }
+
+    #[test]
+    fn archive_with_wrong_contents_is_rejected() {
+        let artifacts = TempDir::new().unwrap();
+        let fake = artifacts.path().join("plugin.tar.gz");
+        fs::write(&fake, "<html>Not Found</html>").unwrap();
+        let err = extract_plugin(&fake, ArchiveKind::TarGz).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("is not a valid gzip-compressed tar archive"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn archive_urls_are_told_apart_from_git_urls() {
+        assert_eq!(
+            archive_url_kind("https://example.com/p.zip#frag"),
+            Some(ArchiveKind::Zip)
+        );
+        assert_eq!(archive_url_kind("https://github.com/o/p.git"), None);
+        assert_eq!(archive_url_kind("./local.tar.gz"), None);
+    }
+
+    #[test]
+    fn add_from_zip_with_nested_plugin() {
+        let artifacts = TempDir::new().unwrap();
+        let archive = artifacts.path().join("release.zip");
+        write_zip(
+            &archive,
+            &[
+                ("release/NOTES.txt", "notes"),
+                (
+                    "release/packed/.claude-plugin/plugin.json",
+                    ARCHIVED_MANIFEST,
+                ),
+            ],
+        );
+
+        let (plugin, _extracted) = extract_plugin(&archive, ArchiveKind::Zip).unwrap();
+        assert!(plugin.ends_with("release/release/packed"));
+        assert_eq!(
+            read_plugin_manifest(&plugin).unwrap().name_str(),
+            Some("packed")
+        );
+    }
+
+    #[test]
+    fn archive_entries_outside_root_are_rejected() {
+        let artifacts = TempDir::new().unwrap();
+        let archive = artifacts.path().join("evil.zip");
+        write_zip(
+            &archive,
+            &[
+                (".claude-plugin/plugin.json", ARCHIVED_MANIFEST),
+                ("../escaped.txt", "gotcha"),
+            ],
+        );
+
+        let err = extract_plugin(&archive, ArchiveKind::Zip).unwrap_err();
+        assert!(err.to_string().contains("outside its root"), "{err}");
+    }
+
+    #[test]
+    fn pax_tarball_from_git_archive_is_extracted() {
+        let artifacts = TempDir::new().unwrap();
+        let tarball = artifacts.path().join("packed-1.0.0.tar.gz");
+        let encoder =
+            flate2::write::GzEncoder::new(fs::File::create(&tarball).unwrap(), Default::default());
+        let mut builder = tar::Builder::new(encoder);
+
+        // `git archive` leads with a pax global header holding the commit id
+        let comment = format!("52 comment={}\n", "a".repeat(40));
+        let mut global = tar::Header::new_ustar();
+        global.set_path("pax_global_header").unwrap();
+        global.set_entry_type(tar::EntryType::XGlobalHeader);
+        global.set_size(comment.len() as u64);
+        global.set_cksum();
+        builder.append(&global, comment.as_bytes()).unwrap();
+
+        let long_name = format!("packed-1.0.0/skills/{}/SKILL.md", "s".repeat(120));
+        for (name, contents) in [
+            ("packed-1.0.0/.claude-plugin/plugin.json", ARCHIVED_MANIFEST),
+            (long_name.as_str(), "# Skill"),
+        ] {
+            let mut header = tar::Header::new_ustar();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            builder
+                .append_data(&mut header, name, contents.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let (plugin, _extracted) = extract_plugin(&tarball, ArchiveKind::TarGz).unwrap();
+        assert!(plugin.ends_with("packed-1.0.0"), "{}", plugin.display());
+        assert!(!plugin.join("pax_global_header").exists());
+        assert_eq!(plugin.parent().unwrap().read_dir().unwrap().count(), 1);
+        assert!(plugin.join(&long_name["packed-1.0.0/".len()..]).is_file());
+    }
+
+    #[test]
+    fn tarball_links_are_rejected() {
+        let artifacts = TempDir::new().unwrap();
+        let tarball = artifacts.path().join("linked.tar.gz");
+        let encoder =
+            flate2::write::GzEncoder::new(fs::File::create(&tarball).unwrap(), Default::default());
+        let mut builder = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(ARCHIVED_MANIFEST.len() as u64);
+        header.set_mode(0o644);
+        builder
+            .append_data(
+                &mut header,
+                ".claude-plugin/plugin.json",
+                ARCHIVED_MANIFEST.as_bytes(),
+            )
+            .unwrap();
+        let mut link = tar::Header::new_gnu();
+        link.set_entry_type(tar::EntryType::Symlink);
+        link.set_size(0);
+        builder
+            .append_link(&mut link, "secrets", "/etc/passwd")
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let err = extract_plugin(&tarball, ArchiveKind::TarGz).unwrap_err();
+        assert!(err.to_string().contains("unsupported entry type"), "{err}");
+    }
+
+    #[test]
+    fn archive_without_plugin_is_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(&tmp, "");
+        let artifacts = TempDir::new().unwrap();
+        let tarball = artifacts.path().join("docs.tgz");
+        write_tarball(&tarball, &[("docs/README.md", "# Docs")]);
+
+        let err = plan_add(
+            &[tarball.to_string_lossy().to_string()],
+            &config,
+            "abort",
+            false,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("No plugin found"), "{err}");
+    }
+
+    #[test]
+    fn stale_temp_dirs_are_old_souk_staging_dirs() {
+        let tmp = TempDir::new().unwrap();
+        for name in [
+            "souk-add-old",
+            "souk-download-old",
+            "souk-add-new",
+            "other-old",
+        ] {
+            fs::create_dir(tmp.path().join(name)).unwrap();
+        }
+        fs::write(tmp.path().join("souk-add-file"), "").unwrap();
+        let old = std::time::SystemTime::now() - std::time::Duration::from_secs(2 * 60 * 60);
+        for name in [
+            "souk-add-old",
+            "souk-download-old",
+            "other-old",
+            "souk-add-file",
+        ] {
+            fs::File::open(tmp.path().join(name))
+                .unwrap()
+                .set_modified(old)
+                .unwrap();
+        }
+
+        let stale =
+            find_stale_temp_dirs(tmp.path(), std::time::Duration::from_secs(60 * 60)).unwrap();
+        assert_eq!(
+            stale,
+            vec![
+                tmp.path().join("souk-add-old"),
+                tmp.path().join("souk-download-old"),
+            ]
+        );
+    }
 }
@@ -0,0 +1,223 @@
+//! Archive plugins in the marketplace.
+//!
+//! Moves one or more entries from `plugins` into the `archived` array of
+//! `marketplace.json`, keeping their files. Optionally moves each plugin
+//! directory out of pluginRoot into `.souk/archive/`.
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::discovery::{load_marketplace_config, MarketplaceConfig};
+use crate::error::SoukError;
+use crate::ops::AtomicGuard;
+use crate::resolution::resolve_source;
+use crate::types::Marketplace;
+use crate::validation::validate_marketplace;
+use crate::version::bump_patch;
+
+/// Directory, relative to the project root, that archived plugin
+/// directories are moved into.
+pub const ARCHIVE_DIR: &str = ".souk/archive";
+
+/// The result of an archive operation.
+#[derive(Debug)]
+pub struct ArchiveResult {
+    /// Plugin names that were moved to the `archived` array.
+    pub archived: Vec<String>,
+    /// Directories moved into [`ARCHIVE_DIR`], as `(from, to)` pairs.
+    pub moved: Vec<(PathBuf, PathBuf)>,
+    /// Non-fatal warnings (e.g., a directory could not be moved).
+    pub warnings: Vec<String>,
+}
+
+/// Archives the named plugins.
+///
+/// For each name in `names`, the entry is moved from `plugins` to
+/// `archived` in marketplace.json and the marketplace version is bumped
+/// (patch). If `move_files` is true, plugin directories inside pluginRoot
+/// are then moved to `<project_root>/.souk/archive/<dir>`.
+///
+/// # Errors
+///
+/// Returns [`SoukError::PluginNotFound`] if any name is not an active
+/// plugin, or [`SoukError::AtomicRollback`] if the marketplace fails
+/// validation after the update.
+pub fn archive_plugins(
+    names: &[String],
+    move_files: bool,
+    config: &MarketplaceConfig,
+) -> Result<ArchiveResult, SoukError> {
+    let mut result = ArchiveResult {
+        archived: Vec::new(),
+        moved: Vec::new(),
+        warnings: Vec::new(),
+    };
+    if names.is_empty() {
+        return Ok(result);
+    }
+
+    for name in names {
+        if !config.marketplace.plugins.iter().any(|p| p.name == *name) {
+            return Err(SoukError::PluginNotFound(name.clone()));
+        }
+    }
+
+    // Resolve directories before mutating so a bad source fails early.
+    let mut move_targets = Vec::new();
+    if move_files {
+        let archive_root = config.project_root.join(ARCHIVE_DIR);
+        for name in names {
+            let entry = config
+                .marketplace
+                .plugins
+                .iter()
+                .find(|p| p.name == *name)
+                .unwrap();
+            let plugin_path = resolve_source(&entry.source, config)?;
+            if !plugin_path.is_dir() {
+                continue;
+            }
+            if !plugin_path.starts_with(&config.plugin_root_abs) {
+                result.warnings.push(format!(
+                    "Not moving '{name}': {} is outside pluginRoot",
+                    plugin_path.display()
+                ));
+                continue;
+            }
+            let dir_name = plugin_path
+                .file_name()
+                .map(|n| n.to_os_string())
+                .unwrap_or_else(|| name.into());
+            let target = archive_root.join(dir_name);
+            if target.exists() {
+                return Err(SoukError::Other(format!(
+                    "Archive target already exists: {}",
+                    target.display()
+                )));
+            }
+            move_targets.push((plugin_path, target));
+        }
+    }
+
+    let guard = AtomicGuard::new(&config.marketplace_path)?;
+
+    let content = fs::read_to_string(&config.marketplace_path)?;
+    let mut marketplace: Marketplace = serde_json::from_str(&content)?;
+
+    for name in names {
+        if let Some(pos) = marketplace.plugins.iter().position(|p| p.name == *name) {
+            let entry = marketplace.plugins.remove(pos);
+            marketplace.archived.push(entry);
+            result.archived.push(name.clone());
+        }
+    }
+
+    marketplace.version = bump_patch(&marketplace.version)?;
+
+    let json = serde_json::to_string_pretty(&marketplace)?;
+    fs::write(&config.marketplace_path, format!("{json}\n"))?;
+
+    let updated_config = load_marketplace_config(&config.marketplace_path)?;
+    let validation = validate_marketplace(&updated_config, true);
+    if validation.has_errors() {
+        drop(guard);
+        return Err(SoukError::AtomicRollback(
+            "Validation failed after archive".to_string(),
+        ));
+    }
+
+    guard.commit()?;
+
+    // Move directories AFTER the marketplace update succeeded
+    for (from, to) in move_targets {
+        let moved = to
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::rename(&from, &to));
+        match moved {
+            Ok(()) => result.moved.push((from, to)),
+            Err(e) => result.warnings.push(format!(
+                "Archived entry but failed to move {} to {}: {e}",
+                from.display(),
+                to.display()
+            )),
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::find_orphaned_dirs;
+    use tempfile::TempDir;
+
+    fn setup_marketplace_with_plugins(tmp: &TempDir, plugin_names: &[&str]) -> MarketplaceConfig {
+        let claude_dir = tmp.path().join(".claude-plugin");
+        fs::create_dir_all(&claude_dir).unwrap();
+        let plugins_dir = tmp.path().join("plugins");
+        fs::create_dir_all(&plugins_dir).unwrap();
+
+        let mut entries = Vec::new();
+        for name in plugin_names {
+            let plugin_claude = plugins_dir.join(name).join(".claude-plugin");
+            fs::create_dir_all(&plugin_claude).unwrap();
+            fs::write(
+                plugin_claude.join("plugin.json"),
+                format!(r#"{{"name":"{name}","version":"1.0.0","description":"test plugin"}}"#),
+            )
+            .unwrap();
+            entries.push(format!(r#"{{"name":"{name}","source":"{name}"}}"#));
+        }
+
+        let mp_json = format!(
+            r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{}]}}"#,
+            entries.join(",")
+        );
+        fs::write(claude_dir.join("marketplace.json"), &mp_json).unwrap();
+        load_marketplace_config(&claude_dir.join("marketplace.json")).unwrap()
+    }
+
+    #[test]
+    fn archive_moves_entry_and_keeps_files() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+
+        let result = archive_plugins(&["alpha".to_string()], false, &config).unwrap();
+        assert_eq!(result.archived, vec!["alpha"]);
+        assert!(result.moved.is_empty());
+
+        let updated = load_marketplace_config(&config.marketplace_path).unwrap();
+        assert_eq!(updated.marketplace.version, "0.1.1");
+        assert_eq!(updated.marketplace.plugins.len(), 1);
+        assert_eq!(updated.marketplace.archived[0].name, "alpha");
+        assert!(config.plugin_root_abs.join("alpha").is_dir());
+
+        // The kept directory must not be treated as an orphan (and pruned).
+        assert!(find_orphaned_dirs(&updated).unwrap().is_empty());
+        assert!(!validate_marketplace(&updated, false).has_errors());
+    }
+
+    #[test]
+    fn archive_with_move_files_relocates_directory() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+
+        let result = archive_plugins(&["alpha".to_string()], true, &config).unwrap();
+
+        let target = config.project_root.join(ARCHIVE_DIR).join("alpha");
+        assert_eq!(result.moved.len(), 1);
+        assert!(target.join(".claude-plugin/plugin.json").is_file());
+        assert!(!config.plugin_root_abs.join("alpha").exists());
+    }
+
+    #[test]
+    fn archive_unknown_plugin_fails() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+
+        let result = archive_plugins(&["ghost".to_string()], false, &config);
+        assert!(matches!(result, Err(SoukError::PluginNotFound(_))));
+    }
+}
@@ -24,16 +24,23 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::SoukError;
 
+/// Per-process sequence number appended to backup names, so guards created
+/// within the same clock tick (or on another thread) never share a backup.
+static BACKUP_SEQ: AtomicU64 = AtomicU64::new(0);
+
 /// An RAII guard that backs up a file before mutation and restores it on drop
 /// unless explicitly committed.
 ///
-/// The backup file is named `{original}.bak.{epoch_nanos}.{pid}` and lives alongside
-/// the original. This mirrors the pattern used by the shell-based atomic helpers
-/// in `temp-reference-scripts/lib/atomic.sh`.
+/// The backup file is named `{original}.bak.{epoch_nanos}.{seq}.{pid}` and lives
+/// alongside the original. `seq` is a per-process counter, so two guards on the
+/// same file never share a backup even when the clock has not advanced. This
+/// mirrors the pattern used by the shell-based atomic helpers in
+/// `temp-reference-scripts/lib/atomic.sh`.
 ///
 /// # Behavior
 ///
@@ -51,6 +58,9 @@ pub struct AtomicGuard {
     backup_path: Option<PathBuf>,
     /// Whether [`commit`](AtomicGuard::commit) has been called.
     committed: bool,
+    /// Remove the file on drop if it did not exist when the guard was made;
+    /// see [`new_removing_created`](AtomicGuard::new_removing_created).
+    remove_created: bool,
 }
 
 impl AtomicGuard {
@@ -71,15 +81,17 @@ impl AtomicGuard {
                 .duration_since(UNIX_EPOCH)
                 .expect("system clock is before UNIX epoch")
                 .as_nanos();
+            let seq = BACKUP_SEQ.fetch_add(1, Ordering::Relaxed);
             let pid = std::process::id();
 
             let backup = original_path.with_extension(format!(
-                "{}.bak.{}.{}",
+                "{}.bak.{}.{}.{}",
                 original_path
                     .extension()
                     .and_then(|e| e.to_str())
                     .unwrap_or(""),
                 nanos,
+                seq,
                 pid
             ));
 
@@ -93,9 +105,22 @@ impl AtomicGuard {
             original_path,
             backup_path,
             committed: false,
+            remove_created: false,
         })
     }
 
+    /// Like [`new`](AtomicGuard::new), but if the file does not exist yet,
+    /// a file the operation creates at `path` is removed on drop.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoukError::Io`] if the file exists but cannot be copied.
+    pub fn new_removing_created(path: &Path) -> Result<Self, SoukError> {
+        let mut guard = Self::new(path)?;
+        guard.remove_created = guard.backup_path.is_none();
+        Ok(guard)
+    }
+
     /// Returns the path to the backup file, if one was created.
     pub fn backup_path(&self) -> Option<&Path> {
         self.backup_path.as_deref()
@@ -134,6 +159,17 @@ impl Drop for AtomicGuard {
             return;
         }
 
+        if self.remove_created && self.original_path.exists() {
+            if let Err(e) = fs::remove_file(&self.original_path) {
+                eprintln!(
+                    "Warning: failed to remove {}: {}",
+                    self.original_path.display(),
+                    e
+                );
+            }
+            return;
+        }
+
         if let Some(ref backup) = self.backup_path {
             if backup.exists() {
                 if let Err(e) = fs::copy(backup, &self.original_path) {
@@ -157,6 +193,59 @@ impl Drop for AtomicGuard {
     }
 }
 
+/// How old an [`AtomicGuard`] backup must be before [`find_stale_backups`]
+/// treats it as left behind. No souk command holds a guard for this long.
+pub const STALE_BACKUP_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Find backups in `dir` that an [`AtomicGuard`] never committed or
+/// restored, because the process was killed mid-operation.
+///
+/// Only files named exactly like a guard's backup
+/// (`{original}.bak.{epoch_nanos}.{seq}.{pid}`) whose timestamp is older than
+/// `older_than` are returned, sorted by path. A missing `dir` has no backups.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Io`] if `dir` exists but cannot be read.
+pub fn find_stale_backups(dir: &Path, older_than: Duration) -> Result<Vec<PathBuf>, SoukError> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let cutoff = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before UNIX epoch")
+        .saturating_sub(older_than)
+        .as_nanos();
+
+    let mut stale = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let name = entry.file_name();
+        if backup_timestamp(&name.to_string_lossy()).is_some_and(|nanos| nanos < cutoff) {
+            stale.push(entry.path());
+        }
+    }
+    stale.sort();
+    Ok(stale)
+}
+
+/// The `epoch_nanos` of a guard backup's file name, or `None` if `name` is
+/// not one.
+fn backup_timestamp(name: &str) -> Option<u128> {
+    let mut parts = name.rsplitn(4, '.');
+    let (pid, seq, nanos, rest) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    let numeric = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !(numeric(pid) && numeric(seq) && numeric(nanos)) {
+        return None;
+    }
+    rest.strip_suffix(".bak")
+        .filter(|original| !original.is_empty())?;
+    nanos.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -271,6 +360,28 @@ mod tests {
         assert!(!file_path.exists());
     }
 
+    #[test]
+    fn removing_created_guard_deletes_file_created_after_it() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let file_path = dir.path().join("CHANGELOG.md");
+
+        let guard = AtomicGuard::new_removing_created(&file_path).unwrap();
+        fs::write(&file_path, "new").unwrap();
+        drop(guard);
+        assert!(!file_path.exists());
+
+        let guard = AtomicGuard::new_removing_created(&file_path).unwrap();
+        fs::write(&file_path, "kept").unwrap();
+        guard.commit().unwrap();
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "kept");
+
+        // An existing file is restored as with `new`
+        let guard = AtomicGuard::new_removing_created(&file_path).unwrap();
+        fs::write(&file_path, "changed").unwrap();
+        drop(guard);
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "kept");
+    }
+
     #[test]
     fn guard_on_nonexistent_file_commit_is_noop() {
         let dir = TempDir::new().expect("failed to create temp dir");
@@ -374,4 +485,84 @@ mod tests {
         guard1.commit().unwrap();
         guard2.commit().unwrap();
     }
+
+    #[test]
+    fn concurrent_guards_on_same_file_get_distinct_backups() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let file_path = dir.path().join("marketplace.json");
+        fs::write(&file_path, "original").unwrap();
+
+        let guards: Vec<AtomicGuard> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| AtomicGuard::new(&file_path).unwrap()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        let mut paths: Vec<PathBuf> = guards
+            .iter()
+            .map(|g| g.backup_path().unwrap().to_path_buf())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        assert_eq!(paths.len(), 8, "every guard should have its own backup");
+
+        for guard in guards {
+            guard.commit().unwrap();
+        }
+    }
+
+    #[test]
+    fn nested_guards_each_restore_their_snapshot() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let file_path = dir.path().join("marketplace.json");
+        fs::write(&file_path, "v1").unwrap();
+
+        let outer = AtomicGuard::new(&file_path).unwrap();
+        fs::write(&file_path, "v2").unwrap();
+        let inner = AtomicGuard::new(&file_path).unwrap();
+        assert_ne!(outer.backup_path(), inner.backup_path());
+        fs::write(&file_path, "v3").unwrap();
+
+        drop(inner);
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "v2");
+        drop(outer);
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "v1");
+    }
+
+    #[test]
+    fn stale_backups_match_only_old_guard_backups() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let file_path = dir.path().join("marketplace.json");
+        fs::write(&file_path, "v1").unwrap();
+        for name in [
+            "marketplace.json.bak.1000.0.4242",
+            "plugin.json.bak.2000.3.17",
+            "notes.bak",
+            "marketplace.json.bak.manual.0.1",
+            "marketplace.json.bak.1000.0",
+        ] {
+            fs::write(dir.path().join(name), "old").unwrap();
+        }
+        let live = AtomicGuard::new(&file_path).unwrap();
+
+        let stale = find_stale_backups(dir.path(), STALE_BACKUP_AGE).unwrap();
+        assert_eq!(
+            stale,
+            vec![
+                dir.path().join("marketplace.json.bak.1000.0.4242"),
+                dir.path().join("plugin.json.bak.2000.3.17"),
+            ]
+        );
+
+        let all = find_stale_backups(dir.path(), Duration::ZERO).unwrap();
+        assert!(all.iter().any(|p| Some(p.as_path()) == live.backup_path()));
+        live.commit().unwrap();
+
+        assert!(
+            find_stale_backups(&dir.path().join("missing"), Duration::ZERO)
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,165 @@
+//! Append-only audit log of mutating commands.
+//!
+//! When souk.toml sets `audit_log`, every command that changes the
+//! marketplace appends one JSON line to that file once it has finished,
+//! whether it succeeded or was rolled back:
+//!
+//! ```json
+//! {"timestamp":"2026-01-01T00:00:00Z","command":"add","plugins":["lint"],"old_version":"0.1.0","new_version":"0.1.1","outcome":"success"}
+//! ```
+//!
+//! Entries are written after the command's guards have committed or
+//! restored their files, so the log never affects whether a change lands.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::SoukConfig;
+use crate::discovery::MarketplaceConfig;
+use crate::error::SoukError;
+use crate::types::Marketplace;
+use crate::util::current_timestamp;
+
+/// Whether an audited command succeeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+/// One line of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the command finished, as RFC 3339 UTC.
+    pub timestamp: String,
+    /// The souk command, such as `add` or `update`.
+    pub command: String,
+    /// Plugins the command acted on.
+    pub plugins: Vec<String>,
+    /// Marketplace version before the command, if there was a marketplace.
+    pub old_version: Option<String>,
+    /// Marketplace version after the command, if it can be read.
+    pub new_version: Option<String>,
+    pub outcome: AuditOutcome,
+    /// Why the command failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    /// A successful run of `command` on `plugins`, timestamped now.
+    pub fn new(command: &str, plugins: Vec<String>) -> Self {
+        Self {
+            timestamp: current_timestamp(),
+            command: command.to_string(),
+            plugins,
+            old_version: None,
+            new_version: None,
+            outcome: AuditOutcome::Success,
+            error: None,
+        }
+    }
+
+    /// Mark the entry as a failure with `error`.
+    pub fn failed(mut self, error: impl ToString) -> Self {
+        self.outcome = AuditOutcome::Failure;
+        self.error = Some(error.to_string());
+        self
+    }
+}
+
+/// Append `entry` as a JSON line to the log at `path`, creating the file
+/// and its parent directories if needed.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Io`] if the log cannot be written.
+pub fn append_audit(path: &Path, entry: &AuditEntry) -> Result<(), SoukError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(entry)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+/// Log `entry` for a command that ran against `config`, if souk.toml sets
+/// `audit_log`.
+///
+/// `old_version` is taken from `config`, which was loaded before the
+/// command ran, and `new_version` is read back from marketplace.json.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Io`] if the log cannot be written.
+pub fn record_audit(config: &MarketplaceConfig, mut entry: AuditEntry) -> Result<(), SoukError> {
+    let Some(log) = &config.settings.audit_log else {
+        return Ok(());
+    };
+    entry.old_version = Some(config.marketplace.version.clone());
+    entry.new_version = read_version(&config.marketplace_path);
+    append_audit(log, &entry)
+}
+
+/// Log `entry` for `souk init` at `project_root`, if the souk.toml there
+/// sets `audit_log`.
+///
+/// There is no earlier marketplace, so only `new_version` is recorded, and
+/// only when the scaffold succeeded.
+///
+/// # Errors
+///
+/// Returns an error if souk.toml is invalid or the log cannot be written.
+pub fn record_init_audit(project_root: &Path, mut entry: AuditEntry) -> Result<(), SoukError> {
+    let Some(log) = SoukConfig::load(project_root)?.audit_log else {
+        return Ok(());
+    };
+    if entry.outcome == AuditOutcome::Success {
+        entry.new_version =
+            read_version(&project_root.join(".claude-plugin").join("marketplace.json"));
+    }
+    append_audit(&log, &entry)
+}
+
+/// The version in the marketplace.json at `path`, if it can be read.
+pub fn read_version(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let marketplace: Marketplace = serde_json::from_str(&content).ok()?;
+    Some(marketplace.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn entries_append_one_line_each() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join(".souk").join("audit.log");
+
+        append_audit(&log, &AuditEntry::new("add", vec!["a".to_string()])).unwrap();
+        append_audit(
+            &log,
+            &AuditEntry::new("remove", vec!["b".to_string()]).failed("Plugin not found: b"),
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(&log).unwrap();
+        let entries: Vec<AuditEntry> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command, "add");
+        assert_eq!(entries[0].outcome, AuditOutcome::Success);
+        assert_eq!(entries[1].outcome, AuditOutcome::Failure);
+        assert_eq!(entries[1].error.as_deref(), Some("Plugin not found: b"));
+        assert!(!content.lines().next().unwrap().contains("\"error\""));
+    }
+}
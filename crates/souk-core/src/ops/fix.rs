@@ -0,0 +1,141 @@
+//! Rewrite marketplace files in souk's canonical formatting.
+//!
+//! The remediation for `--check-formatting`: marketplace.json and each
+//! listed plugin's plugin.json are re-indented and given a trailing newline
+//! without changing their content. See [`canonical_json`].
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::discovery::MarketplaceConfig;
+use crate::error::SoukError;
+use crate::resolution::resolve_source;
+use crate::validation::canonical_json;
+
+/// The result of a fix operation.
+#[derive(Debug)]
+pub struct FixResult {
+    /// Files that were (or, on a dry run, would be) reformatted.
+    pub fixed: Vec<PathBuf>,
+    /// Files left alone because they could not be read or parsed.
+    pub warnings: Vec<String>,
+}
+
+/// Reformats marketplace.json and the plugin.json of every active or
+/// archived plugin whose directory exists.
+///
+/// Files already in canonical formatting are not touched. If `dry_run` is
+/// true, nothing is written and [`FixResult::fixed`] lists what would
+/// change. A file that is not valid JSON is skipped with a warning rather
+/// than failing the whole run.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Io`] if a reformatted file cannot be written.
+pub fn fix_formatting(config: &MarketplaceConfig, dry_run: bool) -> Result<FixResult, SoukError> {
+    let mut files = vec![config.marketplace_path.clone()];
+    files.extend(
+        config
+            .marketplace
+            .plugins
+            .iter()
+            .chain(&config.marketplace.archived)
+            .filter_map(|p| resolve_source(&p.source, config).ok())
+            .map(|dir| dir.join(".claude-plugin").join("plugin.json"))
+            .filter(|path| path.is_file()),
+    );
+
+    let mut fixed = Vec::new();
+    let mut warnings = Vec::new();
+    for path in files {
+        let canonical = match fs::read_to_string(&path)
+            .map_err(SoukError::from)
+            .and_then(|content| canonical_json(&content).map(|c| (content, c)))
+        {
+            Ok((content, canonical)) if content == canonical => continue,
+            Ok((_, canonical)) => canonical,
+            Err(e) => {
+                warnings.push(format!("Skipped {}: {e}", config.display_path(&path)));
+                continue;
+            }
+        };
+        if !dry_run {
+            fs::write(&path, canonical)?;
+        }
+        fixed.push(path);
+    }
+
+    Ok(FixResult { fixed, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::load_marketplace_config;
+    use tempfile::TempDir;
+
+    #[test]
+    fn fix_formatting_canonicalizes_only_drifted_files() {
+        let tmp = TempDir::new().unwrap();
+        let claude = tmp.path().join(".claude-plugin");
+        fs::create_dir_all(&claude).unwrap();
+        let mp_path = claude.join("marketplace.json");
+        fs::write(
+            &mp_path,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"a","source":"a"},{"name":"b","source":"b"}]}"#,
+        )
+        .unwrap();
+        let plugins = tmp.path().join("plugins");
+        for name in ["a", "b"] {
+            fs::create_dir_all(plugins.join(name).join(".claude-plugin")).unwrap();
+        }
+        let a_json = plugins.join("a/.claude-plugin/plugin.json");
+        let b_json = plugins.join("b/.claude-plugin/plugin.json");
+        let canonical_b = "{\n  \"name\": \"b\",\n  \"version\": \"1.0.0\"\n}\n";
+        fs::write(&a_json, "{ \"version\": \"1.0.0\",  \"name\": \"a\" }").unwrap();
+        fs::write(&b_json, canonical_b).unwrap();
+        let config = load_marketplace_config(&mp_path).unwrap();
+
+        let preview = fix_formatting(&config, true).unwrap();
+        assert_eq!(preview.fixed.len(), 2);
+        assert!(!fs::read_to_string(&a_json).unwrap().ends_with('\n'));
+
+        let result = fix_formatting(&config, false).unwrap();
+
+        assert!(result.warnings.is_empty());
+        assert!(result.fixed.iter().any(|p| p.ends_with("marketplace.json")));
+        assert!(result
+            .fixed
+            .iter()
+            .any(|p| p.ends_with("a/.claude-plugin/plugin.json")));
+        assert_eq!(
+            fs::read_to_string(&a_json).unwrap(),
+            "{\n  \"version\": \"1.0.0\",\n  \"name\": \"a\"\n}\n"
+        );
+        assert_eq!(fs::read_to_string(&b_json).unwrap(), canonical_b);
+        assert!(fix_formatting(&config, false).unwrap().fixed.is_empty());
+    }
+
+    #[test]
+    fn fix_formatting_skips_invalid_json() {
+        let tmp = TempDir::new().unwrap();
+        let claude = tmp.path().join(".claude-plugin");
+        fs::create_dir_all(&claude).unwrap();
+        let mp_path = claude.join("marketplace.json");
+        fs::write(
+            &mp_path,
+            "{\n  \"version\": \"0.1.0\",\n  \"pluginRoot\": \"./plugins\",\n  \"plugins\": [\n    {\n      \"name\": \"a\",\n      \"source\": \"a\"\n    }\n  ]\n}\n",
+        )
+        .unwrap();
+        let plugin = tmp.path().join("plugins/a/.claude-plugin");
+        fs::create_dir_all(&plugin).unwrap();
+        fs::write(plugin.join("plugin.json"), "{\"name\": ").unwrap();
+        let config = load_marketplace_config(&mp_path).unwrap();
+
+        let result = fix_formatting(&config, false).unwrap();
+
+        assert!(result.fixed.is_empty());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("plugin.json"));
+    }
+}
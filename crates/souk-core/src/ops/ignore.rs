@@ -0,0 +1,202 @@
+//! Files left behind when copying an external plugin into pluginRoot.
+//!
+//! `souk add` skips version-control metadata, dependency and build output,
+//! and backup files. The list comes from souk.toml `copy_ignore` (defaulting
+//! to [`DEFAULT_COPY_IGNORE`]) plus an optional `.soukignore` at the plugin
+//! root, both using gitignore-style patterns:
+//!
+//! - `#` starts a comment and blank lines are ignored
+//! - `*` and `?` match within one path component; `**` matches any number
+//! - a pattern without `/` matches an entry of that name at any depth
+//! - a pattern containing `/` is matched against the path from the plugin root
+//! - a trailing `/` matches directories only
+//! - a leading `!` re-includes entries matched by an earlier pattern
+//!
+//! An ignored directory is skipped with everything under it.
+
+use std::path::{Component, Path};
+
+use crate::error::SoukError;
+use crate::resolution::pattern::matches_pattern;
+
+/// Patterns ignored when souk.toml does not set `copy_ignore`.
+pub const DEFAULT_COPY_IGNORE: [&str; 5] =
+    [".git", "node_modules", "target", ".DS_Store", "*.bak.*"];
+
+/// Per-plugin ignore file, read from the plugin root.
+pub const SOUKIGNORE_FILE: &str = ".soukignore";
+
+/// A compiled list of ignore patterns.
+#[derive(Debug, Clone, Default)]
+pub struct CopyFilter {
+    patterns: Vec<IgnorePattern>,
+}
+
+#[derive(Debug, Clone)]
+struct IgnorePattern {
+    components: Vec<String>,
+    anchored: bool,
+    dir_only: bool,
+    negated: bool,
+}
+
+impl CopyFilter {
+    /// Compile `patterns`, skipping blank lines and comments.
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        Self {
+            patterns: patterns
+                .iter()
+                .filter_map(|p| IgnorePattern::parse(p.as_ref()))
+                .collect(),
+        }
+    }
+
+    /// The filter for copying the plugin at `plugin_dir`: `patterns`
+    /// followed by the plugin's `.soukignore`, if it has one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `.soukignore` exists but cannot be read.
+    pub fn for_plugin<S: AsRef<str>>(plugin_dir: &Path, patterns: &[S]) -> Result<Self, SoukError> {
+        let mut filter = Self::new(patterns);
+        let ignore_file = plugin_dir.join(SOUKIGNORE_FILE);
+        if ignore_file.is_file() {
+            let content = std::fs::read_to_string(&ignore_file)?;
+            filter
+                .patterns
+                .extend(content.lines().filter_map(IgnorePattern::parse));
+        }
+        Ok(filter)
+    }
+
+    /// Whether the entry at `rel`, relative to the plugin root, is left out
+    /// of the copy. The last matching pattern decides.
+    pub fn is_ignored(&self, rel: &Path, is_dir: bool) -> bool {
+        let components: Vec<String> = rel
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        self.patterns
+            .iter()
+            .rev()
+            .find(|p| p.matches(&components, is_dir))
+            .is_some_and(|p| !p.negated)
+    }
+}
+
+impl IgnorePattern {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let components: Vec<String> = line
+            .split('/')
+            .filter(|c| !c.is_empty())
+            .map(String::from)
+            .collect();
+        if components.is_empty() {
+            return None;
+        }
+        Some(Self {
+            components,
+            anchored,
+            dir_only,
+            negated,
+        })
+    }
+
+    fn matches(&self, path: &[String], is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        if self.anchored {
+            match_components(&self.components, path)
+        } else {
+            path.last()
+                .is_some_and(|name| matches_pattern(&self.components[0], name))
+        }
+    }
+}
+
+/// Match pattern components against path components, where `**` matches
+/// any number of path components.
+fn match_components(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| match_components(rest, &path[skip..]))
+        }
+        Some((first, rest)) => path.split_first().is_some_and(|(name, tail)| {
+            matches_pattern(first, name) && match_components(rest, tail)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn defaults() -> CopyFilter {
+        CopyFilter::new(&DEFAULT_COPY_IGNORE)
+    }
+
+    #[test]
+    fn defaults_skip_vcs_and_junk() {
+        let filter = defaults();
+        assert!(filter.is_ignored(Path::new(".git"), true));
+        assert!(filter.is_ignored(Path::new("scripts/node_modules"), true));
+        assert!(filter.is_ignored(Path::new("target"), true));
+        assert!(filter.is_ignored(Path::new("skills/.DS_Store"), false));
+        assert!(filter.is_ignored(Path::new("plugin.json.bak.1"), false));
+
+        assert!(!filter.is_ignored(Path::new(".claude-plugin/plugin.json"), false));
+        assert!(!filter.is_ignored(Path::new("README.md"), false));
+        assert!(!filter.is_ignored(Path::new(".gitignore"), false));
+    }
+
+    #[test]
+    fn anchored_and_directory_patterns() {
+        let filter = CopyFilter::new(&["/dist", "docs/*.tmp", "build/", "**/fixtures/*.json"]);
+        assert!(filter.is_ignored(Path::new("dist"), true));
+        assert!(!filter.is_ignored(Path::new("src/dist"), true));
+        assert!(filter.is_ignored(Path::new("docs/notes.tmp"), false));
+        assert!(!filter.is_ignored(Path::new("docs/deep/notes.tmp"), false));
+        assert!(filter.is_ignored(Path::new("build"), true));
+        assert!(!filter.is_ignored(Path::new("build"), false));
+        assert!(filter.is_ignored(Path::new("fixtures/a.json"), false));
+        assert!(filter.is_ignored(Path::new("tests/unit/fixtures/a.json"), false));
+    }
+
+    #[test]
+    fn negation_and_comments() {
+        let filter = CopyFilter::new(&["# comment", "", "*.log", "!keep.log"]);
+        assert!(filter.is_ignored(Path::new("debug.log"), false));
+        assert!(!filter.is_ignored(Path::new("keep.log"), false));
+        assert!(!filter.is_ignored(Path::new("# comment"), false));
+    }
+
+    #[test]
+    fn soukignore_extends_the_configured_patterns() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join(SOUKIGNORE_FILE), "*.psd\n!target\n").unwrap();
+
+        let filter = CopyFilter::for_plugin(tmp.path(), &DEFAULT_COPY_IGNORE).unwrap();
+        assert!(filter.is_ignored(Path::new("art/logo.psd"), false));
+        assert!(filter.is_ignored(Path::new(".git"), true));
+        assert!(!filter.is_ignored(Path::new("target"), true));
+    }
+}
@@ -2,11 +2,13 @@
 //!
 //! This module implements the `souk init` core operation, which creates the
 //! `.claude-plugin/` directory, writes a default `marketplace.json`, and
-//! creates the plugin root directory.
+//! creates the plugin root directory. It also implements `souk init --check`,
+//! which verifies an existing scaffold without modifying it.
 
 use std::fs;
 use std::path::Path;
 
+use crate::discovery::{load_marketplace_config, MarketplaceConfig};
 use crate::error::SoukError;
 use crate::types::Marketplace;
 
@@ -43,7 +45,10 @@ pub fn scaffold_marketplace(path: &Path, plugin_root: &str) -> Result<(), SoukEr
     let marketplace = Marketplace {
         version: "0.1.0".to_string(),
         plugin_root: Some(plugin_root.to_string()),
+        keywords: Vec::new(),
+        categories: Vec::new(),
         plugins: Vec::new(),
+        archived: Vec::new(),
     };
 
     let json = serde_json::to_string_pretty(&marketplace)?;
@@ -57,6 +62,31 @@ pub fn scaffold_marketplace(path: &Path, plugin_root: &str) -> Result<(), SoukEr
     Ok(())
 }
 
+/// Verify that the marketplace scaffold at the given path is intact.
+///
+/// Checks that `.claude-plugin/marketplace.json` exists at `path`, that it
+/// parses, and that the configured plugin root directory exists. Nothing is
+/// created or modified.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Other`] if `.claude-plugin/marketplace.json` is
+/// missing or the plugin root directory does not exist.
+///
+/// Returns [`SoukError::Json`] if `marketplace.json` cannot be parsed.
+pub fn check_scaffold(path: &Path) -> Result<MarketplaceConfig, SoukError> {
+    let marketplace_path = path.join(".claude-plugin").join("marketplace.json");
+
+    if !marketplace_path.is_file() {
+        return Err(SoukError::Other(format!(
+            "Marketplace scaffold not found: {} does not exist",
+            marketplace_path.display()
+        )));
+    }
+
+    load_marketplace_config(&marketplace_path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +196,54 @@ mod tests {
         // Directory should be created without the "./" prefix
         assert!(root.join("custom-plugins").is_dir());
     }
+
+    #[test]
+    fn check_scaffold_passes_for_intact_scaffold() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+
+        scaffold_marketplace(root, "./plugins").unwrap();
+
+        let config = check_scaffold(root).unwrap();
+        assert!(config.plugin_root_abs.ends_with("plugins"));
+        assert!(config.marketplace.plugins.is_empty());
+    }
+
+    #[test]
+    fn check_scaffold_fails_when_plugin_root_missing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+
+        scaffold_marketplace(root, "./plugins").unwrap();
+        fs::remove_dir_all(root.join("plugins")).unwrap();
+
+        let err = check_scaffold(root).unwrap_err();
+        assert!(
+            err.to_string().contains("Plugin root directory not found"),
+            "Error: {err}"
+        );
+    }
+
+    #[test]
+    fn check_scaffold_fails_when_marketplace_missing() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        let err = check_scaffold(tmp.path()).unwrap_err();
+        assert!(err.to_string().contains("not found"), "Error: {err}");
+    }
+
+    #[test]
+    fn check_scaffold_fails_on_invalid_json() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+
+        scaffold_marketplace(root, "./plugins").unwrap();
+        fs::write(
+            root.join(".claude-plugin").join("marketplace.json"),
+            "not json",
+        )
+        .unwrap();
+
+        assert!(matches!(check_scaffold(root), Err(SoukError::Json(_))));
+    }
 }
@@ -6,10 +6,18 @@
 //! restores the original file from its backup.
 
 pub mod add;
+pub mod archive;
 pub mod atomic;
+pub mod audit;
+pub mod fix;
+pub mod ignore;
 pub mod init;
 pub mod prune;
 pub mod remove;
+pub mod rename;
+pub mod retry;
+pub mod sync;
 pub mod update;
 
 pub use atomic::AtomicGuard;
+pub use retry::retry_on_rollback;
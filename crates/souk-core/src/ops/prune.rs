@@ -1,20 +1,29 @@
 //! Prune orphaned plugin directories from the filesystem.
 //!
 //! Identifies directories under pluginRoot that are not listed in
-//! marketplace.json and optionally deletes them.
+//! marketplace.json and optionally deletes them. The inverse, marketplace
+//! entries whose directory is gone, is handled by [`prune_stale_entries`].
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
-use crate::discovery::MarketplaceConfig;
+use walkdir::WalkDir;
+
+use crate::discovery::{load_marketplace_config, MarketplaceConfig};
 use crate::error::SoukError;
-use crate::validation::find_orphaned_dirs;
+use crate::ops::AtomicGuard;
+use crate::types::Marketplace;
+use crate::validation::{find_orphaned_dirs, find_stale_entries, validate_marketplace};
+use crate::version::bump_patch;
 
 /// The result of a prune operation.
 #[derive(Debug)]
 pub struct PruneResult {
-    /// Orphaned directories found.
+    /// Orphaned directories found (only stale ones when `older_than` is set).
     pub orphaned: Vec<PathBuf>,
+    /// Orphaned directories kept because they were modified too recently.
+    pub recent: Vec<PathBuf>,
     /// Directories actually deleted (empty if dry-run).
     pub deleted: Vec<PathBuf>,
     /// Non-fatal warnings (e.g., permission denied on delete).
@@ -27,13 +36,36 @@ pub struct PruneResult {
 /// If `apply` is false (dry-run), only reports what would be deleted.
 /// If `apply` is true, actually deletes the orphaned directories.
 ///
+/// With `older_than`, only orphans whose newest file was modified longer ago
+/// than that are pruned; newer ones are reported in [`PruneResult::recent`]
+/// so work in progress is not deleted before it is registered.
+///
+/// Symlinks in pluginRoot are left alone unless `settings.dereference` is
+/// set; see [`find_orphaned_dirs`]. A symlinked orphan found that way has
+/// only the link removed, never its target.
+///
 /// This is a pure filesystem operation — marketplace.json is not modified.
-pub fn prune_plugins(apply: bool, config: &MarketplaceConfig) -> Result<PruneResult, SoukError> {
-    let orphaned = find_orphaned_dirs(config)?;
+pub fn prune_plugins(
+    apply: bool,
+    older_than: Option<Duration>,
+    config: &MarketplaceConfig,
+) -> Result<PruneResult, SoukError> {
+    let mut orphaned = find_orphaned_dirs(config)?;
+
+    let mut recent = Vec::new();
+    if let Some(age) = older_than {
+        let cutoff = SystemTime::now()
+            .checked_sub(age)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        (orphaned, recent) = orphaned
+            .into_iter()
+            .partition(|path| newest_mtime(path).is_some_and(|t| t < cutoff));
+    }
 
     if !apply {
         return Ok(PruneResult {
             orphaned,
+            recent,
             deleted: Vec::new(),
             warnings: Vec::new(),
         });
@@ -43,7 +75,7 @@ pub fn prune_plugins(apply: bool, config: &MarketplaceConfig) -> Result<PruneRes
     let mut warnings = Vec::new();
 
     for path in &orphaned {
-        match fs::remove_dir_all(path) {
+        match remove_orphan(path) {
             Ok(()) => deleted.push(path.clone()),
             Err(e) => warnings.push(format!("Failed to delete {}: {e}", path.display())),
         }
@@ -51,11 +83,112 @@ pub fn prune_plugins(apply: bool, config: &MarketplaceConfig) -> Result<PruneRes
 
     Ok(PruneResult {
         orphaned,
+        recent,
         deleted,
         warnings,
     })
 }
 
+/// Finds marketplace entries whose source directory no longer exists and,
+/// if `apply` is true, removes them from marketplace.json.
+///
+/// Returns the names of the stale entries, whether or not they were
+/// removed. Removal bumps the marketplace version (patch) and re-validates
+/// the marketplace, restoring marketplace.json if validation fails.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Io`] if pluginRoot cannot be read, or
+/// [`SoukError::AtomicRollback`] if the marketplace no longer validates
+/// once the entries are removed.
+pub fn prune_stale_entries(
+    apply: bool,
+    config: &MarketplaceConfig,
+) -> Result<Vec<String>, SoukError> {
+    let stale: Vec<String> = find_stale_entries(config)?
+        .into_iter()
+        .map(|p| p.name.clone())
+        .collect();
+    if !apply || stale.is_empty() {
+        return Ok(stale);
+    }
+
+    let guard = AtomicGuard::new(&config.marketplace_path)?;
+
+    let content = fs::read_to_string(&config.marketplace_path)?;
+    let mut marketplace: Marketplace = serde_json::from_str(&content)?;
+    marketplace.plugins.retain(|p| !stale.contains(&p.name));
+    marketplace.version = bump_patch(&marketplace.version)?;
+
+    let json = serde_json::to_string_pretty(&marketplace)?;
+    fs::write(&config.marketplace_path, format!("{json}\n"))?;
+
+    let updated_config = load_marketplace_config(&config.marketplace_path)?;
+    if validate_marketplace(&updated_config, true).has_errors() {
+        drop(guard);
+        return Err(SoukError::AtomicRollback(
+            "Validation failed after pruning stale entries".to_string(),
+        ));
+    }
+
+    guard.commit()?;
+    Ok(stale)
+}
+
+/// Delete an orphaned directory, or just the link for a symlinked one.
+fn remove_orphan(path: &Path) -> std::io::Result<()> {
+    if path.is_symlink() {
+        fs::remove_file(path)
+    } else {
+        fs::remove_dir_all(path)
+    }
+}
+
+/// The most recent modification time of any file under `dir`, or of `dir`
+/// itself when it contains no files.
+fn newest_mtime(dir: &Path) -> Option<SystemTime> {
+    WalkDir::new(dir)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok()?.modified().ok())
+        .max()
+        .or_else(|| fs::metadata(dir).ok()?.modified().ok())
+}
+
+/// Parse a duration such as `30m`, `12h`, `7d`, or `2w`.
+///
+/// Supported units are `s`, `m`, `h`, `d`, and `w`.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Other`] if the number or unit is missing or invalid.
+pub fn parse_duration(input: &str) -> Result<Duration, SoukError> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    let invalid = || {
+        SoukError::Other(format!(
+            "Invalid duration '{input}': expected a number followed by s, m, h, d, or w"
+        ))
+    };
+    let number: u64 = number.parse().map_err(|_| invalid())?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    number
+        .checked_mul(unit_secs)
+        .map(Duration::from_secs)
+        .ok_or_else(invalid)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,7 +224,7 @@ mod tests {
             &["kept", "orphan1", "orphan2"],
         );
 
-        let result = prune_plugins(false, &config).unwrap();
+        let result = prune_plugins(false, None, &config).unwrap();
 
         assert_eq!(result.orphaned.len(), 2);
         assert!(result.deleted.is_empty());
@@ -109,7 +242,7 @@ mod tests {
             &["kept", "orphan1", "orphan2"],
         );
 
-        let result = prune_plugins(true, &config).unwrap();
+        let result = prune_plugins(true, None, &config).unwrap();
 
         assert_eq!(result.orphaned.len(), 2);
         assert_eq!(result.deleted.len(), 2);
@@ -151,7 +284,7 @@ mod tests {
         // Make orphan1 non-deletable by removing write permission on it
         std::fs::set_permissions(&orphan1_path, std::fs::Permissions::from_mode(0o555)).unwrap();
 
-        let result = prune_plugins(true, &config).unwrap();
+        let result = prune_plugins(true, None, &config).unwrap();
 
         // Restore permissions for cleanup
         std::fs::set_permissions(&orphan1_path, std::fs::Permissions::from_mode(0o755)).unwrap();
@@ -163,6 +296,81 @@ mod tests {
         assert!(result.warnings[0].contains("Failed to delete"));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn prune_leaves_symlinks_alone_by_default() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"kept","source":"kept"}]}"#,
+            &["kept"],
+        );
+        let target = tmp.path().join("elsewhere");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("precious.txt"), "keep me").unwrap();
+        let link = config.plugin_root_abs.join("linked");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let result = prune_plugins(true, None, &config).unwrap();
+
+        assert!(result.orphaned.is_empty());
+        assert!(link.is_symlink());
+        assert!(target.join("precious.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn prune_with_dereference_removes_only_the_link() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"kept","source":"kept"}]}"#,
+            &["kept"],
+        );
+        config.settings.dereference = true;
+        let target = tmp.path().join("elsewhere");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("stale.txt"), "old").unwrap();
+        let link = config.plugin_root_abs.join("linked");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let result = prune_plugins(true, None, &config).unwrap();
+
+        assert_eq!(result.deleted, vec![link.clone()]);
+        assert!(!link.is_symlink());
+        assert_eq!(
+            std::fs::read_to_string(target.join("stale.txt")).unwrap(),
+            "old"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn prune_with_dereference_refuses_link_to_plugin_root() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"a","source":"a"}]}"#,
+            &["a"],
+        );
+        config.settings.dereference = true;
+        std::os::unix::fs::symlink(tmp.path(), config.plugin_root_abs.join("parent")).unwrap();
+
+        let err = prune_plugins(true, None, &config).unwrap_err();
+        assert!(matches!(err, SoukError::ScanAborted(_)), "{err}");
+        fs::remove_file(config.plugin_root_abs.join("parent")).unwrap();
+        std::os::unix::fs::symlink(&config.plugin_root_abs, config.plugin_root_abs.join("self"))
+            .unwrap();
+
+        let err = prune_plugins(true, None, &config).unwrap_err();
+        assert!(err.to_string().contains("symlink loop"), "{err}");
+        assert!(config
+            .plugin_root_abs
+            .join("a/.claude-plugin/plugin.json")
+            .is_file());
+        assert!(config.plugin_root_abs.join("self").is_symlink());
+    }
+
     #[test]
     fn prune_no_orphans() {
         let tmp = TempDir::new().unwrap();
@@ -172,10 +380,100 @@ mod tests {
             &["a"],
         );
 
-        let result = prune_plugins(false, &config).unwrap();
+        let result = prune_plugins(false, None, &config).unwrap();
 
         assert!(result.orphaned.is_empty());
         assert!(result.deleted.is_empty());
         assert!(result.warnings.is_empty());
     }
+
+    #[test]
+    fn prune_older_than_keeps_recent_orphans() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"kept","source":"kept"}]}"#,
+            &["kept", "fresh", "stale"],
+        );
+        let stale_file = config
+            .plugin_root_abs
+            .join("stale")
+            .join(".claude-plugin")
+            .join("plugin.json");
+        let month_ago = SystemTime::now() - Duration::from_secs(30 * 24 * 60 * 60);
+        fs::File::options()
+            .write(true)
+            .open(&stale_file)
+            .unwrap()
+            .set_modified(month_ago)
+            .unwrap();
+
+        let result =
+            prune_plugins(true, Some(Duration::from_secs(7 * 24 * 60 * 60)), &config).unwrap();
+
+        assert_eq!(result.deleted, vec![config.plugin_root_abs.join("stale")]);
+        assert_eq!(result.recent, vec![config.plugin_root_abs.join("fresh")]);
+        assert!(!config.plugin_root_abs.join("stale").exists());
+        assert!(config.plugin_root_abs.join("fresh").exists());
+    }
+
+    #[test]
+    fn prune_stale_entries_dry_run_lists_without_writing() {
+        let tmp = TempDir::new().unwrap();
+        let json = r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"kept","source":"kept"},{"name":"gone","source":"gone"}]}"#;
+        let config = setup_marketplace(&tmp, json, &["kept"]);
+
+        let stale = prune_stale_entries(false, &config).unwrap();
+
+        assert_eq!(stale, vec!["gone".to_string()]);
+        assert_eq!(fs::read_to_string(&config.marketplace_path).unwrap(), json);
+    }
+
+    #[test]
+    fn prune_stale_entries_apply_removes_and_bumps() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"kept","source":"kept"},{"name":"gone","source":"gone"}]}"#,
+            &["kept"],
+        );
+
+        let stale = prune_stale_entries(true, &config).unwrap();
+
+        assert_eq!(stale, vec!["gone".to_string()]);
+        let updated = load_marketplace_config(&config.marketplace_path).unwrap();
+        assert_eq!(updated.marketplace.version, "0.1.1");
+        let names: Vec<&str> = updated
+            .marketplace
+            .plugins
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["kept"]);
+    }
+
+    #[test]
+    fn prune_stale_entries_rolls_back_on_validation_failure() {
+        let tmp = TempDir::new().unwrap();
+        // A duplicate entry keeps the marketplace from validating.
+        let json = r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"kept","source":"kept"},{"name":"kept","source":"kept"},{"name":"gone","source":"gone"}]}"#;
+        let config = setup_marketplace(&tmp, json, &["kept"]);
+
+        let err = prune_stale_entries(true, &config).unwrap_err();
+
+        assert!(matches!(err, SoukError::AtomicRollback(_)));
+        assert_eq!(fs::read_to_string(&config.marketplace_path).unwrap(), json);
+    }
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("45s").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_duration("30m").unwrap(), Duration::from_secs(1800));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::from_secs(43200));
+        assert_eq!(parse_duration("7d").unwrap(), Duration::from_secs(604800));
+        assert_eq!(parse_duration("2w").unwrap(), Duration::from_secs(1209600));
+        assert!(parse_duration("7").is_err());
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("3y").is_err());
+    }
 }
@@ -4,6 +4,7 @@
 //! optional flag to also delete the plugin directory from disk.
 
 use std::fs;
+use std::path::{Path, PathBuf};
 
 use crate::discovery::{load_marketplace_config, MarketplaceConfig};
 use crate::error::SoukError;
@@ -27,8 +28,12 @@ pub struct RemoveResult {
 /// For each name in `names`:
 /// - Finds the matching entry in marketplace.json
 /// - If `delete_files` is true, also removes the plugin directory from disk
+///   (a directory that is already missing is skipped)
 /// - Bumps the marketplace version (patch)
 ///
+/// If `validate_after` is false, the marketplace is not re-validated after
+/// the write.
+///
 /// Returns a [`RemoveResult`] with the removed names and any warnings
 /// (e.g., if a directory could not be deleted after the marketplace entry
 /// was removed).
@@ -49,6 +54,7 @@ pub struct RemoveResult {
 ///     &["my-plugin".to_string()],
 ///     true,  // delete files
 ///     false, // don't allow external deletes
+///     true,  // re-validate the marketplace afterwards
 ///     config,
 /// ).unwrap();
 ///
@@ -63,6 +69,7 @@ pub fn remove_plugins(
     names: &[String],
     delete_files: bool,
     allow_external_delete: bool,
+    validate_after: bool,
     config: &MarketplaceConfig,
 ) -> Result<RemoveResult, SoukError> {
     if names.is_empty() {
@@ -79,13 +86,13 @@ pub fn remove_plugins(
         }
     }
 
-    // Pre-compute delete targets and validate paths before any mutation
-    let mut delete_targets: Vec<(String, std::path::PathBuf)> = Vec::new();
+    // Pre-compute delete targets and validate paths before any mutation.
+    // Sources whose directory is already gone have nothing to delete, so the
+    // entry is still removed rather than failing the whole operation.
+    let mut delete_targets: Vec<(String, PathBuf)> = Vec::new();
     if delete_files {
-        let plugin_root = config
-            .plugin_root_abs
-            .canonicalize()
-            .map_err(SoukError::Io)?;
+        let plugin_root = canonicalize_if_exists(&config.plugin_root_abs)?
+            .unwrap_or_else(|| config.plugin_root_abs.clone());
 
         for name in names {
             let entry = config
@@ -95,23 +102,27 @@ pub fn remove_plugins(
                 .find(|p| p.name == *name)
                 .unwrap();
 
-            if let Ok(plugin_path) = resolve_source(&entry.source, config) {
-                if plugin_path.is_dir() {
-                    let resolved = plugin_path.canonicalize().map_err(SoukError::Io)?;
-                    let is_internal = resolved.starts_with(&plugin_root);
-
-                    if !is_internal && !allow_external_delete {
-                        return Err(SoukError::Other(format!(
-                            "Refusing to delete '{}': path is outside pluginRoot ({}). \
-                             Use --allow-external-delete to override.",
-                            resolved.display(),
-                            plugin_root.display()
-                        )));
-                    }
-
-                    delete_targets.push((name.clone(), resolved));
-                }
+            let Ok(plugin_path) = resolve_source(&entry.source, config) else {
+                continue;
+            };
+            let Some(resolved) = canonicalize_if_exists(&plugin_path)? else {
+                continue;
+            };
+            if !resolved.is_dir() {
+                continue;
+            }
+
+            let is_internal = resolved.starts_with(&plugin_root);
+            if !is_internal && !allow_external_delete {
+                return Err(SoukError::Other(format!(
+                    "Refusing to delete '{}': path is outside pluginRoot ({}). \
+                     Use --allow-external-delete to override.",
+                    resolved.display(),
+                    plugin_root.display()
+                )));
             }
+
+            delete_targets.push((name.clone(), resolved));
         }
     }
 
@@ -137,13 +148,15 @@ pub fn remove_plugins(
     fs::write(&config.marketplace_path, format!("{json}\n"))?;
 
     // Validate
-    let updated_config = load_marketplace_config(&config.marketplace_path)?;
-    let validation = validate_marketplace(&updated_config, true);
-    if validation.has_errors() {
-        drop(guard);
-        return Err(SoukError::AtomicRollback(
-            "Validation failed after remove".to_string(),
-        ));
+    if validate_after {
+        let updated_config = load_marketplace_config(&config.marketplace_path)?;
+        let validation = validate_marketplace(&updated_config, true);
+        if validation.has_errors() {
+            drop(guard);
+            return Err(SoukError::AtomicRollback(
+                "Validation failed after remove".to_string(),
+            ));
+        }
     }
 
     guard.commit()?;
@@ -164,6 +177,15 @@ pub fn remove_plugins(
     Ok(RemoveResult { removed, warnings })
 }
 
+/// Canonicalizes `path`, returning `None` if it does not exist.
+fn canonicalize_if_exists(path: &Path) -> Result<Option<PathBuf>, SoukError> {
+    match path.canonicalize() {
+        Ok(p) => Ok(Some(p)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(SoukError::Io(e)),
+    }
+}
+
 /// Deletes a plugin directory from disk. Exposed for testing or direct use.
 pub fn delete_plugin_dir(
     source: &str,
@@ -232,7 +254,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
 
-        let result = remove_plugins(&["alpha".to_string()], false, false, &config).unwrap();
+        let result = remove_plugins(&["alpha".to_string()], false, false, true, &config).unwrap();
 
         assert_eq!(result.removed, vec!["alpha"]);
         assert!(result.warnings.is_empty());
@@ -252,7 +274,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
 
-        let result = remove_plugins(&["nonexistent".to_string()], false, false, &config);
+        let result = remove_plugins(&["nonexistent".to_string()], false, false, true, &config);
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -272,6 +294,7 @@ mod tests {
             &["alpha".to_string()],
             true, // delete files
             false,
+            true,
             &config,
         )
         .unwrap();
@@ -294,6 +317,7 @@ mod tests {
             &["alpha".to_string()],
             false, // don't delete files
             false,
+            true,
             &config,
         )
         .unwrap();
@@ -313,6 +337,7 @@ mod tests {
             &["alpha".to_string(), "gamma".to_string()],
             false,
             false,
+            true,
             &config,
         )
         .unwrap();
@@ -330,7 +355,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
 
-        let result = remove_plugins(&[], false, false, &config).unwrap();
+        let result = remove_plugins(&[], false, false, true, &config).unwrap();
         assert!(result.removed.is_empty());
 
         let content = fs::read_to_string(&config.marketplace_path).unwrap();
@@ -367,7 +392,7 @@ mod tests {
         let config = load_marketplace_config(&claude_dir.join("marketplace.json")).unwrap();
 
         // Try to delete without allow flag — should fail
-        let result = remove_plugins(&["ext".to_string()], true, false, &config);
+        let result = remove_plugins(&["ext".to_string()], true, false, true, &config);
         assert!(result.is_err());
         let err = result.unwrap_err().to_string();
         assert!(err.contains("outside pluginRoot"), "Error: {err}");
@@ -403,7 +428,7 @@ mod tests {
         let config = load_marketplace_config(&claude_dir.join("marketplace.json")).unwrap();
 
         // Delete with allow flag — should succeed
-        let result = remove_plugins(&["ext".to_string()], true, true, &config).unwrap();
+        let result = remove_plugins(&["ext".to_string()], true, true, true, &config).unwrap();
         assert_eq!(result.removed, vec!["ext"]);
         assert!(!ext_plugin.exists());
     }
@@ -415,8 +440,58 @@ mod tests {
 
         assert!(config.plugin_root_abs.join("alpha").exists());
 
-        let result = remove_plugins(&["alpha".to_string()], true, false, &config).unwrap();
+        let result = remove_plugins(&["alpha".to_string()], true, false, true, &config).unwrap();
         assert_eq!(result.removed, vec!["alpha"]);
         assert!(!config.plugin_root_abs.join("alpha").exists());
     }
+
+    #[test]
+    fn remove_with_final_validation_catches_corruption() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+        fs::remove_dir_all(config.plugin_root_abs.join("beta")).unwrap();
+        let before = fs::read_to_string(&config.marketplace_path).unwrap();
+
+        let result = remove_plugins(&["alpha".to_string()], false, false, true, &config);
+
+        assert!(matches!(result, Err(SoukError::AtomicRollback(_))));
+        assert_eq!(
+            fs::read_to_string(&config.marketplace_path).unwrap(),
+            before
+        );
+    }
+
+    #[test]
+    fn remove_without_final_validation_still_writes() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+        fs::remove_dir_all(config.plugin_root_abs.join("beta")).unwrap();
+
+        let result = remove_plugins(&["alpha".to_string()], false, false, false, &config).unwrap();
+        assert_eq!(result.removed, vec!["alpha"]);
+
+        let content = fs::read_to_string(&config.marketplace_path).unwrap();
+        let mp: Marketplace = serde_json::from_str(&content).unwrap();
+        assert_eq!(mp.version, "0.1.1");
+        assert_eq!(mp.plugins.len(), 1);
+        assert_eq!(mp.plugins[0].name, "beta");
+    }
+
+    #[test]
+    fn remove_with_delete_tolerates_missing_directory() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+        fs::remove_dir_all(config.plugin_root_abs.join("alpha")).unwrap();
+
+        let result = remove_plugins(&["alpha".to_string()], true, false, true, &config).unwrap();
+
+        assert_eq!(result.removed, vec!["alpha"]);
+        assert!(result.warnings.is_empty());
+
+        let content = fs::read_to_string(&config.marketplace_path).unwrap();
+        let mp: Marketplace = serde_json::from_str(&content).unwrap();
+        assert_eq!(mp.plugins.len(), 1);
+        assert_eq!(mp.plugins[0].name, "beta");
+        assert!(config.plugin_root_abs.join("beta").exists());
+    }
 }
@@ -0,0 +1,409 @@
+//! Rename a plugin across the marketplace.
+//!
+//! Renaming touches several files at once: the plugin's own plugin.json,
+//! its directory under pluginRoot, its marketplace entry, and the
+//! extends-plugin.json of every plugin that depends on it. Each is guarded
+//! so a failed rename leaves everything as it was.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::discovery::{load_marketplace_config, MarketplaceConfig};
+use crate::error::SoukError;
+use crate::ops::AtomicGuard;
+use crate::resolution::{plugin_path_to_source, resolve_source};
+use crate::types::plugin::read_manifest_text;
+use crate::types::Marketplace;
+use crate::validation::dependencies::DEPENDENCY_SECTIONS;
+use crate::validation::{check_plugin_name, validate_marketplace, validate_plugin_with};
+use crate::version::bump_patch;
+
+/// The result of a rename operation.
+#[derive(Debug)]
+pub struct RenameResult {
+    /// The plugin directory as `(from, to)`, if it was moved.
+    pub moved: Option<(PathBuf, PathBuf)>,
+    /// The entry's new source.
+    pub source: String,
+    /// Plugins whose extends-plugin.json now depends on the new name.
+    pub dependents: Vec<String>,
+}
+
+/// Renames the active plugin `old` to `new`.
+///
+/// - Sets `name` in the plugin's plugin.json
+/// - If the plugin lives under pluginRoot, renames its directory to `new`
+///   and points the entry's source at it; external plugins stay put
+/// - Renames the marketplace entry
+/// - Rewrites `dependencies` and `optionalDependencies` keys naming `old`
+///   in every other listed plugin's extends-plugin.json, archived included
+///
+/// The marketplace version is bumped (patch). The renamed plugin and the
+/// marketplace are then validated, and any failure restores every file and
+/// moves the directory back.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Other`] if `new` is not a valid plugin name (see
+/// [`check_plugin_name`]), [`SoukError::PluginNotFound`] if `old` is not an
+/// active plugin, [`SoukError::PluginAlreadyExists`] if `new` is already
+/// listed, or
+/// [`SoukError::AtomicRollback`] if validation fails after the rename.
+pub fn rename_plugin(
+    old: &str,
+    new: &str,
+    config: &MarketplaceConfig,
+) -> Result<RenameResult, SoukError> {
+    let new = new.trim();
+    if new.is_empty() {
+        return Err(SoukError::Other("New plugin name is empty".to_string()));
+    }
+    check_plugin_name(new)
+        .map_err(|problem| SoukError::Other(format!("Plugin name '{new}' {problem}")))?;
+    let entry = config
+        .marketplace
+        .plugins
+        .iter()
+        .find(|p| p.name == old)
+        .ok_or_else(|| SoukError::PluginNotFound(old.to_string()))?;
+    if new == old {
+        return Err(SoukError::Other(format!("Plugin is already named '{new}'")));
+    }
+    if config
+        .marketplace
+        .listed_plugins(true)
+        .iter()
+        .any(|(p, _)| p.name == new)
+    {
+        return Err(SoukError::PluginAlreadyExists(new.to_string()));
+    }
+
+    let plugin_path = resolve_source(&entry.source, config)?;
+    let target = plugin_path
+        .starts_with(&config.plugin_root_abs)
+        .then(|| config.plugin_root_abs.join(new));
+    if let Some(target) = &target {
+        if target.exists() {
+            return Err(SoukError::Other(format!(
+                "Rename target already exists: {}",
+                target.display()
+            )));
+        }
+    }
+
+    // Find dependents before anything moves, so their paths still resolve.
+    let mut dependents = Vec::new();
+    for (other, _) in config.marketplace.listed_plugins(true) {
+        if other.name == old {
+            continue;
+        }
+        let Ok(path) = resolve_source(&other.source, config) else {
+            continue;
+        };
+        let extends_path = path.join(".claude-plugin").join("extends-plugin.json");
+        if let Some(doc) = read_json(&extends_path) {
+            if depends_on(&doc, old) {
+                dependents.push((other.name.clone(), extends_path, doc));
+            }
+        }
+    }
+
+    // Move the directory first so the file guards below back up and restore
+    // files at their final paths. Locals drop in reverse order, so on
+    // failure the files are restored before the directory moves back.
+    let moved_dir = match &target {
+        Some(target) => Some(MovedDir::new(&plugin_path, target)?),
+        None => None,
+    };
+    let plugin_path = target.clone().unwrap_or(plugin_path);
+
+    let mp_guard = AtomicGuard::new(&config.marketplace_path)?;
+    let plugin_json_path = plugin_path.join(".claude-plugin").join("plugin.json");
+    let mut file_guards = vec![AtomicGuard::new(&plugin_json_path)?];
+    for (_, extends_path, _) in &dependents {
+        file_guards.push(AtomicGuard::new(extends_path)?);
+    }
+
+    let content = read_manifest_text(&plugin_json_path)
+        .map_err(|e| SoukError::Other(format!("{e} ({old})")))?;
+    let mut manifest: serde_json::Value = serde_json::from_str(&content)?;
+    manifest["name"] = serde_json::Value::String(new.to_string());
+    write_json(&plugin_json_path, &manifest)?;
+
+    for (_, extends_path, doc) in &mut dependents {
+        rename_dependency(doc, old, new);
+        write_json(extends_path, doc)?;
+    }
+
+    let content = fs::read_to_string(&config.marketplace_path)?;
+    let mut marketplace: Marketplace = serde_json::from_str(&content)?;
+    let source = match &target {
+        Some(target) => plugin_path_to_source(target, config).0,
+        None => entry.source.clone(),
+    };
+    if let Some(entry) = marketplace.plugins.iter_mut().find(|p| p.name == old) {
+        entry.name = new.to_string();
+        entry.source = source.clone();
+    }
+    marketplace.version = bump_patch(&marketplace.version)?;
+    write_json(&config.marketplace_path, &marketplace)?;
+
+    if validate_plugin_with(&plugin_path, &config.settings).has_errors() {
+        return Err(SoukError::AtomicRollback(format!(
+            "Plugin validation failed for {new} after rename"
+        )));
+    }
+    let updated_config = load_marketplace_config(&config.marketplace_path)?;
+    if validate_marketplace(&updated_config, true).has_errors() {
+        return Err(SoukError::AtomicRollback(
+            "Marketplace validation failed after rename".to_string(),
+        ));
+    }
+
+    mp_guard.commit()?;
+    for guard in file_guards {
+        guard.commit()?;
+    }
+    let moved = moved_dir.map(MovedDir::commit);
+
+    Ok(RenameResult {
+        moved,
+        source,
+        dependents: dependents.into_iter().map(|(name, _, _)| name).collect(),
+    })
+}
+
+/// A directory rename that is undone on drop unless committed.
+struct MovedDir {
+    from: PathBuf,
+    to: PathBuf,
+    committed: bool,
+}
+
+impl MovedDir {
+    fn new(from: &Path, to: &Path) -> Result<Self, SoukError> {
+        fs::rename(from, to)?;
+        Ok(Self {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+            committed: false,
+        })
+    }
+
+    fn commit(mut self) -> (PathBuf, PathBuf) {
+        self.committed = true;
+        (self.from.clone(), self.to.clone())
+    }
+}
+
+impl Drop for MovedDir {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = fs::rename(&self.to, &self.from);
+        }
+    }
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write_json(path: &Path, value: &impl serde::Serialize) -> Result<(), SoukError> {
+    let json = serde_json::to_string_pretty(value)?;
+    fs::write(path, format!("{json}\n"))?;
+    Ok(())
+}
+
+/// Whether an extends-plugin.json document declares a dependency on `name`.
+fn depends_on(doc: &serde_json::Value, name: &str) -> bool {
+    DEPENDENCY_SECTIONS.iter().any(|section| {
+        doc.get(section)
+            .and_then(|d| d.as_object())
+            .is_some_and(|deps| deps.contains_key(name))
+    })
+}
+
+/// Re-key dependencies on `old` to `new`, keeping their constraints.
+fn rename_dependency(doc: &mut serde_json::Value, old: &str, new: &str) {
+    for section in DEPENDENCY_SECTIONS {
+        if let Some(deps) = doc.get_mut(section).and_then(|d| d.as_object_mut()) {
+            if let Some(constraint) = deps.remove(old) {
+                deps.insert(new.to_string(), constraint);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A marketplace of `(name, extends-plugin.json)` plugins under
+    /// `plugins/`, each at version 1.0.0.
+    fn setup(tmp: &TempDir, plugins: &[(&str, Option<&str>)]) -> MarketplaceConfig {
+        let claude_dir = tmp.path().join(".claude-plugin");
+        fs::create_dir_all(&claude_dir).unwrap();
+
+        let mut entries = Vec::new();
+        for (name, extends) in plugins {
+            let plugin_claude = tmp.path().join("plugins").join(name).join(".claude-plugin");
+            fs::create_dir_all(&plugin_claude).unwrap();
+            fs::write(
+                plugin_claude.join("plugin.json"),
+                format!(r#"{{"name":"{name}","version":"1.0.0","description":"test plugin"}}"#),
+            )
+            .unwrap();
+            if let Some(extends) = extends {
+                fs::write(plugin_claude.join("extends-plugin.json"), extends).unwrap();
+            }
+            entries.push(format!(r#"{{"name":"{name}","source":"{name}"}}"#));
+        }
+
+        let mp_json = format!(
+            r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{}]}}"#,
+            entries.join(",")
+        );
+        fs::write(claude_dir.join("marketplace.json"), &mp_json).unwrap();
+        load_marketplace_config(&claude_dir.join("marketplace.json")).unwrap()
+    }
+
+    fn plugin_json(config: &MarketplaceConfig, dir: &str) -> serde_json::Value {
+        let path = config
+            .plugin_root_abs
+            .join(dir)
+            .join(".claude-plugin/plugin.json");
+        read_json(&path).unwrap()
+    }
+
+    #[test]
+    fn rename_moves_directory_and_updates_entry() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, &[("alpha", None), ("beta", None)]);
+
+        let result = rename_plugin("alpha", "gamma", &config).unwrap();
+        assert_eq!(result.source, "gamma");
+        assert!(result.dependents.is_empty());
+        assert!(!config.plugin_root_abs.join("alpha").exists());
+        assert_eq!(plugin_json(&config, "gamma")["name"], "gamma");
+
+        let updated = load_marketplace_config(&config.marketplace_path).unwrap();
+        assert_eq!(updated.marketplace.version, "0.1.1");
+        let entry = &updated.marketplace.plugins[0];
+        assert_eq!(
+            (entry.name.as_str(), entry.source.as_str()),
+            ("gamma", "gamma")
+        );
+    }
+
+    #[test]
+    fn rename_rewrites_dependents() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(
+            &tmp,
+            &[
+                ("core", None),
+                ("app", Some(r#"{"dependencies":{"core":"^1.0.0"}}"#)),
+                ("tool", Some(r#"{"optionalDependencies":{"core":"*"}}"#)),
+                ("other", Some(r#"{"dependencies":{"app":"*"}}"#)),
+            ],
+        );
+
+        let result = rename_plugin("core", "base", &config).unwrap();
+        assert_eq!(result.dependents, ["app", "tool"]);
+
+        let extends = |dir: &str| {
+            read_json(
+                &config
+                    .plugin_root_abs
+                    .join(dir)
+                    .join(".claude-plugin/extends-plugin.json"),
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            extends("app")["dependencies"],
+            serde_json::json!({"base": "^1.0.0"})
+        );
+        assert_eq!(
+            extends("tool")["optionalDependencies"],
+            serde_json::json!({"base": "*"})
+        );
+        assert_eq!(
+            extends("other")["dependencies"],
+            serde_json::json!({"app": "*"})
+        );
+    }
+
+    #[test]
+    fn failed_validation_rolls_everything_back() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(
+            &tmp,
+            &[
+                ("core", None),
+                ("app", Some(r#"{"dependencies":{"core":"^1.0.0"}}"#)),
+            ],
+        );
+        // An invalid version makes the renamed plugin fail validation.
+        let core_json = config
+            .plugin_root_abs
+            .join("core/.claude-plugin/plugin.json");
+        fs::write(
+            &core_json,
+            r#"{"name":"core","version":"one","description":"d"}"#,
+        )
+        .unwrap();
+        let mp_before = fs::read_to_string(&config.marketplace_path).unwrap();
+        let extends_path = config
+            .plugin_root_abs
+            .join("app/.claude-plugin/extends-plugin.json");
+        let extends_before = fs::read_to_string(&extends_path).unwrap();
+
+        let err = rename_plugin("core", "base", &config).unwrap_err();
+        assert!(matches!(err, SoukError::AtomicRollback(_)), "{err}");
+
+        assert!(!config.plugin_root_abs.join("base").exists());
+        assert_eq!(plugin_json(&config, "core")["name"], "core");
+        assert_eq!(
+            fs::read_to_string(&config.marketplace_path).unwrap(),
+            mp_before
+        );
+        assert_eq!(fs::read_to_string(&extends_path).unwrap(), extends_before);
+    }
+
+    #[test]
+    fn rename_rejects_unknown_and_taken_names() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, &[("alpha", None), ("beta", None)]);
+
+        assert!(matches!(
+            rename_plugin("ghost", "x", &config),
+            Err(SoukError::PluginNotFound(_))
+        ));
+        assert!(matches!(
+            rename_plugin("alpha", "beta", &config),
+            Err(SoukError::PluginAlreadyExists(_))
+        ));
+        assert!(config.plugin_root_abs.join("alpha").is_dir());
+    }
+
+    #[test]
+    fn rename_rejects_names_that_leave_plugin_root() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, &[("alpha", None)]);
+        let before = fs::read_to_string(&config.marketplace_path).unwrap();
+
+        for new in ["../escaped", "nested/alpha", "/tmp/alpha", "..", "a\\b"] {
+            let err = rename_plugin("alpha", new, &config).unwrap_err();
+            assert!(err.to_string().contains("Plugin name"), "{new}: {err}");
+        }
+        assert!(config.plugin_root_abs.join("alpha").is_dir());
+        assert!(!tmp.path().join("escaped").exists());
+        assert_eq!(
+            fs::read_to_string(&config.marketplace_path).unwrap(),
+            before
+        );
+    }
+}
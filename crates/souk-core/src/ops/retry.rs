@@ -0,0 +1,174 @@
+//! Retrying marketplace mutations that were rolled back.
+//!
+//! A mutation whose final validation fails is rolled back by its
+//! [`AtomicGuard`](crate::ops::AtomicGuard)s. When the failure was
+//! transient, such as another writer changing marketplace.json at the same
+//! time, running the whole operation again against a freshly loaded config
+//! often succeeds.
+
+use std::time::Duration;
+
+use crate::discovery::{load_marketplace_config, MarketplaceConfig};
+use crate::error::SoukError;
+
+/// How long [`retry_on_rollback`] waits before each retry.
+pub const RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// Run `op` against `config`, retrying it after a rollback.
+///
+/// If `op` fails with [`SoukError::AtomicRollback`], waits [`RETRY_DELAY`],
+/// reloads the config from its marketplace.json, and runs `op` again, up to
+/// `retries` more times. `on_retry` is called with the retry number
+/// (starting at 1) and the error before each retry. Any other error, or a
+/// failure to reload the config, is returned at once.
+pub fn retry_on_rollback<T>(
+    retries: u32,
+    config: &MarketplaceConfig,
+    mut on_retry: impl FnMut(u32, &SoukError),
+    mut op: impl FnMut(&MarketplaceConfig) -> Result<T, SoukError>,
+) -> Result<T, SoukError> {
+    let mut result = op(config);
+    for attempt in 1..=retries {
+        match &result {
+            Err(e @ SoukError::AtomicRollback(_)) => on_retry(attempt, e),
+            _ => break,
+        }
+        std::thread::sleep(RETRY_DELAY);
+        result = load_marketplace_config(&config.marketplace_path).and_then(|config| op(&config));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::ops::update::{update_plugins, UpdateFields};
+    use crate::types::Marketplace;
+    use crate::version::VersionBump;
+
+    const VALID: &str = r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[
+        {"name":"alpha","source":"alpha"}]}"#;
+
+    /// A marketplace with one plugin, alpha, and the given marketplace.json.
+    fn setup(tmp: &TempDir, marketplace_json: &str) -> MarketplaceConfig {
+        let plugin = tmp.path().join("plugins/alpha/.claude-plugin");
+        fs::create_dir_all(&plugin).unwrap();
+        fs::write(
+            plugin.join("plugin.json"),
+            r#"{"name":"alpha","version":"1.0.0","description":"test"}"#,
+        )
+        .unwrap();
+        let claude = tmp.path().join(".claude-plugin");
+        fs::create_dir_all(&claude).unwrap();
+        fs::write(claude.join("marketplace.json"), marketplace_json).unwrap();
+        load_marketplace_config(&claude.join("marketplace.json")).unwrap()
+    }
+
+    fn bump(config: &MarketplaceConfig) -> Result<Vec<String>, SoukError> {
+        update_plugins(
+            &["alpha".to_string()],
+            Some(&VersionBump::Patch),
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            config,
+        )
+    }
+
+    #[test]
+    fn retry_succeeds_after_validation_failure_clears() {
+        let tmp = TempDir::new().unwrap();
+        // A duplicate entry fails final validation on the first attempt
+        let config = setup(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[
+                {"name":"alpha","source":"alpha"},{"name":"alpha","source":"alpha"}]}"#,
+        );
+
+        let mut attempts = 0;
+        let mut retried = Vec::new();
+        let result = retry_on_rollback(
+            2,
+            &config,
+            |attempt, _| retried.push(attempt),
+            |config| {
+                attempts += 1;
+                let result = bump(config);
+                if attempts == 1 {
+                    // Another writer fixes the marketplace in the meantime
+                    fs::write(&config.marketplace_path, VALID).unwrap();
+                }
+                result
+            },
+        );
+
+        assert_eq!(result.unwrap(), vec!["alpha".to_string()]);
+        assert_eq!(attempts, 2);
+        assert_eq!(retried, vec![1]);
+        let marketplace: Marketplace =
+            serde_json::from_str(&fs::read_to_string(&config.marketplace_path).unwrap()).unwrap();
+        assert_eq!(marketplace.version, "0.1.1");
+    }
+
+    #[test]
+    fn no_retry_by_default() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, VALID);
+
+        let mut attempts = 0;
+        let result: Result<(), _> = retry_on_rollback(
+            0,
+            &config,
+            |_, _| panic!("should not retry"),
+            |_| {
+                attempts += 1;
+                Err(SoukError::AtomicRollback("invalid".into()))
+            },
+        );
+        assert!(matches!(result, Err(SoukError::AtomicRollback(_))));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn only_rollbacks_are_retried() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, VALID);
+
+        let mut attempts = 0;
+        let result: Result<(), _> = retry_on_rollback(
+            3,
+            &config,
+            |_, _| {},
+            |_| {
+                attempts += 1;
+                Err(SoukError::PluginNotFound("beta".into()))
+            },
+        );
+        assert!(matches!(result, Err(SoukError::PluginNotFound(_))));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn gives_up_after_retries() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, VALID);
+
+        let mut attempts = 0;
+        let result: Result<(), _> = retry_on_rollback(
+            2,
+            &config,
+            |_, _| {},
+            |_| {
+                attempts += 1;
+                Err(SoukError::AtomicRollback("invalid".into()))
+            },
+        );
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+    }
+}
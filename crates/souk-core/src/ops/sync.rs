@@ -0,0 +1,295 @@
+//! Reconcile marketplace.json with the plugin directories in pluginRoot.
+//!
+//! A sync adds an entry for every directory in pluginRoot that is not
+//! listed (see [`find_orphaned_dirs`]) and drops every entry whose
+//! directory is missing (see [`find_stale_entries`]), in one atomic write
+//! with a single version bump.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::discovery::{load_marketplace_config, MarketplaceConfig};
+use crate::error::SoukError;
+use crate::ops::add::read_plugin_manifest;
+use crate::ops::AtomicGuard;
+use crate::resolution::plugin_path_to_source;
+use crate::types::{Marketplace, PluginEntry};
+use crate::validation::{
+    find_orphaned_dirs, find_stale_entries, validate_marketplace, validate_plugin_with,
+};
+use crate::version::bump_patch;
+
+/// A plugin directory that a sync will list in the marketplace.
+#[derive(Debug, Clone)]
+pub struct SyncAddition {
+    /// The plugin directory under pluginRoot.
+    pub plugin_path: PathBuf,
+    /// The entry to add, named and tagged from the plugin's plugin.json.
+    pub entry: PluginEntry,
+}
+
+/// The changes a sync will make to marketplace.json.
+#[derive(Debug, Clone, Default)]
+pub struct SyncPlan {
+    /// Unlisted plugin directories to add as entries.
+    pub additions: Vec<SyncAddition>,
+    /// Names of entries whose directory is missing, to drop.
+    pub removals: Vec<String>,
+}
+
+impl SyncPlan {
+    /// Whether the marketplace is already in sync.
+    pub fn is_empty(&self) -> bool {
+        self.additions.is_empty() && self.removals.is_empty()
+    }
+}
+
+/// Plans a sync without modifying anything.
+///
+/// Each unlisted directory's plugin.json supplies the entry's name and tags,
+/// as with `souk add`, and the directory must pass plugin validation.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Io`] if pluginRoot cannot be read,
+/// [`SoukError::PluginAlreadyExists`] if a directory's plugin name is
+/// already listed (and not being dropped) or is used by two directories,
+/// or [`SoukError::Other`] listing every directory whose plugin.json cannot
+/// be read or that fails validation.
+pub fn plan_sync(config: &MarketplaceConfig) -> Result<SyncPlan, SoukError> {
+    let removals: Vec<String> = find_stale_entries(config)?
+        .into_iter()
+        .map(|p| p.name.clone())
+        .collect();
+    let mut names: HashSet<String> = config
+        .marketplace
+        .plugins
+        .iter()
+        .map(|p| p.name.clone())
+        .filter(|name| !removals.contains(name))
+        .collect();
+
+    let mut orphans = find_orphaned_dirs(config)?;
+    orphans.sort();
+
+    let mut additions = Vec::new();
+    let mut errors = Vec::new();
+    for plugin_path in orphans {
+        let manifest = match read_plugin_manifest(&plugin_path) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                errors.push(format!(
+                    "Cannot read plugin.json in {}: {e}",
+                    plugin_path.display()
+                ));
+                continue;
+            }
+        };
+        let Some(name) = manifest.name_str().map(str::to_string) else {
+            errors.push(format!(
+                "Plugin has no name in plugin.json: {}",
+                plugin_path.display()
+            ));
+            continue;
+        };
+
+        if validate_plugin_with(&plugin_path, &config.settings).has_errors() {
+            errors.push(format!(
+                "Plugin validation failed: {name} ({})",
+                plugin_path.display()
+            ));
+            continue;
+        }
+        if !names.insert(name.clone()) {
+            return Err(SoukError::PluginAlreadyExists(name));
+        }
+
+        let (source, _) = plugin_path_to_source(&plugin_path, config);
+        additions.push(SyncAddition {
+            plugin_path,
+            entry: PluginEntry {
+                name,
+                source,
+                tags: manifest.keywords,
+                origin: None,
+                sha: None,
+            },
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(SoukError::Other(errors.join("; ")));
+    }
+
+    Ok(SyncPlan {
+        additions,
+        removals,
+    })
+}
+
+/// Applies `plan` to marketplace.json in one write, bumping the marketplace
+/// version (patch) once. An empty plan changes nothing.
+///
+/// If `validate_after` is false, the marketplace is not re-validated after
+/// the write.
+///
+/// # Errors
+///
+/// Returns [`SoukError::AtomicRollback`] if the marketplace fails
+/// validation after the write, in which case marketplace.json is restored.
+pub fn execute_sync(
+    plan: &SyncPlan,
+    config: &MarketplaceConfig,
+    validate_after: bool,
+) -> Result<(), SoukError> {
+    if plan.is_empty() {
+        return Ok(());
+    }
+
+    let guard = AtomicGuard::new(&config.marketplace_path)?;
+
+    let content = fs::read_to_string(&config.marketplace_path)?;
+    let mut marketplace: Marketplace = serde_json::from_str(&content)?;
+    marketplace
+        .plugins
+        .retain(|p| !plan.removals.contains(&p.name));
+    marketplace
+        .plugins
+        .extend(plan.additions.iter().map(|a| a.entry.clone()));
+    marketplace.version = bump_patch(&marketplace.version)?;
+
+    let json = serde_json::to_string_pretty(&marketplace)?;
+    fs::write(&config.marketplace_path, format!("{json}\n"))?;
+
+    if validate_after {
+        let updated_config = load_marketplace_config(&config.marketplace_path)?;
+        if validate_marketplace(&updated_config, true).has_errors() {
+            drop(guard);
+            return Err(SoukError::AtomicRollback(
+                "Validation failed after sync".to_string(),
+            ));
+        }
+    }
+
+    guard.commit()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup(tmp: &TempDir, listed: &[&str], on_disk: &[(&str, &str)]) -> MarketplaceConfig {
+        let claude = tmp.path().join(".claude-plugin");
+        fs::create_dir_all(&claude).unwrap();
+        for (dir, plugin_json) in on_disk {
+            let p = tmp.path().join("plugins").join(dir).join(".claude-plugin");
+            fs::create_dir_all(&p).unwrap();
+            fs::write(p.join("plugin.json"), plugin_json).unwrap();
+        }
+        fs::create_dir_all(tmp.path().join("plugins")).unwrap();
+        let entries: Vec<String> = listed
+            .iter()
+            .map(|name| format!(r#"{{"name":"{name}","source":"{name}"}}"#))
+            .collect();
+        let mp_path = claude.join("marketplace.json");
+        fs::write(
+            &mp_path,
+            format!(
+                r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{}]}}"#,
+                entries.join(",")
+            ),
+        )
+        .unwrap();
+        load_marketplace_config(&mp_path).unwrap()
+    }
+
+    fn plugin_json(name: &str) -> String {
+        format!(
+            r#"{{"name":"{name}","version":"1.0.0","description":"test","keywords":["tools"]}}"#
+        )
+    }
+
+    #[test]
+    fn sync_adds_new_dirs_and_drops_stale_entries_in_one_bump() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(
+            &tmp,
+            &["kept", "gone"],
+            &[
+                ("kept", &plugin_json("kept")),
+                ("fresh", &plugin_json("fresh")),
+            ],
+        );
+
+        let plan = plan_sync(&config).unwrap();
+        assert_eq!(plan.removals, vec!["gone".to_string()]);
+        assert_eq!(plan.additions.len(), 1);
+        assert_eq!(plan.additions[0].entry.name, "fresh");
+        assert_eq!(plan.additions[0].entry.source, "fresh");
+        assert_eq!(plan.additions[0].entry.tags, vec!["tools".to_string()]);
+
+        execute_sync(&plan, &config, true).unwrap();
+
+        let updated = load_marketplace_config(&config.marketplace_path).unwrap();
+        assert_eq!(updated.marketplace.version, "0.1.1");
+        let names: Vec<&str> = updated
+            .marketplace
+            .plugins
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["kept", "fresh"]);
+        assert!(plan_sync(&updated).unwrap().is_empty());
+    }
+
+    #[test]
+    fn sync_refuses_invalid_new_plugins() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(
+            &tmp,
+            &[],
+            &[("good", &plugin_json("good")), ("bad", r#"{"name":"bad"}"#)],
+        );
+
+        let err = plan_sync(&config).unwrap_err();
+
+        assert!(err.to_string().contains("Plugin validation failed: bad"));
+    }
+
+    #[test]
+    fn sync_refuses_a_name_that_is_already_listed() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(
+            &tmp,
+            &["kept"],
+            &[
+                ("kept", &plugin_json("kept")),
+                ("copy", &plugin_json("kept")),
+            ],
+        );
+
+        assert!(matches!(
+            plan_sync(&config),
+            Err(SoukError::PluginAlreadyExists(name)) if name == "kept"
+        ));
+    }
+
+    #[test]
+    fn empty_plan_leaves_marketplace_untouched() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, &["kept"], &[("kept", &plugin_json("kept"))]);
+        let before = fs::read_to_string(&config.marketplace_path).unwrap();
+
+        let plan = plan_sync(&config).unwrap();
+        assert!(plan.is_empty());
+        execute_sync(&plan, &config, true).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&config.marketplace_path).unwrap(),
+            before
+        );
+    }
+}
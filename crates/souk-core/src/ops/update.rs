@@ -1,18 +1,171 @@
 //! Update plugin metadata in the marketplace.
 //!
 //! Re-reads plugin.json from disk to refresh the marketplace entry, and
-//! optionally bumps the plugin version.
+//! optionally bumps the plugin version; [`check_drift`] reports what that
+//! would change without writing. Entries added from git can instead have
+//! just the commit of their `origin` refreshed. [`bump_dependency`] separately
+//! points dependents' extends-plugin.json constraints at a dependency's
+//! current version.
 
 use std::collections::HashMap;
 use std::fs;
+use std::path::{Path, PathBuf};
+
+use semver::Version;
 
 use crate::discovery::{load_marketplace_config, MarketplaceConfig};
 use crate::error::SoukError;
+use crate::ops::add::origin_sha;
 use crate::ops::AtomicGuard;
 use crate::resolution::resolve_source;
-use crate::types::{Marketplace, PluginManifest};
-use crate::validation::{validate_marketplace, validate_plugin};
-use crate::version::{bump_major, bump_minor, bump_patch};
+use crate::types::plugin::read_manifest_text;
+use crate::types::{Marketplace, PluginEntry, PluginManifest};
+use crate::util::current_date_string;
+use crate::validation::dependencies::{
+    plugin_version, read_extends_at, version_req, DEPENDENCY_SECTIONS,
+};
+use crate::validation::extends::extract_version;
+use crate::validation::{validate_marketplace, validate_plugin_with};
+use crate::version::{bump_patch, VersionBump};
+
+/// The file `update_plugins` records version bumps in, relative to the plugin.
+pub const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// Which marketplace-entry fields `update_plugins` refreshes from plugin.json.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateFields {
+    /// Rename the entry to plugin.json's `name`.
+    pub name: bool,
+    /// Replace the entry's tags with plugin.json's `keywords`.
+    pub tags: bool,
+    /// Record the commit an entry's git `origin` now points at. Entries
+    /// without an origin are left alone.
+    pub sha: bool,
+}
+
+impl Default for UpdateFields {
+    fn default() -> Self {
+        Self {
+            name: true,
+            tags: true,
+            sha: false,
+        }
+    }
+}
+
+impl UpdateFields {
+    /// Refresh only the recorded origin commit (`--refresh-origin-sha`).
+    pub const ORIGIN_SHA: Self = Self {
+        name: false,
+        tags: false,
+        sha: true,
+    };
+
+    /// Parse a comma-separated field list such as `tags` or `name,tags`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoukError::Other`] for an unknown field or an empty list.
+    pub fn parse(spec: &str) -> Result<Self, SoukError> {
+        let mut fields = Self {
+            name: false,
+            tags: false,
+            sha: false,
+        };
+        for field in spec.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            match field {
+                "name" => fields.name = true,
+                "tags" => fields.tags = true,
+                other => {
+                    return Err(SoukError::Other(format!(
+                        "Unknown update field '{other}' (expected name or tags)"
+                    )))
+                }
+            }
+        }
+        if !fields.name && !fields.tags {
+            return Err(SoukError::Other("No update fields given".to_string()));
+        }
+        Ok(fields)
+    }
+}
+
+/// The marketplace entry `update_plugins` would write for `entry`, given
+/// its plugin's `manifest`: only the fields selected by `fields` change.
+pub fn desired_entry(
+    entry: &PluginEntry,
+    manifest: &PluginManifest,
+    fields: UpdateFields,
+) -> PluginEntry {
+    let mut desired = entry.clone();
+    if fields.tags {
+        desired.tags = manifest.keywords.clone();
+    }
+    if let Some(name) = manifest.name_str().filter(|_| fields.name) {
+        desired.name = name.to_string();
+    }
+    desired
+}
+
+/// A marketplace entry that is out of sync with its plugin.json.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryDrift {
+    /// The entry as it is in marketplace.json.
+    pub current: PluginEntry,
+    /// The entry `update_plugins` would write.
+    pub desired: PluginEntry,
+}
+
+impl EntryDrift {
+    /// Names of the fields that differ.
+    pub fn fields(&self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.current.name != self.desired.name {
+            fields.push("name");
+        }
+        if self.current.tags != self.desired.tags {
+            fields.push("tags");
+        }
+        fields
+    }
+}
+
+/// Compares the named plugins' entries with their plugin.json without
+/// writing anything, returning the entries `update_plugins` would change.
+///
+/// # Errors
+///
+/// Returns [`SoukError::PluginNotFound`] if any name does not exist in the
+/// marketplace, or an error if a plugin.json cannot be read.
+pub fn check_drift(
+    names: &[String],
+    fields: UpdateFields,
+    config: &MarketplaceConfig,
+) -> Result<Vec<EntryDrift>, SoukError> {
+    let mut drift = Vec::new();
+    for name in names {
+        let entry = config
+            .marketplace
+            .plugins
+            .iter()
+            .find(|p| p.name == *name)
+            .ok_or_else(|| SoukError::PluginNotFound(name.clone()))?;
+        let plugin_json_path = resolve_source(&entry.source, config)?
+            .join(".claude-plugin")
+            .join("plugin.json");
+        let content = read_manifest_text(&plugin_json_path)
+            .map_err(|e| SoukError::Other(format!("{e} ({name})")))?;
+        let manifest: PluginManifest = serde_json::from_str(&content)?;
+        let desired = desired_entry(entry, &manifest, fields);
+        if desired != *entry {
+            drift.push(EntryDrift {
+                current: entry.clone(),
+                desired,
+            });
+        }
+    }
+    Ok(drift)
+}
 
 /// Updates the named plugins in the marketplace by re-reading their
 /// plugin.json from disk.
@@ -20,12 +173,19 @@ use crate::version::{bump_major, bump_minor, bump_patch};
 /// For each name in `names`:
 /// - Resolves the plugin to its directory via the marketplace source
 /// - Re-reads plugin.json
-/// - Updates the marketplace entry (name, tags)
-/// - If `bump_type` is specified ("major", "minor", or "patch"), bumps
-///   the version in the plugin's plugin.json file
+/// - Updates the marketplace entry fields selected by `fields` (name, tags)
+/// - With `fields.sha`, records the commit each entry's git `origin` now
+///   points at (see [`origin_sha`]), for entries that have one
+/// - If `bump` is specified, applies it to the version in the plugin's
+///   plugin.json file. A major, minor, or patch bump strips pre-release
+///   tags unless `keep_prerelease` is set
+/// - If `changelog` is also given, prepends a dated entry for the new
+///   version to the plugin's CHANGELOG.md, rolled back with the bump
 /// - Re-validates the plugin after update
 ///
-/// The marketplace version is always bumped (patch) at the end.
+/// The marketplace version is always bumped (patch) at the end. If
+/// `validate_after` is false, the final marketplace validation is skipped;
+/// each updated plugin is still validated.
 ///
 /// # Errors
 ///
@@ -35,7 +195,11 @@ use crate::version::{bump_major, bump_minor, bump_patch};
 /// Returns [`SoukError::AtomicRollback`] if post-update validation fails.
 pub fn update_plugins(
     names: &[String],
-    bump_type: Option<&str>,
+    bump: Option<&VersionBump>,
+    changelog: Option<&str>,
+    keep_prerelease: bool,
+    fields: UpdateFields,
+    validate_after: bool,
     config: &MarketplaceConfig,
 ) -> Result<Vec<String>, SoukError> {
     if names.is_empty() {
@@ -62,37 +226,58 @@ pub fn update_plugins(
         plugin_paths.push((name.clone(), plugin_path));
     }
 
+    // Ask each origin for its commit before anything is written
+    let mut origin_shas: HashMap<String, String> = HashMap::new();
+    if fields.sha {
+        for entry in &config.marketplace.plugins {
+            if let Some(origin) = entry
+                .origin
+                .as_deref()
+                .filter(|_| names.contains(&entry.name))
+            {
+                let sha = origin_sha(origin)
+                    .map_err(|e| SoukError::Other(format!("{e} ({})", entry.name)))?;
+                origin_shas.insert(entry.name.clone(), sha);
+            }
+        }
+    }
+
     // Create ALL guards BEFORE any writes
     let mp_guard = AtomicGuard::new(&config.marketplace_path)?;
 
     let mut plugin_guards: Vec<AtomicGuard> = Vec::new();
-    if bump_type.is_some() {
+    if bump.is_some() {
         for (_name, plugin_path) in &plugin_paths {
             let plugin_json_path = plugin_path.join(".claude-plugin").join("plugin.json");
             let guard = AtomicGuard::new(&plugin_json_path)?;
             plugin_guards.push(guard);
+            if changelog.is_some() {
+                let changelog_path = plugin_path.join(CHANGELOG_FILE);
+                plugin_guards.push(AtomicGuard::new_removing_created(&changelog_path)?);
+            }
         }
     }
 
     // Now perform version bumps (protected by guards)
-    if let Some(bump) = bump_type {
+    if let Some(bump) = bump {
         for (name, plugin_path) in &plugin_paths {
             let plugin_json_path = plugin_path.join(".claude-plugin").join("plugin.json");
-            let content = fs::read_to_string(&plugin_json_path).map_err(|e| {
-                SoukError::Other(format!("Cannot read plugin.json for {name}: {e}"))
-            })?;
+            let content = read_manifest_text(&plugin_json_path)
+                .map_err(|e| SoukError::Other(format!("{e} ({name})")))?;
 
             let mut doc: serde_json::Value = serde_json::from_str(&content)?;
 
             if let Some(version) = doc.get("version").and_then(|v| v.as_str()) {
-                let new_version = match bump {
-                    "major" => bump_major(version)?,
-                    "minor" => bump_minor(version)?,
-                    "patch" => bump_patch(version)?,
-                    _ => {
-                        return Err(SoukError::Other(format!("Invalid bump type: {bump}")));
-                    }
-                };
+                let new_version = bump
+                    .apply(version, keep_prerelease)
+                    .map_err(|e| SoukError::Other(format!("Cannot bump {name}: {e}")))?;
+                if let Some(message) = changelog {
+                    prepend_changelog_entry(
+                        &plugin_path.join(CHANGELOG_FILE),
+                        &new_version,
+                        message,
+                    )?;
+                }
                 doc["version"] = serde_json::Value::String(new_version);
             }
 
@@ -107,49 +292,40 @@ pub fn update_plugins(
 
     let mut updated = Vec::new();
     let mut rename_targets: HashMap<String, String> = HashMap::new();
+    // Look entries up before any renames, so swapped names stay matched
+    let positions: Vec<Option<usize>> = plugin_paths
+        .iter()
+        .map(|(name, _)| marketplace.plugins.iter().position(|p| p.name == *name))
+        .collect();
 
-    for (name, plugin_path) in &plugin_paths {
+    for ((name, plugin_path), position) in plugin_paths.iter().zip(positions) {
         let plugin_json_path = plugin_path.join(".claude-plugin").join("plugin.json");
-        let pj_content = fs::read_to_string(&plugin_json_path)
-            .map_err(|e| SoukError::Other(format!("Cannot read plugin.json for {name}: {e}")))?;
+        let pj_content = read_manifest_text(&plugin_json_path)
+            .map_err(|e| SoukError::Other(format!("{e} ({name})")))?;
 
         let manifest: PluginManifest = serde_json::from_str(&pj_content)?;
 
-        // Check for rename collisions
-        if let Some(new_name) = manifest.name_str() {
-            if new_name != name.as_str() {
-                // Check against other renames within this batch
+        if let Some(entry) = position.map(|i| &mut marketplace.plugins[i]) {
+            let mut desired = desired_entry(entry, &manifest, fields);
+            if let Some(sha) = origin_shas.remove(name) {
+                desired.sha = Some(sha);
+            }
+
+            // Check against other renames within this batch
+            if desired.name != *name {
+                let new_name = &desired.name;
                 if let Some(prev) = rename_targets.get(new_name) {
                     return Err(SoukError::Other(format!(
                         "Plugins '{prev}' and '{name}' would both be renamed to '{new_name}'"
                     )));
                 }
-
-                // Check against plugins outside this batch
-                let collides = marketplace
-                    .plugins
-                    .iter()
-                    .any(|p| p.name == new_name && !names.contains(&p.name));
-                if collides {
-                    return Err(SoukError::Other(format!(
-                        "Plugin '{name}' would be renamed to '{new_name}' which conflicts with an existing plugin"
-                    )));
-                }
-
-                rename_targets.insert(new_name.to_string(), name.clone());
+                rename_targets.insert(new_name.clone(), name.clone());
             }
-        }
 
-        if let Some(entry) = marketplace.plugins.iter_mut().find(|p| p.name == *name) {
-            entry.tags = manifest.keywords.clone();
-            if let Some(new_name) = manifest.name_str() {
-                if new_name != name.as_str() {
-                    entry.name = new_name.to_string();
-                }
-            }
+            *entry = desired;
         }
 
-        let validation = validate_plugin(plugin_path);
+        let validation = validate_plugin_with(plugin_path, &config.settings);
         if validation.has_errors() {
             return Err(SoukError::AtomicRollback(format!(
                 "Plugin validation failed for {name} after update"
@@ -159,6 +335,22 @@ pub fn update_plugins(
         updated.push(name.clone());
     }
 
+    // Check renames against the final names, so a plugin that keeps its
+    // name counts whether or not it was in this batch
+    for (new_name, name) in &rename_targets {
+        if marketplace
+            .plugins
+            .iter()
+            .filter(|p| p.name == *new_name)
+            .count()
+            > 1
+        {
+            return Err(SoukError::Other(format!(
+                "Plugin '{name}' would be renamed to '{new_name}' which conflicts with an existing plugin"
+            )));
+        }
+    }
+
     // Bump marketplace version
     marketplace.version = bump_patch(&marketplace.version)?;
 
@@ -167,12 +359,14 @@ pub fn update_plugins(
     fs::write(&config.marketplace_path, format!("{json}\n"))?;
 
     // Final validation
-    let updated_config = load_marketplace_config(&config.marketplace_path)?;
-    let validation = validate_marketplace(&updated_config, true);
-    if validation.has_errors() {
-        return Err(SoukError::AtomicRollback(
-            "Marketplace validation failed after update".to_string(),
-        ));
+    if validate_after {
+        let updated_config = load_marketplace_config(&config.marketplace_path)?;
+        let validation = validate_marketplace(&updated_config, true);
+        if validation.has_errors() {
+            return Err(SoukError::AtomicRollback(
+                "Marketplace validation failed after update".to_string(),
+            ));
+        }
     }
 
     // Success — commit all guards
@@ -184,6 +378,203 @@ pub fn update_plugins(
     Ok(updated)
 }
 
+/// Prepend a dated `## {version} - {date}` entry to a plugin's CHANGELOG.md,
+/// keeping a leading `# ` title first and creating the file if absent.
+fn prepend_changelog_entry(path: &Path, version: &str, message: &str) -> Result<(), SoukError> {
+    let entry = format!("## {version} - {}\n- {message}\n", current_date_string());
+    let existing = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let updated = if existing.is_empty() {
+        format!("# Changelog\n\n{entry}")
+    } else if existing.starts_with("# ") {
+        let (title, rest) = existing.split_once('\n').unwrap_or((&existing, ""));
+        format!("{title}\n\n{entry}\n{}", rest.trim_start_matches('\n'))
+    } else {
+        format!("{entry}\n{existing}")
+    };
+    fs::write(path, updated)?;
+    Ok(())
+}
+
+/// A dependency constraint rewritten by [`bump_dependency`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyBump {
+    /// The plugin whose extends-plugin.json was rewritten.
+    pub plugin: String,
+    /// The section holding the constraint, `dependencies` or
+    /// `optionalDependencies`.
+    pub section: String,
+    /// The constraint before the rewrite.
+    pub from: String,
+    /// The constraint after the rewrite.
+    pub to: String,
+}
+
+impl DependencyBump {
+    /// Whether the constraint already matched and was left as it was.
+    pub fn unchanged(&self) -> bool {
+        self.from == self.to
+    }
+}
+
+/// Points each dependent's constraint on `dependency` at the version in
+/// the dependency's plugin.json.
+///
+/// The constraint's operator is kept (`^1.0.0` becomes `^2.0.0`, `~1.2.0`
+/// becomes `~1.3.0`, a bare version stays an exact match); constraints
+/// without a single leading operator, such as ranges or `*`, become
+/// `^<version>`. Entries in both `dependencies` and `optionalDependencies`
+/// are rewritten, whether given as a string or as an object with a
+/// `version` field; each section's constraint is retargeted on its own and
+/// reported as a separate [`DependencyBump`].
+///
+/// Each extends-plugin.json is guarded; the dependents are re-validated,
+/// along with the marketplace unless `validate_after` is false, and any
+/// failure restores every file.
+///
+/// # Errors
+///
+/// Returns [`SoukError::PluginNotFound`] if a dependent or the dependency
+/// is not in the marketplace, [`SoukError::Other`] if the dependency's
+/// version cannot be read or a dependent does not declare it, and
+/// [`SoukError::AtomicRollback`] if validation fails after the rewrite.
+pub fn bump_dependency(
+    dependents: &[String],
+    dependency: &str,
+    validate_after: bool,
+    config: &MarketplaceConfig,
+) -> Result<Vec<DependencyBump>, SoukError> {
+    let resolve = |name: &str| -> Result<PathBuf, SoukError> {
+        let entry = config
+            .marketplace
+            .plugins
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| SoukError::PluginNotFound(name.to_string()))?;
+        resolve_source(&entry.source, config)
+    };
+
+    let version = plugin_version(&resolve(dependency)?).ok_or_else(|| {
+        SoukError::Other(format!("Cannot read a semver version for {dependency}"))
+    })?;
+
+    // Read every dependent before writing anything
+    let mut targets = Vec::new();
+    for name in dependents {
+        let plugin_path = resolve(name)?;
+        let (extends_path, doc) = read_extends_at(&plugin_path)
+            .filter(|(_, doc)| !declared_constraints(doc, dependency).is_empty())
+            .ok_or_else(|| {
+                SoukError::Other(format!(
+                    "{name} does not declare a dependency on {dependency}"
+                ))
+            })?;
+        targets.push((name.clone(), plugin_path, extends_path, doc));
+    }
+
+    let mut guards = Vec::new();
+    for (_, _, extends_path, _) in &targets {
+        guards.push(AtomicGuard::new(extends_path)?);
+    }
+
+    let mut bumps = Vec::new();
+    for (name, plugin_path, extends_path, doc) in &mut targets {
+        let mut changed = false;
+        for (section, from) in declared_constraints(doc, dependency) {
+            let to = retarget_constraint(&from, &version);
+            if !version_req(&to).is_some_and(|req| req.matches(&version)) {
+                return Err(SoukError::Other(format!(
+                    "Constraint {to} for {dependency} does not accept {version}"
+                )));
+            }
+            if to != from {
+                set_constraint(doc, section, dependency, &to);
+                changed = true;
+            }
+            bumps.push(DependencyBump {
+                plugin: name.clone(),
+                section: section.to_string(),
+                from,
+                to,
+            });
+        }
+        if changed {
+            let json = serde_json::to_string_pretty(doc)?;
+            fs::write(&*extends_path, format!("{json}\n"))?;
+            if validate_plugin_with(plugin_path, &config.settings).has_errors() {
+                return Err(SoukError::AtomicRollback(format!(
+                    "Plugin validation failed for {name} after updating {dependency}"
+                )));
+            }
+        }
+    }
+
+    if validate_after && bumps.iter().any(|b| !b.unchanged()) {
+        let validation = validate_marketplace(config, true);
+        if validation.has_errors() {
+            return Err(SoukError::AtomicRollback(
+                "Marketplace validation failed after update".to_string(),
+            ));
+        }
+    }
+
+    for guard in guards {
+        guard.commit()?;
+    }
+
+    Ok(bumps)
+}
+
+/// The constraint each section of an extends-plugin.json document declares
+/// for `name`, in [`DEPENDENCY_SECTIONS`] order.
+fn declared_constraints(doc: &serde_json::Value, name: &str) -> Vec<(&'static str, String)> {
+    DEPENDENCY_SECTIONS
+        .into_iter()
+        .filter_map(|section| {
+            let constraint = doc
+                .get(section)
+                .and_then(|deps| deps.get(name))
+                .and_then(extract_version)?;
+            Some((section, constraint))
+        })
+        .collect()
+}
+
+/// Replace the constraint for `name` in `section`, keeping other fields of
+/// an object-form entry.
+fn set_constraint(doc: &mut serde_json::Value, section: &str, name: &str, constraint: &str) {
+    let Some(value) = doc.get_mut(section).and_then(|deps| deps.get_mut(name)) else {
+        return;
+    };
+    match value.as_object_mut() {
+        Some(obj) => {
+            obj.insert("version".to_string(), constraint.into());
+        }
+        None => *value = constraint.into(),
+    }
+}
+
+/// `constraint` with its version replaced by `version`, keeping a single
+/// leading operator; anything more complex becomes a caret requirement.
+fn retarget_constraint(constraint: &str, version: &Version) -> String {
+    let constraint = constraint.trim();
+    if Version::parse(constraint).is_ok() {
+        return version.to_string();
+    }
+    for op in [">=", "^", "~", "="] {
+        if let Some(rest) = constraint.strip_prefix(op) {
+            if Version::parse(rest.trim()).is_ok() {
+                return format!("{op}{version}");
+            }
+        }
+    }
+    format!("^{version}")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,7 +621,16 @@ mod tests {
         assert_eq!(config.marketplace.plugins[0].tags, vec!["old"]);
 
         // Update should refresh tags from plugin.json (which has "original")
-        let updated = update_plugins(&["alpha".to_string()], None, &config).unwrap();
+        let updated = update_plugins(
+            &["alpha".to_string()],
+            None,
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        )
+        .unwrap();
 
         assert_eq!(updated, vec!["alpha"]);
 
@@ -245,7 +645,16 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
 
-        let updated = update_plugins(&["alpha".to_string()], Some("patch"), &config).unwrap();
+        let updated = update_plugins(
+            &["alpha".to_string()],
+            Some(&VersionBump::Patch),
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        )
+        .unwrap();
 
         assert_eq!(updated, vec!["alpha"]);
 
@@ -260,12 +669,103 @@ mod tests {
         assert_eq!(manifest.version_str(), Some("1.0.1"));
     }
 
+    #[test]
+    fn update_keep_prerelease_retains_tag() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+        let plugin_json_path = config
+            .plugin_root_abs
+            .join("alpha")
+            .join(".claude-plugin")
+            .join("plugin.json");
+        fs::write(
+            &plugin_json_path,
+            r#"{"name":"alpha","version":"1.0.0-beta.1","description":"d"}"#,
+        )
+        .unwrap();
+
+        update_plugins(
+            &["alpha".to_string()],
+            Some(&VersionBump::Patch),
+            None,
+            true,
+            UpdateFields::default(),
+            true,
+            &config,
+        )
+        .unwrap();
+        let content = fs::read_to_string(&plugin_json_path).unwrap();
+        let manifest: PluginManifest = serde_json::from_str(&content).unwrap();
+        assert_eq!(manifest.version_str(), Some("1.0.1-beta.1"));
+
+        update_plugins(
+            &["alpha".to_string()],
+            Some(&VersionBump::Patch),
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        )
+        .unwrap();
+        let content = fs::read_to_string(&plugin_json_path).unwrap();
+        let manifest: PluginManifest = serde_json::from_str(&content).unwrap();
+        assert_eq!(manifest.version_str(), Some("1.0.2"));
+    }
+
+    #[test]
+    fn update_cuts_and_releases_prerelease() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+        let plugin_json_path = config
+            .plugin_root_abs
+            .join("alpha")
+            .join(".claude-plugin")
+            .join("plugin.json");
+        let version = || {
+            let content = fs::read_to_string(&plugin_json_path).unwrap();
+            let manifest: PluginManifest = serde_json::from_str(&content).unwrap();
+            manifest.version_str().map(str::to_string)
+        };
+        let update = |bump: VersionBump| {
+            update_plugins(
+                &["alpha".to_string()],
+                Some(&bump),
+                None,
+                false,
+                UpdateFields::default(),
+                true,
+                &config,
+            )
+        };
+
+        update(VersionBump::Prerelease("rc".to_string())).unwrap();
+        assert_eq!(version().as_deref(), Some("1.0.0-rc.1"));
+        update(VersionBump::Prerelease("rc".to_string())).unwrap();
+        assert_eq!(version().as_deref(), Some("1.0.0-rc.2"));
+        update(VersionBump::Release).unwrap();
+        assert_eq!(version().as_deref(), Some("1.0.0"));
+
+        let err = update(VersionBump::Release).unwrap_err().to_string();
+        assert!(err.contains("Cannot bump alpha"), "{err}");
+        assert_eq!(version().as_deref(), Some("1.0.0"));
+    }
+
     #[test]
     fn update_with_major_bumps_version() {
         let tmp = TempDir::new().unwrap();
         let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
 
-        update_plugins(&["alpha".to_string()], Some("major"), &config).unwrap();
+        update_plugins(
+            &["alpha".to_string()],
+            Some(&VersionBump::Major),
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        )
+        .unwrap();
 
         let plugin_json_path = config
             .plugin_root_abs
@@ -282,7 +782,16 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
 
-        update_plugins(&["alpha".to_string()], Some("minor"), &config).unwrap();
+        update_plugins(
+            &["alpha".to_string()],
+            Some(&VersionBump::Minor),
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        )
+        .unwrap();
 
         let plugin_json_path = config
             .plugin_root_abs
@@ -299,7 +808,15 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
 
-        let result = update_plugins(&["nonexistent".to_string()], None, &config);
+        let result = update_plugins(
+            &["nonexistent".to_string()],
+            None,
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        );
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -308,6 +825,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn update_reports_non_utf8_plugin_json() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+        let plugin_json_path = config
+            .plugin_root_abs
+            .join("alpha/.claude-plugin/plugin.json");
+        fs::write(&plugin_json_path, b"{\"name\":\"\xff\"}").unwrap();
+
+        let err = update_plugins(
+            &["alpha".to_string()],
+            None,
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("plugin.json is not valid UTF-8"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn update_multiple_plugins() {
         let tmp = TempDir::new().unwrap();
@@ -315,7 +857,11 @@ mod tests {
 
         let updated = update_plugins(
             &["alpha".to_string(), "beta".to_string()],
-            Some("patch"),
+            Some(&VersionBump::Patch),
+            None,
+            false,
+            UpdateFields::default(),
+            true,
             &config,
         )
         .unwrap();
@@ -357,7 +903,15 @@ mod tests {
         let bad_config = load_marketplace_config(&claude_dir.join("marketplace.json")).unwrap();
 
         // This should fail because the marketplace has duplicate names
-        let result = update_plugins(&["alpha".to_string()], Some("patch"), &bad_config);
+        let result = update_plugins(
+            &["alpha".to_string()],
+            Some(&VersionBump::Patch),
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &bad_config,
+        );
         assert!(result.is_err());
 
         // plugin.json should be restored to original version
@@ -370,6 +924,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn update_changelog_prepends_newest_entry_first() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+        let changelog_path = config.plugin_root_abs.join("alpha").join(CHANGELOG_FILE);
+
+        for (bump, message) in [
+            (VersionBump::Minor, "Add search"),
+            (VersionBump::Patch, "Fix search"),
+        ] {
+            let config = load_marketplace_config(&config.marketplace_path).unwrap();
+            update_plugins(
+                &["alpha".to_string()],
+                Some(&bump),
+                Some(message),
+                false,
+                UpdateFields::default(),
+                true,
+                &config,
+            )
+            .unwrap();
+        }
+
+        let date = current_date_string();
+        assert_eq!(
+            fs::read_to_string(&changelog_path).unwrap(),
+            format!(
+                "# Changelog\n\n## 1.1.1 - {date}\n- Fix search\n\n## 1.1.0 - {date}\n- Add search\n"
+            )
+        );
+    }
+
+    #[test]
+    fn update_changelog_keeps_existing_content_below_entry() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+        let changelog_path = config.plugin_root_abs.join("alpha").join(CHANGELOG_FILE);
+        fs::write(&changelog_path, "## 1.0.0\n- Initial release\n").unwrap();
+
+        update_plugins(
+            &["alpha".to_string()],
+            Some(&VersionBump::Major),
+            Some("Breaking change"),
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&changelog_path).unwrap(),
+            format!(
+                "## 2.0.0 - {}\n- Breaking change\n\n## 1.0.0\n- Initial release\n",
+                current_date_string()
+            )
+        );
+    }
+
+    #[test]
+    fn update_changelog_rolls_back_on_validation_failure() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+        let alpha_changelog = config.plugin_root_abs.join("alpha").join(CHANGELOG_FILE);
+        let beta_changelog = config.plugin_root_abs.join("beta").join(CHANGELOG_FILE);
+        fs::write(
+            &beta_changelog,
+            "# Changelog\n\n## 1.0.0\n- Initial release\n",
+        )
+        .unwrap();
+
+        let claude_dir = tmp.path().join(".claude-plugin");
+        let mp_json = r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[
+            {"name":"alpha","source":"alpha"},
+            {"name":"beta","source":"beta"},
+            {"name":"beta","source":"beta"}
+        ]}"#;
+        fs::write(claude_dir.join("marketplace.json"), mp_json).unwrap();
+        let bad_config = load_marketplace_config(&claude_dir.join("marketplace.json")).unwrap();
+
+        let result = update_plugins(
+            &["alpha".to_string(), "beta".to_string()],
+            Some(&VersionBump::Patch),
+            Some("Fix"),
+            false,
+            UpdateFields::default(),
+            true,
+            &bad_config,
+        );
+        assert!(matches!(result, Err(SoukError::AtomicRollback(_))));
+
+        assert!(
+            !alpha_changelog.exists(),
+            "created changelog should be removed"
+        );
+        assert_eq!(
+            fs::read_to_string(&beta_changelog).unwrap(),
+            "# Changelog\n\n## 1.0.0\n- Initial release\n"
+        );
+    }
+
     #[test]
     fn update_detects_rename_collision() {
         let tmp = TempDir::new().unwrap();
@@ -388,7 +1043,15 @@ mod tests {
         .unwrap();
 
         // Update alpha — should detect the rename collision with beta
-        let result = update_plugins(&["alpha".to_string()], None, &config);
+        let result = update_plugins(
+            &["alpha".to_string()],
+            None,
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        );
         assert!(result.is_err());
         let err = result.unwrap_err().to_string();
         assert!(
@@ -423,7 +1086,15 @@ mod tests {
             .unwrap();
         }
 
-        let result = update_plugins(&["alpha".to_string(), "beta".to_string()], None, &config);
+        let result = update_plugins(
+            &["alpha".to_string(), "beta".to_string()],
+            None,
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        );
         assert!(result.is_err());
         let err = result.unwrap_err().to_string();
         assert!(
@@ -438,4 +1109,249 @@ mod tests {
         assert!(mp.plugins.iter().any(|p| p.name == "alpha"));
         assert!(mp.plugins.iter().any(|p| p.name == "beta"));
     }
+
+    #[test]
+    fn update_detects_rename_collision_with_unrenamed_plugin_in_batch() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+        fs::write(
+            config
+                .plugin_root_abs
+                .join("alpha")
+                .join(".claude-plugin")
+                .join("plugin.json"),
+            r#"{"name":"beta","version":"1.0.0","description":"test plugin"}"#,
+        )
+        .unwrap();
+
+        let err = update_plugins(
+            &["alpha".to_string(), "beta".to_string()],
+            None,
+            None,
+            false,
+            UpdateFields::default(),
+            false,
+            &config,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("conflicts"), "Should report collision: {err}");
+        let content = fs::read_to_string(&config.marketplace_path).unwrap();
+        let mp: Marketplace = serde_json::from_str(&content).unwrap();
+        assert!(mp.plugins.iter().any(|p| p.name == "alpha"));
+    }
+
+    #[test]
+    fn update_allows_swapping_names_within_batch() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+        for (dir, name) in [("alpha", "beta"), ("beta", "alpha")] {
+            fs::write(
+                config
+                    .plugin_root_abs
+                    .join(dir)
+                    .join(".claude-plugin")
+                    .join("plugin.json"),
+                format!(r#"{{"name":"{name}","version":"1.0.0","description":"test plugin"}}"#),
+            )
+            .unwrap();
+        }
+
+        update_plugins(
+            &["alpha".to_string(), "beta".to_string()],
+            None,
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        )
+        .unwrap();
+        let content = fs::read_to_string(&config.marketplace_path).unwrap();
+        let mp: Marketplace = serde_json::from_str(&content).unwrap();
+        assert_eq!(mp.plugins[0].name, "beta");
+        assert_eq!(mp.plugins[1].name, "alpha");
+    }
+
+    #[test]
+    fn update_tags_only_leaves_drifted_name() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+        fs::write(
+            config
+                .plugin_root_abs
+                .join("alpha")
+                .join(".claude-plugin")
+                .join("plugin.json"),
+            r#"{"name":"renamed","version":"1.0.0","description":"test plugin","keywords":["fresh"]}"#,
+        )
+        .unwrap();
+
+        let fields = UpdateFields::parse("tags").unwrap();
+        update_plugins(
+            &["alpha".to_string()],
+            None,
+            None,
+            false,
+            fields,
+            true,
+            &config,
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(&config.marketplace_path).unwrap();
+        let mp: Marketplace = serde_json::from_str(&content).unwrap();
+        assert_eq!(mp.plugins[0].name, "alpha");
+        assert_eq!(mp.plugins[0].tags, vec!["fresh"]);
+    }
+
+    #[test]
+    fn check_drift_reports_only_drifted_entries() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+        let names = vec!["alpha".to_string(), "beta".to_string()];
+
+        // Entries start with tags ["old"] against keywords ["original"]
+        let drift = check_drift(&names, UpdateFields::default(), &config).unwrap();
+        assert_eq!(drift.len(), 2);
+        assert_eq!(drift[0].fields(), vec!["tags"]);
+        assert_eq!(drift[0].desired.tags, vec!["original"]);
+
+        update_plugins(
+            &names,
+            None,
+            None,
+            false,
+            UpdateFields::default(),
+            true,
+            &config,
+        )
+        .unwrap();
+        let config = load_marketplace_config(&config.marketplace_path).unwrap();
+        let before = fs::read_to_string(&config.marketplace_path).unwrap();
+        assert!(check_drift(&names, UpdateFields::default(), &config)
+            .unwrap()
+            .is_empty());
+
+        fs::write(
+            config
+                .plugin_root_abs
+                .join("beta")
+                .join(".claude-plugin")
+                .join("plugin.json"),
+            r#"{"name":"beta-2","version":"1.0.0","description":"test plugin","keywords":["original"]}"#,
+        )
+        .unwrap();
+        let drift = check_drift(&names, UpdateFields::default(), &config).unwrap();
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].current.name, "beta");
+        assert_eq!(drift[0].fields(), vec!["name"]);
+        let tags_only = UpdateFields::parse("tags").unwrap();
+        assert!(check_drift(&names, tags_only, &config).unwrap().is_empty());
+
+        // Nothing is written
+        assert_eq!(
+            fs::read_to_string(&config.marketplace_path).unwrap(),
+            before
+        );
+    }
+
+    #[test]
+    fn parse_update_fields() {
+        assert_eq!(
+            UpdateFields::parse("name, tags").unwrap(),
+            UpdateFields::default()
+        );
+        assert_eq!(
+            UpdateFields::parse("tags").unwrap(),
+            UpdateFields {
+                name: false,
+                tags: true,
+                sha: false,
+            }
+        );
+        assert!(UpdateFields::parse("version").is_err());
+        assert!(UpdateFields::parse("").is_err());
+    }
+
+    #[test]
+    fn bump_dependency_follows_a_major_bump() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+        let extends_path = config
+            .plugin_root_abs
+            .join("beta")
+            .join(".claude-plugin")
+            .join("extends-plugin.json");
+        fs::write(
+            &extends_path,
+            r#"{"dependencies":{"alpha":"^1.0.0"},"optionalDependencies":{"alpha":{"version":"~1.0.0"}}}"#,
+        )
+        .unwrap();
+
+        update_plugins(
+            &["alpha".to_string()],
+            Some(&VersionBump::Major),
+            None,
+            false,
+            UpdateFields::default(),
+            false,
+            &config,
+        )
+        .unwrap();
+        let config = load_marketplace_config(&config.marketplace_path).unwrap();
+        assert!(crate::validation::check_dependency_resolution(&config).has_errors());
+
+        let bumps = bump_dependency(&["beta".to_string()], "alpha", true, &config).unwrap();
+        assert_eq!(
+            bumps,
+            vec![
+                DependencyBump {
+                    plugin: "beta".to_string(),
+                    section: "dependencies".to_string(),
+                    from: "^1.0.0".to_string(),
+                    to: "^2.0.0".to_string(),
+                },
+                DependencyBump {
+                    plugin: "beta".to_string(),
+                    section: "optionalDependencies".to_string(),
+                    from: "~1.0.0".to_string(),
+                    to: "~2.0.0".to_string(),
+                },
+            ]
+        );
+
+        let extends: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&extends_path).unwrap()).unwrap();
+        assert_eq!(extends["dependencies"]["alpha"], "^2.0.0");
+        assert_eq!(
+            extends["optionalDependencies"]["alpha"],
+            serde_json::json!({"version": "~2.0.0"})
+        );
+        assert!(!crate::validation::check_dependency_resolution(&config).has_errors());
+    }
+
+    #[test]
+    fn bump_dependency_requires_a_declared_dependency() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+
+        let err = bump_dependency(&["beta".to_string()], "alpha", true, &config).unwrap_err();
+        assert!(err.to_string().contains("does not declare"), "{err}");
+        assert!(matches!(
+            bump_dependency(&["beta".to_string()], "gamma", true, &config),
+            Err(SoukError::PluginNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn retarget_keeps_the_operator() {
+        let version = Version::parse("2.1.0").unwrap();
+        assert_eq!(retarget_constraint("^1.0.0", &version), "^2.1.0");
+        assert_eq!(retarget_constraint("~1.2.0", &version), "~2.1.0");
+        assert_eq!(retarget_constraint(">= 1.0.0", &version), ">=2.1.0");
+        assert_eq!(retarget_constraint("1.0.0", &version), "2.1.0");
+        assert_eq!(retarget_constraint(">=1.0.0, <2.0.0", &version), "^2.1.0");
+        assert_eq!(retarget_constraint("*", &version), "^2.1.0");
+    }
 }
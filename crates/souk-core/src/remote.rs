@@ -0,0 +1,214 @@
+//! Remote marketplace indexes.
+//!
+//! A remote index is the bundle `souk export --format json` writes (see
+//! [`ExportBundle`](crate::export::ExportBundle)), published at an
+//! `http(s)://` or `file://` URL. [`fetch_index`] reads one and keeps a copy
+//! on disk for [`INDEX_CACHE_TTL`], so repeated searches do not refetch it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::error::SoukError;
+use crate::ops::add::is_fetchable_source;
+use crate::review::provider::http_client;
+use crate::types::PluginEntry;
+use crate::util::Fnv1a;
+
+/// How long a fetched index is reused before it is fetched again.
+pub const INDEX_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// The parts of an exported marketplace bundle a remote index needs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteIndex {
+    /// The URL the index was fetched from.
+    #[serde(skip)]
+    pub url: String,
+    /// One record per marketplace entry, in marketplace order.
+    pub plugins: Vec<RemotePlugin>,
+}
+
+/// A plugin listed in a [`RemoteIndex`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemotePlugin {
+    /// The plugin's entry in the remote marketplace.json.
+    pub entry: PluginEntry,
+    /// The raw plugin.json document, if the index has it.
+    #[serde(default)]
+    pub manifest: Option<serde_json::Value>,
+}
+
+impl RemoteIndex {
+    /// The marketplace entries of every plugin in the index.
+    pub fn entries(&self) -> Vec<PluginEntry> {
+        self.plugins.iter().map(|p| p.entry.clone()).collect()
+    }
+
+    /// The source `souk add` can install the plugin called `name` from: a
+    /// git or archive URL, as accepted by
+    /// [`plan_add`](crate::ops::add::plan_add).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoukError::PluginNotFound`] if the index has no plugin called
+    /// `name`, or [`SoukError::Other`] if its source is a path relative to the
+    /// remote marketplace, which cannot be fetched.
+    pub fn installable_source(&self, name: &str) -> Result<&str, SoukError> {
+        let plugin = self
+            .plugins
+            .iter()
+            .find(|p| p.entry.name == name)
+            .ok_or_else(|| SoukError::PluginNotFound(format!("{name} (in {})", self.url)))?;
+        let source = plugin.entry.source.as_str();
+        if !is_fetchable_source(source) {
+            return Err(SoukError::Other(format!(
+                "{name} in {} has source '{source}', which is not a git or archive URL",
+                self.url
+            )));
+        }
+        Ok(source)
+    }
+}
+
+/// Where [`fetch_index`] caches indexes by default: `souk-index-cache` in
+/// the system temp directory.
+pub fn index_cache_dir() -> PathBuf {
+    std::env::temp_dir().join("souk-index-cache")
+}
+
+/// Fetch and parse the index at `url`, reusing a copy in `cache_dir` that is
+/// younger than [`INDEX_CACHE_TTL`].
+///
+/// `http(s)://` URLs are downloaded with the shared HTTP client; `file://`
+/// URLs are read from disk. Only an index that parses is cached, and a cache
+/// that cannot be written only costs a refetch.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Other`] if the URL scheme is unsupported, the index
+/// cannot be read or downloaded, or it is not a valid index.
+pub fn fetch_index(url: &str, cache_dir: &Path) -> Result<RemoteIndex, SoukError> {
+    let mut hasher = Fnv1a::new();
+    hasher.write_field(url.as_bytes());
+    let cache_path = cache_dir.join(format!("{:016x}.json", hasher.finish()));
+
+    let fresh = fs::metadata(&cache_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < INDEX_CACHE_TTL);
+    if fresh {
+        if let Ok(index) = fs::read_to_string(&cache_path)
+            .map_err(SoukError::from)
+            .and_then(|text| parse_index(url, &text))
+        {
+            return Ok(index);
+        }
+    }
+
+    let text = read_index(url)?;
+    let index = parse_index(url, &text)?;
+    if fs::create_dir_all(cache_dir).is_ok() {
+        let _ = fs::write(&cache_path, &text);
+    }
+    Ok(index)
+}
+
+fn parse_index(url: &str, text: &str) -> Result<RemoteIndex, SoukError> {
+    let mut index: RemoteIndex = serde_json::from_str(text)
+        .map_err(|e| SoukError::Other(format!("Invalid marketplace index at {url}: {e}")))?;
+    index.url = url.to_string();
+    Ok(index)
+}
+
+/// The body of the index at `url`, without the cache.
+fn read_index(url: &str) -> Result<String, SoukError> {
+    if let Some(path) = url.strip_prefix("file://") {
+        return fs::read_to_string(path)
+            .map_err(|e| SoukError::Other(format!("Cannot read index {url}: {e}")));
+    }
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err(SoukError::Other(format!(
+            "Unsupported index URL {url}; expected http://, https://, or file://"
+        )));
+    }
+
+    let fail = |e: &dyn std::fmt::Display| SoukError::Other(format!("Failed to fetch {url}: {e}"));
+    let response = http_client(None).get(url).send().map_err(|e| fail(&e))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(fail(&format!("HTTP {status}")));
+    }
+    response.text().map_err(|e| fail(&e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const INDEX: &str = r#"{"marketplace":{"version":"1.0.0","plugins":[]},"plugins":[
+        {"entry":{"name":"linter","source":"linter","tags":["lint"]},"manifest":null,
+         "skills":[],"extends":null}]}"#;
+
+    #[test]
+    fn file_index_is_parsed_and_cached() {
+        let tmp = TempDir::new().unwrap();
+        let index_path = tmp.path().join("index.json");
+        fs::write(&index_path, INDEX).unwrap();
+        let url = format!("file://{}", index_path.display());
+        let cache = tmp.path().join("cache");
+
+        let index = fetch_index(&url, &cache).unwrap();
+        assert_eq!(index.entries()[0].name, "linter");
+
+        // A fresh cached copy is used even once the source is gone
+        fs::remove_file(&index_path).unwrap();
+        let index = fetch_index(&url, &cache).unwrap();
+        assert_eq!(index.entries()[0].tags, ["lint"]);
+    }
+
+    #[test]
+    fn invalid_or_missing_indexes_are_errors() {
+        let tmp = TempDir::new().unwrap();
+        let cache = tmp.path().join("cache");
+        let index_path = tmp.path().join("index.json");
+        fs::write(&index_path, "<html>").unwrap();
+
+        let url = format!("file://{}", index_path.display());
+        let err = fetch_index(&url, &cache).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid marketplace index"),
+            "{err}"
+        );
+        assert!(!cache.exists());
+
+        let err = fetch_index("ftp://example.com/index.json", &cache).unwrap_err();
+        assert!(err.to_string().contains("Unsupported index URL"), "{err}");
+    }
+
+    #[test]
+    fn installable_source_needs_a_listed_plugin_with_a_url_source() {
+        let index: RemoteIndex = serde_json::from_str(
+            r#"{"plugins":[
+                {"entry":{"name":"linter","source":"https://example.com/linter.tgz"}},
+                {"entry":{"name":"docs","source":"docs"}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            index.installable_source("linter").unwrap(),
+            "https://example.com/linter.tgz"
+        );
+        assert!(matches!(
+            index.installable_source("missing"),
+            Err(SoukError::PluginNotFound(_))
+        ));
+        let err = index.installable_source("docs").unwrap_err();
+        assert!(
+            err.to_string().contains("not a git or archive URL"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! JUnit XML output for validation results.
+//!
+//! CI dashboards that track tests read JUnit XML, so validation is
+//! reported as tests:
+//!
+//! - a `marketplace` testsuite with one `marketplace.json` testcase for
+//!   diagnostics not attributed to a plugin
+//! - a `plugins` testsuite with one testcase per plugin
+//! - with skill granularity, a `skills` testsuite with one `plugin/skill`
+//!   testcase per skill
+//!
+//! A testcase with errors has a single `<failure>` listing each error with
+//! its path; warnings go to the testcase's `<system-out>`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::error::{ValidationDiagnostic, ValidationResult};
+
+/// One skill's validation, reported as its own testcase.
+#[derive(Debug)]
+pub struct SkillCase {
+    /// The plugin the skill belongs to.
+    pub plugin: String,
+    /// The skill's directory name.
+    pub skill: String,
+    /// Diagnostics from [`validate_skill`](crate::validation::validate_skill).
+    pub result: ValidationResult,
+}
+
+/// Render `result` as a JUnit XML document.
+///
+/// Every name in `plugins` gets a testcase, passing if no diagnostic is
+/// attributed to it, as does any other plugin a diagnostic names. The
+/// marketplace testsuite is included when `with_marketplace` is set or
+/// some diagnostic belongs to no plugin. Each of `skills` adds a testcase
+/// to a `skills` testsuite.
+pub fn to_junit_xml(
+    result: &ValidationResult,
+    plugins: &[String],
+    with_marketplace: bool,
+    skills: &[SkillCase],
+) -> String {
+    let mut marketplace = Vec::new();
+    let mut by_plugin: BTreeMap<&str, Vec<&ValidationDiagnostic>> = plugins
+        .iter()
+        .map(|name| (name.as_str(), Vec::new()))
+        .collect();
+    for diagnostic in &result.diagnostics {
+        match &diagnostic.plugin {
+            Some(name) => by_plugin.entry(name).or_default().push(diagnostic),
+            None => marketplace.push(diagnostic),
+        }
+    }
+
+    let skill_names: Vec<String> = skills
+        .iter()
+        .map(|case| format!("{}/{}", case.plugin, case.skill))
+        .collect();
+
+    let mut suites = Vec::new();
+    if with_marketplace || !marketplace.is_empty() {
+        suites.push(("marketplace", vec![("marketplace.json", marketplace)]));
+    }
+    if !by_plugin.is_empty() {
+        suites.push(("plugins", by_plugin.into_iter().collect()));
+    }
+    if !skills.is_empty() {
+        let cases = skills
+            .iter()
+            .zip(&skill_names)
+            .map(|(case, name)| (name.as_str(), case.result.diagnostics.iter().collect()))
+            .collect();
+        suites.push(("skills", cases));
+    }
+
+    let failed = |cases: &[(&str, Vec<&ValidationDiagnostic>)]| {
+        cases
+            .iter()
+            .filter(|(_, diagnostics)| diagnostics.iter().any(|d| d.is_error()))
+            .count()
+    };
+    let tests: usize = suites.iter().map(|(_, cases)| cases.len()).sum();
+    let failures: usize = suites.iter().map(|(_, cases)| failed(cases)).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        r#"<testsuites name="souk" tests="{tests}" failures="{failures}">"#
+    );
+    for (suite, cases) in &suites {
+        let _ = writeln!(
+            xml,
+            r#"  <testsuite name="{suite}" tests="{}" failures="{}">"#,
+            cases.len(),
+            failed(cases)
+        );
+        for (name, diagnostics) in cases {
+            write_testcase(&mut xml, suite, name, diagnostics);
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn write_testcase(
+    xml: &mut String,
+    suite: &str,
+    name: &str,
+    diagnostics: &[&ValidationDiagnostic],
+) {
+    let (errors, warnings): (Vec<&ValidationDiagnostic>, Vec<&ValidationDiagnostic>) =
+        diagnostics.iter().copied().partition(|d| d.is_error());
+    let open = format!(
+        r#"    <testcase classname="{}" name="{}""#,
+        escape(suite),
+        escape(name)
+    );
+    if errors.is_empty() && warnings.is_empty() {
+        let _ = writeln!(xml, "{open}/>");
+        return;
+    }
+
+    let _ = writeln!(xml, "{open}>");
+    if let Some(first) = errors.first() {
+        let _ = writeln!(
+            xml,
+            r#"      <failure message="{}" type="validation">{}</failure>"#,
+            escape(&first.message),
+            escape(&lines(&errors))
+        );
+    }
+    if !warnings.is_empty() {
+        let _ = writeln!(
+            xml,
+            "      <system-out>{}</system-out>",
+            escape(&lines(&warnings))
+        );
+    }
+    xml.push_str("    </testcase>\n");
+}
+
+/// One `message (path)` line per diagnostic.
+fn lines(diagnostics: &[&ValidationDiagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|d| match &d.path {
+            Some(path) => format!("{} ({})", d.message, path.display()),
+            None => d.message.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escape text for an XML attribute or element, dropping characters XML
+/// 1.0 cannot represent.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passing_plugin_is_an_empty_testcase() {
+        let xml = to_junit_xml(&ValidationResult::new(), &["good".to_string()], true, &[]);
+        assert_eq!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="souk" tests="2" failures="0">
+  <testsuite name="marketplace" tests="1" failures="0">
+    <testcase classname="marketplace" name="marketplace.json"/>
+  </testsuite>
+  <testsuite name="plugins" tests="1" failures="0">
+    <testcase classname="plugins" name="good"/>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn failing_plugin_carries_messages_and_paths() {
+        let mut result = ValidationResult::new();
+        result.push(
+            ValidationDiagnostic::error("Invalid JSON in plugin.json: expected `<value>`")
+                .with_path("plugins/bad/.claude-plugin/plugin.json")
+                .with_plugin("bad"),
+        );
+        result
+            .push(ValidationDiagnostic::error("Missing field: \"name\" & more").with_plugin("bad"));
+        result.push(ValidationDiagnostic::warning("README title 'X' differs").with_plugin("bad"));
+
+        let xml = to_junit_xml(
+            &result,
+            &["bad".to_string(), "good".to_string()],
+            false,
+            &[],
+        );
+        assert_eq!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="souk" tests="2" failures="1">
+  <testsuite name="plugins" tests="2" failures="1">
+    <testcase classname="plugins" name="bad">
+      <failure message="Invalid JSON in plugin.json: expected `&lt;value&gt;`" type="validation">Invalid JSON in plugin.json: expected `&lt;value&gt;` (plugins/bad/.claude-plugin/plugin.json)
+Missing field: &quot;name&quot; &amp; more</failure>
+      <system-out>README title &apos;X&apos; differs</system-out>
+    </testcase>
+    <testcase classname="plugins" name="good"/>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn marketplace_diagnostics_get_their_own_suite() {
+        let mut result = ValidationResult::new();
+        result.push(ValidationDiagnostic::error(
+            "Invalid marketplace version: nope",
+        ));
+        let xml = to_junit_xml(&result, &[], false, &[]);
+        assert!(xml.contains(r#"<testsuite name="marketplace" tests="1" failures="1">"#));
+        assert!(!xml.contains(r#"name="plugins""#));
+    }
+
+    #[test]
+    fn skill_granularity_adds_a_testcase_per_skill() {
+        let mut broken = ValidationResult::new();
+        broken.push(
+            ValidationDiagnostic::error("Missing description in frontmatter")
+                .with_path("plugins/tools/skills/lint/SKILL.md"),
+        );
+        let skills = [
+            SkillCase {
+                plugin: "tools".to_string(),
+                skill: "format".to_string(),
+                result: ValidationResult::new(),
+            },
+            SkillCase {
+                plugin: "tools".to_string(),
+                skill: "lint".to_string(),
+                result: broken,
+            },
+        ];
+        let xml = to_junit_xml(
+            &ValidationResult::new(),
+            &["tools".to_string()],
+            false,
+            &skills,
+        );
+        assert_eq!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="souk" tests="3" failures="1">
+  <testsuite name="plugins" tests="1" failures="0">
+    <testcase classname="plugins" name="tools"/>
+  </testsuite>
+  <testsuite name="skills" tests="2" failures="1">
+    <testcase classname="skills" name="tools/format"/>
+    <testcase classname="skills" name="tools/lint">
+      <failure message="Missing description in frontmatter" type="validation">Missing description in frontmatter (plugins/tools/skills/lint/SKILL.md)</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn escape_drops_invalid_control_characters() {
+        assert_eq!(escape("a\u{1}b\tc"), "ab\tc");
+    }
+}
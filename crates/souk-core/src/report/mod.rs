@@ -0,0 +1,7 @@
+//! Validation results in formats for other tools.
+
+pub mod junit;
+pub mod sarif;
+
+pub use junit::{to_junit_xml, SkillCase};
+pub use sarif::{rule_id, to_sarif};
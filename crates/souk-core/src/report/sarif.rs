@@ -0,0 +1,426 @@
+//! SARIF 2.1.0 output for validation results.
+//!
+//! GitHub code scanning accepts SARIF uploads and shows each result inline
+//! on pull requests. Every [`ValidationDiagnostic`] becomes one `result`:
+//!
+//! - `ruleId` is a stable code for the kind of message (see [`rule_id`])
+//! - `level` is `error` or `warning`, from its [`Severity`]
+//! - its path becomes a `physicalLocation`, relative to `%SRCROOT%` when it
+//!   lies under the given root
+//!
+//! souk does not track line numbers, so locations name a file only.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::error::{Severity, ValidationDiagnostic, ValidationResult};
+
+/// The SARIF schema this module writes.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Base id that repository-relative artifact URIs are resolved against.
+const SRCROOT: &str = "%SRCROOT%";
+
+/// Words kept from a message without a `Category: detail` prefix.
+const MAX_RULE_WORDS: usize = 6;
+
+/// Messages that name a plugin, directory, or title before any `": "`, as
+/// a pattern, rule id, and rule name each.
+const RULE_TEMPLATES: [(&str, &str, &str); 5] = [
+    (
+        r"^Plugin directory \S+ does not match its plugin\.json name ",
+        "souk/plugin-directory-mismatch",
+        "Plugin directory does not match its plugin.json name",
+    ),
+    (
+        r"^Plugin name '.*' ",
+        "souk/invalid-plugin-name",
+        "Invalid plugin name",
+    ),
+    (
+        r"^Plugin source for \S+ ",
+        "souk/invalid-plugin-source",
+        "Invalid plugin source",
+    ),
+    (
+        r#"^README title ".*" does not match plugin\.json name "#,
+        "souk/readme-title-mismatch",
+        "README title does not match plugin.json name",
+    ),
+    (
+        r"^pluginRoot \S+ (is the directory of|is inside) plugin ",
+        "souk/plugin-root-inside-plugin",
+        "pluginRoot is inside a plugin",
+    ),
+];
+
+static RULE_TEMPLATE_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    RULE_TEMPLATES
+        .iter()
+        .map(|(pattern, _, _)| Regex::new(pattern).expect("rule template is a valid regex"))
+        .collect()
+});
+
+/// Quoted text in a message, which names something rather than describing
+/// the problem.
+static QUOTED_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"'[^']*'|"[^"]*"|`[^`]*`"#).expect("valid regex"));
+
+/// Convert `result` into a SARIF 2.1.0 log with a single run.
+///
+/// Paths under `root` are written relative to it; others as absolute
+/// `file://` URIs. A diagnostic without a path is located at
+/// `default_path` if one is given, and has no location otherwise.
+pub fn to_sarif(result: &ValidationResult, root: &Path, default_path: Option<&Path>) -> Value {
+    let mut rules = BTreeMap::new();
+    let results: Vec<Value> = result
+        .diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let id = rule_id(&diagnostic.message);
+            rules
+                .entry(id.clone())
+                .or_insert_with(|| category(&diagnostic.message));
+            sarif_result(diagnostic, id, root, default_path)
+        })
+        .collect();
+
+    let rules: Vec<Value> = rules
+        .into_iter()
+        .map(|(id, name)| json!({ "id": id, "shortDescription": { "text": name } }))
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "souk",
+                    "semanticVersion": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "originalUriBaseIds": {
+                SRCROOT: { "uri": file_uri(root, true) }
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// A stable SARIF rule id for a diagnostic message, such as
+/// `souk/unsatisfied-dependency` for `Unsatisfied dependency: ...`.
+///
+/// Messages that name a plugin or value up front, such as
+/// `Plugin directory {dir} does not match its plugin.json name {name}`, map
+/// to a fixed id. Otherwise the category is the text before the first
+/// `": "`. Messages without one use their first few words, skipping quoted
+/// text and words containing digits, dots, or slashes, since those are
+/// usually names, counts, or paths.
+pub fn rule_id(message: &str) -> String {
+    if let Some(i) = template_index(message) {
+        return RULE_TEMPLATES[i].1.to_string();
+    }
+    let slug: Vec<String> = category(message)
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    if slug.is_empty() {
+        "souk/validation".to_string()
+    } else {
+        format!("souk/{}", slug.join("-"))
+    }
+}
+
+/// The index of the [`RULE_TEMPLATES`] entry `message` matches, if any.
+fn template_index(message: &str) -> Option<usize> {
+    RULE_TEMPLATE_RES.iter().position(|re| re.is_match(message))
+}
+
+/// The human-readable category of a message; see [`rule_id`].
+fn category(message: &str) -> String {
+    if let Some(i) = template_index(message) {
+        return RULE_TEMPLATES[i].2.to_string();
+    }
+    if let Some((prefix, _)) = message.split_once(": ") {
+        return prefix.trim().to_string();
+    }
+    QUOTED_RE
+        .replace_all(message, "")
+        .split_whitespace()
+        .filter(|word| !word.contains(|c: char| c.is_ascii_digit() || c == '.' || c == '/'))
+        .take(MAX_RULE_WORDS)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn sarif_result(
+    diagnostic: &ValidationDiagnostic,
+    rule_id: String,
+    root: &Path,
+    default_path: Option<&Path>,
+) -> Value {
+    let level = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+    let mut result = json!({
+        "ruleId": rule_id,
+        "level": level,
+        "message": { "text": diagnostic.message },
+    });
+
+    if let Some(path) = diagnostic.path.as_deref().or(default_path) {
+        let artifact = match path.strip_prefix(root) {
+            Ok(rel) => json!({ "uri": relative_uri(rel), "uriBaseId": SRCROOT }),
+            Err(_) => json!({ "uri": file_uri(path, false) }),
+        };
+        result["locations"] = json!([{ "physicalLocation": { "artifactLocation": artifact } }]);
+    }
+
+    let mut properties = serde_json::Map::new();
+    if let Some(field) = &diagnostic.field {
+        properties.insert("field".to_string(), json!(field));
+    }
+    if let Some(plugin) = &diagnostic.plugin {
+        properties.insert("plugin".to_string(), json!(plugin));
+    }
+    if !properties.is_empty() {
+        result["properties"] = Value::Object(properties);
+    }
+    result
+}
+
+/// A relative path as a URI reference, with `/` separators and each
+/// segment percent-encoded.
+fn relative_uri(path: &Path) -> String {
+    let parts: Vec<String> = path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(percent_encode(&part.to_string_lossy(), b"")),
+            _ => None,
+        })
+        .collect();
+    parts.join("/")
+}
+
+/// An absolute path as a percent-encoded `file://` URI; `dir` adds the
+/// trailing slash SARIF requires on base URIs.
+fn file_uri(path: &Path, dir: bool) -> String {
+    let mut uri = path.to_string_lossy().replace('\\', "/");
+    if !uri.starts_with('/') {
+        uri.insert(0, '/');
+    }
+    if dir && !uri.ends_with('/') {
+        uri.push('/');
+    }
+    // Keep the `:` of a Windows drive letter such as `/C:/`
+    format!("file://{}", percent_encode(&uri, b"/:"))
+}
+
+/// `text` with every byte other than an unreserved URI character or one of
+/// `keep` written as `%XX`.
+fn percent_encode(text: &str, keep: &[u8]) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for &b in text.as_bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) || keep.contains(&b) {
+            encoded.push(char::from(b));
+        } else {
+            encoded.push_str(&format!("%{b:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mixed_result(root: &Path) -> ValidationResult {
+        let mut result = ValidationResult::new();
+        result.push(
+            ValidationDiagnostic::error("Unsatisfied dependency: app requires core ^2.0.0")
+                .with_path(root.join("plugins/app/.claude-plugin/extends-plugin.json"))
+                .with_field("dependencies.core")
+                .with_plugin("app"),
+        );
+        result.push(
+            ValidationDiagnostic::warning(
+                "Plugin directory old-name does not match its plugin.json name new-name; \
+                 rename the directory to new-name and update its source",
+            )
+            .with_path(root.join("plugins/old-name")),
+        );
+        result.push(ValidationDiagnostic::error("Plugin entry 3 has empty name"));
+        result
+    }
+
+    #[test]
+    fn mixed_result_is_well_formed_sarif() {
+        let root = Path::new("/work/market");
+        let marketplace = root.join(".claude-plugin/marketplace.json");
+        let sarif = to_sarif(&mixed_result(root), root, Some(&marketplace));
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(sarif["$schema"], SARIF_SCHEMA);
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "souk");
+        assert_eq!(
+            run["originalUriBaseIds"][SRCROOT]["uri"],
+            "file:///work/market/"
+        );
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["ruleId"], "souk/unsatisfied-dependency");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["message"]["text"],
+            "Unsatisfied dependency: app requires core ^2.0.0"
+        );
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"],
+            json!({
+                "uri": "plugins/app/.claude-plugin/extends-plugin.json",
+                "uriBaseId": SRCROOT,
+            })
+        );
+        assert_eq!(
+            results[0]["properties"],
+            json!({ "field": "dependencies.core", "plugin": "app" })
+        );
+
+        assert_eq!(results[1]["level"], "warning");
+        assert_eq!(results[1]["ruleId"], "souk/plugin-directory-mismatch");
+        assert!(results[1].get("properties").is_none());
+
+        assert_eq!(results[2]["ruleId"], "souk/plugin-entry-has-empty-name");
+        assert_eq!(
+            results[2]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            ".claude-plugin/marketplace.json"
+        );
+
+        // Every result's rule is declared once on the driver.
+        let rules: Vec<&str> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                "souk/plugin-directory-mismatch",
+                "souk/plugin-entry-has-empty-name",
+                "souk/unsatisfied-dependency",
+            ]
+        );
+    }
+
+    #[test]
+    fn paths_outside_root_are_absolute_and_pathless_results_unlocated() {
+        let root = Path::new("/work/market");
+        let mut result = ValidationResult::new();
+        result.push(ValidationDiagnostic::error("Missing plugin.json").with_path("/elsewhere/p"));
+        result.push(ValidationDiagnostic::warning("hooks.json defines no hooks"));
+
+        let sarif = to_sarif(&result, root, None);
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"],
+            json!({ "uri": "file:///elsewhere/p" })
+        );
+        assert!(results[1].get("locations").is_none());
+        assert_eq!(results[1]["ruleId"], "souk/defines-no-hooks");
+    }
+
+    #[test]
+    fn rule_ids_ignore_variable_detail() {
+        assert_eq!(
+            rule_id("Invalid semver version: banana"),
+            "souk/invalid-semver-version"
+        );
+        assert_eq!(
+            rule_id("Plugin entry 12 has empty source"),
+            rule_id("Plugin entry 4 has empty source")
+        );
+        assert_eq!(rule_id("1.2.3"), "souk/validation");
+    }
+
+    #[test]
+    fn rule_ids_do_not_leak_plugin_names() {
+        let pairs = [
+            (
+                "Plugin directory alpha does not match its plugin.json name beta; \
+                 rename the directory to beta and update its source",
+                "Plugin directory gamma does not match its plugin.json name delta; \
+                 rename the directory to delta and update its source",
+            ),
+            (
+                "Plugin name 'Alpha' must be lowercase",
+                "Plugin name 'beta gamma' must not contain whitespace",
+            ),
+            (
+                "Plugin source for alpha escapes pluginRoot: ../alpha",
+                "Plugin source for beta is an absolute path: /srv/beta",
+            ),
+            (
+                r#"README title "Alpha Tools" does not match plugin.json name "alpha""#,
+                r#"README title "Beta" does not match plugin.json name "beta""#,
+            ),
+            (
+                "pluginRoot ./plugins/alpha is the directory of plugin alpha",
+                "pluginRoot ./vendor is inside plugin vendor",
+            ),
+        ];
+        for (a, b) in pairs {
+            assert_eq!(rule_id(a), rule_id(b), "{a} / {b}");
+            for name in ["alpha", "beta", "gamma", "vendor"] {
+                assert!(!rule_id(a).contains(name), "{}", rule_id(a));
+                assert!(
+                    !category(b).to_lowercase().contains(name),
+                    "{}",
+                    category(b)
+                );
+            }
+        }
+        assert_eq!(
+            rule_id("Unknown hook event: Startup"),
+            rule_id("Unknown hook event: PreToolUse")
+        );
+    }
+
+    #[test]
+    fn uris_are_percent_encoded() {
+        let root = Path::new("/work/my market");
+        let mut result = ValidationResult::new();
+        result.push(
+            ValidationDiagnostic::error("Missing plugin.json")
+                .with_path(root.join("plugins/100% café")),
+        );
+        result.push(ValidationDiagnostic::error("Missing plugin.json").with_path("/other/a#b"));
+
+        let sarif = to_sarif(&result, root, None);
+        let run = &sarif["runs"][0];
+        assert_eq!(
+            run["originalUriBaseIds"][SRCROOT]["uri"],
+            "file:///work/my%20market/"
+        );
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "plugins/100%25%20caf%C3%A9"
+        );
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "file:///other/a%23b"
+        );
+    }
+}
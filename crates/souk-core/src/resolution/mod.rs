@@ -1,5 +1,9 @@
+pub mod pattern;
 pub mod plugin;
 pub mod skill;
 
-pub use plugin::{plugin_path_to_source, resolve_plugin, resolve_source};
+pub use pattern::{expand_plugin_patterns, is_pattern, matches_pattern};
+pub use plugin::{
+    classify_source, plugin_path_to_source, resolve_plugin, resolve_source, SourceKind,
+};
 pub use skill::{enumerate_skills, resolve_skill};
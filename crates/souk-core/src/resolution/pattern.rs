@@ -0,0 +1,146 @@
+//! Wildcard selection of plugins by name.
+//!
+//! Commands that take several plugin names also accept shell-style patterns
+//! such as `lint-*` or `*-agent`, expanded here against a set of known
+//! names. Quote patterns on the command line so the shell does not expand
+//! them against files first.
+
+use crate::error::SoukError;
+
+/// Whether `input` contains a wildcard (`*` or `?`).
+pub fn is_pattern(input: &str) -> bool {
+    input.contains(['*', '?'])
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of characters
+/// (including none) and `?` matches exactly one. Matching is case-sensitive,
+/// like plugin names.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and the name index it was tried at.
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                // Let the last `*` absorb one more character and retry.
+                Some((sp, sn)) => {
+                    p = sp + 1;
+                    n = sn + 1;
+                    star = Some((sp, sn + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Expand wildcard patterns in `inputs` against `names`.
+///
+/// Inputs without wildcards pass through unchanged, so literal names and
+/// paths keep their usual handling. Each pattern is replaced by the
+/// `names` it matches, in `names` order, skipping any already selected by
+/// an earlier input.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Other`] naming the first pattern that matches
+/// nothing.
+pub fn expand_plugin_patterns(
+    inputs: &[String],
+    names: &[String],
+) -> Result<Vec<String>, SoukError> {
+    let mut expanded: Vec<String> = Vec::new();
+    for input in inputs {
+        if !is_pattern(input) {
+            expanded.push(input.clone());
+            continue;
+        }
+        let mut matched = false;
+        for name in names.iter().filter(|n| matches_pattern(input, n)) {
+            matched = true;
+            if !expanded.contains(name) {
+                expanded.push(name.clone());
+            }
+        }
+        if !matched {
+            return Err(SoukError::Other(format!(
+                "No plugins match pattern: {input}"
+            )));
+        }
+    }
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn wildcard_matching() {
+        assert!(matches_pattern("*", "anything"));
+        assert!(matches_pattern("*", ""));
+        assert!(matches_pattern("lint-*", "lint-rust"));
+        assert!(!matches_pattern("lint-*", "my-lint-rust"));
+        assert!(matches_pattern("*-agent", "review-agent"));
+        assert!(!matches_pattern("*-agent", "review-agents"));
+        assert!(matches_pattern("a*b*c", "aXbYbZc"));
+        assert!(matches_pattern("v?", "v2"));
+        assert!(!matches_pattern("v?", "v10"));
+        assert!(!matches_pattern("Lint-*", "lint-rust"));
+    }
+
+    #[test]
+    fn expands_star_prefix_and_suffix() {
+        let all = names(&["lint-go", "code-agent", "lint-rust", "docs-agent"]);
+        assert_eq!(expand_plugin_patterns(&names(&["*"]), &all).unwrap(), all);
+        assert_eq!(
+            expand_plugin_patterns(&names(&["lint-*"]), &all).unwrap(),
+            ["lint-go", "lint-rust"]
+        );
+        assert_eq!(
+            expand_plugin_patterns(&names(&["*-agent", "lint-go"]), &all).unwrap(),
+            ["code-agent", "docs-agent", "lint-go"]
+        );
+    }
+
+    #[test]
+    fn literals_pass_through_unchanged() {
+        let all = names(&["alpha"]);
+        assert_eq!(
+            expand_plugin_patterns(&names(&["./plugins/beta", "gamma"]), &all).unwrap(),
+            ["./plugins/beta", "gamma"]
+        );
+    }
+
+    #[test]
+    fn overlapping_patterns_are_deduplicated() {
+        let all = names(&["lint-go", "lint-rust"]);
+        assert_eq!(
+            expand_plugin_patterns(&names(&["lint-go", "lint-*"]), &all).unwrap(),
+            ["lint-go", "lint-rust"]
+        );
+    }
+
+    #[test]
+    fn unmatched_pattern_is_an_error() {
+        let err = expand_plugin_patterns(&names(&["lint-*", "*-agent"]), &names(&["lint-go"]))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "No plugins match pattern: *-agent");
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 use crate::discovery::MarketplaceConfig;
 use crate::error::SoukError;
@@ -39,6 +39,49 @@ pub fn resolve_source(source: &str, config: &MarketplaceConfig) -> Result<PathBu
     }
 }
 
+/// Where a marketplace `source` points relative to pluginRoot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    /// Inside pluginRoot.
+    Internal,
+    /// A relative path that resolves outside pluginRoot.
+    Escaping,
+    /// An absolute, machine-specific path.
+    Absolute,
+}
+
+/// Classify `source` without touching the filesystem.
+///
+/// Relative sources are resolved like [`resolve_source`] and normalized
+/// lexically, so `../elsewhere` is [`SourceKind::Escaping`] even if it does
+/// not exist.
+pub fn classify_source(source: &str, config: &MarketplaceConfig) -> SourceKind {
+    if Path::new(source).is_absolute() {
+        return SourceKind::Absolute;
+    }
+    let resolved = resolve_source(source, config).unwrap_or_else(|_| PathBuf::from(source));
+    if normalize(&resolved).starts_with(normalize(&config.plugin_root_abs)) {
+        SourceKind::Internal
+    } else {
+        SourceKind::Escaping
+    }
+}
+
+/// Lexically remove `.` and `..` components.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
 pub fn plugin_path_to_source(path: &Path, config: &MarketplaceConfig) -> (String, bool) {
     let canon_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
     let canon_root = &config.plugin_root_abs;
@@ -143,4 +186,25 @@ mod tests {
         assert!(!is_internal);
         assert!(std::path::Path::new(&source).is_absolute());
     }
+
+    #[test]
+    fn classify_source_kinds() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp);
+
+        assert_eq!(classify_source("my-plugin", &config), SourceKind::Internal);
+        assert_eq!(
+            classify_source("./plugins/my-plugin", &config),
+            SourceKind::Internal
+        );
+        assert_eq!(
+            classify_source("../elsewhere", &config),
+            SourceKind::Escaping
+        );
+        assert_eq!(classify_source("./vendor/x", &config), SourceKind::Escaping);
+        assert_eq!(
+            classify_source("/opt/plugin", &config),
+            SourceKind::Absolute
+        );
+    }
 }
@@ -0,0 +1,168 @@
+//! On-disk cache for LLM review results.
+//!
+//! Entries are keyed by a [`CacheKey`] made of two hashes: one over the
+//! provider, model, and rendered prompt, and one over the raw input files
+//! (plugin.json, extends-plugin.json, README, and every file in each skill).
+//! A prompt-template change therefore misses the cache even when the inputs
+//! are unchanged, and an edit to a file that only appears in the prompt by
+//! name (such as a skill body) misses it too.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::error::SoukError;
+use crate::resolution::skill::enumerate_skills;
+use crate::review::provider::LlmProvider;
+use crate::util::{hash_file, Fnv1a};
+
+/// Identifies a cached review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheKey {
+    /// Hash of the provider name, model, and prompt text.
+    pub prompt_hash: u64,
+    /// Hash of the review's input files.
+    pub content_hash: u64,
+}
+
+impl CacheKey {
+    /// Build a key for sending `prompt` to `provider` over inputs hashing to
+    /// `content_hash`.
+    pub fn new(provider: &dyn LlmProvider, prompt: &str, content_hash: u64) -> Self {
+        Self {
+            prompt_hash: prompt_hash(provider, prompt),
+            content_hash,
+        }
+    }
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}-{:016x}", self.prompt_hash, self.content_hash)
+    }
+}
+
+/// Hash the provider name, model, and `prompt`, as
+/// [`CacheKey::prompt_hash`].
+pub fn prompt_hash(provider: &dyn LlmProvider, prompt: &str) -> u64 {
+    let mut hasher = Fnv1a::new();
+    hasher.write_field(provider.name().as_bytes());
+    hasher.write_field(provider.model().as_bytes());
+    hasher.write_field(prompt.as_bytes());
+    hasher.finish()
+}
+
+/// A directory of cached review texts, one file per [`CacheKey`].
+#[derive(Debug, Clone)]
+pub struct ReviewCache {
+    dir: PathBuf,
+}
+
+impl ReviewCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cached review text for `key`, if present.
+    pub fn get(&self, key: &CacheKey) -> Option<String> {
+        std::fs::read_to_string(self.entry_path(key)).ok()
+    }
+
+    /// Store `review_text` under `key`, creating the cache directory if needed.
+    pub fn put(&self, key: &CacheKey, review_text: &str) -> Result<(), SoukError> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.entry_path(key), review_text)?;
+        Ok(())
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join(format!("{key}.md"))
+    }
+}
+
+/// Hash the files a plugin review reads.
+///
+/// Covers `.claude-plugin/plugin.json`, `.claude-plugin/extends-plugin.json`,
+/// `README.md`, and every file under each skill directory. Paths are hashed
+/// alongside contents so renames and moves change the result.
+pub fn plugin_content_hash(plugin_path: &Path) -> u64 {
+    let mut hasher = Fnv1a::new();
+
+    for rel in [
+        ".claude-plugin/plugin.json",
+        ".claude-plugin/extends-plugin.json",
+        "README.md",
+    ] {
+        hash_file(&mut hasher, plugin_path, &plugin_path.join(rel));
+    }
+
+    for skill in enumerate_skills(plugin_path) {
+        let files = WalkDir::new(&skill.path)
+            .sort_by_file_name()
+            .into_iter()
+            .flatten()
+            .filter(|e| e.file_type().is_file());
+        for entry in files {
+            hash_file(&mut hasher, plugin_path, entry.path());
+        }
+    }
+
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::review::provider::MockProvider;
+    use tempfile::TempDir;
+
+    fn setup_plugin(tmp: &TempDir) -> PathBuf {
+        let plugin = tmp.path().join("p");
+        std::fs::create_dir_all(plugin.join(".claude-plugin")).unwrap();
+        std::fs::write(
+            plugin.join(".claude-plugin").join("plugin.json"),
+            r#"{"name":"p","version":"1.0.0","description":"d"}"#,
+        )
+        .unwrap();
+        let skill = plugin.join("skills").join("s");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "---\nname: s\n---\nBody").unwrap();
+        plugin
+    }
+
+    #[test]
+    fn content_hash_changes_with_skill_file() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = setup_plugin(&tmp);
+        let before = plugin_content_hash(&plugin);
+        assert_eq!(before, plugin_content_hash(&plugin));
+
+        std::fs::write(
+            plugin.join("skills").join("s").join("SKILL.md"),
+            "---\nname: s\n---\nEdited",
+        )
+        .unwrap();
+        assert_ne!(before, plugin_content_hash(&plugin));
+    }
+
+    #[test]
+    fn key_changes_with_prompt_but_not_content() {
+        let provider = MockProvider::new("x");
+        let a = CacheKey::new(&provider, "template v1", 7);
+        let b = CacheKey::new(&provider, "template v2", 7);
+        assert_ne!(a, b);
+        assert_eq!(a.content_hash, b.content_hash);
+    }
+
+    #[test]
+    fn cache_round_trips() {
+        let tmp = TempDir::new().unwrap();
+        let cache = ReviewCache::new(tmp.path().join("cache"));
+        let key = CacheKey::new(&MockProvider::new("x"), "prompt", 1);
+
+        assert!(cache.get(&key).is_none());
+        cache.put(&key, "review").unwrap();
+        assert_eq!(cache.get(&key).as_deref(), Some("review"));
+    }
+}
@@ -0,0 +1,287 @@
+//! Posting review results to a GitHub pull request.
+//!
+//! In a GitHub Actions run for a pull request, [`PullRequest::from_env`]
+//! finds the repository, pull request number, and token, and
+//! [`post_comment`] adds the review as a PR comment through the issues API.
+
+use std::fmt;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::error::SoukError;
+use crate::review::plugin::ReviewReport;
+use crate::review::provider::{http_client, send_with_retry, RetryPolicy};
+
+/// API base used when `GITHUB_API_URL` is unset.
+pub const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+
+/// The pull request a GitHub Actions run was triggered for.
+///
+/// The `Debug` output redacts the token, so it cannot end up in logs.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    /// REST API base, e.g. `https://api.github.com`.
+    pub api_url: String,
+    /// `owner/name` of the repository.
+    pub repository: String,
+    /// Pull request number.
+    pub number: u64,
+    /// Token with permission to comment on pull requests.
+    pub token: String,
+}
+
+impl fmt::Debug for PullRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PullRequest")
+            .field("api_url", &self.api_url)
+            .field("repository", &self.repository)
+            .field("number", &self.number)
+            .field("token", &"<redacted>")
+            .finish()
+    }
+}
+
+impl PullRequest {
+    /// Read the pull request from GitHub Actions' environment, looking
+    /// variables up with `var`.
+    ///
+    /// Needs `GITHUB_TOKEN` and `GITHUB_REPOSITORY`. The number comes from
+    /// the event payload at `GITHUB_EVENT_PATH`, or from a `GITHUB_REF` of
+    /// the form `refs/pull/<n>/merge`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoukError::Other`] naming what is missing when not running
+    /// in a pull request workflow.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Result<Self, SoukError> {
+        let var = |name: &str| var(name).filter(|v| !v.is_empty());
+        let token = var("GITHUB_TOKEN")
+            .ok_or_else(|| SoukError::Other("GITHUB_TOKEN is not set".to_string()))?;
+        let repository = var("GITHUB_REPOSITORY").ok_or_else(|| {
+            SoukError::Other("GITHUB_REPOSITORY is not set; not running in GitHub Actions".into())
+        })?;
+        let number = var("GITHUB_EVENT_PATH")
+            .and_then(|path| event_pr_number(Path::new(&path)))
+            .or_else(|| var("GITHUB_REF").and_then(|r| ref_pr_number(&r)))
+            .ok_or_else(|| {
+                SoukError::Other(
+                    "Not running for a pull request (no PR number in GITHUB_EVENT_PATH or GITHUB_REF)"
+                        .to_string(),
+                )
+            })?;
+        let api_url = var("GITHUB_API_URL").unwrap_or_else(|| DEFAULT_GITHUB_API_URL.to_string());
+        Ok(Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            repository,
+            number,
+            token,
+        })
+    }
+
+    /// The endpoint that creates a comment on this pull request.
+    pub fn comments_url(&self) -> String {
+        format!(
+            "{}/repos/{}/issues/{}/comments",
+            self.api_url, self.repository, self.number
+        )
+    }
+}
+
+/// The pull request number in an Actions event payload, if it has one.
+fn event_pr_number(path: &Path) -> Option<u64> {
+    let event: Value = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    event["pull_request"]["number"].as_u64()
+}
+
+/// The number in a `refs/pull/<n>/merge` or `refs/pull/<n>/head` ref.
+fn ref_pr_number(git_ref: &str) -> Option<u64> {
+    git_ref
+        .strip_prefix("refs/pull/")?
+        .split('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// The JSON body of a PR comment presenting `report`.
+pub fn comment_body(report: &ReviewReport) -> Value {
+    let mut body = format!(
+        "## souk review: {}\n\n{}\n\n",
+        report.plugin_name,
+        report.review_text.trim_end()
+    );
+    if let Some(rating) = report.rating {
+        body.push_str(&format!("**Rating:** {rating}/10\n\n"));
+    }
+    body.push_str(&format!(
+        "<sub>Reviewed with {} ({})</sub>\n",
+        report.provider_name, report.model_name
+    ));
+    json!({ "body": body })
+}
+
+/// Post `report` as a comment on `pr`.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Other`] if GitHub rejects the request or cannot be
+/// reached.
+pub fn post_comment(pr: &PullRequest, report: &ReviewReport) -> Result<(), SoukError> {
+    let client = http_client(None);
+    let url = pr.comments_url();
+    let body = comment_body(report);
+    send_with_retry(&RetryPolicy::default(), || {
+        client
+            .post(&url)
+            .bearer_auth(&pr.token)
+            .header("accept", "application/vnd.github+json")
+            .header("x-github-api-version", "2022-11-28")
+            .header("user-agent", concat!("souk/", env!("CARGO_PKG_VERSION")))
+            .json(&body)
+            .send()
+    })
+    .map(drop)
+    .map_err(|e| SoukError::Other(format!("Failed to post GitHub comment: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn report() -> ReviewReport {
+        ReviewReport {
+            plugin_name: "lint".to_string(),
+            provider_name: "anthropic".to_string(),
+            model_name: "claude-sonnet-4-6".to_string(),
+            review_text: "Looks good.\n".to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            rating: Some(8),
+            max_tokens: Some(4096),
+            temperature: None,
+            prompt_hash: "0123456789abcdef".to_string(),
+            cache_key: None,
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn pull_request_from_ref() {
+        let pr = PullRequest::from_env(env(&[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_REPOSITORY", "owner/repo"),
+            ("GITHUB_REF", "refs/pull/42/merge"),
+        ]))
+        .unwrap();
+        assert_eq!(pr.number, 42);
+        assert_eq!(
+            pr.comments_url(),
+            "https://api.github.com/repos/owner/repo/issues/42/comments"
+        );
+    }
+
+    #[test]
+    fn pull_request_from_event_payload() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let event = tmp.path().join("event.json");
+        std::fs::write(&event, r#"{"pull_request": {"number": 7}}"#).unwrap();
+
+        let pr = PullRequest::from_env(env(&[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_REPOSITORY", "owner/repo"),
+            ("GITHUB_EVENT_PATH", event.to_str().unwrap()),
+            ("GITHUB_REF", "refs/heads/main"),
+            ("GITHUB_API_URL", "https://ghe.example.com/api/v3/"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            pr.comments_url(),
+            "https://ghe.example.com/api/v3/repos/owner/repo/issues/7/comments"
+        );
+    }
+
+    #[test]
+    fn pull_request_outside_actions_names_what_is_missing() {
+        let err = PullRequest::from_env(env(&[])).unwrap_err();
+        assert!(err.to_string().contains("GITHUB_TOKEN"));
+
+        let err = PullRequest::from_env(env(&[
+            ("GITHUB_TOKEN", "t"),
+            ("GITHUB_REPOSITORY", "owner/repo"),
+            ("GITHUB_REF", "refs/heads/main"),
+        ]))
+        .unwrap_err();
+        assert!(err.to_string().contains("Not running for a pull request"));
+    }
+
+    #[test]
+    fn comment_body_presents_the_review() {
+        let body = comment_body(&report());
+        let text = body["body"].as_str().unwrap();
+        assert!(text.starts_with("## souk review: lint\n\nLooks good.\n\n"));
+        assert!(text.contains("**Rating:** 8/10"));
+        assert!(text.contains("Reviewed with anthropic (claude-sonnet-4-6)"));
+    }
+
+    #[test]
+    fn post_comment_sends_body_to_issue_comments_endpoint() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = Vec::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                head.push(line);
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(
+                    b"HTTP/1.1 201 Created\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}",
+                )
+                .unwrap();
+            (head, body)
+        });
+
+        let pr = PullRequest {
+            api_url: format!("http://127.0.0.1:{port}"),
+            repository: "owner/repo".to_string(),
+            number: 42,
+            token: "secret".to_string(),
+        };
+        post_comment(&pr, &report()).unwrap();
+
+        let (head, body) = server.join().unwrap();
+        assert_eq!(
+            head[0],
+            "POST /repos/owner/repo/issues/42/comments HTTP/1.1\r\n"
+        );
+        assert!(head
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case("authorization: Bearer secret\r\n")));
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body, comment_body(&report()));
+    }
+}
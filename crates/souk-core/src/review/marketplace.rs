@@ -4,14 +4,22 @@
 //! structured prompt to an [`LlmProvider`] requesting an overall quality
 //! assessment. The resulting report can optionally be persisted to disk.
 
-use std::path::Path;
+use serde::Serialize;
 
 use crate::discovery::MarketplaceConfig;
 use crate::error::SoukError;
+use crate::resolution::resolve_source;
+use crate::review::cache::ReviewCache;
+use crate::review::plugin::{review_plugin, ReviewReport};
+use crate::review::prompt::{with_rubric, PromptBuilder};
 use crate::review::provider::LlmProvider;
+use crate::review::rating::parse_rating;
+use crate::review::report::{write_report, ReportOutput};
+use crate::types::plugin::read_manifest_text;
+use crate::util::{current_timestamp, parallel_map};
 
 /// The result of an LLM-powered marketplace review.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MarketplaceReviewReport {
     /// Name of the LLM provider that generated the review (e.g. "anthropic").
     pub provider_name: String,
@@ -19,6 +27,11 @@ pub struct MarketplaceReviewReport {
     pub model_name: String,
     /// The raw review text returned by the LLM.
     pub review_text: String,
+    /// When the review was produced, as an RFC 3339 UTC timestamp.
+    pub timestamp: String,
+    /// Overall 1-10 rating found in `review_text`, if any; see
+    /// [`parse_rating`].
+    pub rating: Option<u8>,
 }
 
 /// Review the entire marketplace using an LLM provider.
@@ -29,8 +42,12 @@ pub struct MarketplaceReviewReport {
 /// 3. Builds a structured review prompt combining the marketplace definition
 ///    and all plugin summaries.
 /// 4. Sends the prompt to `provider` and captures the response.
-/// 5. If `output_dir` is provided, writes a Markdown report to
-///    `<output_dir>/marketplace-review-report.md`.
+/// 5. With an `output`, writes `marketplace-review-report.md` and/or `.json`
+///    to its directory.
+///
+/// When `summary_only` is set, the prompt asks for just an overall rating
+/// and a three-bullet summary instead of the full section-by-section review.
+/// A `rubric` is appended to the prompt.
 ///
 /// # Errors
 ///
@@ -39,8 +56,98 @@ pub struct MarketplaceReviewReport {
 pub fn review_marketplace(
     config: &MarketplaceConfig,
     provider: &dyn LlmProvider,
-    output_dir: Option<&Path>,
+    output: Option<ReportOutput<'_>>,
+    summary_only: bool,
+    rubric: Option<&str>,
 ) -> Result<MarketplaceReviewReport, SoukError> {
+    let prompt = read_marketplace_review_prompt(config, summary_only, rubric)?;
+
+    // 4. Send to LLM
+    let review_text = provider.complete(&prompt)?;
+
+    let report = MarketplaceReviewReport {
+        provider_name: provider.name().to_string(),
+        model_name: provider.model().to_string(),
+        review_text: review_text.clone(),
+        timestamp: current_timestamp(),
+        rating: parse_rating(&review_text),
+    };
+
+    // 5. Save report if an output directory was given
+    if let Some(output) = output {
+        let title = if summary_only {
+            "Marketplace Review Summary"
+        } else {
+            "Marketplace Review"
+        };
+        let content = format!(
+            "# {}\n\n**Marketplace:** {}\n**Provider:** {} ({})\n\n---\n\n{}\n",
+            title,
+            config.display_path(&config.marketplace_path),
+            report.provider_name,
+            report.model_name,
+            review_text,
+        );
+        write_report(output, "marketplace-review-report", &content, &report)?;
+    }
+
+    Ok(report)
+}
+
+/// The outcome of reviewing one plugin in [`review_marketplace_plugins`].
+#[derive(Debug)]
+pub struct PluginReviewOutcome {
+    /// The plugin's marketplace entry name.
+    pub plugin_name: String,
+    /// The review, or why it failed.
+    pub result: Result<ReviewReport, SoukError>,
+}
+
+/// Review every plugin listed in the marketplace, up to `concurrency` at a
+/// time.
+///
+/// Each plugin is reviewed as by [`review_plugin`], writing its report to
+/// `output` when given and reusing reviews in `cache`. `on_complete` is
+/// called from the reviewing thread as each plugin finishes, so callers can
+/// show results while others are still in flight. A failed review does not
+/// stop the others.
+///
+/// Returns one outcome per plugin, ordered by plugin name.
+pub fn review_marketplace_plugins(
+    config: &MarketplaceConfig,
+    provider: &dyn LlmProvider,
+    output: Option<ReportOutput<'_>>,
+    concurrency: usize,
+    rubric: Option<&str>,
+    cache: Option<&ReviewCache>,
+    on_complete: &(dyn Fn(&PluginReviewOutcome) + Sync),
+) -> Vec<PluginReviewOutcome> {
+    let mut entries: Vec<_> = config.marketplace.plugins.iter().collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    parallel_map(&entries, concurrency, |entry| {
+        let result = resolve_source(&entry.source, config)
+            .and_then(|path| review_plugin(&path, provider, output, cache, false, rubric));
+        let outcome = PluginReviewOutcome {
+            plugin_name: entry.name.clone(),
+            result,
+        };
+        on_complete(&outcome);
+        outcome
+    })
+}
+
+/// Read marketplace.json and every plugin manifest and build the review
+/// prompt, with `rubric` appended, without contacting a provider.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Io`] if the marketplace file cannot be read.
+pub fn read_marketplace_review_prompt(
+    config: &MarketplaceConfig,
+    summary_only: bool,
+    rubric: Option<&str>,
+) -> Result<String, SoukError> {
     // 1. Read marketplace.json
     let marketplace_json = std::fs::read_to_string(&config.marketplace_path)?;
 
@@ -49,7 +156,7 @@ pub fn review_marketplace(
     for entry in &config.marketplace.plugins {
         let plugin_path = config.plugin_root_abs.join(&entry.source);
         let plugin_json_path = plugin_path.join(".claude-plugin").join("plugin.json");
-        if let Ok(content) = std::fs::read_to_string(&plugin_json_path) {
+        if let Ok(content) = read_manifest_text(&plugin_json_path) {
             plugin_summaries.push(format!(
                 "### {} (source: {})\n```json\n{}\n```",
                 entry.name, entry.source, content
@@ -63,66 +170,61 @@ pub fn review_marketplace(
     }
 
     // 3. Build prompt
-    let prompt = build_marketplace_review_prompt(&marketplace_json, &plugin_summaries);
-
-    // 4. Send to LLM
-    let review_text = provider.complete(&prompt)?;
-
-    let report = MarketplaceReviewReport {
-        provider_name: provider.name().to_string(),
-        model_name: provider.model().to_string(),
-        review_text: review_text.clone(),
-    };
-
-    // 5. Save report if output_dir is given
-    if let Some(dir) = output_dir {
-        std::fs::create_dir_all(dir)?;
-        let report_path = dir.join("marketplace-review-report.md");
-        let content = format!(
-            "# Marketplace Review\n\n**Provider:** {} ({})\n\n---\n\n{}\n",
-            report.provider_name, report.model_name, review_text,
-        );
-        std::fs::write(&report_path, content)?;
-    }
-
-    Ok(report)
+    let prompt =
+        build_marketplace_review_prompt(&marketplace_json, &plugin_summaries, summary_only);
+    Ok(with_rubric(prompt, rubric))
 }
 
 /// Build the structured review prompt sent to the LLM.
-fn build_marketplace_review_prompt(marketplace_json: &str, plugin_summaries: &[String]) -> String {
-    let mut prompt = String::new();
-    prompt.push_str(
+///
+/// With `summary_only`, the section list is replaced by a request for a
+/// terse rating plus three bullet points.
+fn build_marketplace_review_prompt(
+    marketplace_json: &str,
+    plugin_summaries: &[String],
+    summary_only: bool,
+) -> String {
+    let mut prompt = PromptBuilder::new(
         "You are a senior code reviewer. Review this Claude Code plugin marketplace \
-         for quality, consistency, and best practices.\n\n",
+         for quality, consistency, and best practices.",
     );
-    prompt.push_str("## marketplace.json\n```json\n");
-    prompt.push_str(marketplace_json);
-    prompt.push_str("\n```\n\n");
+    prompt.add_code_section("marketplace.json", "json", marketplace_json);
 
     if !plugin_summaries.is_empty() {
-        prompt.push_str("## Plugins\n\n");
-        for summary in plugin_summaries {
-            prompt.push_str(summary);
-            prompt.push_str("\n\n");
-        }
+        // Summaries are separated by blank lines, with one after the heading.
+        prompt.add_text_section("Plugins", &format!("\n{}", plugin_summaries.join("\n\n")));
     }
 
-    prompt.push_str("Please provide:\n");
-    prompt.push_str("1. Overall marketplace quality assessment\n");
-    prompt.push_str("2. Plugin consistency analysis\n");
-    prompt.push_str("3. Dependency concerns\n");
-    prompt.push_str("4. Naming and organization feedback\n");
-    prompt.push_str("5. Suggestions for improvement\n");
-    prompt.push_str("6. Overall rating (1-10)\n");
+    if summary_only {
+        return prompt
+            .add_raw(
+                "Respond tersely with only:\n\
+                 - A first line of the form `Overall rating: N/10`\n\
+                 - Exactly 3 bullet points summarizing the most important findings\n\
+                 Do not include any other sections.\n",
+            )
+            .build();
+    }
 
     prompt
+        .add_request_list(&[
+            "Overall marketplace quality assessment",
+            "Plugin consistency analysis",
+            "Dependency concerns",
+            "Naming and organization feedback",
+            "Suggestions for improvement",
+            "Overall rating (1-10)",
+        ])
+        .build()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::review::provider::MockProvider;
+    use crate::review::report::ReportFormat;
     use crate::types::marketplace::{Marketplace, PluginEntry};
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use tempfile::TempDir;
 
     /// Helper: create a realistic marketplace on disk and return a `MarketplaceConfig`.
@@ -147,13 +249,18 @@ mod tests {
                 name: name.to_string(),
                 source: name.to_string(),
                 tags: vec![],
+                origin: None,
+                sha: None,
             });
         }
 
         let marketplace = Marketplace {
             version: "0.1.0".to_string(),
             plugin_root: Some("./plugins".to_string()),
+            keywords: vec![],
+            categories: vec![],
             plugins: entries,
+            archived: vec![],
         };
 
         let claude_dir = tmp.path().join(".claude-plugin");
@@ -167,6 +274,7 @@ mod tests {
             project_root: tmp.path().to_path_buf(),
             plugin_root_abs: plugins_dir,
             marketplace,
+            settings: Default::default(),
         }
     }
 
@@ -177,7 +285,7 @@ mod tests {
         let config = setup_marketplace_config(&tmp, &[("greeter", Some(plugin_manifest))]);
 
         let provider = MockProvider::new("Looks great! Rating: 9/10");
-        let report = review_marketplace(&config, &provider, None).unwrap();
+        let report = review_marketplace(&config, &provider, None, false, None).unwrap();
 
         assert_eq!(report.provider_name, "mock");
         assert_eq!(report.model_name, "mock-model");
@@ -191,7 +299,14 @@ mod tests {
 
         let output_dir = tmp.path().join("reviews");
         let provider = MockProvider::new("Overall: solid marketplace.");
-        let report = review_marketplace(&config, &provider, Some(&output_dir)).unwrap();
+        let report = review_marketplace(
+            &config,
+            &provider,
+            Some(ReportOutput::new(&output_dir)),
+            false,
+            None,
+        )
+        .unwrap();
 
         let report_path = output_dir.join("marketplace-review-report.md");
         assert!(
@@ -201,9 +316,40 @@ mod tests {
 
         let content = std::fs::read_to_string(&report_path).unwrap();
         assert!(content.contains("# Marketplace Review"));
+        assert!(content.contains("**Marketplace:** .claude-plugin/marketplace.json"));
         assert!(content.contains("mock"));
         assert!(content.contains("mock-model"));
         assert!(content.contains(&report.review_text));
+
+        let json =
+            std::fs::read_to_string(output_dir.join("marketplace-review-report.json")).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["provider_name"], "mock");
+        assert_eq!(json["review_text"], "Overall: solid marketplace.");
+        assert_eq!(json["timestamp"], report.timestamp);
+    }
+
+    #[test]
+    fn review_marketplace_markdown_format_skips_json() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_config(&tmp, &[]);
+        let output_dir = tmp.path().join("reviews");
+        let provider = MockProvider::new("ok");
+
+        review_marketplace(
+            &config,
+            &provider,
+            Some(ReportOutput {
+                dir: &output_dir,
+                format: ReportFormat::Markdown,
+            }),
+            false,
+            None,
+        )
+        .unwrap();
+
+        assert!(output_dir.join("marketplace-review-report.md").is_file());
+        assert!(!output_dir.join("marketplace-review-report.json").exists());
     }
 
     #[test]
@@ -212,7 +358,7 @@ mod tests {
         let config = setup_marketplace_config(&tmp, &[]);
 
         let provider = MockProvider::new("Empty marketplace, structure looks fine.");
-        let report = review_marketplace(&config, &provider, None).unwrap();
+        let report = review_marketplace(&config, &provider, None, false, None).unwrap();
 
         assert_eq!(
             report.review_text,
@@ -234,16 +380,101 @@ mod tests {
         );
 
         let provider = MockProvider::new("Mixed quality.");
-        let report = review_marketplace(&config, &provider, None).unwrap();
+        let report = review_marketplace(&config, &provider, None, false, None).unwrap();
 
         // The function should still succeed even if a plugin.json is missing.
         assert_eq!(report.review_text, "Mixed quality.");
     }
 
+    #[test]
+    fn review_marketplace_plugins_reviews_each_plugin_in_name_order() {
+        let tmp = TempDir::new().unwrap();
+        let names = ["delta", "alpha", "charlie", "bravo", "echo"];
+        let plugins: Vec<_> = names
+            .iter()
+            .map(|n| {
+                (
+                    *n,
+                    Some(r#"{"name":"x","version":"1.0.0","description":"d"}"#),
+                )
+            })
+            .collect();
+        let config = setup_marketplace_config(&tmp, &plugins);
+        let output_dir = tmp.path().join("reviews");
+        let provider = MockProvider::new("Fine. Rating: 7/10");
+        let completed = AtomicUsize::new(0);
+
+        let outcomes = review_marketplace_plugins(
+            &config,
+            &provider,
+            Some(ReportOutput::new(&output_dir)),
+            3,
+            None,
+            None,
+            &|_| {
+                completed.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+
+        assert_eq!(completed.load(Ordering::SeqCst), names.len());
+        let order: Vec<_> = outcomes.iter().map(|o| o.plugin_name.as_str()).collect();
+        assert_eq!(order, ["alpha", "bravo", "charlie", "delta", "echo"]);
+        for outcome in &outcomes {
+            let report = outcome.result.as_ref().unwrap();
+            assert_eq!(report.rating, Some(7));
+            assert!(output_dir
+                .join(format!("{}-review-report.md", outcome.plugin_name))
+                .is_file());
+        }
+    }
+
+    #[test]
+    fn review_marketplace_plugins_continues_past_failures() {
+        let tmp = TempDir::new().unwrap();
+        let manifest = Some(r#"{"name":"x","version":"1.0.0","description":"d"}"#);
+        let config = setup_marketplace_config(
+            &tmp,
+            &[
+                ("alpha", manifest),
+                ("broken", manifest),
+                ("gamma", manifest),
+            ],
+        );
+        std::fs::remove_dir_all(config.plugin_root_abs.join("broken")).unwrap();
+        let provider = MockProvider::new("ok");
+
+        let outcomes = review_marketplace_plugins(&config, &provider, None, 1, None, None, &|_| {});
+
+        assert_eq!(outcomes.len(), 3);
+        assert!(outcomes[0].result.is_ok());
+        assert_eq!(outcomes[1].plugin_name, "broken");
+        assert!(outcomes[1].result.is_err());
+        assert!(outcomes[2].result.is_ok());
+    }
+
+    #[test]
+    fn review_marketplace_plugins_reuses_cached_reviews() {
+        let tmp = TempDir::new().unwrap();
+        let manifest = Some(r#"{"name":"x","version":"1.0.0","description":"d"}"#);
+        let config = setup_marketplace_config(&tmp, &[("alpha", manifest), ("beta", manifest)]);
+        let cache = ReviewCache::new(tmp.path().join("cache"));
+
+        let first = MockProvider::new("First. Rating: 6/10");
+        review_marketplace_plugins(&config, &first, None, 2, None, Some(&cache), &|_| {});
+        let second = MockProvider::new("Second. Rating: 9/10");
+        let outcomes =
+            review_marketplace_plugins(&config, &second, None, 2, None, Some(&cache), &|_| {});
+
+        for outcome in &outcomes {
+            let report = outcome.result.as_ref().unwrap();
+            assert_eq!(report.review_text, "First. Rating: 6/10");
+        }
+    }
+
     #[test]
     fn build_prompt_contains_marketplace_json() {
         let marketplace_json = r#"{"version": "0.1.0", "plugins": []}"#;
-        let prompt = build_marketplace_review_prompt(marketplace_json, &[]);
+        let prompt = build_marketplace_review_prompt(marketplace_json, &[], false);
 
         assert!(prompt.contains(marketplace_json));
         assert!(prompt.contains("senior code reviewer"));
@@ -258,11 +489,58 @@ mod tests {
             "### beta (source: beta) -- plugin.json not readable".to_string(),
         ];
 
-        let prompt = build_marketplace_review_prompt(marketplace_json, &summaries);
+        let prompt = build_marketplace_review_prompt(marketplace_json, &summaries, false);
 
         assert!(prompt.contains("## Plugins"));
         assert!(prompt.contains("### alpha"));
         assert!(prompt.contains("### beta"));
         assert!(prompt.contains("plugin.json not readable"));
     }
+
+    #[test]
+    fn summary_only_prompt_requests_terse_format() {
+        let marketplace_json = r#"{"version": "0.1.0", "plugins": []}"#;
+        let prompt = build_marketplace_review_prompt(marketplace_json, &[], true);
+
+        assert!(prompt.contains(marketplace_json));
+        assert!(prompt.contains("Overall rating: N/10"));
+        assert!(prompt.contains("Exactly 3 bullet points"));
+        assert!(!prompt.contains("Plugin consistency analysis"));
+        assert!(!prompt.contains("Suggestions for improvement"));
+    }
+
+    #[test]
+    fn rubric_text_appears_in_marketplace_prompt() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_config(&tmp, &[]);
+
+        let prompt =
+            read_marketplace_review_prompt(&config, false, Some("- Consistent naming")).unwrap();
+
+        assert!(prompt.contains("## Review Rubric"));
+        assert!(prompt.ends_with("- Consistent naming\n"));
+    }
+
+    #[test]
+    fn summary_only_report_is_titled_as_summary() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace_config(&tmp, &[("alpha", Some(r#"{"name": "alpha"}"#))]);
+
+        let output_dir = tmp.path().join("reviews");
+        let provider = MockProvider::new("Overall rating: 8/10\n- a\n- b\n- c");
+        let report = review_marketplace(
+            &config,
+            &provider,
+            Some(ReportOutput::new(&output_dir)),
+            true,
+            None,
+        )
+        .unwrap();
+        assert_eq!(report.rating, Some(8));
+
+        let content =
+            std::fs::read_to_string(output_dir.join("marketplace-review-report.md")).unwrap();
+        assert!(content.starts_with("# Marketplace Review Summary"));
+        assert!(content.contains("Overall rating: 8/10"));
+    }
 }
@@ -4,13 +4,31 @@
 //! to frontier LLM APIs (Anthropic, OpenAI, Gemini) and receiving review
 //! text. See [`provider::detect_provider`] for automatic API key detection.
 
+pub mod cache;
+pub mod github;
 pub mod marketplace;
+pub mod model_map;
 pub mod plugin;
+pub mod prompt;
 pub mod provider;
+pub mod rating;
+pub mod report;
 pub mod skill;
 
-pub use plugin::{review_plugin, ReviewReport};
+pub use cache::{CacheKey, ReviewCache};
+pub use github::{post_comment, PullRequest};
+pub use marketplace::{
+    read_marketplace_review_prompt, review_marketplace, review_marketplace_plugins,
+    MarketplaceReviewReport, PluginReviewOutcome,
+};
+pub use model_map::{ModelMap, ReviewScope};
+pub use plugin::{read_plugin_review_prompt, review_plugin, ReviewReport};
+pub use prompt::{with_rubric, PromptBuilder};
 pub use provider::{
-    detect_provider, AnthropicProvider, GeminiProvider, LlmProvider, MockProvider, OpenAiProvider,
+    detect_provider, preflight, send_with_retry, AnthropicProvider, AzureOpenAiProvider,
+    FallbackProvider, GeminiProvider, LlmProvider, MockProvider, OllamaProvider, OpenAiProvider,
+    RateLimiter, RetryPolicy, PROVIDERS,
 };
-pub use skill::{review_skills, SkillReviewReport};
+pub use rating::parse_rating;
+pub use report::{ReportFormat, ReportOutput};
+pub use skill::{read_skill_review_prompts, review_skills, SkillReviewReport};
@@ -0,0 +1,141 @@
+//! Per-scope model selection for reviews.
+//!
+//! A [`ModelMap`] is parsed from a `scope=model` list such as
+//! `skill=gpt-4o-mini,marketplace=gpt-4o` and lets cheap and strong models
+//! be mixed across review types.
+
+use crate::error::SoukError;
+
+/// The kind of review a model is chosen for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewScope {
+    Plugin,
+    Skill,
+    Marketplace,
+}
+
+impl ReviewScope {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "plugin" => Some(Self::Plugin),
+            "skill" => Some(Self::Skill),
+            "marketplace" => Some(Self::Marketplace),
+            _ => None,
+        }
+    }
+}
+
+/// Model overrides keyed by [`ReviewScope`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelMap {
+    pub plugin: Option<String>,
+    pub skill: Option<String>,
+    pub marketplace: Option<String>,
+}
+
+impl ModelMap {
+    /// Parse a comma-separated `scope=model` list.
+    ///
+    /// Valid scopes are `plugin`, `skill`, and `marketplace`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoukError::Other`] for an entry without `=`, an unknown
+    /// scope, or an empty model name.
+    pub fn parse(spec: &str) -> Result<Self, SoukError> {
+        let mut map = Self::default();
+        for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (scope, model) = pair.split_once('=').ok_or_else(|| {
+                SoukError::Other(format!(
+                    "Invalid model map entry '{pair}': expected scope=model"
+                ))
+            })?;
+            let scope = ReviewScope::parse(scope.trim()).ok_or_else(|| {
+                SoukError::Other(format!(
+                    "Unknown review scope '{}' in model map (expected plugin, skill, or marketplace)",
+                    scope.trim()
+                ))
+            })?;
+            let model = model.trim();
+            if model.is_empty() {
+                return Err(SoukError::Other(format!(
+                    "Empty model name in model map entry '{pair}'"
+                )));
+            }
+            *map.slot(scope) = Some(model.to_string());
+        }
+        Ok(map)
+    }
+
+    /// The model to use for `scope`, falling back to `default` when the
+    /// scope is unmapped.
+    pub fn model_for<'a>(
+        &'a self,
+        scope: ReviewScope,
+        default: Option<&'a str>,
+    ) -> Option<&'a str> {
+        let mapped = match scope {
+            ReviewScope::Plugin => &self.plugin,
+            ReviewScope::Skill => &self.skill,
+            ReviewScope::Marketplace => &self.marketplace,
+        };
+        mapped.as_deref().or(default)
+    }
+
+    fn slot(&mut self, scope: ReviewScope) -> &mut Option<String> {
+        match scope {
+            ReviewScope::Plugin => &mut self.plugin,
+            ReviewScope::Skill => &mut self.skill,
+            ReviewScope::Marketplace => &mut self.marketplace,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::review::provider::{LlmProvider, OpenAiProvider};
+
+    #[test]
+    fn parses_all_scopes() {
+        let map = ModelMap::parse("skill=gpt-4o-mini, marketplace=gpt-4o,plugin=gpt-4o").unwrap();
+        assert_eq!(map.skill.as_deref(), Some("gpt-4o-mini"));
+        assert_eq!(map.marketplace.as_deref(), Some("gpt-4o"));
+        assert_eq!(map.plugin.as_deref(), Some("gpt-4o"));
+    }
+
+    #[test]
+    fn unmapped_scope_falls_back_to_default() {
+        let map = ModelMap::parse("skill=small").unwrap();
+        assert_eq!(map.model_for(ReviewScope::Plugin, Some("big")), Some("big"));
+        assert_eq!(map.model_for(ReviewScope::Marketplace, None), None);
+    }
+
+    #[test]
+    fn providers_use_mapped_model_per_scope() {
+        let map = ModelMap::parse("skill=gpt-4o-mini,marketplace=gpt-4o").unwrap();
+
+        let skill_provider = OpenAiProvider::new(
+            "key".into(),
+            map.model_for(ReviewScope::Skill, Some("default"))
+                .map(String::from),
+            None,
+        );
+        let marketplace_provider = OpenAiProvider::new(
+            "key".into(),
+            map.model_for(ReviewScope::Marketplace, Some("default"))
+                .map(String::from),
+            None,
+        );
+
+        assert_eq!(skill_provider.model(), "gpt-4o-mini");
+        assert_eq!(marketplace_provider.model(), "gpt-4o");
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        assert!(ModelMap::parse("skill").is_err());
+        assert!(ModelMap::parse("agent=x").is_err());
+        assert!(ModelMap::parse("skill=").is_err());
+    }
+}
@@ -6,12 +6,22 @@
 
 use std::path::Path;
 
+use serde::Serialize;
+
 use crate::error::SoukError;
 use crate::resolution::skill::enumerate_skills;
+use crate::review::cache::{plugin_content_hash, prompt_hash, CacheKey, ReviewCache};
+use crate::review::prompt::{with_rubric, PromptBuilder};
 use crate::review::provider::LlmProvider;
+use crate::review::rating::parse_rating;
+use crate::review::report::{write_report, ReportOutput};
+use crate::types::plugin::read_manifest_text;
+use crate::util::{current_date_string, current_timestamp};
 
 /// The result of reviewing a plugin with an LLM provider.
-#[derive(Debug, Clone)]
+///
+/// Serializes to the JSON report: the cache fields are omitted.
+#[derive(Debug, Clone, Serialize)]
 pub struct ReviewReport {
     /// Name of the reviewed plugin (derived from directory name).
     pub plugin_name: String,
@@ -21,13 +31,57 @@ pub struct ReviewReport {
     pub model_name: String,
     /// The full review text returned by the LLM.
     pub review_text: String,
+    /// When the review was produced, as an RFC 3339 UTC timestamp.
+    pub timestamp: String,
+    /// Overall 1-10 rating found in `review_text`, if any; see
+    /// [`parse_rating`].
+    pub rating: Option<u8>,
+    /// `max_tokens` sent with the request; `None` when the provider leaves
+    /// it to the API's default.
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature sent with the request; `None` when the provider
+    /// leaves it to the API's default.
+    pub temperature: Option<f32>,
+    /// Hash of the provider, model, and prompt, as 16 hex digits; the same
+    /// value as the first half of a [`CacheKey`].
+    pub prompt_hash: String,
+    /// Cache key for this review, when a [`ReviewCache`] was used.
+    #[serde(skip)]
+    pub cache_key: Option<CacheKey>,
+    /// Whether `review_text` came from the cache rather than the provider.
+    #[serde(skip)]
+    pub cached: bool,
+}
+
+impl ReviewReport {
+    /// The request parameters, as shown in report headers: e.g.
+    /// `max_tokens 4096, temperature default, prompt hash 1a2b…`.
+    pub fn parameters(&self) -> String {
+        let max_tokens = self
+            .max_tokens
+            .map_or_else(|| "default".to_string(), |n| n.to_string());
+        let temperature = self
+            .temperature
+            .map_or_else(|| "default".to_string(), |t| t.to_string());
+        format!(
+            "max_tokens {max_tokens}, temperature {temperature}, prompt hash {}",
+            self.prompt_hash
+        )
+    }
 }
 
 /// Review a plugin using an LLM provider.
 ///
 /// Reads plugin files from `plugin_path`, constructs a structured review
-/// prompt, sends it to `provider`, and returns the review report. If
-/// `output_dir` is specified, the report is also saved as a Markdown file.
+/// prompt, sends it to `provider`, and returns the review report. With an
+/// `output`, the report is also saved as `<plugin>-review-report.md` and/or
+/// `.json` in its directory.
+///
+/// With a `cache`, a previous review with the same prompt and the same input
+/// file contents is reused instead of calling the provider; see
+/// [`crate::review::cache`]. With `manifest_only`, the prompt is built from
+/// plugin.json alone, and a `rubric` is appended to it; see
+/// [`read_plugin_review_prompt`].
 ///
 /// # Errors
 ///
@@ -36,11 +90,97 @@ pub struct ReviewReport {
 pub fn review_plugin(
     plugin_path: &Path,
     provider: &dyn LlmProvider,
-    output_dir: Option<&Path>,
+    output: Option<ReportOutput<'_>>,
+    cache: Option<&ReviewCache>,
+    manifest_only: bool,
+    rubric: Option<&str>,
 ) -> Result<ReviewReport, SoukError> {
+    let prompt = read_plugin_review_prompt(plugin_path, manifest_only, rubric)?;
+
+    // Send to LLM, unless an identical review is cached
+    let cache_key =
+        cache.map(|_| CacheKey::new(provider, &prompt, plugin_content_hash(plugin_path)));
+    let cached_text = cache.zip(cache_key.as_ref()).and_then(|(c, k)| c.get(k));
+    let cached = cached_text.is_some();
+    let review_text = match cached_text {
+        Some(text) => text,
+        None => {
+            let text = provider.complete(&prompt)?;
+            if let (Some(cache), Some(key)) = (cache, &cache_key) {
+                cache.put(key, &text)?;
+            }
+            text
+        }
+    };
+
+    // Build report
+    let plugin_name = plugin_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let report = ReviewReport {
+        plugin_name: plugin_name.clone(),
+        provider_name: provider.name().to_string(),
+        model_name: provider.model().to_string(),
+        review_text: review_text.clone(),
+        timestamp: current_timestamp(),
+        rating: parse_rating(&review_text),
+        max_tokens: provider.max_tokens(),
+        temperature: provider.temperature(),
+        prompt_hash: format!("{:016x}", prompt_hash(provider, &prompt)),
+        cache_key,
+        cached,
+    };
+
+    // Save report if an output directory was given
+    if let Some(output) = output {
+        let report_content = format!(
+            "# Plugin Review: {plugin_name}\n\n\
+             **Provider:** {} ({})\n\
+             **Parameters:** {}\n\
+             **Date:** {}\n\n\
+             ---\n\n\
+             {review_text}\n",
+            report.provider_name,
+            report.model_name,
+            report.parameters(),
+            current_date_string(),
+        );
+        write_report(
+            output,
+            &format!("{plugin_name}-review-report"),
+            &report_content,
+            &report,
+        )?;
+    }
+
+    Ok(report)
+}
+
+/// Read a plugin's files and build its review prompt.
+///
+/// With `manifest_only`, only plugin.json is read, so large READMEs and skill
+/// trees are neither loaded nor sent to the provider. A `rubric` is appended
+/// as a "Review Rubric" section.
+///
+/// # Errors
+///
+/// Returns `SoukError::Io` if the required `plugin.json` cannot be read.
+pub fn read_plugin_review_prompt(
+    plugin_path: &Path,
+    manifest_only: bool,
+    rubric: Option<&str>,
+) -> Result<String, SoukError> {
     // 1. Read plugin.json (required)
     let plugin_json_path = plugin_path.join(".claude-plugin").join("plugin.json");
-    let plugin_json = std::fs::read_to_string(&plugin_json_path)?;
+    let plugin_json = read_manifest_text(&plugin_json_path)
+        .map_err(|e| SoukError::Other(format!("{e} ({})", plugin_json_path.display())))?;
+
+    if manifest_only {
+        let prompt = build_plugin_review_prompt(&plugin_json, None, None, &[]);
+        return Ok(with_rubric(prompt, rubric));
+    }
 
     // 2. Read extends-plugin.json (optional)
     let extends_path = plugin_path
@@ -66,41 +206,7 @@ pub fn review_plugin(
         readme.as_deref(),
         &skills_summary,
     );
-
-    // 6. Send to LLM
-    let review_text = provider.complete(&prompt)?;
-
-    // 7. Build report
-    let plugin_name = plugin_path
-        .file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| "unknown".to_string());
-
-    let report = ReviewReport {
-        plugin_name: plugin_name.clone(),
-        provider_name: provider.name().to_string(),
-        model_name: provider.model().to_string(),
-        review_text: review_text.clone(),
-    };
-
-    // 8. Save report if output_dir specified
-    if let Some(dir) = output_dir {
-        std::fs::create_dir_all(dir)?;
-        let report_path = dir.join(format!("{plugin_name}-review-report.md"));
-        let report_content = format!(
-            "# Plugin Review: {plugin_name}\n\n\
-             **Provider:** {} ({})\n\
-             **Date:** {}\n\n\
-             ---\n\n\
-             {review_text}\n",
-            report.provider_name,
-            report.model_name,
-            current_date_string(),
-        );
-        std::fs::write(&report_path, report_content)?;
-    }
-
-    Ok(report)
+    Ok(with_rubric(prompt, rubric))
 }
 
 /// Build the structured review prompt from plugin content.
@@ -113,84 +219,36 @@ pub fn build_plugin_review_prompt(
     readme: Option<&str>,
     skills: &[String],
 ) -> String {
-    let mut prompt = String::with_capacity(2048);
-
-    prompt.push_str(
+    let mut prompt = PromptBuilder::new(
         "You are a senior code reviewer specializing in Claude Code plugins. \
-         Review this plugin for quality, security, and best practices.\n\n",
+         Review this plugin for quality, security, and best practices.",
     );
 
-    prompt.push_str("## plugin.json\n```json\n");
-    prompt.push_str(plugin_json);
-    prompt.push_str("\n```\n\n");
+    prompt.add_code_section("plugin.json", "json", plugin_json);
 
     if let Some(extends) = extends_json {
-        prompt.push_str("## extends-plugin.json\n```json\n");
-        prompt.push_str(extends);
-        prompt.push_str("\n```\n\n");
+        prompt.add_code_section("extends-plugin.json", "json", extends);
     }
 
     if let Some(readme) = readme {
-        prompt.push_str("## README.md\n");
-        prompt.push_str(readme);
-        prompt.push_str("\n\n");
+        prompt.add_text_section("README.md", readme);
     }
 
     if !skills.is_empty() {
-        prompt.push_str("## Skills\n");
-        for skill in skills {
-            prompt.push_str(skill);
-            prompt.push('\n');
-        }
-        prompt.push('\n');
+        prompt.add_text_section("Skills", &skills.join("\n"));
     }
 
-    prompt.push_str(
-        "Please provide:\n\
-         1. Executive Summary\n\
-         2. Component Analysis (agents, skills, commands, hooks, MCP servers)\n\
-         3. Code Quality Assessment\n\
-         4. Documentation Review\n\
-         5. Security Considerations\n\
-         6. Recommendations (critical issues, suggested improvements, optional enhancements)\n\
-         7. Overall Rating (1-10)\n",
-    );
-
     prompt
-}
-
-/// Returns the current date as a `YYYY-MM-DD` string.
-///
-/// Uses `std::time::SystemTime` to avoid pulling in the `chrono` crate.
-fn current_date_string() -> String {
-    let now = std::time::SystemTime::now();
-    let since_epoch = now
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default();
-    let secs = since_epoch.as_secs();
-    // 86400 seconds per day; epoch is 1970-01-01.
-    let days = secs / 86400;
-
-    // Compute year/month/day from days since epoch (civil calendar).
-    let (year, month, day) = days_to_civil(days as i64);
-    format!("{year:04}-{month:02}-{day:02}")
-}
-
-/// Convert days since Unix epoch to (year, month, day).
-///
-/// Algorithm from Howard Hinnant's `chrono`-compatible date library.
-fn days_to_civil(days: i64) -> (i32, u32, u32) {
-    let z = days + 719468;
-    let era = (if z >= 0 { z } else { z - 146096 }) / 146097;
-    let doe = (z - era * 146097) as u32;
-    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-    let y = yoe as i64 + era * 400;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-    let mp = (5 * doy + 2) / 153;
-    let d = doy - (153 * mp + 2) / 5 + 1;
-    let m = if mp < 10 { mp + 3 } else { mp - 9 };
-    let y = if m <= 2 { y + 1 } else { y };
-    (y as i32, m, d)
+        .add_request_list(&[
+            "Executive Summary",
+            "Component Analysis (agents, skills, commands, hooks, MCP servers)",
+            "Code Quality Assessment",
+            "Documentation Review",
+            "Security Considerations",
+            "Recommendations (critical issues, suggested improvements, optional enhancements)",
+            "Overall Rating (1-10)",
+        ])
+        .build()
 }
 
 // ---------------------------------------------------------------------------
@@ -201,6 +259,7 @@ fn days_to_civil(days: i64) -> (i32, u32, u32) {
 mod tests {
     use super::*;
     use crate::review::provider::MockProvider;
+    use crate::review::report::ReportFormat;
     use tempfile::TempDir;
 
     /// Create a minimal plugin directory with the required plugin.json.
@@ -252,7 +311,7 @@ mod tests {
         let plugin = setup_full_plugin(&tmp);
         let provider = MockProvider::new("Great plugin! Rating: 9/10");
 
-        let report = review_plugin(&plugin, &provider, None).unwrap();
+        let report = review_plugin(&plugin, &provider, None, None, false, None).unwrap();
 
         assert_eq!(report.plugin_name, "test-plugin");
         assert_eq!(report.provider_name, "mock");
@@ -267,7 +326,15 @@ mod tests {
         let output_dir = tmp.path().join("output");
         let provider = MockProvider::new("Looks good!");
 
-        let report = review_plugin(&plugin, &provider, Some(&output_dir)).unwrap();
+        let report = review_plugin(
+            &plugin,
+            &provider,
+            Some(ReportOutput::new(&output_dir)),
+            None,
+            false,
+            None,
+        )
+        .unwrap();
 
         assert_eq!(report.plugin_name, "test-plugin");
 
@@ -280,13 +347,84 @@ mod tests {
         assert!(content.contains("Looks good!"));
     }
 
+    #[test]
+    fn review_plugin_json_report_has_metadata() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = setup_plugin(&tmp);
+        let output_dir = tmp.path().join("output");
+        let provider = MockProvider::new("Looks good!");
+
+        review_plugin(
+            &plugin,
+            &provider,
+            Some(ReportOutput {
+                dir: &output_dir,
+                format: ReportFormat::Json,
+            }),
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+
+        assert!(!output_dir.join("test-plugin-review-report.md").exists());
+        let content =
+            std::fs::read_to_string(output_dir.join("test-plugin-review-report.json")).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(json["plugin_name"], "test-plugin");
+        assert_eq!(json["provider_name"], "mock");
+        assert_eq!(json["model_name"], "mock-model");
+        assert_eq!(json["review_text"], "Looks good!");
+        assert!(json["timestamp"].as_str().unwrap().ends_with('Z'));
+        assert!(json["rating"].is_null());
+        assert!(json.get("cached").is_none());
+    }
+
+    #[test]
+    fn review_plugin_reports_request_parameters() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = setup_plugin(&tmp);
+        let output_dir = tmp.path().join("output");
+        let provider = MockProvider::new("Looks good!").with_max_tokens(4096);
+
+        let report = review_plugin(
+            &plugin,
+            &provider,
+            Some(ReportOutput {
+                dir: &output_dir,
+                format: ReportFormat::Both,
+            }),
+            Some(&ReviewCache::new(tmp.path().join("cache"))),
+            false,
+            None,
+        )
+        .unwrap();
+
+        let key = report.cache_key.unwrap();
+        assert_eq!(report.prompt_hash, format!("{:016x}", key.prompt_hash));
+
+        let content =
+            std::fs::read_to_string(output_dir.join("test-plugin-review-report.json")).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(json["max_tokens"], 4096);
+        assert!(json["temperature"].is_null());
+        assert_eq!(json["prompt_hash"], report.prompt_hash.as_str());
+
+        let markdown =
+            std::fs::read_to_string(output_dir.join("test-plugin-review-report.md")).unwrap();
+        assert!(markdown.contains(&format!(
+            "**Parameters:** max_tokens 4096, temperature default, prompt hash {}",
+            report.prompt_hash
+        )));
+    }
+
     #[test]
     fn review_plugin_minimal_plugin_no_extras() {
         let tmp = TempDir::new().unwrap();
         let plugin = setup_plugin(&tmp);
         let provider = MockProvider::new("Minimal but valid.");
 
-        let report = review_plugin(&plugin, &provider, None).unwrap();
+        let report = review_plugin(&plugin, &provider, None, None, false, None).unwrap();
 
         assert_eq!(report.plugin_name, "test-plugin");
         assert_eq!(report.review_text, "Minimal but valid.");
@@ -299,10 +437,63 @@ mod tests {
         std::fs::create_dir_all(&plugin).unwrap();
         let provider = MockProvider::new("should not reach");
 
-        let result = review_plugin(&plugin, &provider, None);
+        let result = review_plugin(&plugin, &provider, None, None, false, None);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn review_plugin_reuses_cache_until_skill_changes() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = setup_full_plugin(&tmp);
+        let cache = ReviewCache::new(tmp.path().join("cache"));
+
+        let first = review_plugin(
+            &plugin,
+            &MockProvider::new("first"),
+            None,
+            Some(&cache),
+            false,
+            None,
+        )
+        .unwrap();
+        assert!(!first.cached);
+        assert!(first.cache_key.is_some());
+
+        let second = review_plugin(
+            &plugin,
+            &MockProvider::new("second"),
+            None,
+            Some(&cache),
+            false,
+            None,
+        )
+        .unwrap();
+        assert!(second.cached);
+        assert_eq!(second.review_text, "first");
+        assert_eq!(second.cache_key, first.cache_key);
+
+        std::fs::write(
+            plugin.join("skills").join("my-skill").join("SKILL.md"),
+            "---\nname: My Skill\ndescription: Does other things\n---\n# My Skill",
+        )
+        .unwrap();
+
+        let third = review_plugin(
+            &plugin,
+            &MockProvider::new("third"),
+            None,
+            Some(&cache),
+            false,
+            None,
+        )
+        .unwrap();
+        assert!(!third.cached);
+        assert_eq!(third.review_text, "third");
+        let (before, after) = (first.cache_key.unwrap(), third.cache_key.unwrap());
+        assert_eq!(before.prompt_hash, after.prompt_hash);
+        assert_ne!(before.content_hash, after.content_hash);
+    }
+
     #[test]
     fn build_prompt_contains_plugin_json() {
         let prompt = build_plugin_review_prompt(r#"{"name": "foo"}"#, None, None, &[]);
@@ -367,14 +558,37 @@ mod tests {
     }
 
     #[test]
-    fn current_date_string_has_correct_format() {
-        let date = current_date_string();
-        // Format: YYYY-MM-DD
-        assert_eq!(date.len(), 10);
-        assert_eq!(&date[4..5], "-");
-        assert_eq!(&date[7..8], "-");
-        // Year should be plausible (2020+)
-        let year: i32 = date[..4].parse().unwrap();
-        assert!(year >= 2020);
+    fn manifest_only_prompt_skips_readme_and_skills() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = setup_plugin(&tmp);
+        std::fs::write(plugin.join("README.md"), "# Test Plugin").unwrap();
+        let skill = plugin.join("skills").join("s");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "---\nname: s\n---\nBody").unwrap();
+
+        let full = read_plugin_review_prompt(&plugin, false, None).unwrap();
+        assert!(full.contains("## README.md"));
+        assert!(full.contains("## Skills"));
+
+        let prompt = read_plugin_review_prompt(&plugin, true, None).unwrap();
+        assert!(prompt.contains("## plugin.json"));
+        assert!(prompt.contains("\"name\": \"test-plugin\""));
+        assert!(!prompt.contains("## README.md"));
+        assert!(!prompt.contains("## Skills"));
+    }
+
+    #[test]
+    fn rubric_text_appears_in_prompt() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = setup_plugin(&tmp);
+        let rubric = "1. Every skill has an example\n2. No network access";
+
+        for manifest_only in [false, true] {
+            let prompt = read_plugin_review_prompt(&plugin, manifest_only, Some(rubric)).unwrap();
+            assert!(prompt.contains("## Review Rubric"));
+            assert!(prompt.contains(rubric));
+        }
+        let plain = read_plugin_review_prompt(&plugin, false, None).unwrap();
+        assert!(!plain.contains("## Review Rubric"));
     }
 }
@@ -0,0 +1,112 @@
+//! Shared Markdown assembly for review prompts.
+//!
+//! Every review prompt is an introduction, a series of `##` sections, and a
+//! closing numbered list of what the reviewer should provide. [`PromptBuilder`]
+//! keeps that layout consistent across the plugin, skill, and marketplace
+//! prompts. An organisation's review rubric can be appended to any of them
+//! with [`with_rubric`].
+
+/// Incrementally builds a review prompt.
+#[derive(Debug, Clone, Default)]
+pub struct PromptBuilder {
+    prompt: String,
+}
+
+impl PromptBuilder {
+    /// Start a prompt with an introductory paragraph.
+    pub fn new(intro: &str) -> Self {
+        let mut prompt = String::with_capacity(2048);
+        prompt.push_str(intro);
+        prompt.push_str("\n\n");
+        Self { prompt }
+    }
+
+    /// Append a section whose body is a fenced code block in `lang`.
+    pub fn add_code_section(&mut self, title: &str, lang: &str, body: &str) -> &mut Self {
+        self.prompt
+            .push_str(&format!("## {title}\n```{lang}\n{body}\n```\n\n"));
+        self
+    }
+
+    /// Append a section whose body is plain Markdown.
+    pub fn add_text_section(&mut self, title: &str, body: &str) -> &mut Self {
+        self.prompt.push_str(&format!("## {title}\n{body}\n\n"));
+        self
+    }
+
+    /// Append the closing "Please provide:" list, numbered from 1.
+    pub fn add_request_list(&mut self, items: &[&str]) -> &mut Self {
+        self.prompt.push_str("Please provide:\n");
+        for (i, item) in items.iter().enumerate() {
+            self.prompt.push_str(&format!("{}. {item}\n", i + 1));
+        }
+        self
+    }
+
+    /// Append free-form instructions verbatim.
+    pub fn add_raw(&mut self, text: &str) -> &mut Self {
+        self.prompt.push_str(text);
+        self
+    }
+
+    /// Finish and return the prompt text.
+    pub fn build(&self) -> String {
+        self.prompt.clone()
+    }
+}
+
+/// Append a "## Review Rubric" section asking the model to score against
+/// `rubric`. Returns `prompt` unchanged when there is no rubric.
+pub fn with_rubric(mut prompt: String, rubric: Option<&str>) -> String {
+    if let Some(rubric) = rubric {
+        prompt.push_str(&format!(
+            "\n## Review Rubric\n\
+             Score the review against this rubric. For each criterion, state \
+             whether it is met and why.\n\n{}\n",
+            rubric.trim_end()
+        ));
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intro_is_followed_by_blank_line() {
+        assert_eq!(PromptBuilder::new("Hello.").build(), "Hello.\n\n");
+    }
+
+    #[test]
+    fn code_section_is_fenced() {
+        let prompt = PromptBuilder::new("I")
+            .add_code_section("plugin.json", "json", "{}")
+            .build();
+        assert_eq!(prompt, "I\n\n## plugin.json\n```json\n{}\n```\n\n");
+    }
+
+    #[test]
+    fn text_section_has_heading() {
+        let prompt = PromptBuilder::new("I")
+            .add_text_section("README.md", "Hi")
+            .build();
+        assert_eq!(prompt, "I\n\n## README.md\nHi\n\n");
+    }
+
+    #[test]
+    fn request_list_is_numbered() {
+        let prompt = PromptBuilder::new("I")
+            .add_request_list(&["First", "Second"])
+            .build();
+        assert_eq!(prompt, "I\n\nPlease provide:\n1. First\n2. Second\n");
+    }
+
+    #[test]
+    fn rubric_is_appended_as_section() {
+        let prompt = with_rubric("P\n".into(), Some("- Has tests\n"));
+        assert!(prompt.starts_with("P\n\n## Review Rubric\n"));
+        assert!(prompt.ends_with("\n\n- Has tests\n"));
+        assert_eq!(with_rubric("P\n".into(), None), "P\n");
+    }
+}
@@ -1,11 +1,23 @@
 //! LLM provider abstraction for AI-powered reviews.
 //!
-//! Supports Anthropic, OpenAI, and Gemini APIs with automatic provider
-//! detection from environment variables. See decision D4 in the project
-//! spec: all LLM interaction goes through direct API calls, not CLI tools.
+//! Supports Anthropic, OpenAI, Azure OpenAI, and Gemini APIs, plus a local
+//! Ollama server, with automatic provider detection from environment
+//! variables. See decision D4 in the project spec: all LLM interaction goes
+//! through direct API calls, not CLI tools.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use reqwest::blocking::Response;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
 
 use crate::error::SoukError;
 
+/// `max_tokens` sent by the providers whose APIs take one.
+pub const MAX_TOKENS: u32 = 4096;
+
 /// Trait for LLM API providers.
 ///
 /// Implementations must be `Send + Sync` so providers can be shared across
@@ -21,6 +33,182 @@ pub trait LlmProvider: Send + Sync {
 
     /// Model identifier being used (e.g., "claude-sonnet-4-20250514").
     fn model(&self) -> &str;
+
+    /// Maximum tokens requested per completion, or `None` when the request
+    /// leaves it to the API's default.
+    fn max_tokens(&self) -> Option<u32> {
+        None
+    }
+
+    /// Sampling temperature sent with each request, or `None` when the
+    /// request leaves it to the API's default.
+    fn temperature(&self) -> Option<f32> {
+        None
+    }
+
+    /// Check the configured credentials with a cheap request, such as
+    /// listing models, before any prompt is sent.
+    ///
+    /// The default accepts any configuration.
+    fn validate_config(&self) -> Result<(), SoukError> {
+        Ok(())
+    }
+}
+
+/// Run `provider`'s [`LlmProvider::validate_config`], naming the provider
+/// in the error, so a bad key fails before a review builds its prompts.
+pub fn preflight(provider: &dyn LlmProvider) -> Result<(), SoukError> {
+    let name = provider.name().to_string();
+    provider
+        .validate_config()
+        .map_err(|e| SoukError::LlmApiError(format!("{name} auth check failed: {e}")))
+}
+
+/// How long a provider waits for a response when no timeout is given.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Build the HTTP client shared by a provider's requests.
+pub(crate) fn http_client(timeout: Option<Duration>) -> reqwest::blocking::Client {
+    reqwest::blocking::Client::builder()
+        .timeout(timeout.unwrap_or(DEFAULT_TIMEOUT))
+        .build()
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// Retry
+// ---------------------------------------------------------------------------
+
+/// How [`send_with_retry`] retries transient HTTP failures.
+///
+/// The delay before retry `n` (counting from 1) is `base_delay * 2^(n-1)`,
+/// jittered to between half and all of that value, unless the server sent a
+/// `Retry-After` header. Either way the wait is capped at `max_delay`, so a
+/// server asking for an hour cannot stall a review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub base_delay: Duration,
+    /// Longest wait before any retry, including one set by `Retry-After`.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The jittered delay before retry number `retry` (1-based).
+    fn backoff(&self, retry: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)));
+        // Sub-second clock noise is plenty to spread out parallel reviews.
+        let noise = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos() % 1000)
+            .unwrap_or(0);
+        delay.mul_f64(0.5 + f64::from(noise) / 2000.0)
+    }
+
+    /// The wait before retry number `retry` (1-based): the server's
+    /// `retry_after` if it sent one, else the backoff, capped at
+    /// `max_delay`.
+    fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after
+            .unwrap_or_else(|| self.backoff(retry))
+            .min(self.max_delay)
+    }
+}
+
+/// Whether an HTTP status is worth retrying: rate limiting or a transient
+/// server-side failure.
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 500 | 502 | 503 | 504)
+}
+
+/// The delay requested by a `Retry-After` header given in whole seconds.
+/// HTTP-date values are ignored in favour of the policy's backoff.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// The outcome of one attempt inside [`run_with_retry`].
+enum Attempt<T> {
+    Done(T),
+    Retryable {
+        error: String,
+        retry_after: Option<Duration>,
+    },
+    Fatal(String),
+}
+
+/// Call `attempt` until it succeeds, fails fatally, or `policy` runs out of
+/// retries. Failures become [`SoukError::LlmApiError`] with the number of
+/// attempts made.
+fn run_with_retry<T>(
+    policy: &RetryPolicy,
+    mut attempt: impl FnMut() -> Attempt<T>,
+) -> Result<T, SoukError> {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let error = match attempt() {
+            Attempt::Done(value) => return Ok(value),
+            Attempt::Retryable { retry_after, .. } if attempts <= policy.max_retries => {
+                std::thread::sleep(policy.delay(attempts, retry_after));
+                continue;
+            }
+            Attempt::Retryable { error, .. } | Attempt::Fatal(error) => error,
+        };
+        let plural = if attempts == 1 { "" } else { "s" };
+        return Err(SoukError::LlmApiError(format!(
+            "{error} (after {attempts} attempt{plural})"
+        )));
+    }
+}
+
+/// Send an HTTP request built by `send`, retrying on 429, 500, 502, 503, and
+/// 504 according to `policy` and honoring `Retry-After` up to the policy's
+/// `max_delay`.
+///
+/// Returns the first successful response. Timeouts, other transport errors,
+/// and other non-success statuses fail immediately.
+pub fn send_with_retry(
+    policy: &RetryPolicy,
+    mut send: impl FnMut() -> reqwest::Result<Response>,
+) -> Result<Response, SoukError> {
+    run_with_retry(policy, || match send() {
+        Err(e) if e.is_timeout() => Attempt::Fatal("request timed out".to_string()),
+        Err(e) => Attempt::Fatal(format!("Request failed: {e}")),
+        Ok(response) if response.status().is_success() => Attempt::Done(response),
+        Ok(response) => {
+            let status = response.status();
+            let retry_after = retry_after(response.headers());
+            let text = response.text().unwrap_or_default();
+            let error = format!("HTTP {status}: {text}");
+            if is_retryable_status(status.as_u16()) {
+                Attempt::Retryable { error, retry_after }
+            } else {
+                Attempt::Fatal(error)
+            }
+        }
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -37,15 +225,16 @@ pub struct AnthropicProvider {
 impl AnthropicProvider {
     /// Create a new Anthropic provider.
     ///
-    /// If `model` is `None`, defaults to `claude-sonnet-4-6`.
+    /// If `model` is `None`, defaults to `claude-sonnet-4-6`. If `timeout` is
+    /// `None`, requests time out after [`DEFAULT_TIMEOUT`].
     ///
     /// Anthropic does not offer a `-latest` alias; this must be updated
     /// manually when new model versions are released.
-    pub fn new(api_key: String, model: Option<String>) -> Self {
+    pub fn new(api_key: String, model: Option<String>, timeout: Option<Duration>) -> Self {
         Self {
             api_key,
             model: model.unwrap_or_else(|| "claude-sonnet-4-6".to_string()),
-            client: reqwest::blocking::Client::new(),
+            client: http_client(timeout),
         }
     }
 }
@@ -54,27 +243,21 @@ impl LlmProvider for AnthropicProvider {
     fn complete(&self, prompt: &str) -> Result<String, SoukError> {
         let body = serde_json::json!({
             "model": self.model,
-            "max_tokens": 4096,
+            "max_tokens": MAX_TOKENS,
             "messages": [
                 {"role": "user", "content": prompt}
             ]
         });
 
-        let response = self
-            .client
-            .post("https://api.anthropic.com/v1/messages")
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&body)
-            .send()
-            .map_err(|e| SoukError::LlmApiError(format!("Request failed: {e}")))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().unwrap_or_default();
-            return Err(SoukError::LlmApiError(format!("HTTP {status}: {text}")));
-        }
+        let response = send_with_retry(&RetryPolicy::default(), || {
+            self.client
+                .post("https://api.anthropic.com/v1/messages")
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", "2023-06-01")
+                .header("content-type", "application/json")
+                .json(&body)
+                .send()
+        })?;
 
         let json: serde_json::Value = response
             .json()
@@ -90,9 +273,24 @@ impl LlmProvider for AnthropicProvider {
         "anthropic"
     }
 
+    fn validate_config(&self) -> Result<(), SoukError> {
+        send_with_retry(&RetryPolicy::default(), || {
+            self.client
+                .get("https://api.anthropic.com/v1/models")
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", "2023-06-01")
+                .send()
+        })
+        .map(drop)
+    }
+
     fn model(&self) -> &str {
         &self.model
     }
+
+    fn max_tokens(&self) -> Option<u32> {
+        Some(MAX_TOKENS)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -109,12 +307,13 @@ pub struct OpenAiProvider {
 impl OpenAiProvider {
     /// Create a new OpenAI provider.
     ///
-    /// If `model` is `None`, defaults to `gpt-5-mini`.
-    pub fn new(api_key: String, model: Option<String>) -> Self {
+    /// If `model` is `None`, defaults to `gpt-5-mini`. If `timeout` is `None`,
+    /// requests time out after [`DEFAULT_TIMEOUT`].
+    pub fn new(api_key: String, model: Option<String>, timeout: Option<Duration>) -> Self {
         Self {
             api_key,
             model: model.unwrap_or_else(|| "gpt-5-mini".to_string()),
-            client: reqwest::blocking::Client::new(),
+            client: http_client(timeout),
         }
     }
 }
@@ -126,23 +325,17 @@ impl LlmProvider for OpenAiProvider {
             "messages": [
                 {"role": "user", "content": prompt}
             ],
-            "max_tokens": 4096
+            "max_tokens": MAX_TOKENS
         });
 
-        let response = self
-            .client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("content-type", "application/json")
-            .json(&body)
-            .send()
-            .map_err(|e| SoukError::LlmApiError(format!("Request failed: {e}")))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().unwrap_or_default();
-            return Err(SoukError::LlmApiError(format!("HTTP {status}: {text}")));
-        }
+        let response = send_with_retry(&RetryPolicy::default(), || {
+            self.client
+                .post("https://api.openai.com/v1/chat/completions")
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("content-type", "application/json")
+                .json(&body)
+                .send()
+        })?;
 
         let json: serde_json::Value = response
             .json()
@@ -158,9 +351,136 @@ impl LlmProvider for OpenAiProvider {
         "openai"
     }
 
+    fn validate_config(&self) -> Result<(), SoukError> {
+        send_with_retry(&RetryPolicy::default(), || {
+            self.client
+                .get("https://api.openai.com/v1/models")
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .send()
+        })
+        .map(drop)
+    }
+
     fn model(&self) -> &str {
         &self.model
     }
+
+    fn max_tokens(&self) -> Option<u32> {
+        Some(MAX_TOKENS)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Azure OpenAI
+// ---------------------------------------------------------------------------
+
+/// Azure OpenAI REST API version used for chat completions.
+const AZURE_API_VERSION: &str = "2024-02-01";
+
+/// LLM provider for an Azure OpenAI deployment.
+///
+/// Azure routes requests by deployment rather than model, so
+/// [`LlmProvider::model`] reports the deployment name.
+pub struct AzureOpenAiProvider {
+    api_key: String,
+    endpoint: String,
+    deployment: String,
+    client: reqwest::blocking::Client,
+}
+
+impl AzureOpenAiProvider {
+    /// Create a new Azure OpenAI provider for `deployment` on `endpoint`
+    /// (e.g. `https://my-resource.openai.azure.com`).
+    ///
+    /// If `timeout` is `None`, requests time out after [`DEFAULT_TIMEOUT`].
+    pub fn new(
+        api_key: String,
+        endpoint: String,
+        deployment: String,
+        timeout: Option<Duration>,
+    ) -> Self {
+        Self {
+            api_key,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            deployment,
+            client: http_client(timeout),
+        }
+    }
+
+    /// Build a provider from `AZURE_OPENAI_API_KEY`, `AZURE_OPENAI_ENDPOINT`,
+    /// and `AZURE_OPENAI_DEPLOYMENT`, with `deployment` overriding the last.
+    ///
+    /// Returns `None` unless the key, endpoint, and a deployment are all set.
+    fn from_env(deployment: Option<String>, timeout: Option<Duration>) -> Option<Self> {
+        let api_key = std::env::var("AZURE_OPENAI_API_KEY").ok()?;
+        let endpoint = std::env::var("AZURE_OPENAI_ENDPOINT").ok()?;
+        let deployment = deployment.or_else(|| std::env::var("AZURE_OPENAI_DEPLOYMENT").ok())?;
+        Some(Self::new(api_key, endpoint, deployment, timeout))
+    }
+
+    /// The chat completions URL for this deployment.
+    fn url(&self) -> String {
+        format!(
+            "{}/openai/deployments/{}/chat/completions?api-version={AZURE_API_VERSION}",
+            self.endpoint, self.deployment
+        )
+    }
+}
+
+impl LlmProvider for AzureOpenAiProvider {
+    fn complete(&self, prompt: &str) -> Result<String, SoukError> {
+        let body = serde_json::json!({
+            "messages": [
+                {"role": "user", "content": prompt}
+            ],
+            "max_tokens": MAX_TOKENS
+        });
+
+        let url = self.url();
+        let response = send_with_retry(&RetryPolicy::default(), || {
+            self.client
+                .post(&url)
+                .header("api-key", &self.api_key)
+                .header("content-type", "application/json")
+                .json(&body)
+                .send()
+        })?;
+
+        let json: serde_json::Value = response
+            .json()
+            .map_err(|e| SoukError::LlmApiError(format!("Failed to parse response: {e}")))?;
+
+        json["choices"][0]["message"]["content"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| SoukError::LlmApiError("No content in response".into()))
+    }
+
+    fn name(&self) -> &str {
+        "azure"
+    }
+
+    fn validate_config(&self) -> Result<(), SoukError> {
+        let url = format!(
+            "{}/openai/models?api-version={AZURE_API_VERSION}",
+            self.endpoint
+        );
+        send_with_retry(&RetryPolicy::default(), || {
+            self.client
+                .get(&url)
+                .header("api-key", &self.api_key)
+                .send()
+        })
+        .map(drop)
+    }
+
+    fn model(&self) -> &str {
+        &self.deployment
+    }
+
+    fn max_tokens(&self) -> Option<u32> {
+        Some(MAX_TOKENS)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -177,12 +497,13 @@ pub struct GeminiProvider {
 impl GeminiProvider {
     /// Create a new Gemini provider.
     ///
-    /// If `model` is `None`, defaults to `gemini-flash-latest`.
-    pub fn new(api_key: String, model: Option<String>) -> Self {
+    /// If `model` is `None`, defaults to `gemini-flash-latest`. If `timeout` is
+    /// `None`, requests time out after [`DEFAULT_TIMEOUT`].
+    pub fn new(api_key: String, model: Option<String>, timeout: Option<Duration>) -> Self {
         Self {
             api_key,
             model: model.unwrap_or_else(|| "gemini-flash-latest".to_string()),
-            client: reqwest::blocking::Client::new(),
+            client: http_client(timeout),
         }
     }
 }
@@ -200,19 +521,13 @@ impl LlmProvider for GeminiProvider {
             ]
         });
 
-        let response = self
-            .client
-            .post(&url)
-            .header("content-type", "application/json")
-            .json(&body)
-            .send()
-            .map_err(|e| SoukError::LlmApiError(format!("Request failed: {e}")))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().unwrap_or_default();
-            return Err(SoukError::LlmApiError(format!("HTTP {status}: {text}")));
-        }
+        let response = send_with_retry(&RetryPolicy::default(), || {
+            self.client
+                .post(&url)
+                .header("content-type", "application/json")
+                .json(&body)
+                .send()
+        })?;
 
         let json: serde_json::Value = response
             .json()
@@ -228,6 +543,100 @@ impl LlmProvider for GeminiProvider {
         "gemini"
     }
 
+    fn validate_config(&self) -> Result<(), SoukError> {
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models?key={}",
+            self.api_key
+        );
+        send_with_retry(&RetryPolicy::default(), || self.client.get(&url).send()).map(drop)
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Ollama
+// ---------------------------------------------------------------------------
+
+/// Ollama server used when `OLLAMA_HOST` is unset.
+pub const DEFAULT_OLLAMA_HOST: &str = "http://localhost:11434";
+
+/// LLM provider for a local Ollama server's generate API.
+///
+/// Needs no API key, so plugin source never leaves the machine.
+pub struct OllamaProvider {
+    url: String,
+    model: String,
+    client: reqwest::blocking::Client,
+}
+
+impl OllamaProvider {
+    /// Create a new Ollama provider.
+    ///
+    /// If `host` is `None`, uses [`DEFAULT_OLLAMA_HOST`]. If `model` is
+    /// `None`, defaults to `llama3.1`. If `timeout` is `None`, requests time
+    /// out after [`DEFAULT_TIMEOUT`].
+    pub fn new(host: Option<String>, model: Option<String>, timeout: Option<Duration>) -> Self {
+        Self {
+            url: ollama_generate_url(host.as_deref().unwrap_or(DEFAULT_OLLAMA_HOST)),
+            model: model.unwrap_or_else(|| "llama3.1".to_string()),
+            client: http_client(timeout),
+        }
+    }
+}
+
+/// The generate endpoint for an `OLLAMA_HOST`-style value.
+///
+/// Accepts a bare `host:port` (as the Ollama CLI does) as well as a full URL,
+/// with or without a trailing slash.
+fn ollama_generate_url(host: &str) -> String {
+    let host = host.trim().trim_end_matches('/');
+    if host.contains("://") {
+        format!("{host}/api/generate")
+    } else {
+        format!("http://{host}/api/generate")
+    }
+}
+
+impl LlmProvider for OllamaProvider {
+    fn complete(&self, prompt: &str) -> Result<String, SoukError> {
+        let body = serde_json::json!({
+            "model": self.model,
+            "prompt": prompt,
+            "stream": false
+        });
+
+        let response = send_with_retry(&RetryPolicy::default(), || {
+            self.client
+                .post(&self.url)
+                .header("content-type", "application/json")
+                .json(&body)
+                .send()
+        })?;
+
+        let json: serde_json::Value = response
+            .json()
+            .map_err(|e| SoukError::LlmApiError(format!("Failed to parse response: {e}")))?;
+
+        json["response"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| SoukError::LlmApiError("No text in response".into()))
+    }
+
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    /// Ollama has no credentials, so this checks the server is reachable by
+    /// listing its local models.
+    fn validate_config(&self) -> Result<(), SoukError> {
+        let url = format!("{}tags", self.url.trim_end_matches("generate"));
+        send_with_retry(&RetryPolicy::default(), || self.client.get(&url).send()).map(drop)
+    }
+
     fn model(&self) -> &str {
         &self.model
     }
@@ -238,50 +647,361 @@ impl LlmProvider for GeminiProvider {
 // ---------------------------------------------------------------------------
 
 /// A mock LLM provider that returns a fixed response. For use in tests.
+///
+/// [`MockProvider::with_failures`] makes it answer with simulated HTTP
+/// statuses first, retried the same way the real providers retry.
 pub struct MockProvider {
+    name: String,
     response: String,
+    failures: Mutex<VecDeque<u16>>,
+    retry: RetryPolicy,
+    calls: AtomicUsize,
+    auth_error: Option<String>,
+    validations: AtomicUsize,
+    max_tokens: Option<u32>,
 }
 
 impl MockProvider {
     /// Create a mock provider that always returns the given response.
     pub fn new(response: impl Into<String>) -> Self {
         Self {
+            name: "mock".to_string(),
             response: response.into(),
+            failures: Mutex::new(VecDeque::new()),
+            retry: RetryPolicy::default(),
+            calls: AtomicUsize::new(0),
+            auth_error: None,
+            validations: AtomicUsize::new(0),
+            max_tokens: None,
+        }
+    }
+
+    /// Fail the next calls with these HTTP statuses, in order, before
+    /// returning the response.
+    pub fn with_failures(self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        Self {
+            failures: Mutex::new(statuses.into_iter().collect()),
+            ..self
+        }
+    }
+
+    /// Use `retry` instead of the default policy.
+    pub fn with_retry_policy(self, retry: RetryPolicy) -> Self {
+        Self { retry, ..self }
+    }
+
+    /// Report `name` instead of "mock" from [`LlmProvider::name`].
+    pub fn with_name(self, name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..self
+        }
+    }
+
+    /// Report `max_tokens` from [`LlmProvider::max_tokens`], as a provider
+    /// that sends one would.
+    pub fn with_max_tokens(self, max_tokens: u32) -> Self {
+        Self {
+            max_tokens: Some(max_tokens),
+            ..self
+        }
+    }
+
+    /// Number of simulated HTTP requests made so far.
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Fail [`LlmProvider::validate_config`] with `error`, as a rejected
+    /// API key would.
+    pub fn with_auth_error(self, error: impl Into<String>) -> Self {
+        Self {
+            auth_error: Some(error.into()),
+            ..self
         }
     }
+
+    /// Number of times [`LlmProvider::validate_config`] has been called.
+    pub fn validations(&self) -> usize {
+        self.validations.load(Ordering::SeqCst)
+    }
 }
 
 impl LlmProvider for MockProvider {
     fn complete(&self, _prompt: &str) -> Result<String, SoukError> {
-        Ok(self.response.clone())
+        run_with_retry(&self.retry, || {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let next = self
+                .failures
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .pop_front();
+            match next {
+                None => Attempt::Done(self.response.clone()),
+                Some(status) => {
+                    let error = format!("HTTP {status}: simulated failure");
+                    if is_retryable_status(status) {
+                        Attempt::Retryable {
+                            error,
+                            retry_after: None,
+                        }
+                    } else {
+                        Attempt::Fatal(error)
+                    }
+                }
+            }
+        })
     }
 
     fn name(&self) -> &str {
-        "mock"
+        &self.name
     }
 
     fn model(&self) -> &str {
         "mock-model"
     }
+
+    fn max_tokens(&self) -> Option<u32> {
+        self.max_tokens
+    }
+
+    fn validate_config(&self) -> Result<(), SoukError> {
+        self.validations.fetch_add(1, Ordering::SeqCst);
+        match &self.auth_error {
+            Some(error) => Err(SoukError::LlmApiError(error.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rate limiting
+// ---------------------------------------------------------------------------
+
+/// A provider wrapper that throttles calls to [`LlmProvider::complete`] with
+/// a token bucket.
+///
+/// The bucket holds up to `requests` tokens and refills continuously at a
+/// rate of `requests` per `period`, so short bursts are allowed while the
+/// long-run rate never exceeds the limit. The bucket is guarded by a mutex,
+/// so a single `RateLimiter` can be shared across concurrent review threads.
+pub struct RateLimiter {
+    inner: Box<dyn LlmProvider>,
+    bucket: Mutex<TokenBucket>,
+}
+
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Wrap `inner` so it makes at most `requests_per_minute` calls per minute.
+    pub fn new(inner: Box<dyn LlmProvider>, requests_per_minute: u32) -> Self {
+        Self::with_period(inner, requests_per_minute, Duration::from_secs(60))
+    }
+
+    /// Wrap `inner` so it makes at most `requests` calls per `period`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests` is zero or `period` is zero.
+    pub fn with_period(inner: Box<dyn LlmProvider>, requests: u32, period: Duration) -> Self {
+        assert!(requests > 0, "rate limit must allow at least one request");
+        assert!(!period.is_zero(), "rate limit period must be non-zero");
+
+        let capacity = f64::from(requests);
+        Self {
+            inner,
+            bucket: Mutex::new(TokenBucket {
+                capacity,
+                tokens: capacity,
+                refill_per_sec: capacity / period.as_secs_f64(),
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Block until a token is available, then consume it.
+    fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+                let now = Instant::now();
+                let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+                bucket.tokens =
+                    (bucket.tokens + elapsed * bucket.refill_per_sec).min(bucket.capacity);
+                bucket.last_refill = now;
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+
+                Duration::from_secs_f64((1.0 - bucket.tokens) / bucket.refill_per_sec)
+            };
+            std::thread::sleep(wait);
+        }
+    }
+}
+
+impl LlmProvider for RateLimiter {
+    fn complete(&self, prompt: &str) -> Result<String, SoukError> {
+        self.acquire();
+        self.inner.complete(prompt)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    fn max_tokens(&self) -> Option<u32> {
+        self.inner.max_tokens()
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        self.inner.temperature()
+    }
+
+    fn validate_config(&self) -> Result<(), SoukError> {
+        self.acquire();
+        self.inner.validate_config()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Fallback
+// ---------------------------------------------------------------------------
+
+/// A provider that switches to a secondary provider when the primary fails.
+///
+/// A prompt the primary cannot complete, after its own retries, is sent to
+/// the fallback once. The switch is sticky, so later prompts skip a primary
+/// that is likely down, and [`name`](LlmProvider::name) and
+/// [`model`](LlmProvider::model) report whichever provider is answering.
+/// Review reports therefore record the provider that produced them.
+pub struct FallbackProvider {
+    primary: Box<dyn LlmProvider>,
+    fallback: Box<dyn LlmProvider>,
+    switched: AtomicBool,
+}
+
+impl FallbackProvider {
+    /// Use `fallback` if `primary` fails.
+    pub fn new(primary: Box<dyn LlmProvider>, fallback: Box<dyn LlmProvider>) -> Self {
+        Self {
+            primary,
+            fallback,
+            switched: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether the primary has failed and the fallback is in use.
+    pub fn switched(&self) -> bool {
+        self.switched.load(Ordering::SeqCst)
+    }
+
+    fn active(&self) -> &dyn LlmProvider {
+        if self.switched() {
+            self.fallback.as_ref()
+        } else {
+            self.primary.as_ref()
+        }
+    }
+}
+
+impl LlmProvider for FallbackProvider {
+    fn complete(&self, prompt: &str) -> Result<String, SoukError> {
+        if !self.switched() {
+            let primary_error = match self.primary.complete(prompt) {
+                Ok(text) => return Ok(text),
+                Err(e) => e,
+            };
+            self.switched.store(true, Ordering::SeqCst);
+            return self.fallback.complete(prompt).map_err(|e| {
+                SoukError::LlmApiError(format!(
+                    "{} failed ({primary_error}); fallback {} also failed ({e})",
+                    self.primary.name(),
+                    self.fallback.name()
+                ))
+            });
+        }
+        self.fallback.complete(prompt)
+    }
+
+    fn name(&self) -> &str {
+        self.active().name()
+    }
+
+    fn model(&self) -> &str {
+        self.active().model()
+    }
+
+    fn max_tokens(&self) -> Option<u32> {
+        self.active().max_tokens()
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        self.active().temperature()
+    }
+
+    /// Accepts the configuration if either provider does. A primary that
+    /// fails the check is switched away from straight away, as if it had
+    /// failed a prompt.
+    fn validate_config(&self) -> Result<(), SoukError> {
+        if self.switched() {
+            return self.fallback.validate_config();
+        }
+        let primary_error = match self.primary.validate_config() {
+            Ok(()) => return Ok(()),
+            Err(e) => e,
+        };
+        self.switched.store(true, Ordering::SeqCst);
+        self.fallback.validate_config().map_err(|e| {
+            SoukError::LlmApiError(format!(
+                "{} failed ({primary_error}); fallback {} also failed ({e})",
+                self.primary.name(),
+                self.fallback.name()
+            ))
+        })
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Auto-detection
 // ---------------------------------------------------------------------------
 
+/// Provider names accepted by [`detect_provider`]'s `provider_override`.
+pub const PROVIDERS: [&str; 5] = ["anthropic", "openai", "azure", "gemini", "ollama"];
+
 /// Detect the best available LLM provider from environment variables.
 ///
-/// Priority order: `ANTHROPIC_API_KEY` > `OPENAI_API_KEY` > `GEMINI_API_KEY`.
+/// Priority order: `ANTHROPIC_API_KEY` > `OPENAI_API_KEY` >
+/// `AZURE_OPENAI_API_KEY` > `GEMINI_API_KEY` > `OLLAMA_HOST`.
+///
+/// Azure also needs `AZURE_OPENAI_ENDPOINT` and a deployment, taken from
+/// `model_override` or `AZURE_OPENAI_DEPLOYMENT`.
+///
+/// Ollama needs no key, so it is only auto-detected when `OLLAMA_HOST` is
+/// set; `--provider ollama` selects it regardless.
 ///
 /// Use `provider_override` (from `--provider` flag) to force a specific
 /// provider. Use `model_override` (from `--model` flag) to override the
-/// default model for the selected provider.
+/// default model for the selected provider. `timeout` (from `--timeout`)
+/// bounds each HTTP request, defaulting to [`DEFAULT_TIMEOUT`].
 ///
 /// Returns `SoukError::NoApiKey` if no provider can be configured, or
 /// `SoukError::Other` if an unknown provider name is given.
 pub fn detect_provider(
     provider_override: Option<&str>,
     model_override: Option<&str>,
+    timeout: Option<Duration>,
 ) -> Result<Box<dyn LlmProvider>, SoukError> {
     let model = model_override.map(|s| s.to_string());
 
@@ -289,15 +1009,22 @@ pub fn detect_provider(
         return match provider_name {
             "anthropic" => {
                 let key = std::env::var("ANTHROPIC_API_KEY").map_err(|_| SoukError::NoApiKey)?;
-                Ok(Box::new(AnthropicProvider::new(key, model)))
+                Ok(Box::new(AnthropicProvider::new(key, model, timeout)))
             }
             "openai" => {
                 let key = std::env::var("OPENAI_API_KEY").map_err(|_| SoukError::NoApiKey)?;
-                Ok(Box::new(OpenAiProvider::new(key, model)))
+                Ok(Box::new(OpenAiProvider::new(key, model, timeout)))
             }
+            "azure" => AzureOpenAiProvider::from_env(model, timeout)
+                .map(|p| Box::new(p) as Box<dyn LlmProvider>)
+                .ok_or(SoukError::NoApiKey),
             "gemini" => {
                 let key = std::env::var("GEMINI_API_KEY").map_err(|_| SoukError::NoApiKey)?;
-                Ok(Box::new(GeminiProvider::new(key, model)))
+                Ok(Box::new(GeminiProvider::new(key, model, timeout)))
+            }
+            "ollama" => {
+                let host = std::env::var("OLLAMA_HOST").ok();
+                Ok(Box::new(OllamaProvider::new(host, model, timeout)))
             }
             _ => Err(SoukError::Other(format!(
                 "Unknown provider: {provider_name}"
@@ -307,13 +1034,19 @@ pub fn detect_provider(
 
     // Auto-detect: try providers in priority order.
     if let Ok(key) = std::env::var("ANTHROPIC_API_KEY") {
-        return Ok(Box::new(AnthropicProvider::new(key, model)));
+        return Ok(Box::new(AnthropicProvider::new(key, model, timeout)));
     }
     if let Ok(key) = std::env::var("OPENAI_API_KEY") {
-        return Ok(Box::new(OpenAiProvider::new(key, model)));
+        return Ok(Box::new(OpenAiProvider::new(key, model, timeout)));
+    }
+    if let Some(provider) = AzureOpenAiProvider::from_env(model.clone(), timeout) {
+        return Ok(Box::new(provider));
     }
     if let Ok(key) = std::env::var("GEMINI_API_KEY") {
-        return Ok(Box::new(GeminiProvider::new(key, model)));
+        return Ok(Box::new(GeminiProvider::new(key, model, timeout)));
+    }
+    if let Ok(host) = std::env::var("OLLAMA_HOST") {
+        return Ok(Box::new(OllamaProvider::new(Some(host), model, timeout)));
     }
 
     Err(SoukError::NoApiKey)
@@ -350,11 +1083,15 @@ mod tests {
             std::env::remove_var("ANTHROPIC_API_KEY");
             std::env::remove_var("OPENAI_API_KEY");
             std::env::remove_var("GEMINI_API_KEY");
+            std::env::remove_var("OLLAMA_HOST");
+            std::env::remove_var("AZURE_OPENAI_API_KEY");
+            std::env::remove_var("AZURE_OPENAI_ENDPOINT");
+            std::env::remove_var("AZURE_OPENAI_DEPLOYMENT");
         }
 
         // No env vars → NoApiKey
         clear_all();
-        match detect_provider(None, None) {
+        match detect_provider(None, None, None) {
             Err(SoukError::NoApiKey) => {}
             Err(other) => panic!("Expected NoApiKey, got: {other:?}"),
             Ok(_) => panic!("Expected error, got Ok"),
@@ -363,40 +1100,75 @@ mod tests {
         // Anthropic key → anthropic provider
         clear_all();
         std::env::set_var("ANTHROPIC_API_KEY", "test-key-123");
-        let provider = detect_provider(None, None).unwrap();
+        let provider = detect_provider(None, None, None).unwrap();
         assert_eq!(provider.name(), "anthropic");
         assert_eq!(provider.model(), "claude-sonnet-4-6");
 
         // OpenAI key → openai provider
         clear_all();
         std::env::set_var("OPENAI_API_KEY", "test-key-456");
-        let provider = detect_provider(None, None).unwrap();
+        let provider = detect_provider(None, None, None).unwrap();
         assert_eq!(provider.name(), "openai");
         assert_eq!(provider.model(), "gpt-5-mini");
 
         // Gemini key → gemini provider
         clear_all();
         std::env::set_var("GEMINI_API_KEY", "test-key-789");
-        let provider = detect_provider(None, None).unwrap();
+        let provider = detect_provider(None, None, None).unwrap();
         assert_eq!(provider.name(), "gemini");
         assert_eq!(provider.model(), "gemini-flash-latest");
 
+        // Azure key, endpoint, and deployment → azure provider
+        clear_all();
+        std::env::set_var("AZURE_OPENAI_API_KEY", "key-az");
+        std::env::set_var("AZURE_OPENAI_ENDPOINT", "https://res.openai.azure.com");
+        assert!(matches!(
+            detect_provider(Some("azure"), None, None),
+            Err(SoukError::NoApiKey)
+        ));
+        std::env::set_var("AZURE_OPENAI_DEPLOYMENT", "gpt4o-prod");
+        let provider = detect_provider(None, None, None).unwrap();
+        assert_eq!(provider.name(), "azure");
+        assert_eq!(provider.model(), "gpt4o-prod");
+        let provider = detect_provider(Some("azure"), Some("gpt4o-dev"), None).unwrap();
+        assert_eq!(provider.model(), "gpt4o-dev");
+
+        // Priority: openai wins over azure
+        std::env::set_var("OPENAI_API_KEY", "key-o");
+        let provider = detect_provider(None, None, None).unwrap();
+        assert_eq!(provider.name(), "openai");
+
+        // Ollama host → ollama provider, but only after keyed providers
+        clear_all();
+        std::env::set_var("OLLAMA_HOST", "127.0.0.1:11434");
+        let provider = detect_provider(None, None, None).unwrap();
+        assert_eq!(provider.name(), "ollama");
+        assert_eq!(provider.model(), "llama3.1");
+        std::env::set_var("GEMINI_API_KEY", "test-key-789");
+        let provider = detect_provider(None, None, None).unwrap();
+        assert_eq!(provider.name(), "gemini");
+
+        // Explicit ollama needs no key or host
+        clear_all();
+        let provider = detect_provider(Some("ollama"), None, None).unwrap();
+        assert_eq!(provider.name(), "ollama");
+
         // Priority: anthropic wins over openai
         clear_all();
         std::env::set_var("ANTHROPIC_API_KEY", "key-a");
         std::env::set_var("OPENAI_API_KEY", "key-o");
-        let provider = detect_provider(None, None).unwrap();
+        let provider = detect_provider(None, None, None).unwrap();
         assert_eq!(provider.name(), "anthropic");
 
         // Explicit override picks the requested provider
         clear_all();
         std::env::set_var("OPENAI_API_KEY", "key-o");
-        let provider = detect_provider(Some("openai"), None).unwrap();
+        let provider = detect_provider(Some("openai"), None, None).unwrap();
         assert_eq!(provider.name(), "openai");
 
         // Explicit override with missing key → NoApiKey
         clear_all();
-        match detect_provider(Some("anthropic"), None) {
+        match detect_provider(Some("anthropic"), None, None) {
             Err(SoukError::NoApiKey) => {}
             Err(other) => panic!("Expected NoApiKey, got: {other:?}"),
             Ok(_) => panic!("Expected error, got Ok"),
@@ -404,7 +1176,7 @@ mod tests {
 
         // Unknown provider → error
         clear_all();
-        match detect_provider(Some("unknown-provider"), None) {
+        match detect_provider(Some("unknown-provider"), None, None) {
             Err(SoukError::Other(msg)) => {
                 assert!(
                     msg.contains("Unknown provider"),
@@ -415,10 +1187,21 @@ mod tests {
             Ok(_) => panic!("Expected error, got Ok"),
         }
 
+        // Every listed provider is recognised, even without credentials
+        for name in PROVIDERS {
+            assert!(
+                !matches!(
+                    detect_provider(Some(name), None, None),
+                    Err(SoukError::Other(_))
+                ),
+                "{name} is not recognised"
+            );
+        }
+
         // Model override
         clear_all();
         std::env::set_var("ANTHROPIC_API_KEY", "key-a");
-        let provider = detect_provider(None, Some("claude-opus-4-20250514")).unwrap();
+        let provider = detect_provider(None, Some("claude-opus-4-20250514"), None).unwrap();
         assert_eq!(provider.name(), "anthropic");
         assert_eq!(provider.model(), "claude-opus-4-20250514");
 
@@ -435,28 +1218,312 @@ mod tests {
 
     #[test]
     fn anthropic_provider_default_model() {
-        let provider = AnthropicProvider::new("key".into(), None);
+        let provider = AnthropicProvider::new("key".into(), None, None);
         assert_eq!(provider.model(), "claude-sonnet-4-6");
         assert_eq!(provider.name(), "anthropic");
     }
 
     #[test]
     fn openai_provider_default_model() {
-        let provider = OpenAiProvider::new("key".into(), None);
+        let provider = OpenAiProvider::new("key".into(), None, None);
         assert_eq!(provider.model(), "gpt-5-mini");
         assert_eq!(provider.name(), "openai");
     }
 
     #[test]
     fn gemini_provider_default_model() {
-        let provider = GeminiProvider::new("key".into(), None);
+        let provider = GeminiProvider::new("key".into(), None, None);
         assert_eq!(provider.model(), "gemini-flash-latest");
         assert_eq!(provider.name(), "gemini");
     }
 
+    #[test]
+    fn rate_limiter_delegates_to_inner_provider() {
+        let limited = RateLimiter::new(Box::new(MockProvider::new("limited")), 60);
+        assert_eq!(limited.name(), "mock");
+        assert_eq!(limited.model(), "mock-model");
+        assert_eq!(limited.complete("anything").unwrap(), "limited");
+    }
+
+    #[test]
+    fn fallback_answers_when_primary_fails() {
+        let primary = MockProvider::new("primary")
+            .with_name("down")
+            .with_failures([503; 4])
+            .with_retry_policy(fast_retry());
+        let fallback = MockProvider::new("secondary").with_name("backup");
+        let provider = FallbackProvider::new(Box::new(primary), Box::new(fallback));
+
+        assert_eq!(provider.name(), "down");
+        assert_eq!(provider.complete("prompt").unwrap(), "secondary");
+        assert!(provider.switched());
+        assert_eq!(provider.name(), "backup");
+        assert_eq!(provider.model(), "mock-model");
+
+        // Once switched, the primary is not tried again.
+        assert_eq!(provider.complete("again").unwrap(), "secondary");
+    }
+
+    #[test]
+    fn fallback_unused_while_primary_succeeds() {
+        let provider = FallbackProvider::new(
+            Box::new(MockProvider::new("primary").with_name("up")),
+            Box::new(MockProvider::new("secondary").with_name("backup")),
+        );
+        assert_eq!(provider.complete("prompt").unwrap(), "primary");
+        assert!(!provider.switched());
+        assert_eq!(provider.name(), "up");
+    }
+
+    #[test]
+    fn fallback_failure_reports_both_errors() {
+        let provider = FallbackProvider::new(
+            Box::new(
+                MockProvider::new("a")
+                    .with_name("down")
+                    .with_failures([401]),
+            ),
+            Box::new(
+                MockProvider::new("b")
+                    .with_name("backup")
+                    .with_failures([403]),
+            ),
+        );
+        let err = provider.complete("prompt").unwrap_err().to_string();
+        assert!(
+            err.contains("down failed") && err.contains("HTTP 401"),
+            "{err}"
+        );
+        assert!(
+            err.contains("fallback backup also failed") && err.contains("HTTP 403"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn preflight_calls_validate_config() {
+        let provider = MockProvider::new("ok");
+        preflight(&provider).unwrap();
+        assert_eq!(provider.validations(), 1);
+        assert_eq!(provider.calls(), 0);
+
+        let rejected = MockProvider::new("ok").with_auth_error("HTTP 401: invalid x-api-key");
+        let err = preflight(&rejected).unwrap_err().to_string();
+        assert!(err.contains("mock auth check failed"), "{err}");
+        assert!(err.contains("HTTP 401"), "{err}");
+        assert_eq!(rejected.validations(), 1);
+    }
+
+    #[test]
+    fn wrappers_delegate_validate_config() {
+        let limited = RateLimiter::new(
+            Box::new(MockProvider::new("ok").with_auth_error("bad key")),
+            60,
+        );
+        assert!(preflight(&limited).is_err());
+
+        let provider = FallbackProvider::new(
+            Box::new(
+                MockProvider::new("a")
+                    .with_name("down")
+                    .with_auth_error("bad key"),
+            ),
+            Box::new(MockProvider::new("b").with_name("backup")),
+        );
+        preflight(&provider).unwrap();
+        assert!(provider.switched());
+        assert_eq!(provider.complete("prompt").unwrap(), "b");
+    }
+
+    #[test]
+    fn rate_limiter_allows_initial_burst() {
+        let limited = RateLimiter::with_period(
+            Box::new(MockProvider::new("ok")),
+            5,
+            Duration::from_secs(60),
+        );
+
+        let start = Instant::now();
+        for _ in 0..5 {
+            limited.complete("prompt").unwrap();
+        }
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn rate_limiter_spaces_requests_beyond_limit() {
+        // 2 requests per 200ms: the first 2 calls are immediate, the next 2
+        // must each wait ~100ms for a token to refill.
+        let limited = RateLimiter::with_period(
+            Box::new(MockProvider::new("ok")),
+            2,
+            Duration::from_millis(200),
+        );
+
+        let start = Instant::now();
+        for _ in 0..4 {
+            limited.complete("prompt").unwrap();
+        }
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= Duration::from_millis(180),
+            "expected calls to be throttled, took {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn rate_limiter_is_shared_across_threads() {
+        let limited = std::sync::Arc::new(RateLimiter::with_period(
+            Box::new(MockProvider::new("ok")),
+            2,
+            Duration::from_millis(200),
+        ));
+
+        let start = Instant::now();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let limited = limited.clone();
+                std::thread::spawn(move || limited.complete("prompt").unwrap())
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(180));
+    }
+
     #[test]
     fn provider_custom_model() {
-        let provider = AnthropicProvider::new("key".into(), Some("custom-model".into()));
+        let provider = AnthropicProvider::new("key".into(), Some("custom-model".into()), None);
         assert_eq!(provider.model(), "custom-model");
     }
+
+    fn fast_retry() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+        }
+    }
+
+    #[test]
+    fn retries_transient_failures_then_succeeds() {
+        let provider = MockProvider::new("ok")
+            .with_failures([429, 503])
+            .with_retry_policy(fast_retry());
+        assert_eq!(provider.complete("prompt").unwrap(), "ok");
+        assert_eq!(provider.calls(), 3);
+    }
+
+    #[test]
+    fn gives_up_after_max_retries_with_attempt_count() {
+        let provider = MockProvider::new("ok")
+            .with_failures([500, 502, 503, 504])
+            .with_retry_policy(fast_retry());
+        match provider.complete("prompt") {
+            Err(SoukError::LlmApiError(msg)) => {
+                assert!(msg.contains("HTTP 504"), "{msg}");
+                assert!(msg.contains("after 4 attempts"), "{msg}");
+            }
+            other => panic!("Expected LlmApiError, got: {other:?}"),
+        }
+        assert_eq!(provider.calls(), 4);
+    }
+
+    #[test]
+    fn non_retryable_status_fails_immediately() {
+        let provider = MockProvider::new("ok")
+            .with_failures([401])
+            .with_retry_policy(fast_retry());
+        let err = provider.complete("prompt").unwrap_err();
+        assert!(err.to_string().contains("after 1 attempt)"), "{err}");
+        assert_eq!(provider.calls(), 1);
+    }
+
+    #[test]
+    fn retry_after_header_is_parsed_as_seconds() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, "2".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(2)));
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn retry_after_is_clamped_to_max_delay() {
+        let policy = RetryPolicy::default();
+        let hour = Some(Duration::from_secs(3600));
+        assert_eq!(policy.delay(1, hour), policy.max_delay);
+        let short = Some(Duration::from_secs(2));
+        assert_eq!(policy.delay(1, short), Duration::from_secs(2));
+        assert!(policy.delay(40, None) <= policy.max_delay);
+    }
+
+    #[test]
+    fn backoff_doubles_within_jitter_bounds() {
+        let policy = RetryPolicy::default();
+        for retry in 1..=3 {
+            let full = Duration::from_millis(500 * 2u64.pow(retry - 1));
+            let delay = policy.backoff(retry);
+            assert!(delay >= full / 2 && delay <= full, "{delay:?}");
+        }
+    }
+
+    #[test]
+    fn timeout_is_reported_plainly() {
+        // Nothing answers on a listener that never accepts, so the request
+        // hangs until the client timeout fires.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let client = http_client(Some(Duration::from_millis(100)));
+
+        let err = send_with_retry(&RetryPolicy::default(), || client.get(&url).send())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("request timed out"), "{err}");
+    }
+
+    #[test]
+    fn ollama_generate_url_accepts_host_forms() {
+        assert_eq!(
+            ollama_generate_url(DEFAULT_OLLAMA_HOST),
+            "http://localhost:11434/api/generate"
+        );
+        assert_eq!(
+            ollama_generate_url("127.0.0.1:11434"),
+            "http://127.0.0.1:11434/api/generate"
+        );
+        assert_eq!(
+            ollama_generate_url("https://ollama.internal/"),
+            "https://ollama.internal/api/generate"
+        );
+    }
+
+    #[test]
+    fn ollama_provider_default_model() {
+        let provider = OllamaProvider::new(None, None, None);
+        assert_eq!(provider.model(), "llama3.1");
+        assert_eq!(provider.name(), "ollama");
+    }
+
+    #[test]
+    fn azure_url_routes_by_deployment() {
+        let provider = AzureOpenAiProvider::new(
+            "key".into(),
+            "https://res.openai.azure.com/".into(),
+            "my-deploy".into(),
+            None,
+        );
+        assert_eq!(
+            provider.url(),
+            "https://res.openai.azure.com/openai/deployments/my-deploy/chat/completions\
+             ?api-version=2024-02-01"
+        );
+        assert_eq!(provider.name(), "azure");
+        assert_eq!(provider.model(), "my-deploy");
+    }
 }
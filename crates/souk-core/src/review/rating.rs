@@ -0,0 +1,179 @@
+//! Extracting the overall 1-10 rating from review text.
+//!
+//! Every review prompt asks for an "Overall Rating (1-10)", but models phrase
+//! the answer differently: `Rating: 8/10`, `**Overall Rating:** 8`,
+//! `7 out of 10`, or a heading followed by the score on its own line.
+//! [`parse_rating`] accepts all of these.
+
+/// Find the overall rating in `text`.
+///
+/// Lines mentioning "overall rating" are tried first, then any line
+/// mentioning "rating" as a word (not inside "operating" or "generating"),
+/// then the first `N/10` anywhere. On a rating line the
+/// score is the first `N/10` after the keyword, or else the first number
+/// outside parentheses, so the `(1-10)` echoed from the prompt is ignored.
+/// A rating heading with no score is followed to the next non-empty line.
+/// Fractional scores are rounded down. Returns `None` if no score between 0
+/// and 10 is found.
+pub fn parse_rating(text: &str) -> Option<u8> {
+    let lines: Vec<String> = text.lines().map(str::to_lowercase).collect();
+
+    for keyword in ["overall rating", "rating"] {
+        for (i, line) in lines.iter().enumerate() {
+            let Some(pos) = find_word(line, keyword) else {
+                continue;
+            };
+            let rest = &line[pos + keyword.len()..];
+            if let Some(rating) = out_of_ten(rest).or_else(|| first_bare_number(rest)) {
+                return Some(rating);
+            }
+            let next = lines[i + 1..].iter().find(|l| !l.trim().is_empty());
+            if let Some(rating) = next.and_then(|l| out_of_ten(l).or_else(|| lone_number(l))) {
+                return Some(rating);
+            }
+        }
+    }
+
+    out_of_ten(&lines.join("\n"))
+}
+
+/// The byte offset of the first occurrence of `word` in `line` that is not
+/// part of a longer word.
+fn find_word(line: &str, word: &str) -> Option<usize> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    line.match_indices(word).map(|(pos, _)| pos).find(|&pos| {
+        let before = line[..pos].chars().next_back();
+        let after = line[pos + word.len()..].chars().next();
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    })
+}
+
+/// A number found in text, with the byte offset just past it.
+struct Number {
+    value: f64,
+    end: usize,
+    in_parens: bool,
+}
+
+fn scan_numbers(s: &str) -> Vec<Number> {
+    let bytes = s.as_bytes();
+    let mut numbers = Vec::new();
+    let mut depth = 0u32;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            b'0'..=b'9' => {
+                let start = i;
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_digit()
+                        || (bytes[i] == b'.' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)))
+                {
+                    i += 1;
+                }
+                if let Ok(value) = s[start..i].parse() {
+                    numbers.push(Number {
+                        value,
+                        end: i,
+                        in_parens: depth > 0,
+                    });
+                }
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    numbers
+}
+
+/// The first `N/10` or `N out of 10` in `s`, tolerating spaces and Markdown
+/// emphasis around the slash.
+fn out_of_ten(s: &str) -> Option<u8> {
+    const FILLER: [char; 2] = [' ', '*'];
+    scan_numbers(s).into_iter().find_map(|n| {
+        let rest = s[n.end..].trim_start_matches(FILLER);
+        let rest = rest
+            .strip_prefix('/')
+            .or_else(|| rest.strip_prefix("out of"))?;
+        let after = rest.trim_start_matches(FILLER).strip_prefix("10")?;
+        if after.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        to_score(n.value)
+    })
+}
+
+/// The first number in `s` that is not inside parentheses.
+fn first_bare_number(s: &str) -> Option<u8> {
+    scan_numbers(s)
+        .into_iter()
+        .find(|n| !n.in_parens)
+        .and_then(|n| to_score(n.value))
+}
+
+/// A line holding nothing but a number, such as `**8**`.
+fn lone_number(line: &str) -> Option<u8> {
+    let trimmed = line.trim_matches(|c: char| c.is_whitespace() || c == '*' || c == '#');
+    trimmed.parse::<f64>().ok().and_then(to_score)
+}
+
+fn to_score(value: f64) -> Option<u8> {
+    (0.0..=10.0).contains(&value).then_some(value as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slash_ten_forms() {
+        assert_eq!(parse_rating("Rating: 8/10"), Some(8));
+        assert_eq!(parse_rating("Overall: 8 / 10"), Some(8));
+        assert_eq!(parse_rating("I'd give it 7 out of 10."), Some(7));
+        assert_eq!(parse_rating("**Overall Rating:** **9**/10"), Some(9));
+    }
+
+    #[test]
+    fn bare_number_after_keyword() {
+        assert_eq!(parse_rating("Overall Rating: 8"), Some(8));
+        assert_eq!(parse_rating("## Overall Rating (1-10): 6"), Some(6));
+    }
+
+    #[test]
+    fn heading_with_score_on_next_line() {
+        let text = "## 7. Overall Rating (1-10)\n\n**8/10** - solid work.";
+        assert_eq!(parse_rating(text), Some(8));
+        assert_eq!(parse_rating("### Overall Rating\n\n**7**\n"), Some(7));
+    }
+
+    #[test]
+    fn overall_rating_wins_over_section_ratings() {
+        let text = "Security rating: 4/10\nDocs: 6/10\n\nOverall Rating: 7/10";
+        assert_eq!(parse_rating(text), Some(7));
+    }
+
+    #[test]
+    fn rating_must_be_a_whole_word() {
+        let text = "Operating on 3 files, generating 2 reports.\nRating: 8/10";
+        assert_eq!(parse_rating(text), Some(8));
+        assert_eq!(parse_rating("Operating system support: 5 platforms"), None);
+        assert_eq!(parse_rating("**Rating**: 6"), Some(6));
+    }
+
+    #[test]
+    fn fractional_scores_round_down() {
+        assert_eq!(parse_rating("Overall rating: 7.5/10"), Some(7));
+    }
+
+    #[test]
+    fn out_of_range_and_missing() {
+        assert_eq!(parse_rating("Looks good!"), None);
+        assert_eq!(parse_rating("Rating: 85/100"), None);
+        assert_eq!(
+            parse_rating("Overall Rating (1-10)\n\nThe plugin has 3 skills."),
+            None
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Persisting review reports to disk.
+//!
+//! Each review can be saved as a Markdown report for people, a JSON document
+//! for tooling, or both, as selected by a [`ReportFormat`]. Both files share
+//! a stem in the output directory, e.g. `my-plugin-review-report.md` and
+//! `my-plugin-review-report.json`.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::SoukError;
+
+/// Which report files a review writes to its output directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum ReportFormat {
+    /// Markdown only.
+    Markdown,
+    /// JSON only.
+    Json,
+    /// Markdown and JSON.
+    #[default]
+    Both,
+}
+
+impl ReportFormat {
+    /// Parse `markdown`, `json`, or `both`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoukError::Other`] for any other value.
+    pub fn parse(spec: &str) -> Result<Self, SoukError> {
+        match spec.trim().to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            "both" => Ok(Self::Both),
+            other => Err(SoukError::Other(format!(
+                "Unknown report format '{other}' (expected markdown, json, or both)"
+            ))),
+        }
+    }
+
+    fn markdown(self) -> bool {
+        matches!(self, Self::Markdown | Self::Both)
+    }
+
+    fn json(self) -> bool {
+        matches!(self, Self::Json | Self::Both)
+    }
+}
+
+impl TryFrom<String> for ReportFormat {
+    type Error = SoukError;
+
+    fn try_from(spec: String) -> Result<Self, Self::Error> {
+        Self::parse(&spec)
+    }
+}
+
+/// Where and how a review saves its report.
+#[derive(Debug, Clone, Copy)]
+pub struct ReportOutput<'a> {
+    /// Directory the report files are written to; created if missing.
+    pub dir: &'a Path,
+    /// Which report files to write.
+    pub format: ReportFormat,
+}
+
+impl<'a> ReportOutput<'a> {
+    /// Save reports to `dir` in the default format (Markdown and JSON).
+    pub fn new(dir: &'a Path) -> Self {
+        Self {
+            dir,
+            format: ReportFormat::default(),
+        }
+    }
+}
+
+/// Write `<stem>.md` and/or `<stem>.json` into the output directory
+/// according to its format.
+pub(crate) fn write_report(
+    output: ReportOutput<'_>,
+    stem: &str,
+    markdown: &str,
+    json: &impl Serialize,
+) -> Result<(), SoukError> {
+    std::fs::create_dir_all(output.dir)?;
+    if output.format.markdown() {
+        std::fs::write(output.dir.join(format!("{stem}.md")), markdown)?;
+    }
+    if output.format.json() {
+        let content = serde_json::to_string_pretty(json)?;
+        std::fs::write(
+            output.dir.join(format!("{stem}.json")),
+            format!("{content}\n"),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(
+            ReportFormat::parse("markdown").unwrap(),
+            ReportFormat::Markdown
+        );
+        assert_eq!(ReportFormat::parse("JSON").unwrap(), ReportFormat::Json);
+        assert_eq!(ReportFormat::parse("both").unwrap(), ReportFormat::Both);
+        assert!(ReportFormat::parse("yaml").is_err());
+    }
+
+    #[test]
+    fn writes_only_selected_files() {
+        let tmp = TempDir::new().unwrap();
+        let json = serde_json::json!({"ok": true});
+
+        let output = |format| ReportOutput {
+            dir: tmp.path(),
+            format,
+        };
+
+        write_report(output(ReportFormat::Markdown), "a", "# A", &json).unwrap();
+        write_report(output(ReportFormat::Json), "b", "# B", &json).unwrap();
+        write_report(output(ReportFormat::Both), "c", "# C", &json).unwrap();
+
+        assert!(tmp.path().join("a.md").is_file());
+        assert!(!tmp.path().join("a.json").exists());
+        assert!(!tmp.path().join("b.md").exists());
+        assert!(tmp.path().join("b.json").is_file());
+        assert!(tmp.path().join("c.md").is_file());
+        assert!(tmp.path().join("c.json").is_file());
+    }
+}
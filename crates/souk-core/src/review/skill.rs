@@ -6,13 +6,19 @@
 
 use std::path::Path;
 
+use serde::Serialize;
+
 use crate::error::SoukError;
 use crate::resolution::skill::enumerate_skills;
+use crate::review::prompt::{with_rubric, PromptBuilder};
 use crate::review::provider::LlmProvider;
+use crate::review::rating::parse_rating;
+use crate::review::report::{write_report, ReportOutput};
 use crate::types::skill::SkillMetadata;
+use crate::util::{current_timestamp, parallel_map_until};
 
 /// The result of reviewing a single skill via an LLM provider.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SkillReviewReport {
     /// The human-readable skill name (from SKILL.md frontmatter or directory name).
     pub skill_name: String,
@@ -24,6 +30,11 @@ pub struct SkillReviewReport {
     pub model_name: String,
     /// The full review text returned by the LLM.
     pub review_text: String,
+    /// When the review was produced, as an RFC 3339 UTC timestamp.
+    pub timestamp: String,
+    /// Overall 1-10 rating found in `review_text`, if any; see
+    /// [`parse_rating`].
+    pub rating: Option<u8>,
 }
 
 /// Review selected skills in a plugin using an LLM provider.
@@ -40,10 +51,19 @@ pub struct SkillReviewReport {
 ///
 /// # Output
 ///
-/// When `output_dir` is provided, a Markdown report file is written for each
-/// reviewed skill at `<output_dir>/<skill-dir-name>-skill-review.md`.
+/// With an `output`, a report is written for each reviewed skill at
+/// `<dir>/<skill-dir-name>-skill-review.md` and/or `.json`.
+///
+/// A `rubric` is appended to every skill's prompt.
+///
+/// Returns a [`SkillReviewReport`] for every successfully reviewed skill, in
+/// the order the skills were selected.
 ///
-/// Returns a [`SkillReviewReport`] for every successfully reviewed skill.
+/// # Concurrency
+///
+/// Up to `concurrency` skills are reviewed at once on scoped threads sharing
+/// `provider`. A `concurrency` of 0 is treated as 1. After the first failure
+/// no further skills are started.
 ///
 /// # Errors
 ///
@@ -51,14 +71,71 @@ pub struct SkillReviewReport {
 /// - [`SoukError::Other`] if no skills are specified and `all` is `false`.
 /// - [`SoukError::SkillNotFound`] if a requested skill name cannot be resolved.
 /// - [`SoukError::Io`] if SKILL.md cannot be read or reports cannot be written.
-/// - [`SoukError::LlmApiError`] if the LLM provider call fails.
+/// - [`SoukError::LlmApiError`] if the LLM provider call fails, prefixed with
+///   the name of the skill being reviewed.
 pub fn review_skills(
     plugin_path: &Path,
     skill_names: &[String],
     all: bool,
     provider: &dyn LlmProvider,
-    output_dir: Option<&Path>,
+    output: Option<ReportOutput<'_>>,
+    concurrency: usize,
+    rubric: Option<&str>,
 ) -> Result<Vec<SkillReviewReport>, SoukError> {
+    let skills_to_review = select_skills(plugin_path, skill_names, all)?;
+
+    let results = parallel_map_until(
+        &skills_to_review,
+        concurrency,
+        |skill| review_skill(skill, provider, output, rubric),
+        Result::is_err,
+    );
+
+    // Skills never started because of an earlier failure are skipped; the
+    // failure itself is reported below.
+    let mut reports = Vec::with_capacity(results.len());
+    for result in results {
+        match result {
+            Some(Ok(report)) => reports.push(report),
+            Some(Err(e)) => return Err(e),
+            None => {}
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Build the review prompt for each selected skill without contacting a
+/// provider, returning `(skill name, prompt)` pairs in review order.
+///
+/// Skills are selected and a `rubric` appended exactly as in [`review_skills`].
+///
+/// # Errors
+///
+/// The selection errors of [`review_skills`], or [`SoukError::Io`] if a
+/// SKILL.md cannot be read.
+pub fn read_skill_review_prompts(
+    plugin_path: &Path,
+    skill_names: &[String],
+    all: bool,
+    rubric: Option<&str>,
+) -> Result<Vec<(String, String)>, SoukError> {
+    select_skills(plugin_path, skill_names, all)?
+        .into_iter()
+        .map(|skill| {
+            let content = std::fs::read_to_string(skill.path.join("SKILL.md"))?;
+            let prompt = build_skill_review_prompt(&skill.display_name, &content);
+            Ok((skill.display_name, with_rubric(prompt, rubric)))
+        })
+        .collect()
+}
+
+/// Resolve which skills [`review_skills`] should review.
+fn select_skills(
+    plugin_path: &Path,
+    skill_names: &[String],
+    all: bool,
+) -> Result<Vec<SkillMetadata>, SoukError> {
     let available = enumerate_skills(plugin_path);
 
     if available.is_empty() {
@@ -66,8 +143,8 @@ pub fn review_skills(
     }
 
     // Determine which skills to review.
-    let skills_to_review: Vec<&SkillMetadata> = if all {
-        available.iter().collect()
+    if all {
+        Ok(available)
     } else if skill_names.is_empty() {
         // Return available skills for the caller to handle interactive selection.
         let listing = available
@@ -76,9 +153,9 @@ pub fn review_skills(
             .map(|(i, s)| format!("  {}. {} ({})", i + 1, s.display_name, s.dir_name))
             .collect::<Vec<_>>()
             .join("\n");
-        return Err(SoukError::Other(format!(
+        Err(SoukError::Other(format!(
             "No skills specified. Available skills:\n{listing}"
-        )));
+        )))
     } else {
         let mut resolved = Vec::new();
         for name in skill_names {
@@ -86,7 +163,7 @@ pub fn review_skills(
                 .iter()
                 .find(|s| s.dir_name == *name || s.display_name == *name)
             {
-                resolved.push(skill);
+                resolved.push(skill.clone());
             } else {
                 let plugin_name = plugin_path
                     .file_name()
@@ -98,61 +175,75 @@ pub fn review_skills(
                 });
             }
         }
-        resolved
-    };
+        Ok(resolved)
+    }
+}
 
-    let mut reports = Vec::new();
-
-    for skill in &skills_to_review {
-        let skill_md_path = skill.path.join("SKILL.md");
-        let skill_content = std::fs::read_to_string(&skill_md_path).map_err(SoukError::Io)?;
-
-        let prompt = build_skill_review_prompt(&skill.display_name, &skill_content);
-        let review_text = provider.complete(&prompt)?;
-
-        let report = SkillReviewReport {
-            skill_name: skill.display_name.clone(),
-            skill_dir: skill.dir_name.clone(),
-            provider_name: provider.name().to_string(),
-            model_name: provider.model().to_string(),
-            review_text: review_text.clone(),
-        };
-
-        if let Some(dir) = output_dir {
-            std::fs::create_dir_all(dir)?;
-            let report_path = dir.join(format!("{}-skill-review.md", skill.dir_name));
-            let content = format!(
-                "# Skill Review: {}\n\n\
-                 **Provider:** {} ({})\n\n\
-                 ---\n\n\
-                 {}\n",
-                skill.display_name, report.provider_name, report.model_name, review_text,
-            );
-            std::fs::write(&report_path, content)?;
+/// Review one skill and, with an `output`, write its report files.
+fn review_skill(
+    skill: &SkillMetadata,
+    provider: &dyn LlmProvider,
+    output: Option<ReportOutput<'_>>,
+    rubric: Option<&str>,
+) -> Result<SkillReviewReport, SoukError> {
+    let skill_md_path = skill.path.join("SKILL.md");
+    let skill_content = std::fs::read_to_string(&skill_md_path).map_err(SoukError::Io)?;
+
+    let prompt = with_rubric(
+        build_skill_review_prompt(&skill.display_name, &skill_content),
+        rubric,
+    );
+    let review_text = provider.complete(&prompt).map_err(|e| match e {
+        SoukError::LlmApiError(msg) => {
+            SoukError::LlmApiError(format!("skill '{}': {msg}", skill.display_name))
         }
+        other => other,
+    })?;
+
+    let report = SkillReviewReport {
+        skill_name: skill.display_name.clone(),
+        skill_dir: skill.dir_name.clone(),
+        provider_name: provider.name().to_string(),
+        model_name: provider.model().to_string(),
+        review_text: review_text.clone(),
+        timestamp: current_timestamp(),
+        rating: parse_rating(&review_text),
+    };
 
-        reports.push(report);
+    if let Some(output) = output {
+        let content = format!(
+            "# Skill Review: {}\n\n\
+             **Provider:** {} ({})\n\n\
+             ---\n\n\
+             {}\n",
+            skill.display_name, report.provider_name, report.model_name, review_text,
+        );
+        write_report(
+            output,
+            &format!("{}-skill-review", skill.dir_name),
+            &content,
+            &report,
+        )?;
     }
 
-    Ok(reports)
+    Ok(report)
 }
 
 /// Build the LLM prompt for reviewing a single skill.
 fn build_skill_review_prompt(skill_name: &str, skill_content: &str) -> String {
-    format!(
+    PromptBuilder::new(&format!(
         "You are a senior code reviewer. Review this Claude Code skill named \
-         '{skill_name}' for quality, clarity, and effectiveness.\n\n\
-         ## SKILL.md Content\n\
-         ```markdown\n\
-         {skill_content}\n\
-         ```\n\n\
-         Please provide:\n\
-         1. Overall assessment of the skill's purpose and clarity\n\
-         2. Quality of instructions and examples\n\
-         3. Potential issues or ambiguities\n\
-         4. Suggestions for improvement\n\
-         5. Rating (1-10)\n"
-    )
+         '{skill_name}' for quality, clarity, and effectiveness."
+    ))
+    .add_code_section("SKILL.md Content", "markdown", skill_content)
+    .add_request_list(&[
+        "Overall assessment of the skill's purpose and clarity",
+        "Quality of instructions and examples",
+        "Potential issues or ambiguities",
+        "Suggestions for improvement",
+        "Rating (1-10)",
+    ])
+    .build()
 }
 
 #[cfg(test)]
@@ -200,7 +291,7 @@ mod tests {
         let plugin = setup_plugin_with_skills(&tmp);
         let provider = MockProvider::new("Looks good! Rating: 8/10");
 
-        let reports = review_skills(&plugin, &[], true, &provider, None).unwrap();
+        let reports = review_skills(&plugin, &[], true, &provider, None, 1, None).unwrap();
 
         assert_eq!(reports.len(), 2);
 
@@ -210,6 +301,7 @@ mod tests {
         assert_eq!(reports[0].provider_name, "mock");
         assert_eq!(reports[0].model_name, "mock-model");
         assert_eq!(reports[0].review_text, "Looks good! Rating: 8/10");
+        assert_eq!(reports[0].rating, Some(8));
 
         assert_eq!(reports[1].skill_dir, "git-commit");
         assert_eq!(reports[1].skill_name, "commit-message"); // from frontmatter
@@ -222,7 +314,7 @@ mod tests {
         let provider = MockProvider::new("Excellent skill.");
 
         let names = vec!["code-review".to_string()];
-        let reports = review_skills(&plugin, &names, false, &provider, None).unwrap();
+        let reports = review_skills(&plugin, &names, false, &provider, None, 1, None).unwrap();
 
         assert_eq!(reports.len(), 1);
         assert_eq!(reports[0].skill_dir, "code-review");
@@ -236,7 +328,7 @@ mod tests {
         let provider = MockProvider::new("Great commit skill.");
 
         let names = vec!["commit-message".to_string()];
-        let reports = review_skills(&plugin, &names, false, &provider, None).unwrap();
+        let reports = review_skills(&plugin, &names, false, &provider, None, 1, None).unwrap();
 
         assert_eq!(reports.len(), 1);
         assert_eq!(reports[0].skill_dir, "git-commit");
@@ -250,7 +342,7 @@ mod tests {
         let provider = MockProvider::new("ignored");
 
         let names = vec!["nonexistent".to_string()];
-        let result = review_skills(&plugin, &names, false, &provider, None);
+        let result = review_skills(&plugin, &names, false, &provider, None, 1, None);
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -269,7 +361,16 @@ mod tests {
         let output_dir = tmp.path().join("reviews");
         let provider = MockProvider::new("Review output here.");
 
-        let reports = review_skills(&plugin, &[], true, &provider, Some(&output_dir)).unwrap();
+        let reports = review_skills(
+            &plugin,
+            &[],
+            true,
+            &provider,
+            Some(ReportOutput::new(&output_dir)),
+            1,
+            None,
+        )
+        .unwrap();
 
         assert_eq!(reports.len(), 2);
 
@@ -287,6 +388,14 @@ mod tests {
 
         let content2 = std::fs::read_to_string(&report2).unwrap();
         assert!(content2.contains("# Skill Review: commit-message"));
+
+        let json =
+            std::fs::read_to_string(output_dir.join("git-commit-skill-review.json")).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["skill_name"], "commit-message");
+        assert_eq!(json["skill_dir"], "git-commit");
+        assert_eq!(json["review_text"], "Review output here.");
+        assert!(json["timestamp"].is_string());
     }
 
     #[test]
@@ -295,7 +404,7 @@ mod tests {
         let plugin = setup_plugin_without_skills(&tmp);
         let provider = MockProvider::new("ignored");
 
-        let result = review_skills(&plugin, &[], true, &provider, None);
+        let result = review_skills(&plugin, &[], true, &provider, None, 1, None);
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -312,7 +421,7 @@ mod tests {
         let plugin = setup_plugin_with_skills(&tmp);
         let provider = MockProvider::new("ignored");
 
-        let result = review_skills(&plugin, &[], false, &provider, None);
+        let result = review_skills(&plugin, &[], false, &provider, None, 1, None);
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -341,7 +450,7 @@ mod tests {
         let provider = MockProvider::new("Reviewed.");
 
         let names = vec!["code-review".to_string(), "commit-message".to_string()];
-        let reports = review_skills(&plugin, &names, false, &provider, None).unwrap();
+        let reports = review_skills(&plugin, &names, false, &provider, None, 1, None).unwrap();
 
         assert_eq!(reports.len(), 2);
         assert_eq!(reports[0].skill_dir, "code-review");
@@ -356,4 +465,106 @@ mod tests {
         assert!(prompt.contains("Does things."));
         assert!(prompt.contains("Rating (1-10)"));
     }
+
+    /// Provider that takes a while to answer, to observe parallelism.
+    struct SlowProvider;
+
+    impl LlmProvider for SlowProvider {
+        fn complete(&self, _prompt: &str) -> Result<String, SoukError> {
+            std::thread::sleep(std::time::Duration::from_millis(150));
+            Ok("slow".into())
+        }
+
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn model(&self) -> &str {
+            "slow-model"
+        }
+    }
+
+    fn setup_plugin_with_n_skills(tmp: &TempDir, n: usize) -> PathBuf {
+        let plugin = tmp.path().join("many");
+        for i in 0..n {
+            let dir = plugin.join("skills").join(format!("skill-{i:02}"));
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("SKILL.md"), format!("# Skill {i}")).unwrap();
+        }
+        plugin
+    }
+
+    #[test]
+    fn concurrent_review_keeps_sorted_order() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = setup_plugin_with_n_skills(&tmp, 6);
+        let output_dir = tmp.path().join("reviews");
+
+        let start = std::time::Instant::now();
+        let reports = review_skills(
+            &plugin,
+            &[],
+            true,
+            &SlowProvider,
+            Some(ReportOutput::new(&output_dir)),
+            6,
+            None,
+        )
+        .unwrap();
+
+        assert!(start.elapsed() < std::time::Duration::from_millis(600));
+        let dirs: Vec<_> = reports.iter().map(|r| r.skill_dir.as_str()).collect();
+        assert_eq!(
+            dirs,
+            ["skill-00", "skill-01", "skill-02", "skill-03", "skill-04", "skill-05"]
+        );
+        for dir in dirs {
+            assert!(output_dir.join(format!("{dir}-skill-review.md")).is_file());
+        }
+    }
+
+    #[test]
+    fn concurrent_review_failure_names_skill() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = setup_plugin_with_n_skills(&tmp, 1);
+        let provider = MockProvider::new("ok").with_failures([401]);
+
+        match review_skills(&plugin, &[], true, &provider, None, 4, None) {
+            Err(SoukError::LlmApiError(msg)) => {
+                assert!(msg.contains("skill 'skill-00'"), "{msg}");
+            }
+            other => panic!("Expected LlmApiError, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn read_prompts_match_selection_without_provider() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = setup_plugin_with_skills(&tmp);
+
+        let prompts =
+            read_skill_review_prompts(&plugin, &["commit-message".to_string()], false, None)
+                .unwrap();
+
+        assert_eq!(prompts.len(), 1);
+        assert_eq!(prompts[0].0, "commit-message");
+        assert!(prompts[0]
+            .1
+            .contains("Generate clear, conventional commit messages."));
+    }
+
+    #[test]
+    fn rubric_text_appears_in_skill_prompts() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = setup_plugin_with_skills(&tmp);
+
+        let prompts =
+            read_skill_review_prompts(&plugin, &[], true, Some("- Names are kebab-case")).unwrap();
+
+        assert_eq!(prompts.len(), 2);
+        for (_, prompt) in &prompts {
+            assert!(prompt.contains("## Review Rubric"));
+            assert!(prompt.contains("- Names are kebab-case"));
+        }
+    }
 }
@@ -0,0 +1,182 @@
+//! Aggregate numbers about a marketplace, for `souk stats`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::discovery::MarketplaceConfig;
+use crate::resolution::{enumerate_skills, plugin_path_to_source, resolve_source};
+use crate::validation::dependencies::{read_extends_at, DEPENDENCY_SECTIONS};
+
+/// Summary counts over a marketplace's active plugins.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MarketplaceStats {
+    /// Active plugin entries.
+    pub plugins: usize,
+    /// Active plugins stored under pluginRoot.
+    pub internal: usize,
+    /// Active plugins stored outside pluginRoot.
+    pub external: usize,
+    /// Archived plugin entries, which are not counted anywhere else.
+    pub archived: usize,
+    /// Skills across all active plugins.
+    pub skills: usize,
+    /// Active plugins whose extends-plugin.json declares at least one
+    /// dependency or optional dependency.
+    pub with_dependencies: usize,
+    /// How many active plugins carry each tag.
+    pub tags: BTreeMap<String, usize>,
+    /// The marketplace's own discovery keywords.
+    pub keywords: Vec<String>,
+    /// The catalog categories the marketplace belongs to.
+    pub categories: Vec<String>,
+}
+
+/// Compute [`MarketplaceStats`] for `config`.
+///
+/// Plugins whose directory is missing still count as plugins and by source,
+/// but contribute no skills or dependencies.
+pub fn compute_stats(config: &MarketplaceConfig) -> MarketplaceStats {
+    let mut stats = MarketplaceStats {
+        plugins: config.marketplace.plugins.len(),
+        archived: config.marketplace.archived.len(),
+        keywords: config.marketplace.keywords.clone(),
+        categories: config.marketplace.categories.clone(),
+        ..MarketplaceStats::default()
+    };
+    for entry in &config.marketplace.plugins {
+        for tag in &entry.tags {
+            *stats.tags.entry(tag.clone()).or_default() += 1;
+        }
+        let Ok(path) = resolve_source(&entry.source, config) else {
+            stats.external += 1;
+            continue;
+        };
+        if plugin_path_to_source(&path, config).1 {
+            stats.internal += 1;
+        } else {
+            stats.external += 1;
+        }
+        stats.skills += enumerate_skills(&path).len();
+        if declares_dependencies(&path) {
+            stats.with_dependencies += 1;
+        }
+    }
+    stats
+}
+
+fn declares_dependencies(plugin_path: &Path) -> bool {
+    let Some((_, doc)) = read_extends_at(plugin_path) else {
+        return false;
+    };
+    DEPENDENCY_SECTIONS.iter().any(|section| {
+        doc.get(section)
+            .and_then(|d| d.as_object())
+            .is_some_and(|deps| !deps.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::load_marketplace_config;
+    use tempfile::TempDir;
+
+    fn write_plugin(dir: &Path, skills: &[&str], extends: Option<&str>) {
+        let claude = dir.join(".claude-plugin");
+        std::fs::create_dir_all(&claude).unwrap();
+        std::fs::write(
+            claude.join("plugin.json"),
+            r#"{"name":"p","version":"1.0.0","description":"d"}"#,
+        )
+        .unwrap();
+        if let Some(extends) = extends {
+            std::fs::write(claude.join("extends-plugin.json"), extends).unwrap();
+        }
+        for skill in skills {
+            let skill_dir = dir.join("skills").join(skill);
+            std::fs::create_dir_all(&skill_dir).unwrap();
+            std::fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {skill}\ndescription: d\n---\n"),
+            )
+            .unwrap();
+        }
+    }
+
+    fn load(tmp: &TempDir, plugins_json: &str, archived_json: &str) -> MarketplaceConfig {
+        let claude = tmp.path().join(".claude-plugin");
+        std::fs::create_dir_all(&claude).unwrap();
+        std::fs::create_dir_all(tmp.path().join("plugins")).unwrap();
+        std::fs::write(
+            claude.join("marketplace.json"),
+            format!(
+                r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{plugins_json}],"archived":[{archived_json}],"keywords":["devtools"],"categories":["productivity"]}}"#
+            ),
+        )
+        .unwrap();
+        load_marketplace_config(&claude.join("marketplace.json")).unwrap()
+    }
+
+    #[test]
+    fn counts_plugins_skills_sources_tags_and_dependencies() {
+        let tmp = TempDir::new().unwrap();
+        let plugins = tmp.path().join("plugins");
+        write_plugin(
+            &plugins.join("alpha"),
+            &["one", "two"],
+            Some(r#"{"dependencies":{"beta":"^1.0.0"}}"#),
+        );
+        write_plugin(
+            &plugins.join("beta"),
+            &["three"],
+            Some(r#"{"dependencies":{}}"#),
+        );
+        let external = TempDir::new().unwrap();
+        write_plugin(
+            external.path(),
+            &[],
+            Some(r#"{"optionalDependencies":{"alpha":"*"}}"#),
+        );
+        let config = load(
+            &tmp,
+            &format!(
+                r#"{{"name":"alpha","source":"alpha","tags":["lint","docs"]}},
+                   {{"name":"beta","source":"beta","tags":["lint"]}},
+                   {{"name":"ext","source":"{}"}}"#,
+                external.path().display()
+            ),
+            r#"{"name":"old","source":"old"}"#,
+        );
+
+        let stats = compute_stats(&config);
+
+        assert_eq!(stats.plugins, 3);
+        assert_eq!(stats.internal, 2);
+        assert_eq!(stats.external, 1);
+        assert_eq!(stats.archived, 1);
+        assert_eq!(stats.skills, 3);
+        assert_eq!(stats.with_dependencies, 2);
+        assert_eq!(
+            stats.tags,
+            BTreeMap::from([("docs".to_string(), 1), ("lint".to_string(), 2)])
+        );
+        assert_eq!(stats.keywords, vec!["devtools"]);
+        assert_eq!(stats.categories, vec!["productivity"]);
+    }
+
+    #[test]
+    fn empty_marketplace_has_zero_counts() {
+        let tmp = TempDir::new().unwrap();
+        let config = load(&tmp, "", "");
+        assert_eq!(
+            compute_stats(&config),
+            MarketplaceStats {
+                keywords: vec!["devtools".to_string()],
+                categories: vec!["productivity".to_string()],
+                ..MarketplaceStats::default()
+            }
+        );
+    }
+}
@@ -1,12 +1,18 @@
 use serde::{Deserialize, Serialize};
 
 /// A plugin entry in marketplace.json.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PluginEntry {
     pub name: String,
     pub source: String,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    /// The git source `souk add` cloned the plugin from, with any `#ref`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// The commit of `origin` the plugin was copied from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha: Option<String>,
 }
 
 /// The marketplace.json root document.
@@ -19,7 +25,17 @@ pub struct Marketplace {
         skip_serializing_if = "Option::is_none"
     )]
     pub plugin_root: Option<String>,
+    /// Discovery keywords describing the marketplace as a whole.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// Catalog categories the marketplace belongs to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
     pub plugins: Vec<PluginEntry>,
+    /// Entries retired with `souk archive`. They are kept for reference but
+    /// are not listed, validated, or reviewed by default.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub archived: Vec<PluginEntry>,
 }
 
 fn default_plugin_root() -> Option<String> {
@@ -31,6 +47,19 @@ impl Marketplace {
         self.plugin_root.as_deref().unwrap_or("./plugins")
     }
 
+    /// Active plugin entries, followed by archived ones when
+    /// `include_archived` is set. The flag in each pair marks archived entries.
+    pub fn listed_plugins(&self, include_archived: bool) -> Vec<(&PluginEntry, bool)> {
+        let active = self.plugins.iter().map(|p| (p, false));
+        if include_archived {
+            active
+                .chain(self.archived.iter().map(|p| (p, true)))
+                .collect()
+        } else {
+            active.collect()
+        }
+    }
+
     pub fn normalized_plugin_root(&self) -> String {
         let root = self.plugin_root();
         if root.starts_with("./") || root.starts_with('/') {
@@ -81,14 +110,87 @@ mod tests {
         let mp = Marketplace {
             version: "1.0.0".to_string(),
             plugin_root: Some("./plugins".to_string()),
+            keywords: vec![],
+            categories: vec![],
             plugins: vec![PluginEntry {
                 name: "test".to_string(),
                 source: "test".to_string(),
                 tags: vec![],
+                origin: None,
+                sha: None,
             }],
+            archived: vec![],
         };
         let json = serde_json::to_string_pretty(&mp).unwrap();
         let mp2: Marketplace = serde_json::from_str(&json).unwrap();
         assert_eq!(mp2.version, "1.0.0");
     }
+
+    #[test]
+    fn archived_round_trips() {
+        let json = r#"{
+            "version": "0.1.0",
+            "plugins": [{"name": "active", "source": "active"}],
+            "archived": [{"name": "old", "source": "old", "tags": ["legacy"]}]
+        }"#;
+        let mp: Marketplace = serde_json::from_str(json).unwrap();
+        assert_eq!(mp.archived.len(), 1);
+
+        let out = serde_json::to_string(&mp).unwrap();
+        let back: Marketplace = serde_json::from_str(&out).unwrap();
+        assert_eq!(back.archived[0].name, "old");
+        assert_eq!(back.archived[0].tags, vec!["legacy"]);
+    }
+
+    #[test]
+    fn keywords_and_categories_round_trip() {
+        let json = r#"{
+            "version": "0.1.0",
+            "keywords": ["devtools", "git"],
+            "categories": ["productivity"],
+            "plugins": []
+        }"#;
+        let mp: Marketplace = serde_json::from_str(json).unwrap();
+        assert_eq!(mp.keywords, vec!["devtools", "git"]);
+        assert_eq!(mp.categories, vec!["productivity"]);
+
+        let out = serde_json::to_string(&mp).unwrap();
+        let back: Marketplace = serde_json::from_str(&out).unwrap();
+        assert_eq!(back.keywords, mp.keywords);
+        assert_eq!(back.categories, mp.categories);
+
+        let bare: Marketplace =
+            serde_json::from_str(r#"{"version": "0.1.0", "plugins": []}"#).unwrap();
+        let out = serde_json::to_string(&bare).unwrap();
+        assert!(!out.contains("keywords") && !out.contains("categories"));
+    }
+
+    #[test]
+    fn empty_archived_is_not_serialized() {
+        let mp: Marketplace =
+            serde_json::from_str(r#"{"version": "0.1.0", "plugins": []}"#).unwrap();
+        assert!(!serde_json::to_string(&mp).unwrap().contains("archived"));
+    }
+
+    #[test]
+    fn listed_plugins_hides_archived_by_default() {
+        let json = r#"{
+            "version": "0.1.0",
+            "plugins": [{"name": "active", "source": "active"}],
+            "archived": [{"name": "old", "source": "old"}]
+        }"#;
+        let mp: Marketplace = serde_json::from_str(json).unwrap();
+
+        let names: Vec<_> = mp
+            .listed_plugins(false)
+            .iter()
+            .map(|(p, _)| p.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["active"]);
+
+        let all = mp.listed_plugins(true);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1].0.name, "old");
+        assert!(all[1].1);
+    }
 }
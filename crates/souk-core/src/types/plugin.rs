@@ -1,5 +1,27 @@
+use std::path::Path;
+
 use serde::{Deserialize, Serialize};
 
+/// The newest plugin.json schema version this build of souk understands.
+///
+/// Manifests that omit a schema version are assumed to be compatible.
+pub const SUPPORTED_SCHEMA_VERSION: u64 = 1;
+
+/// Reads the raw text of a plugin.json file.
+///
+/// The error is a ready-to-report message. A file that is not valid UTF-8,
+/// such as one accidentally saved as binary, gets a dedicated message naming
+/// the offset of the first bad byte instead of a generic IO error.
+pub fn read_manifest_text(path: &Path) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Cannot read plugin.json: {e}"))?;
+    String::from_utf8(bytes).map_err(|e| {
+        format!(
+            "plugin.json is not valid UTF-8 (invalid byte at offset {})",
+            e.utf8_error().valid_up_to()
+        )
+    })
+}
+
 /// A plugin.json manifest.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginManifest {
@@ -8,6 +30,13 @@ pub struct PluginManifest {
     pub description: Option<serde_json::Value>,
     #[serde(default)]
     pub keywords: Vec<String>,
+    #[serde(
+        default,
+        rename = "schemaVersion",
+        alias = "manifestVersion",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub schema_version: Option<serde_json::Value>,
 }
 
 impl PluginManifest {
@@ -22,6 +51,17 @@ impl PluginManifest {
     pub fn description_str(&self) -> Option<&str> {
         self.description.as_ref().and_then(|v| v.as_str())
     }
+
+    /// Returns the declared schema version as a number.
+    ///
+    /// Accepts either a JSON integer (`1`) or a numeric string (`"1"`).
+    pub fn schema_version_num(&self) -> Option<u64> {
+        match self.schema_version.as_ref()? {
+            serde_json::Value::Number(n) => n.as_u64(),
+            serde_json::Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -57,5 +97,15 @@ mod tests {
         assert_eq!(pm.name_str(), None);
         assert_eq!(pm.version_str(), None);
         assert_eq!(pm.description_str(), None);
+        assert_eq!(pm.schema_version_num(), None);
+    }
+
+    #[test]
+    fn schema_version_from_either_key() {
+        let pm: PluginManifest = serde_json::from_str(r#"{"schemaVersion": 2}"#).unwrap();
+        assert_eq!(pm.schema_version_num(), Some(2));
+
+        let pm: PluginManifest = serde_json::from_str(r#"{"manifestVersion": "3"}"#).unwrap();
+        assert_eq!(pm.schema_version_num(), Some(3));
     }
 }
@@ -0,0 +1,239 @@
+//! Small helpers shared across modules.
+//!
+//! Dates are computed from `std::time::SystemTime` to avoid pulling in the
+//! `chrono` crate.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use walkdir::WalkDir;
+
+/// Returns the current UTC time as an RFC 3339 string,
+/// `YYYY-MM-DDTHH:MM:SSZ`.
+pub fn current_timestamp() -> String {
+    let secs = unix_seconds();
+    let (year, month, day) = days_to_civil((secs / 86400) as i64);
+    let rem = secs % 86400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Returns the current date as a `YYYY-MM-DD` string.
+///
+/// Uses `std::time::SystemTime` to avoid pulling in the `chrono` crate.
+pub fn current_date_string() -> String {
+    let (year, month, day) = days_to_civil((unix_seconds() / 86400) as i64);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn unix_seconds() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Convert days since Unix epoch to (year, month, day).
+///
+/// Algorithm from Howard Hinnant's `chrono`-compatible date library.
+fn days_to_civil(days: i64) -> (i32, u32, u32) {
+    let z = days + 719468;
+    let era = (if z >= 0 { z } else { z - 146096 }) / 146097;
+    let doe = (z - era * 146097) as u32;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if m <= 2 { y + 1 } else { y };
+    (y as i32, m, d)
+}
+
+/// Runs `work` on every item of `items` using up to `workers` scoped
+/// threads, returning the results in `items` order.
+///
+/// A `workers` of 0 is treated as 1; no more threads are spawned than
+/// there are items.
+pub(crate) fn parallel_map<T: Sync, R: Send>(
+    items: &[T],
+    workers: usize,
+    work: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    parallel_map_until(items, workers, work, |_| false)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Like [`parallel_map`], but once a result satisfies `stop` no further
+/// items are started.
+///
+/// Items already in flight still finish. An item that was never started
+/// yields `None`.
+pub(crate) fn parallel_map_until<T: Sync, R: Send>(
+    items: &[T],
+    workers: usize,
+    work: impl Fn(&T) -> R + Sync,
+    stop: impl Fn(&R) -> bool + Sync,
+) -> Vec<Option<R>> {
+    let results: Vec<Mutex<Option<R>>> = items.iter().map(|_| Mutex::new(None)).collect();
+    let next = AtomicUsize::new(0);
+    let stopped = AtomicBool::new(false);
+    let workers = workers.clamp(1, items.len().max(1));
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while !stopped.load(Ordering::SeqCst) {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(item) = items.get(i) else {
+                        break;
+                    };
+                    let result = work(item);
+                    if stop(&result) {
+                        stopped.store(true, Ordering::SeqCst);
+                    }
+                    *results[i].lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|r| r.into_inner().unwrap_or_else(|e| e.into_inner()))
+        .collect()
+}
+
+/// Hash every file under `dir`, in a stable order.
+///
+/// Paths are hashed alongside contents, so two directories hash equal only
+/// when they hold the same files with the same contents.
+pub fn tree_hash(dir: &Path) -> u64 {
+    tree_hash_where(dir, |_, _| true)
+}
+
+/// [`tree_hash`] over only the entries `keep` accepts, given their path
+/// relative to `dir` and whether they are directories. A rejected directory
+/// is skipped with everything under it.
+pub fn tree_hash_where(dir: &Path, keep: impl Fn(&Path, bool) -> bool) -> u64 {
+    let mut hasher = Fnv1a::new();
+    let files = WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0
+                || keep(
+                    e.path().strip_prefix(dir).unwrap_or(e.path()),
+                    e.file_type().is_dir(),
+                )
+        })
+        .flatten()
+        .filter(|e| e.file_type().is_file());
+    for entry in files {
+        hash_file(&mut hasher, dir, entry.path());
+    }
+    hasher.finish()
+}
+
+/// Feed a file's path relative to `root` and its contents into `hasher`.
+/// Missing files contribute only their path, so adding one later changes
+/// the hash.
+pub(crate) fn hash_file(hasher: &mut Fnv1a, root: &Path, path: &Path) {
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    hasher.write_field(rel.to_string_lossy().as_bytes());
+    match std::fs::read(path) {
+        Ok(bytes) => hasher.write_field(&bytes),
+        Err(_) => hasher.write_field(b"\0missing"),
+    }
+}
+
+/// 64-bit FNV-1a. Stable across Rust releases, unlike `DefaultHasher`, which
+/// matters because hashes are persisted to disk.
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    /// Hash `bytes` prefixed with their length so adjacent fields cannot
+    /// run together.
+    pub(crate) fn write_field(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_is_rfc3339_utc() {
+        let ts = current_timestamp();
+        assert_eq!(ts.len(), 20);
+        assert_eq!(&ts[4..5], "-");
+        assert_eq!(&ts[10..11], "T");
+        assert!(ts.ends_with('Z'));
+    }
+
+    #[test]
+    fn parallel_map_keeps_item_order() {
+        let items: Vec<u32> = (0..20).collect();
+        assert_eq!(
+            parallel_map(&items, 4, |n| n * 2),
+            (0..20).map(|n| n * 2).collect::<Vec<_>>()
+        );
+        assert_eq!(parallel_map(&items[..1], 0, |n| n + 1), vec![1]);
+        assert!(parallel_map(&[] as &[u32], 4, |n| *n).is_empty());
+    }
+
+    #[test]
+    fn parallel_map_until_starts_nothing_after_stop() {
+        let items: Vec<u32> = (0..10).collect();
+        let results = parallel_map_until(&items, 1, |n| *n, |n| *n == 3);
+        assert_eq!(
+            results,
+            [Some(0), Some(1), Some(2), Some(3)]
+                .into_iter()
+                .chain(std::iter::repeat_n(None, 6))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn civil_date_conversion() {
+        assert_eq!(days_to_civil(0), (1970, 1, 1));
+        assert_eq!(days_to_civil(19723), (2024, 1, 1));
+    }
+
+    #[test]
+    fn current_date_string_has_correct_format() {
+        let date = current_date_string();
+        // Format: YYYY-MM-DD
+        assert_eq!(date.len(), 10);
+        assert_eq!(&date[4..5], "-");
+        assert_eq!(&date[7..8], "-");
+        // Year should be plausible (2020+)
+        let year: i32 = date[..4].parse().unwrap();
+        assert!(year >= 2020);
+    }
+}
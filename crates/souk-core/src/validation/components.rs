@@ -0,0 +1,559 @@
+use std::path::Path;
+
+use crate::error::{ValidationDiagnostic, ValidationResult};
+use crate::ops::add::escapes_root;
+
+/// Hook events Claude Code dispatches to plugin hooks.
+const HOOK_EVENTS: &[&str] = &[
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+/// Validates a plugin's `agents/`, `commands/`, and `hooks/` directories.
+///
+/// - Every `agents/*.md` and `commands/*.md` file must start with a
+///   non-empty YAML frontmatter block that has a `description`.
+/// - `hooks/hooks.json` must be a JSON object whose `hooks` key maps event
+///   names to arrays of `{ "matcher"?, "hooks": [{ "type", ... }] }` entries.
+///   Unknown event names are reported as warnings.
+///
+/// Directories that exist but contain nothing to load produce warnings;
+/// malformed files produce errors. Absent directories are skipped, since
+/// every component type is optional.
+pub fn validate_components(plugin_path: &Path) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    for dir in ["agents", "commands"] {
+        validate_markdown_dir(&mut result, &plugin_path.join(dir), dir);
+    }
+    validate_hooks_dir(&mut result, &plugin_path.join("hooks"));
+    result
+}
+
+/// Validates the `SKILL.md` of the skill directory `skill_path`.
+///
+/// The file must start with a YAML frontmatter block giving a `name` and a
+/// `description`; every problem is an error located at `SKILL.md`.
+pub fn validate_skill(skill_path: &Path) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    let skill_md = skill_path.join("SKILL.md");
+    let content = match std::fs::read_to_string(&skill_md) {
+        Ok(c) => c,
+        Err(e) => {
+            result.push(
+                ValidationDiagnostic::error(format!("Cannot read SKILL.md: {e}"))
+                    .with_path(&skill_md),
+            );
+            return result;
+        }
+    };
+    match frontmatter(&content) {
+        None => {
+            result.push(ValidationDiagnostic::error("Missing frontmatter").with_path(&skill_md))
+        }
+        Some(lines) if lines.iter().all(|l| l.trim().is_empty()) => {
+            result.push(ValidationDiagnostic::error("Empty frontmatter").with_path(&skill_md))
+        }
+        Some(lines) => {
+            for key in ["name", "description"] {
+                if !has_value(&lines, key) {
+                    result.push(
+                        ValidationDiagnostic::error(format!("Missing {key} in frontmatter"))
+                            .with_path(&skill_md)
+                            .with_field(key),
+                    );
+                }
+            }
+        }
+    }
+    result
+}
+
+/// plugin.json fields that point at component files or directories.
+const REFERENCE_FIELDS: [(&str, &str); 3] = [
+    ("commands", "command"),
+    ("agents", "agent"),
+    ("hooks", "hooks"),
+];
+
+/// Validates the `commands`, `agents`, and `hooks` paths declared in
+/// plugin.json, given its top-level `fields`.
+///
+/// Each may be a path or an array of paths, relative to the plugin
+/// directory; every one must exist inside it. An absolute path or one that
+/// climbs out of the plugin with `..` is an error. An inline `hooks` object
+/// declares no path and is skipped. A value of any other type is an error.
+pub fn validate_manifest_references(
+    plugin_path: &Path,
+    plugin_json_path: &Path,
+    fields: &serde_json::Map<String, serde_json::Value>,
+) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    for (field, label) in REFERENCE_FIELDS {
+        let paths: Vec<(String, &serde_json::Value)> = match fields.get(field) {
+            None | Some(serde_json::Value::Null) => continue,
+            Some(serde_json::Value::Object(_)) if field == "hooks" => continue,
+            Some(value @ serde_json::Value::String(_)) => vec![(field.to_string(), value)],
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| (format!("{field}[{i}]"), item))
+                .collect(),
+            Some(_) => {
+                result.push(
+                    ValidationDiagnostic::error(format!(
+                        "{field} must be a path or an array of paths"
+                    ))
+                    .with_path(plugin_json_path)
+                    .with_field(field),
+                );
+                continue;
+            }
+        };
+        for (item_field, value) in paths {
+            let Some(rel) = value.as_str() else {
+                result.push(
+                    ValidationDiagnostic::error(format!("{item_field} must be a string path"))
+                        .with_path(plugin_json_path)
+                        .with_field(item_field),
+                );
+                continue;
+            };
+            if Path::new(rel).is_absolute() || escapes_root(Path::new(rel)) {
+                result.push(
+                    ValidationDiagnostic::error(format!(
+                        "Declared {label} path is outside the plugin: {rel}"
+                    ))
+                    .with_path(plugin_json_path)
+                    .with_field(item_field),
+                );
+            } else if !plugin_path.join(rel).exists() {
+                result.push(
+                    ValidationDiagnostic::error(format!("Declared {label} file not found: {rel}"))
+                        .with_path(plugin_json_path)
+                        .with_field(item_field),
+                );
+            }
+        }
+    }
+    result
+}
+
+fn validate_markdown_dir(result: &mut ValidationResult, dir: &Path, label: &str) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut files: Vec<_> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    files.sort();
+
+    if files.is_empty() {
+        result.push(
+            ValidationDiagnostic::warning(format!("{label}/ directory contains no .md files"))
+                .with_path(dir),
+        );
+        return;
+    }
+
+    for file in files {
+        let content = match std::fs::read_to_string(&file) {
+            Ok(c) => c,
+            Err(e) => {
+                result.push(
+                    ValidationDiagnostic::error(format!("Cannot read {label} file: {e}"))
+                        .with_path(&file),
+                );
+                continue;
+            }
+        };
+        let problem = match frontmatter(&content) {
+            None => Some("Missing frontmatter"),
+            Some(lines) if lines.iter().all(|l| l.trim().is_empty()) => Some("Empty frontmatter"),
+            Some(lines) if !has_value(&lines, "description") => {
+                Some("Missing description in frontmatter")
+            }
+            Some(_) => None,
+        };
+        if let Some(problem) = problem {
+            let mut diagnostic = ValidationDiagnostic::error(problem).with_path(&file);
+            if problem.contains("description") {
+                diagnostic = diagnostic.with_field("description");
+            }
+            result.push(diagnostic);
+        }
+    }
+}
+
+/// The lines between the opening and closing `---` of a frontmatter block.
+fn frontmatter(content: &str) -> Option<Vec<&str>> {
+    let mut lines = content.lines();
+    if lines.next()?.trim() != "---" {
+        return None;
+    }
+    let mut body = Vec::new();
+    for line in lines {
+        if line.trim() == "---" {
+            return Some(body);
+        }
+        body.push(line);
+    }
+    None
+}
+
+/// Whether a top-level `key:` in the frontmatter has a non-empty value.
+fn has_value(lines: &[&str], key: &str) -> bool {
+    lines.iter().any(|line| {
+        line.strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .is_some_and(|value| !value.trim().trim_matches(['"', '\'']).is_empty())
+    })
+}
+
+fn validate_hooks_dir(result: &mut ValidationResult, dir: &Path) {
+    if !dir.is_dir() {
+        return;
+    }
+
+    let hooks_path = dir.join("hooks.json");
+    if !hooks_path.is_file() {
+        result.push(
+            ValidationDiagnostic::warning("hooks/ directory has no hooks.json").with_path(dir),
+        );
+        return;
+    }
+
+    let doc: serde_json::Value = match std::fs::read_to_string(&hooks_path)
+        .map_err(|e| format!("Cannot read hooks.json: {e}"))
+        .and_then(|c| {
+            serde_json::from_str(&c).map_err(|e| format!("Invalid JSON in hooks.json: {e}"))
+        }) {
+        Ok(v) => v,
+        Err(e) => {
+            result.push(ValidationDiagnostic::error(e).with_path(&hooks_path));
+            return;
+        }
+    };
+
+    let Some(events) = doc.get("hooks").and_then(|h| h.as_object()) else {
+        result.push(
+            ValidationDiagnostic::error(
+                "hooks.json must contain a \"hooks\" object keyed by event",
+            )
+            .with_path(&hooks_path)
+            .with_field("hooks"),
+        );
+        return;
+    };
+
+    if events.is_empty() {
+        result.push(
+            ValidationDiagnostic::warning("hooks.json defines no hooks")
+                .with_path(&hooks_path)
+                .with_field("hooks"),
+        );
+    }
+
+    for (event, entries) in events {
+        let field = format!("hooks.{event}");
+        if !HOOK_EVENTS.contains(&event.as_str()) {
+            result.push(
+                ValidationDiagnostic::warning(format!("Unknown hook event: {event}"))
+                    .with_path(&hooks_path)
+                    .with_field(&field),
+            );
+        }
+
+        let Some(entries) = entries.as_array() else {
+            result.push(
+                ValidationDiagnostic::error(format!("{field} must be an array"))
+                    .with_path(&hooks_path)
+                    .with_field(&field),
+            );
+            continue;
+        };
+
+        for (i, entry) in entries.iter().enumerate() {
+            let entry_field = format!("{field}[{i}]");
+            let handlers = entry.get("hooks").and_then(|h| h.as_array());
+            let Some(handlers) = handlers else {
+                result.push(
+                    ValidationDiagnostic::error(format!(
+                        "{entry_field} must be an object with a \"hooks\" array"
+                    ))
+                    .with_path(&hooks_path)
+                    .with_field(&entry_field),
+                );
+                continue;
+            };
+            for (j, handler) in handlers.iter().enumerate() {
+                if handler.get("type").and_then(|t| t.as_str()).is_none() {
+                    let handler_field = format!("{entry_field}.hooks[{j}]");
+                    result.push(
+                        ValidationDiagnostic::error(format!(
+                            "{handler_field} is missing a string \"type\""
+                        ))
+                        .with_path(&hooks_path)
+                        .with_field(handler_field),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    const GOOD_HOOKS: &str = r#"{
+        "hooks": {
+            "PostToolUse": [
+                {"matcher": "Write|Edit", "hooks": [{"type": "command", "command": "fmt.sh"}]}
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn well_formed_components_pass() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "agents/reviewer.md",
+            "---\nname: reviewer\ndescription: Reviews code\n---\nBody",
+        );
+        write(
+            tmp.path(),
+            "commands/deploy.md",
+            "---\ndescription: \"Deploy the app\"\n---\nRun deploy",
+        );
+        write(tmp.path(), "hooks/hooks.json", GOOD_HOOKS);
+
+        let result = validate_components(tmp.path());
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    }
+
+    #[test]
+    fn plugin_without_components_passes() {
+        let tmp = TempDir::new().unwrap();
+        assert!(validate_components(tmp.path()).diagnostics.is_empty());
+    }
+
+    #[test]
+    fn command_missing_description_is_error() {
+        let tmp = TempDir::new().unwrap();
+        write(tmp.path(), "commands/a.md", "---\nname: a\n---\nBody");
+        write(tmp.path(), "commands/b.md", "No frontmatter here");
+        write(tmp.path(), "commands/c.md", "---\n\n---\nBody");
+
+        let result = validate_components(tmp.path());
+        let messages: Vec<_> = result
+            .diagnostics
+            .iter()
+            .map(|d| d.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Missing description in frontmatter",
+                "Missing frontmatter",
+                "Empty frontmatter",
+            ]
+        );
+        assert_eq!(result.error_count(), 3);
+        assert_eq!(result.diagnostics[0].field.as_deref(), Some("description"));
+    }
+
+    #[test]
+    fn skill_frontmatter_needs_name_and_description() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "good/SKILL.md",
+            "---\nname: good\ndescription: Does it\n---\n",
+        );
+        write(
+            tmp.path(),
+            "nameless/SKILL.md",
+            "---\ndescription: Does it\n---\n",
+        );
+        write(tmp.path(), "bare/SKILL.md", "# Bare");
+        std::fs::create_dir_all(tmp.path().join("missing")).unwrap();
+
+        let messages = |dir: &str| -> Vec<String> {
+            validate_skill(&tmp.path().join(dir))
+                .diagnostics
+                .iter()
+                .map(|d| d.message.clone())
+                .collect()
+        };
+        assert!(messages("good").is_empty());
+        assert_eq!(messages("nameless"), ["Missing name in frontmatter"]);
+        assert_eq!(messages("bare"), ["Missing frontmatter"]);
+        assert!(messages("missing")[0].starts_with("Cannot read SKILL.md"));
+    }
+
+    #[test]
+    fn corrupt_hooks_json_is_error() {
+        let tmp = TempDir::new().unwrap();
+        write(tmp.path(), "hooks/hooks.json", "{ not json");
+
+        let result = validate_components(tmp.path());
+        assert_eq!(result.error_count(), 1);
+        assert!(result.diagnostics[0]
+            .message
+            .starts_with("Invalid JSON in hooks.json"));
+    }
+
+    #[test]
+    fn malformed_hook_structure_is_error() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "hooks/hooks.json",
+            r#"{"hooks": {"PreToolUse": {"oops": true}, "Stop": [{"hooks": [{"command": "x"}]}]}}"#,
+        );
+
+        let result = validate_components(tmp.path());
+        let fields: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter_map(|d| d.field.as_deref())
+            .collect();
+        assert_eq!(fields, vec!["hooks.PreToolUse", "hooks.Stop[0].hooks[0]"]);
+        assert_eq!(result.error_count(), 2);
+    }
+
+    fn manifest_fields(json: &str) -> serde_json::Map<String, serde_json::Value> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn present_manifest_references_pass() {
+        let tmp = TempDir::new().unwrap();
+        write(tmp.path(), "extra/deploy.md", "---\ndescription: d\n---\n");
+        write(
+            tmp.path(),
+            "team-agents/reviewer.md",
+            "---\ndescription: r\n---\n",
+        );
+        write(tmp.path(), "config/hooks.json", GOOD_HOOKS);
+        let fields = manifest_fields(
+            r#"{"commands": ["./extra/deploy.md"], "agents": "./team-agents",
+                "hooks": "./config/hooks.json"}"#,
+        );
+
+        let result =
+            validate_manifest_references(tmp.path(), &tmp.path().join("plugin.json"), &fields);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+
+        let inline = manifest_fields(r#"{"hooks": {"hooks": {}}}"#);
+        let result =
+            validate_manifest_references(tmp.path(), &tmp.path().join("plugin.json"), &inline);
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn dangling_manifest_references_are_errors() {
+        let tmp = TempDir::new().unwrap();
+        write(tmp.path(), "extra/deploy.md", "---\ndescription: d\n---\n");
+        let fields = manifest_fields(
+            r#"{"commands": ["./extra/deploy.md", "./extra/missing.md"], "agents": 3,
+                "hooks": "./hooks/absent.json"}"#,
+        );
+
+        let result =
+            validate_manifest_references(tmp.path(), &tmp.path().join("plugin.json"), &fields);
+        let found: Vec<_> = result
+            .diagnostics
+            .iter()
+            .map(|d| (d.message.as_str(), d.field.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "Declared command file not found: ./extra/missing.md",
+                    Some("commands[1]")
+                ),
+                ("agents must be a path or an array of paths", Some("agents")),
+                (
+                    "Declared hooks file not found: ./hooks/absent.json",
+                    Some("hooks")
+                ),
+            ]
+        );
+        assert_eq!(result.error_count(), 3);
+    }
+
+    #[test]
+    fn manifest_references_outside_the_plugin_are_errors() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = tmp.path().join("plugin");
+        write(&plugin, "commands/deploy.md", "---\ndescription: d\n---\n");
+        // Present on disk, but outside the plugin
+        write(tmp.path(), "shared/agent.md", "---\ndescription: a\n---\n");
+        let absolute = tmp.path().join("shared/agent.md");
+        let fields = manifest_fields(&format!(
+            r#"{{"commands": ["./commands/../commands/deploy.md", "../shared/agent.md"],
+                "agents": {:?}}}"#,
+            absolute.to_str().unwrap()
+        ));
+
+        let result = validate_manifest_references(&plugin, &plugin.join("plugin.json"), &fields);
+        let found: Vec<_> = result
+            .diagnostics
+            .iter()
+            .map(|d| (d.message.as_str(), d.field.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "Declared command path is outside the plugin: ../shared/agent.md",
+                    Some("commands[1]")
+                ),
+                (
+                    format!(
+                        "Declared agent path is outside the plugin: {}",
+                        absolute.display()
+                    )
+                    .as_str(),
+                    Some("agents")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_dirs_and_unknown_events_warn() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("agents")).unwrap();
+        write(tmp.path(), "commands/notes.txt", "not markdown");
+        write(
+            tmp.path(),
+            "hooks/hooks.json",
+            r#"{"hooks": {"OnLunch": [{"hooks": [{"type": "command"}]}]}}"#,
+        );
+
+        let result = validate_components(tmp.path());
+        assert!(!result.has_errors());
+        assert_eq!(result.warning_count(), 3);
+    }
+}
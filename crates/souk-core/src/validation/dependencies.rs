@@ -0,0 +1,659 @@
+//! Marketplace-wide checks on plugins' `extends-plugin.json` dependencies.
+//!
+//! [`validate_extends_plugin`](super::validate_extends_plugin) checks each
+//! file in isolation; the checks here need the whole marketplace to see
+//! whether dependencies exist, match their constraints, and form cycles.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use semver::{Version, VersionReq};
+use serde::Serialize;
+
+use crate::discovery::MarketplaceConfig;
+use crate::error::{ValidationDiagnostic, ValidationResult};
+use crate::resolution::resolve_source;
+use crate::types::plugin::read_manifest_text;
+use crate::types::PluginManifest;
+use crate::validation::extends::extract_version;
+
+/// The extends-plugin.json sections that declare dependencies on other
+/// marketplace plugins, as opposed to system packages.
+pub const DEPENDENCY_SECTIONS: [&str; 2] = ["dependencies", "optionalDependencies"];
+
+/// Reports `dependencies` that are missing from the marketplace or whose
+/// version does not satisfy the declared constraint.
+///
+/// For every listed plugin's `.claude-plugin/extends-plugin.json`:
+/// - A `dependencies` entry naming no marketplace plugin is an error.
+/// - An `optionalDependencies` entry naming no marketplace plugin is a
+///   warning.
+/// - An entry in either section whose plugin.json version does not satisfy
+///   its constraint is an error giving both versions. A bare version such as
+///   `1.2.0` must match exactly.
+///
+/// Malformed constraints and dependencies without a readable plugin.json
+/// version are left to plugin validation.
+pub fn check_dependency_resolution(config: &MarketplaceConfig) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    let versions: HashMap<&str, Option<Version>> = config
+        .marketplace
+        .plugins
+        .iter()
+        .map(|entry| {
+            let version = resolve_source(&entry.source, config)
+                .ok()
+                .and_then(|path| plugin_version(&path));
+            (entry.name.as_str(), version)
+        })
+        .collect();
+
+    for entry in &config.marketplace.plugins {
+        let Some((extends_path, doc)) = read_extends(&entry.source, config) else {
+            continue;
+        };
+        for (section, optional) in DEPENDENCY_SECTIONS.into_iter().zip([false, true]) {
+            let Some(deps) = doc.get(section).and_then(|d| d.as_object()) else {
+                continue;
+            };
+            for (dep, value) in deps {
+                let field = format!("{section}.{dep}");
+                let Some(found) = versions.get(dep.as_str()) else {
+                    let diagnostic = if optional {
+                        ValidationDiagnostic::warning(format!(
+                            "Optional dependency not in marketplace: {dep} (for {})",
+                            entry.name
+                        ))
+                    } else {
+                        ValidationDiagnostic::error(format!(
+                            "Dependency not in marketplace: {dep} (required by {})",
+                            entry.name
+                        ))
+                    };
+                    result.push(
+                        diagnostic
+                            .with_path(&extends_path)
+                            .with_field(field)
+                            .with_plugin(&entry.name),
+                    );
+                    continue;
+                };
+
+                let Some(constraint) = extract_version(value) else {
+                    continue;
+                };
+                let (Some(req), Some(found)) = (version_req(&constraint), found) else {
+                    continue;
+                };
+                if !req.matches(found) {
+                    result.push(
+                        ValidationDiagnostic::error(format!(
+                            "Unsatisfied dependency: {} requires {dep} {constraint}, found {found}",
+                            entry.name
+                        ))
+                        .with_path(&extends_path)
+                        .with_field(field)
+                        .with_plugin(&entry.name),
+                    );
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Reports circular `dependencies` between plugins in the marketplace.
+///
+/// Builds a graph from the `dependencies` section of every listed plugin's
+/// `.claude-plugin/extends-plugin.json`. Only names that match a plugin in
+/// the marketplace become edges; other sections, including
+/// `systemDependencies`, are ignored. Each cycle, including a plugin that
+/// depends on itself, is reported once as an error naming the full path,
+/// e.g. `a -> b -> a`.
+///
+/// Unreadable or malformed `extends-plugin.json` files are skipped here;
+/// [`validate_extends_plugin`](super::validate_extends_plugin) reports them.
+pub fn check_dependency_cycles(config: &MarketplaceConfig) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    let names: HashSet<&str> = config
+        .marketplace
+        .plugins
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect();
+
+    let mut graph: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    let mut extends_paths: BTreeMap<&str, PathBuf> = BTreeMap::new();
+    for entry in &config.marketplace.plugins {
+        let Some((extends_path, doc)) = read_extends(&entry.source, config) else {
+            continue;
+        };
+        let deps: BTreeSet<String> = doc
+            .get("dependencies")
+            .and_then(|d| d.as_object())
+            .into_iter()
+            .flat_map(|deps| deps.keys())
+            .filter(|dep| names.contains(dep.as_str()))
+            .cloned()
+            .collect();
+        if !deps.is_empty() {
+            graph.insert(&entry.name, deps);
+            extends_paths.insert(&entry.name, extends_path);
+        }
+    }
+
+    let mut reported = HashSet::new();
+    let mut done = HashSet::new();
+    for start in graph.keys() {
+        let mut stack = Vec::new();
+        find_cycles(start, &graph, &mut stack, &mut done, &mut reported);
+    }
+
+    let mut cycles: Vec<_> = reported.into_iter().collect();
+    cycles.sort();
+    for cycle in cycles {
+        let first = cycle[0].as_str();
+        let mut path = cycle.join(" -> ");
+        path.push_str(&format!(" -> {first}"));
+        let mut diagnostic =
+            ValidationDiagnostic::error(format!("Circular plugin dependency: {path}"))
+                .with_field("dependencies")
+                .with_plugin(first);
+        if let Some(extends_path) = extends_paths.get(first) {
+            diagnostic = diagnostic.with_path(extends_path);
+        }
+        result.push(diagnostic);
+    }
+
+    result
+}
+
+/// Whether a dependency in a [`DependencyNode`] tree can be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyStatus {
+    /// Listed in the marketplace at a version meeting the constraint, or
+    /// one souk cannot check.
+    Satisfied,
+    /// Not listed in the marketplace.
+    Missing,
+    /// Listed, but its version does not meet the constraint.
+    Unsatisfied,
+    /// Already an ancestor in the tree; not expanded again.
+    Cycle,
+}
+
+/// A plugin and, transitively, the plugins it depends on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyNode {
+    pub name: String,
+    /// The constraint the parent declared; `None` at the root.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+    /// The version in the plugin's plugin.json, if it can be read.
+    pub version: Option<String>,
+    /// Declared under `optionalDependencies` rather than `dependencies`.
+    pub optional: bool,
+    pub status: DependencyStatus,
+    pub dependencies: Vec<DependencyNode>,
+}
+
+/// Build the dependency tree of the plugin `name` at `plugin_path`.
+///
+/// Both `dependencies` and `optionalDependencies` from extends-plugin.json
+/// are followed, each section sorted by name, and resolved against the marketplace's
+/// listed plugins as in [`check_dependency_resolution`]. Missing plugins
+/// are leaves, and a plugin that is already one of its own ancestors is
+/// marked [`DependencyStatus::Cycle`] instead of being expanded again.
+///
+/// The root need not be listed in the marketplace.
+pub fn dependency_tree(
+    plugin_path: &Path,
+    name: &str,
+    config: &MarketplaceConfig,
+) -> DependencyNode {
+    let mut ancestors = vec![name.to_string()];
+    DependencyNode {
+        name: name.to_string(),
+        constraint: None,
+        version: plugin_version(plugin_path).map(|v| v.to_string()),
+        optional: false,
+        status: DependencyStatus::Satisfied,
+        dependencies: dependency_children(plugin_path, config, &mut ancestors),
+    }
+}
+
+fn dependency_children(
+    plugin_path: &Path,
+    config: &MarketplaceConfig,
+    ancestors: &mut Vec<String>,
+) -> Vec<DependencyNode> {
+    let Some((_, doc)) = read_extends_at(plugin_path) else {
+        return Vec::new();
+    };
+    let mut children = Vec::new();
+    for (section, optional) in DEPENDENCY_SECTIONS.into_iter().zip([false, true]) {
+        let Some(deps) = doc.get(section).and_then(|d| d.as_object()) else {
+            continue;
+        };
+        for (dep, value) in deps {
+            let constraint = extract_version(value);
+            let path = config
+                .marketplace
+                .plugins
+                .iter()
+                .find(|p| p.name == *dep)
+                .and_then(|entry| resolve_source(&entry.source, config).ok());
+            let version = path.as_deref().and_then(plugin_version);
+
+            let status = if path.is_none() {
+                DependencyStatus::Missing
+            } else if ancestors.contains(dep) {
+                DependencyStatus::Cycle
+            } else {
+                let req = constraint.as_deref().and_then(version_req);
+                match (req, &version) {
+                    (Some(req), Some(found)) if !req.matches(found) => {
+                        DependencyStatus::Unsatisfied
+                    }
+                    _ => DependencyStatus::Satisfied,
+                }
+            };
+
+            let dependencies = match (&path, status) {
+                (Some(path), DependencyStatus::Satisfied | DependencyStatus::Unsatisfied) => {
+                    ancestors.push(dep.clone());
+                    let children = dependency_children(path, config, ancestors);
+                    ancestors.pop();
+                    children
+                }
+                _ => Vec::new(),
+            };
+
+            children.push(DependencyNode {
+                name: dep.clone(),
+                constraint,
+                version: version.map(|v| v.to_string()),
+                optional,
+                status,
+                dependencies,
+            });
+        }
+    }
+    children
+}
+
+/// The path and parsed contents of a plugin's extends-plugin.json, or
+/// `None` if the plugin cannot be located or the file is absent or unusable.
+fn read_extends(source: &str, config: &MarketplaceConfig) -> Option<(PathBuf, serde_json::Value)> {
+    read_extends_at(&resolve_source(source, config).ok()?)
+}
+
+/// Like [`read_extends`], for a plugin directory.
+pub(crate) fn read_extends_at(plugin_path: &Path) -> Option<(PathBuf, serde_json::Value)> {
+    let extends_path = plugin_path
+        .join(".claude-plugin")
+        .join("extends-plugin.json");
+    let content = std::fs::read_to_string(&extends_path).ok()?;
+    let doc = serde_json::from_str(&content).ok()?;
+    Some((extends_path, doc))
+}
+
+/// The semver version declared in a plugin's plugin.json.
+pub(crate) fn plugin_version(plugin_path: &Path) -> Option<Version> {
+    let content =
+        read_manifest_text(&plugin_path.join(".claude-plugin").join("plugin.json")).ok()?;
+    let manifest: PluginManifest = serde_json::from_str(&content).ok()?;
+    Version::parse(manifest.version_str()?).ok()
+}
+
+/// Parse an extends-plugin.json version constraint. Unlike Cargo, a bare
+/// version means an exact match.
+pub(crate) fn version_req(constraint: &str) -> Option<VersionReq> {
+    let constraint = constraint.trim();
+    if constraint.starts_with(|c: char| c.is_ascii_digit()) {
+        VersionReq::parse(&format!("={constraint}")).ok()
+    } else {
+        VersionReq::parse(constraint).ok()
+    }
+}
+
+/// Depth-first search from `node`, recording every cycle that closes on the
+/// current `stack`. Cycles are rotated to start at their smallest name so
+/// each is recorded once, whichever node the search entered it from.
+fn find_cycles<'a>(
+    node: &'a str,
+    graph: &'a BTreeMap<&'a str, BTreeSet<String>>,
+    stack: &mut Vec<&'a str>,
+    done: &mut HashSet<&'a str>,
+    reported: &mut HashSet<Vec<String>>,
+) {
+    if done.contains(node) {
+        return;
+    }
+    if let Some(pos) = stack.iter().position(|n| *n == node) {
+        let cycle = &stack[pos..];
+        let min = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+        let rotated = cycle[min..]
+            .iter()
+            .chain(&cycle[..min])
+            .map(|n| n.to_string())
+            .collect();
+        reported.insert(rotated);
+        return;
+    }
+
+    stack.push(node);
+    for dep in graph.get(node).into_iter().flatten() {
+        find_cycles(dep, graph, stack, done, reported);
+    }
+    stack.pop();
+    done.insert(node);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::load_marketplace_config;
+    use tempfile::TempDir;
+
+    /// Create a marketplace of `(name, version, extends-plugin.json)` plugins.
+    fn setup_with_extends(
+        tmp: &TempDir,
+        plugins: &[(&str, &str, Option<serde_json::Value>)],
+    ) -> MarketplaceConfig {
+        let claude = tmp.path().join(".claude-plugin");
+        std::fs::create_dir_all(&claude).unwrap();
+        let mut entries = Vec::new();
+        for (name, version, extends) in plugins {
+            let dir = tmp.path().join("plugins").join(name).join(".claude-plugin");
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("plugin.json"),
+                format!(r#"{{"name":"{name}","version":"{version}","description":"test"}}"#),
+            )
+            .unwrap();
+            if let Some(extends) = extends {
+                std::fs::write(dir.join("extends-plugin.json"), extends.to_string()).unwrap();
+            }
+            entries.push(format!(r#"{{"name":"{name}","source":"{name}"}}"#));
+        }
+        std::fs::write(
+            claude.join("marketplace.json"),
+            format!(
+                r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{}]}}"#,
+                entries.join(",")
+            ),
+        )
+        .unwrap();
+        load_marketplace_config(&claude.join("marketplace.json")).unwrap()
+    }
+
+    /// Create a marketplace whose plugins declare the given `dependencies`.
+    fn setup(tmp: &TempDir, plugins: &[(&str, &[&str])]) -> MarketplaceConfig {
+        let plugins: Vec<_> = plugins
+            .iter()
+            .map(|(name, deps)| {
+                let extends = (!deps.is_empty()).then(|| {
+                    let deps: serde_json::Map<_, _> = deps
+                        .iter()
+                        .map(|d| (d.to_string(), serde_json::json!("^1.0.0")))
+                        .collect();
+                    serde_json::json!({
+                        "dependencies": deps,
+                        "systemDependencies": {"a": "^1.0.0"},
+                    })
+                });
+                (*name, "1.0.0", extends)
+            })
+            .collect();
+        setup_with_extends(tmp, &plugins)
+    }
+
+    fn messages(result: &ValidationResult) -> Vec<&str> {
+        result
+            .diagnostics
+            .iter()
+            .map(|d| d.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn dag_has_no_cycles() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(
+            &tmp,
+            &[
+                ("a", &["b", "c"]),
+                ("b", &["c", "not-in-marketplace"]),
+                ("c", &[]),
+            ],
+        );
+        let result = check_dependency_cycles(&config);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    }
+
+    #[test]
+    fn two_node_cycle() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, &[("a", &["b"]), ("b", &["a"])]);
+        let result = check_dependency_cycles(&config);
+        assert_eq!(
+            messages(&result),
+            vec!["Circular plugin dependency: a -> b -> a"]
+        );
+        assert_eq!(result.diagnostics[0].plugin.as_deref(), Some("a"));
+        assert!(result.diagnostics[0]
+            .path
+            .as_ref()
+            .unwrap()
+            .ends_with("a/.claude-plugin/extends-plugin.json"));
+    }
+
+    #[test]
+    fn three_node_cycle_is_reported_once() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(
+            &tmp,
+            &[("x", &["y"]), ("y", &["z"]), ("z", &["x"]), ("w", &["x"])],
+        );
+        let result = check_dependency_cycles(&config);
+        assert_eq!(
+            messages(&result),
+            vec!["Circular plugin dependency: x -> y -> z -> x"]
+        );
+    }
+
+    #[test]
+    fn self_dependency_is_a_cycle() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, &[("a", &["a"])]);
+        let result = check_dependency_cycles(&config);
+        assert_eq!(
+            messages(&result),
+            vec!["Circular plugin dependency: a -> a"]
+        );
+    }
+
+    #[test]
+    fn system_dependencies_are_ignored() {
+        // Every plugin lists "a" as a system dependency in `setup`.
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, &[("a", &["b"]), ("b", &["c"]), ("c", &[])]);
+        assert!(check_dependency_cycles(&config).diagnostics.is_empty());
+    }
+
+    #[test]
+    fn resolved_dependencies_pass() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_with_extends(
+            &tmp,
+            &[
+                (
+                    "app",
+                    "1.0.0",
+                    Some(serde_json::json!({
+                        "dependencies": {"lib": "^1.0.0", "core": {"version": "2.0.0"}},
+                        "optionalDependencies": {"extra": "*"},
+                        "systemDependencies": {"node": ">=18.0.0"},
+                    })),
+                ),
+                ("lib", "1.4.2", None),
+                ("core", "2.0.0", None),
+                ("extra", "0.3.0", None),
+            ],
+        );
+        let result = check_dependency_resolution(&config);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    }
+
+    #[test]
+    fn missing_dependencies() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_with_extends(
+            &tmp,
+            &[(
+                "app",
+                "1.0.0",
+                Some(serde_json::json!({
+                    "dependencies": {"ghost": "^1.0.0"},
+                    "optionalDependencies": {"maybe": "^1.0.0"},
+                })),
+            )],
+        );
+        let result = check_dependency_resolution(&config);
+        assert_eq!(
+            messages(&result),
+            vec![
+                "Dependency not in marketplace: ghost (required by app)",
+                "Optional dependency not in marketplace: maybe (for app)",
+            ]
+        );
+        assert_eq!(result.error_count(), 1);
+        assert_eq!(result.warning_count(), 1);
+        assert_eq!(
+            result.diagnostics[0].field.as_deref(),
+            Some("dependencies.ghost")
+        );
+    }
+
+    #[test]
+    fn unsatisfied_constraints_are_errors() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_with_extends(
+            &tmp,
+            &[
+                (
+                    "app",
+                    "1.0.0",
+                    Some(serde_json::json!({
+                        "dependencies": {"lib": "^2.0.0", "pinned": "1.2.0"},
+                        "optionalDependencies": {"extra": ">=1.0.0"},
+                    })),
+                ),
+                ("lib", "1.4.2", None),
+                ("pinned", "1.2.1", None),
+                ("extra", "0.3.0", None),
+            ],
+        );
+        let result = check_dependency_resolution(&config);
+        assert_eq!(
+            messages(&result),
+            vec![
+                "Unsatisfied dependency: app requires lib ^2.0.0, found 1.4.2",
+                "Unsatisfied dependency: app requires pinned 1.2.0, found 1.2.1",
+                "Unsatisfied dependency: app requires extra >=1.0.0, found 0.3.0",
+            ]
+        );
+        assert_eq!(result.error_count(), 3);
+    }
+
+    fn statuses(node: &DependencyNode) -> Vec<(String, DependencyStatus)> {
+        let mut out = Vec::new();
+        for child in &node.dependencies {
+            out.push((child.name.clone(), child.status));
+            out.extend(statuses(child));
+        }
+        out
+    }
+
+    fn root_tree(config: &MarketplaceConfig, name: &str) -> DependencyNode {
+        dependency_tree(&config.plugin_root_abs.join(name), name, config)
+    }
+
+    #[test]
+    fn tree_follows_dependencies_transitively() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_with_extends(
+            &tmp,
+            &[
+                (
+                    "app",
+                    "1.0.0",
+                    Some(serde_json::json!({
+                        "dependencies": {"lib": "^1.0.0"},
+                        "optionalDependencies": {"extras": {"version": "2.0.0"}},
+                    })),
+                ),
+                (
+                    "lib",
+                    "1.4.0",
+                    Some(serde_json::json!({"dependencies": {"core": ">=1.0.0"}})),
+                ),
+                ("core", "1.1.0", None),
+                ("extras", "1.0.0", None),
+            ],
+        );
+
+        let tree = root_tree(&config, "app");
+        assert_eq!(tree.version.as_deref(), Some("1.0.0"));
+        assert_eq!(
+            statuses(&tree),
+            [
+                ("lib".to_string(), DependencyStatus::Satisfied),
+                ("core".to_string(), DependencyStatus::Satisfied),
+                ("extras".to_string(), DependencyStatus::Unsatisfied),
+            ]
+        );
+        let lib = &tree.dependencies[0];
+        assert_eq!(lib.constraint.as_deref(), Some("^1.0.0"));
+        assert_eq!(lib.version.as_deref(), Some("1.4.0"));
+        assert_eq!(lib.dependencies[0].name, "core");
+        assert!(tree.dependencies[1].optional);
+    }
+
+    #[test]
+    fn tree_flags_missing_transitive_dependency() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, &[("app", &["lib"]), ("lib", &["ghost"])]);
+
+        let tree = root_tree(&config, "app");
+        assert_eq!(
+            statuses(&tree),
+            [
+                ("lib".to_string(), DependencyStatus::Satisfied),
+                ("ghost".to_string(), DependencyStatus::Missing),
+            ]
+        );
+        let ghost = &tree.dependencies[0].dependencies[0];
+        assert_eq!(ghost.version, None);
+        assert!(ghost.dependencies.is_empty());
+    }
+
+    #[test]
+    fn tree_stops_at_cycles() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup(&tmp, &[("a", &["b"]), ("b", &["a"])]);
+
+        assert_eq!(
+            statuses(&root_tree(&config, "a")),
+            [
+                ("b".to_string(), DependencyStatus::Satisfied),
+                ("a".to_string(), DependencyStatus::Cycle),
+            ]
+        );
+    }
+}
@@ -122,7 +122,7 @@ pub fn validate_extends_plugin(plugin_path: &Path) -> ValidationResult {
 /// - A string (the version constraint itself)
 /// - An object with an optional `version` field (defaults to `"*"`)
 /// - Anything else returns `None` (invalid)
-fn extract_version(value: &serde_json::Value) -> Option<String> {
+pub(crate) fn extract_version(value: &serde_json::Value) -> Option<String> {
     if let Some(s) = value.as_str() {
         Some(s.to_string())
     } else {
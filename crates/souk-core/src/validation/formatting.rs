@@ -0,0 +1,171 @@
+//! Canonical formatting of souk's JSON files.
+//!
+//! souk writes marketplace.json and plugin.json pretty-printed with
+//! two-space indentation and a trailing newline. Hand-edited files that
+//! drift from that produce noisy diffs the next time souk rewrites them, so
+//! `--check-formatting` flags them and `souk fix` rewrites them.
+
+use std::iter::Peekable;
+use std::path::Path;
+use std::str::Chars;
+
+use crate::error::{SoukError, ValidationDiagnostic, ValidationResult};
+
+/// Reformat the JSON document `content` the way souk writes it.
+///
+/// Only whitespace changes: key order, number spelling, and string escapes
+/// are kept as written, so the result of a file souk wrote itself is
+/// identical to its content.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Json`] if `content` is not valid JSON.
+pub fn canonical_json(content: &str) -> Result<String, SoukError> {
+    serde_json::from_str::<serde::de::IgnoredAny>(content)?;
+
+    let mut out = String::with_capacity(content.len());
+    let mut depth = 0;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => copy_string(&mut chars, &mut out),
+            '{' | '[' => {
+                let close = if c == '{' { '}' } else { ']' };
+                skip_whitespace(&mut chars);
+                out.push(c);
+                if chars.peek() == Some(&close) {
+                    chars.next();
+                    out.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth -= 1;
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(',');
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            ' ' | '\t' | '\n' | '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out.push('\n');
+    Ok(out)
+}
+
+/// Copy a string literal whose opening quote has been consumed.
+fn copy_string(chars: &mut Peekable<Chars>, out: &mut String) {
+    out.push('"');
+    while let Some(c) = chars.next() {
+        out.push(c);
+        match c {
+            '\\' => out.extend(chars.next()),
+            '"' => return,
+            _ => {}
+        }
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars
+        .next_if(|c| matches!(c, ' ' | '\t' | '\n' | '\r'))
+        .is_some()
+    {}
+}
+
+fn newline(out: &mut String, depth: usize) {
+    out.push('\n');
+    out.extend(std::iter::repeat_n("  ", depth));
+}
+
+/// Warns if the JSON file at `path` is not in canonical formatting.
+///
+/// Files that cannot be read or parsed are skipped; the other checks
+/// already report them.
+pub fn check_formatting(path: &Path) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return result;
+    };
+    let Ok(canonical) = canonical_json(&content) else {
+        return result;
+    };
+    if content == canonical {
+        return result;
+    }
+
+    let problem = if content.trim_end() == canonical.trim_end() {
+        "File does not end with exactly one newline"
+    } else {
+        "File is not pretty-printed with two-space indentation"
+    };
+    result.push(
+        ValidationDiagnostic::warning(format!("{problem}; run `souk fix` to reformat"))
+            .with_path(path),
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn canonical_json_matches_serde_pretty_printing() {
+        let value = serde_json::json!({
+            "name": "p",
+            "tags": [],
+            "meta": {},
+            "deps": {"a": "^1.0", "b": [1, 2.5, true, null]},
+            "text": "quote \" and \\ and {braces}, [brackets]: done",
+        });
+        let pretty = format!("{}\n", serde_json::to_string_pretty(&value).unwrap());
+        let compact = serde_json::to_string(&value).unwrap();
+
+        assert_eq!(canonical_json(&compact).unwrap(), pretty);
+        assert_eq!(canonical_json(&pretty).unwrap(), pretty);
+    }
+
+    #[test]
+    fn canonical_json_keeps_key_order() {
+        let out = canonical_json(r#"{"version":"1.0.0","name":"p"}"#).unwrap();
+        assert_eq!(out, "{\n  \"version\": \"1.0.0\",\n  \"name\": \"p\"\n}\n");
+    }
+
+    #[test]
+    fn canonical_json_rejects_invalid_json() {
+        assert!(canonical_json("{\"name\": ").is_err());
+    }
+
+    #[test]
+    fn check_formatting_flags_non_canonical_files() {
+        let tmp = TempDir::new().unwrap();
+        let canonical = tmp.path().join("canonical.json");
+        std::fs::write(&canonical, "{\n  \"name\": \"p\"\n}\n").unwrap();
+        let no_newline = tmp.path().join("no-newline.json");
+        std::fs::write(&no_newline, "{\n  \"name\": \"p\"\n}").unwrap();
+        let compact = tmp.path().join("compact.json");
+        std::fs::write(&compact, "{\"name\":\"p\"}\n").unwrap();
+
+        assert!(check_formatting(&canonical).diagnostics.is_empty());
+
+        let result = check_formatting(&no_newline);
+        assert_eq!(result.warning_count(), 1);
+        assert!(result.diagnostics[0].message.contains("newline"));
+
+        let result = check_formatting(&compact);
+        assert_eq!(result.warning_count(), 1);
+        assert!(result.diagnostics[0].message.contains("pretty-printed"));
+        assert_eq!(
+            result.diagnostics[0].path.as_deref(),
+            Some(compact.as_path())
+        );
+    }
+}
@@ -1,21 +1,50 @@
 use std::collections::HashSet;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::discovery::MarketplaceConfig;
-use crate::error::{ValidationDiagnostic, ValidationResult};
-use crate::validation::plugin::validate_plugin;
+use crate::error::{SoukError, ValidationDiagnostic, ValidationResult};
+use crate::resolution::{classify_source, resolve_source, SourceKind};
+use crate::types::plugin::read_manifest_text;
+use crate::types::{PluginEntry, PluginManifest};
+use crate::util::parallel_map;
+use crate::validation::dependencies::{check_dependency_cycles, check_dependency_resolution};
+use crate::validation::formatting::check_formatting;
+use crate::validation::plugin::{check_plugin_name, validate_plugin_with};
 
 /// Validates a marketplace configuration and optionally its plugins.
 ///
 /// Checks that:
 /// - The marketplace version is valid semver
-/// - The plugin root directory exists
+/// - The plugin root directory exists, is not the project root, and does not
+///   overlap `.claude-plugin` or a registered plugin's directory
 /// - There are no duplicate plugin names
-/// - Each plugin entry has a non-empty name and source
+/// - Each plugin entry has a non-empty source and a name that is usable as a
+///   directory name (see [`check_plugin_name`])
+/// - If `allow_external_sources` is disabled in `souk.toml`, no source is an
+///   absolute path or escapes the plugin root
 /// - Filesystem completeness: every directory in the plugin root is listed
 ///   in the marketplace, and every marketplace entry has a corresponding directory
+/// - Each internal plugin's directory is named after its plugin.json `name`
+///   (a warning)
+/// - Marketplace `keywords` and `categories` are well formatted (warnings)
+/// - Every `extends-plugin.json` dependency is listed in the marketplace at a
+///   version satisfying its constraint
+/// - No plugins depend on each other in a cycle via `extends-plugin.json`
 /// - If `skip_plugins` is false, each plugin is individually validated
 pub fn validate_marketplace(config: &MarketplaceConfig, skip_plugins: bool) -> ValidationResult {
+    validate_marketplace_with_jobs(config, skip_plugins, 1)
+}
+
+/// Like [`validate_marketplace`], but validates up to `jobs` plugins at once
+/// on scoped threads. A `jobs` of 0 is treated as 1.
+///
+/// Plugin diagnostics are merged in marketplace order, so the result is the
+/// same as a serial run whatever the thread count.
+pub fn validate_marketplace_with_jobs(
+    config: &MarketplaceConfig,
+    skip_plugins: bool,
+    jobs: usize,
+) -> ValidationResult {
     let mut result = ValidationResult::new();
     let mp = &config.marketplace;
 
@@ -31,11 +60,25 @@ pub fn validate_marketplace(config: &MarketplaceConfig, skip_plugins: bool) -> V
         result.push(
             ValidationDiagnostic::error(format!(
                 "Plugin root directory not found: {}",
-                config.plugin_root_abs.display()
+                config.display_path(&config.plugin_root_abs)
             ))
             .with_path(&config.marketplace_path)
             .with_field("pluginRoot"),
         );
+    } else {
+        result.merge(check_plugin_root_placement(config));
+    }
+
+    if config.settings.check_formatting {
+        result.merge(check_formatting(&config.marketplace_path));
+    }
+
+    for (field, values) in [("keywords", &mp.keywords), ("categories", &mp.categories)] {
+        result.merge(check_keyword_format(
+            field,
+            values,
+            &config.marketplace_path,
+        ));
     }
 
     let mut seen_names = HashSet::new();
@@ -55,6 +98,12 @@ pub fn validate_marketplace(config: &MarketplaceConfig, skip_plugins: bool) -> V
                     .with_path(&config.marketplace_path)
                     .with_field(format!("plugins[{i}].name")),
             );
+        } else if let Err(problem) = check_plugin_name(&entry.name) {
+            result.push(
+                ValidationDiagnostic::error(format!("Plugin name '{}' {problem}", entry.name))
+                    .with_path(&config.marketplace_path)
+                    .with_field(format!("plugins[{i}].name")),
+            );
         }
         if entry.source.is_empty() {
             result.push(
@@ -63,26 +112,115 @@ pub fn validate_marketplace(config: &MarketplaceConfig, skip_plugins: bool) -> V
                     .with_field(format!("plugins[{i}].source")),
             );
         }
+        if !config.settings.allow_external_sources && !entry.source.is_empty() {
+            let problem = match classify_source(&entry.source, config) {
+                SourceKind::Internal => None,
+                SourceKind::Escaping => Some("escapes pluginRoot"),
+                SourceKind::Absolute => Some("is an absolute path"),
+            };
+            if let Some(problem) = problem {
+                result.push(
+                    ValidationDiagnostic::error(format!(
+                        "Plugin source for {} {problem}: {}",
+                        entry.name, entry.source
+                    ))
+                    .with_path(&config.marketplace_path)
+                    .with_field(format!("plugins[{i}].source")),
+                );
+            }
+        }
     }
 
     if config.plugin_root_abs.is_dir() {
         let completeness = check_completeness(config);
         result.merge(completeness);
+        result.merge(check_directory_names(config));
     }
 
+    result.merge(check_dependency_resolution(config));
+    result.merge(check_dependency_cycles(config));
+
     if !skip_plugins && config.plugin_root_abs.is_dir() {
-        for entry in &mp.plugins {
-            let source = &entry.source;
-            let plugin_path = crate::resolution::resolve_source(source, config)
-                .unwrap_or_else(|_| config.plugin_root_abs.join(source));
-
-            if plugin_path.is_dir() {
-                let plugin_result = validate_plugin(&plugin_path);
-                result.merge(plugin_result);
-            }
-        }
+        result.merge(validate_plugins(config, jobs));
+    }
+
+    result
+}
+
+/// Validates every listed plugin using up to `jobs` worker threads,
+/// returning the diagnostics in marketplace order.
+fn validate_plugins(config: &MarketplaceConfig, jobs: usize) -> ValidationResult {
+    let results = parallel_map(&config.marketplace.plugins, jobs, |entry| {
+        let plugin_path = crate::resolution::resolve_source(&entry.source, config)
+            .unwrap_or_else(|_| config.plugin_root_abs.join(&entry.source));
+        plugin_path.is_dir().then(|| {
+            let mut plugin_result = validate_plugin_with(&plugin_path, &config.settings);
+            plugin_result.tag_plugin(&entry.name);
+            plugin_result
+        })
+    });
+
+    let mut merged = ValidationResult::new();
+    for result in results.into_iter().flatten() {
+        merged.merge(result);
+    }
+    merged
+}
+
+/// Check that pluginRoot is separate from the project root, `.claude-plugin`,
+/// and every registered plugin's directory, comparing canonical paths.
+///
+/// Plugins normally live inside pluginRoot; the reverse, or pluginRoot
+/// containing the marketplace itself, makes plugin discovery and orphan
+/// detection treat unrelated directories as plugins.
+fn check_plugin_root_placement(config: &MarketplaceConfig) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    let root = &config.plugin_root_abs;
+    let shown = config.display_path(root);
+    let error = |message: String| {
+        ValidationDiagnostic::error(message)
+            .with_path(&config.marketplace_path)
+            .with_field("pluginRoot")
+    };
+
+    let claude_dir = config
+        .marketplace_path
+        .parent()
+        .unwrap_or(&config.project_root);
+    if *root == config.project_root {
+        result.push(error(
+            "pluginRoot must not be the project root; keep plugins in a subdirectory such as ./plugins"
+                .to_string(),
+        ));
+    } else if root.starts_with(claude_dir) {
+        result.push(error(format!(
+            "pluginRoot {shown} is inside .claude-plugin"
+        )));
+    } else if claude_dir.starts_with(root) {
+        result.push(error(format!(
+            "pluginRoot {shown} contains the marketplace's .claude-plugin directory"
+        )));
     }
 
+    for entry in &config.marketplace.plugins {
+        let Some(plugin_dir) = resolve_source(&entry.source, config)
+            .ok()
+            .and_then(|p| p.canonicalize().ok())
+        else {
+            continue;
+        };
+        if root.starts_with(&plugin_dir) {
+            let relation = if *root == plugin_dir {
+                "is the directory of"
+            } else {
+                "is inside"
+            };
+            result.push(error(format!(
+                "pluginRoot {shown} {relation} plugin {}",
+                entry.name
+            )));
+        }
+    }
     result
 }
 
@@ -90,37 +228,266 @@ pub fn validate_marketplace(config: &MarketplaceConfig, skip_plugins: bool) -> V
 ///
 /// Scans the plugin root directory and compares against the marketplace entries.
 /// Used by both validation (to warn) and prune (to delete).
-pub fn find_orphaned_dirs(
-    config: &MarketplaceConfig,
-) -> Result<Vec<std::path::PathBuf>, crate::error::SoukError> {
-    let fs_plugins: HashSet<String> = match std::fs::read_dir(&config.plugin_root_abs) {
-        Ok(entries) => entries
-            .flatten()
-            .filter(|e| e.path().is_dir())
-            .map(|e| e.file_name().to_string_lossy().to_string())
-            .collect(),
-        Err(e) => return Err(crate::error::SoukError::Io(e)),
+///
+/// Symlinks are skipped unless `settings.dereference` is set, so a link to a
+/// plugin kept elsewhere is never reported or pruned by default. When
+/// following, a symlink is an orphan only if neither its name nor its target
+/// matches a marketplace entry.
+///
+/// # Errors
+///
+/// Returns [`SoukError::ScanAborted`] if a followed link loops or leads back
+/// to pluginRoot or a directory above it, or if pluginRoot holds more than
+/// `settings.max_scan_entries` entries, and [`SoukError::Io`] if pluginRoot
+/// cannot be read.
+pub fn find_orphaned_dirs(config: &MarketplaceConfig) -> Result<Vec<PathBuf>, SoukError> {
+    let dereference = config.settings.dereference;
+    let mut scan = DirScan::new(config.settings.max_scan_entries);
+    let mut fs_plugins: Vec<(String, Option<PathBuf>)> = Vec::new();
+    for entry in scan.read_dir(&config.plugin_root_abs)? {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if !file_type.is_symlink() {
+            if entry.path().is_dir() {
+                fs_plugins.push((entry.file_name().to_string_lossy().to_string(), None));
+            }
+        } else if dereference {
+            if let Some(target) = scan.follow(&entry.path())? {
+                fs_plugins.push((
+                    entry.file_name().to_string_lossy().to_string(),
+                    Some(target),
+                ));
+            }
+        }
+    }
+
+    // Archived entries whose directories were left in place are not orphans.
+    let entries = || {
+        config
+            .marketplace
+            .plugins
+            .iter()
+            .chain(&config.marketplace.archived)
+    };
+    let mp_sources: HashSet<String> = entries().map(|p| source_dir_name(&p.source)).collect();
+    let link_targets: HashSet<PathBuf> = if dereference {
+        entries()
+            .filter_map(|p| crate::resolution::resolve_source(&p.source, config).ok())
+            .filter_map(|p| p.canonicalize().ok())
+            .collect()
+    } else {
+        HashSet::new()
     };
 
-    let mp_sources: HashSet<String> = config
+    let orphans = fs_plugins
+        .into_iter()
+        .filter(|(name, _)| !mp_sources.contains(name))
+        .filter(|(_, target)| target.as_ref().is_none_or(|t| !link_targets.contains(t)))
+        .map(|(name, _)| config.plugin_root_abs.join(name))
+        .collect();
+
+    Ok(orphans)
+}
+
+/// Returns the marketplace entries whose source directory does not exist.
+///
+/// Relative sources are looked up by directory name under pluginRoot;
+/// absolute sources (added with `--no-copy`) are checked where they are.
+/// Used by both validation (to report errors) and prune (to remove them).
+pub fn find_stale_entries(config: &MarketplaceConfig) -> Result<Vec<&PluginEntry>, SoukError> {
+    let fs_plugins = plugin_dir_names(config)?;
+
+    Ok(config
         .marketplace
         .plugins
         .iter()
-        .map(|p| {
-            Path::new(&p.source)
-                .file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| p.source.clone())
+        .filter(|p| {
+            if Path::new(&p.source).is_absolute() {
+                !Path::new(&p.source).is_dir()
+            } else {
+                !fs_plugins.contains(&source_dir_name(&p.source))
+            }
         })
-        .collect();
+        .collect())
+}
 
-    let orphans = fs_plugins
-        .iter()
-        .filter(|name| !mp_sources.contains(*name))
-        .map(|name| config.plugin_root_abs.join(name))
-        .collect();
+/// Names of the directories directly under pluginRoot.
+fn plugin_dir_names(config: &MarketplaceConfig) -> Result<HashSet<String>, SoukError> {
+    Ok(DirScan::new(config.settings.max_scan_entries)
+        .read_dir(&config.plugin_root_abs)?
+        .into_iter()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect())
+}
 
-    Ok(orphans)
+/// A scan of pluginRoot that remembers the canonical path of every
+/// directory it reads, so a symlink leading back into the scan fails
+/// instead of being followed round a loop, and that gives up after
+/// `max_entries` directory entries.
+struct DirScan {
+    visited: HashSet<PathBuf>,
+    entries: usize,
+    max_entries: Option<usize>,
+}
+
+impl DirScan {
+    fn new(max_entries: Option<usize>) -> Self {
+        Self {
+            visited: HashSet::new(),
+            entries: 0,
+            max_entries,
+        }
+    }
+
+    /// The readable entries of `dir`, which is marked visited.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoukError::ScanAborted`] if `dir` was already read or the
+    /// scan passes `max_entries`, and [`SoukError::Io`] if `dir` cannot be
+    /// read.
+    fn read_dir(&mut self, dir: &Path) -> Result<Vec<std::fs::DirEntry>, SoukError> {
+        let canonical = dir.canonicalize()?;
+        if !self.visited.insert(canonical.clone()) {
+            return Err(SoukError::ScanAborted(format!(
+                "symlink loop: {} was already scanned as {}",
+                dir.display(),
+                canonical.display()
+            )));
+        }
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            self.entries += 1;
+            if let Some(max) = self.max_entries.filter(|&max| self.entries > max) {
+                return Err(SoukError::ScanAborted(format!(
+                    "more than {max} entries under {} (raise --max-scan-entries or \
+                     souk.toml max_scan_entries to scan further)",
+                    dir.display()
+                )));
+            }
+            entries.extend(entry.ok());
+        }
+        Ok(entries)
+    }
+
+    /// The canonical directory the symlink at `link` leads to, or `None` if
+    /// it is dangling or not a directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoukError::ScanAborted`] if the chain of links loops, or
+    /// leads to a visited directory or one above it.
+    fn follow(&self, link: &Path) -> Result<Option<PathBuf>, SoukError> {
+        let loop_error = |target: &Path| {
+            SoukError::ScanAborted(format!(
+                "symlink loop: {} leads back to {}",
+                link.display(),
+                target.display()
+            ))
+        };
+        let mut seen = HashSet::new();
+        let mut current = link.to_path_buf();
+        while current.is_symlink() {
+            if !seen.insert(current.clone()) {
+                return Err(loop_error(&current));
+            }
+            let target = std::fs::read_link(&current)?;
+            current = match current.parent() {
+                Some(parent) => parent.join(target),
+                None => target,
+            };
+        }
+        let Ok(target) = current.canonicalize() else {
+            return Ok(None);
+        };
+        if self.visited.iter().any(|dir| dir.starts_with(&target)) {
+            return Err(loop_error(&target));
+        }
+        Ok(target.is_dir().then_some(target))
+    }
+}
+
+/// How the marketplace's entries line up with the directories in pluginRoot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct DirectoryCounts {
+    /// Marketplace entries, not counting archived ones.
+    pub entries: usize,
+    /// Directories directly under pluginRoot.
+    pub directories: usize,
+    /// Directories with no marketplace entry; see [`find_orphaned_dirs`].
+    pub orphaned: usize,
+    /// Entries whose directory does not exist; see [`find_stale_entries`].
+    pub missing: usize,
+}
+
+impl std::fmt::Display for DirectoryCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} entries, {} plugin directories on disk, {} orphaned, {} missing",
+            self.entries, self.directories, self.orphaned, self.missing
+        )
+    }
+}
+
+/// Count entries, pluginRoot directories, orphaned directories, and entries
+/// with no directory, as a quick check that the two agree.
+///
+/// # Errors
+///
+/// Returns [`SoukError::Io`](crate::error::SoukError::Io) if pluginRoot
+/// cannot be read.
+pub fn directory_counts(config: &MarketplaceConfig) -> Result<DirectoryCounts, SoukError> {
+    Ok(DirectoryCounts {
+        entries: config.marketplace.plugins.len(),
+        directories: plugin_dir_names(config)?.len(),
+        orphaned: find_orphaned_dirs(config)?.len(),
+        missing: find_stale_entries(config)?.len(),
+    })
+}
+
+/// The directory name a relative source refers to under pluginRoot.
+fn source_dir_name(source: &str) -> String {
+    Path::new(source)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| source.to_string())
+}
+
+/// Checks the formatting of a list of discovery keywords.
+///
+/// Each entry should be a non-empty, lowercase, hyphenated word listed only
+/// once. Problems are warnings, since keywords only affect discovery.
+fn check_keyword_format(field: &str, values: &[String], path: &Path) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    let mut seen = HashSet::new();
+
+    for (i, value) in values.iter().enumerate() {
+        let problem = if value.trim().is_empty() {
+            Some(format!("Empty entry in {field}"))
+        } else if value.chars().any(char::is_whitespace) {
+            Some(format!(
+                "Entry in {field} contains whitespace (use hyphens): {value}"
+            ))
+        } else if value.chars().any(char::is_uppercase) {
+            Some(format!("Entry in {field} should be lowercase: {value}"))
+        } else if !seen.insert(value.as_str()) {
+            Some(format!("Duplicate entry in {field}: {value}"))
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            result.push(
+                ValidationDiagnostic::warning(problem)
+                    .with_path(path)
+                    .with_field(format!("{field}[{i}]")),
+            );
+        }
+    }
+
+    result
 }
 
 /// Checks that the filesystem and marketplace are in sync.
@@ -146,43 +513,82 @@ fn check_completeness(config: &MarketplaceConfig) -> ValidationResult {
                 );
             }
         }
+        Err(e @ SoukError::ScanAborted(_)) => {
+            result.push(
+                ValidationDiagnostic::error(e.to_string()).with_path(&config.plugin_root_abs),
+            );
+            return result;
+        }
         Err(_) => return result,
     }
 
-    // Missing dirs from marketplace — keep existing logic
-    let fs_plugins: HashSet<String> = match std::fs::read_dir(&config.plugin_root_abs) {
-        Ok(entries) => entries
-            .flatten()
-            .filter(|e| e.path().is_dir())
-            .map(|e| e.file_name().to_string_lossy().to_string())
-            .collect(),
+    // Missing dirs from marketplace
+    let stale = match find_stale_entries(config) {
+        Ok(stale) => stale,
+        Err(e @ SoukError::ScanAborted(_)) => {
+            result.push(
+                ValidationDiagnostic::error(e.to_string()).with_path(&config.plugin_root_abs),
+            );
+            return result;
+        }
         Err(_) => return result,
     };
+    let mut reported = HashSet::new();
+    for entry in stale {
+        let message = if Path::new(&entry.source).is_absolute() {
+            format!("Plugin source directory not found: {}", entry.source)
+        } else {
+            let dir = source_dir_name(&entry.source);
+            if !reported.insert(dir.clone()) {
+                continue;
+            }
+            format!(
+                "Plugin in marketplace but not in filesystem: {dir}. \
+                 Run `souk remove {dir}` or `souk prune --stale-entries --apply` \
+                 to clean up the stale entry."
+            )
+        };
+        result.push(ValidationDiagnostic::error(message).with_path(&config.marketplace_path));
+    }
 
-    let mp_sources: HashSet<String> = config
-        .marketplace
-        .plugins
-        .iter()
-        .map(|p| {
-            Path::new(&p.source)
-                .file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| p.source.clone())
-        })
-        .collect();
+    result
+}
 
-    for mp_source in &mp_sources {
-        if !fs_plugins.contains(mp_source) {
+/// Warns for each internal plugin whose directory name differs from the
+/// `name` in its plugin.json, since lookups by name then miss the directory.
+///
+/// Plugins whose plugin.json cannot be read are skipped; plugin validation
+/// reports those.
+fn check_directory_names(config: &MarketplaceConfig) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    for (i, entry) in config.marketplace.plugins.iter().enumerate() {
+        if entry.source.is_empty() || classify_source(&entry.source, config) != SourceKind::Internal
+        {
+            continue;
+        }
+        let Ok(path) = resolve_source(&entry.source, config) else {
+            continue;
+        };
+        let Some(dir_name) = path.file_name().map(|n| n.to_string_lossy()) else {
+            continue;
+        };
+        let manifest = read_manifest_text(&path.join(".claude-plugin").join("plugin.json"))
+            .ok()
+            .and_then(|c| serde_json::from_str::<PluginManifest>(&c).ok());
+        let Some(name) = manifest.as_ref().and_then(|m| m.name_str()) else {
+            continue;
+        };
+        if name != dir_name {
             result.push(
-                ValidationDiagnostic::error(format!(
-                    "Plugin in marketplace but not in filesystem: {mp_source}. \
-                     Run `souk remove {mp_source}` to clean up the stale entry."
+                ValidationDiagnostic::warning(format!(
+                    "Plugin directory {dir_name} does not match its plugin.json name {name}; \
+                     rename the directory to {name} and update its source"
                 ))
-                .with_path(&config.marketplace_path),
+                .with_path(&path)
+                .with_field(format!("plugins[{i}].source")),
             );
         }
     }
-
     result
 }
 
@@ -212,6 +618,19 @@ mod tests {
         load_marketplace_config(&claude.join("marketplace.json")).unwrap()
     }
 
+    #[test]
+    fn path_like_plugin_name_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"../escaped","source":"a"}]}"#,
+            &["a"],
+        );
+        let result = validate_marketplace(&config, true);
+        assert!(result.diagnostics.iter().any(|d| d.is_error()
+            && d.message == "Plugin name '../escaped' must not contain path separators"));
+    }
+
     #[test]
     fn valid_marketplace() {
         let tmp = TempDir::new().unwrap();
@@ -228,6 +647,154 @@ mod tests {
         );
     }
 
+    fn plugin_root_errors(result: &ValidationResult) -> Vec<&str> {
+        result
+            .diagnostics
+            .iter()
+            .filter(|d| d.is_error() && d.field.as_deref() == Some("pluginRoot"))
+            .map(|d| d.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn plugin_root_at_project_root_is_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":".","plugins":[]}"#,
+            &[],
+        );
+        let result = validate_marketplace(&config, true);
+        let errors = plugin_root_errors(&result);
+        assert_eq!(errors.len(), 1, "{:?}", result.diagnostics);
+        assert!(errors[0].contains("must not be the project root"));
+    }
+
+    #[test]
+    fn plugin_root_inside_claude_plugin_is_rejected() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".claude-plugin/plugins")).unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./.claude-plugin/plugins","plugins":[]}"#,
+            &[],
+        );
+        let result = validate_marketplace(&config, true);
+        assert_eq!(
+            plugin_root_errors(&result),
+            ["pluginRoot .claude-plugin/plugins is inside .claude-plugin"]
+        );
+    }
+
+    #[test]
+    fn plugin_root_nested_inside_a_plugin_is_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = tmp.path().join("plugins/host");
+        std::fs::create_dir_all(plugin.join("nested")).unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins/host/nested",
+                "plugins":[{"name":"host","source":"./plugins/host"}]}"#,
+            &["host"],
+        );
+        let result = validate_marketplace(&config, true);
+        assert_eq!(
+            plugin_root_errors(&result),
+            ["pluginRoot plugins/host/nested is inside plugin host"]
+        );
+    }
+
+    #[test]
+    fn marketplace_keyword_formatting_warns() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins",
+                "keywords":["devtools","Git","code review","devtools",""],
+                "categories":["productivity","Testing"],
+                "plugins":[]}"#,
+            &[],
+        );
+        let result = validate_marketplace(&config, false);
+        assert!(!result.has_errors(), "{:?}", result.diagnostics);
+
+        let fields: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter_map(|d| d.field.as_deref())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                "keywords[1]",
+                "keywords[2]",
+                "keywords[3]",
+                "keywords[4]",
+                "categories[1]"
+            ]
+        );
+        assert_eq!(
+            result.diagnostics[0].message,
+            "Entry in keywords should be lowercase: Git"
+        );
+    }
+
+    #[test]
+    fn well_formatted_marketplace_keywords_pass() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins",
+                "keywords":["devtools","code-review"],"categories":["productivity"],
+                "plugins":[]}"#,
+            &[],
+        );
+        assert!(validate_marketplace(&config, false).diagnostics.is_empty());
+    }
+
+    #[test]
+    fn parallel_plugin_validation_matches_serial() {
+        let tmp = TempDir::new().unwrap();
+        let names: Vec<String> = (0..12).map(|i| format!("p{i:02}")).collect();
+        let dirs: Vec<&str> = names.iter().map(String::as_str).collect();
+        let entries: Vec<String> = names
+            .iter()
+            .map(|n| format!(r#"{{"name":"{n}","source":"{n}"}}"#))
+            .collect();
+        let config = setup_marketplace(
+            &tmp,
+            &format!(
+                r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{}]}}"#,
+                entries.join(",")
+            ),
+            &dirs,
+        );
+        for broken in ["p03", "p07", "p10"] {
+            std::fs::write(
+                tmp.path()
+                    .join("plugins")
+                    .join(broken)
+                    .join(".claude-plugin/plugin.json"),
+                format!(r#"{{"name":"{broken}","version":"bad","description":"test"}}"#),
+            )
+            .unwrap();
+        }
+
+        let summarize = |result: &ValidationResult| -> Vec<(Option<String>, String)> {
+            result
+                .diagnostics
+                .iter()
+                .map(|d| (d.plugin.clone(), d.message.clone()))
+                .collect()
+        };
+        let serial = summarize(&validate_marketplace(&config, false));
+        assert_eq!(serial.len(), 3);
+        for jobs in [0, 2, 4, 64] {
+            let parallel = validate_marketplace_with_jobs(&config, false, jobs);
+            assert_eq!(summarize(&parallel), serial, "jobs = {jobs}");
+        }
+    }
+
     #[test]
     fn invalid_version() {
         let tmp = TempDir::new().unwrap();
@@ -344,6 +911,31 @@ mod tests {
         assert!(!result.has_errors());
     }
 
+    #[test]
+    fn directory_counts_report_orphan_and_missing_entry() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[
+                {"name":"kept","source":"kept"},{"name":"ghost","source":"ghost"}]}"#,
+            &["kept", "orphan"],
+        );
+        let counts = directory_counts(&config).unwrap();
+        assert_eq!(
+            counts,
+            DirectoryCounts {
+                entries: 2,
+                directories: 2,
+                orphaned: 1,
+                missing: 1,
+            }
+        );
+        assert_eq!(
+            counts.to_string(),
+            "2 entries, 2 plugin directories on disk, 1 orphaned, 1 missing"
+        );
+    }
+
     #[test]
     fn find_orphaned_dirs_returns_correct_paths() {
         let tmp = TempDir::new().unwrap();
@@ -362,6 +954,36 @@ mod tests {
         assert!(names.contains(&"orphan2".to_string()));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_plugin_is_not_an_orphan() {
+        let tmp = TempDir::new().unwrap();
+        let external = tmp.path().join("external").join("registered");
+        std::fs::create_dir_all(&external).unwrap();
+        let external = external.canonicalize().unwrap();
+        let mut config = setup_marketplace(
+            &tmp,
+            &format!(
+                r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{{"name":"registered","source":"{}"}}]}}"#,
+                external.display()
+            ),
+            &[],
+        );
+        let stray = tmp.path().join("external").join("stray");
+        std::fs::create_dir_all(&stray).unwrap();
+        std::os::unix::fs::symlink(&external, config.plugin_root_abs.join("link-to-registered"))
+            .unwrap();
+        std::os::unix::fs::symlink(&stray, config.plugin_root_abs.join("link-to-stray")).unwrap();
+
+        assert!(find_orphaned_dirs(&config).unwrap().is_empty());
+
+        config.settings.dereference = true;
+        assert_eq!(
+            find_orphaned_dirs(&config).unwrap(),
+            vec![config.plugin_root_abs.join("link-to-stray")]
+        );
+    }
+
     #[test]
     fn find_orphaned_dirs_empty_when_all_registered() {
         let tmp = TempDir::new().unwrap();
@@ -373,4 +995,182 @@ mod tests {
         let orphans = find_orphaned_dirs(&config).unwrap();
         assert!(orphans.is_empty());
     }
+
+    #[test]
+    fn plugin_diagnostics_are_tagged_with_entry_name() {
+        let tmp = TempDir::new().unwrap();
+        let plugins = tmp.path().join("plugins");
+        let bad = plugins.join("bad").join(".claude-plugin");
+        std::fs::create_dir_all(&bad).unwrap();
+        std::fs::write(bad.join("plugin.json"), "not json").unwrap();
+
+        let claude = tmp.path().join(".claude-plugin");
+        std::fs::create_dir_all(&claude).unwrap();
+        std::fs::write(
+            claude.join("marketplace.json"),
+            r#"{"version":"bad","pluginRoot":"./plugins","plugins":[{"name":"bad-plugin","source":"bad"}]}"#,
+        )
+        .unwrap();
+
+        let config = load_marketplace_config(&claude.join("marketplace.json")).unwrap();
+        let result = validate_marketplace(&config, false);
+        let grouped = result.group_by_plugin();
+
+        assert!(grouped
+            .marketplace
+            .iter()
+            .any(|d| d.message.contains("Invalid marketplace version")));
+        assert!(grouped.plugins["bad-plugin"]
+            .iter()
+            .any(|d| d.message.contains("Invalid JSON in plugin")));
+    }
+
+    #[test]
+    fn external_sources_rejected_when_disallowed() {
+        let tmp = TempDir::new().unwrap();
+        let external = TempDir::new().unwrap();
+        let ext = external.path().join("ext").join(".claude-plugin");
+        std::fs::create_dir_all(&ext).unwrap();
+        std::fs::write(
+            ext.join("plugin.json"),
+            r#"{"name":"ext","version":"1.0.0","description":"test"}"#,
+        )
+        .unwrap();
+        let ext_source = external
+            .path()
+            .join("ext")
+            .to_string_lossy()
+            .replace('\\', "/");
+        let mut config = setup_marketplace(
+            &tmp,
+            &format!(
+                r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{{"name":"a","source":"a"}},{{"name":"ext","source":"{ext_source}"}}]}}"#
+            ),
+            &["a"],
+        );
+
+        assert!(!validate_marketplace(&config, true).has_errors());
+
+        config.settings.allow_external_sources = false;
+        let result = validate_marketplace(&config, true);
+        let errors: Vec<_> = result.diagnostics.iter().filter(|d| d.is_error()).collect();
+        assert_eq!(errors.len(), 1, "diagnostics: {:?}", result.diagnostics);
+        assert!(errors[0].message.contains("absolute path"));
+        assert_eq!(errors[0].field.as_deref(), Some("plugins[1].source"));
+    }
+
+    #[test]
+    fn relative_sources_pass_when_external_disallowed() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"a","source":"a"},{"name":"b","source":"./plugins/b"}]}"#,
+            &["a", "b"],
+        );
+        config.settings.allow_external_sources = false;
+
+        let result = validate_marketplace(&config, true);
+        assert!(
+            !result.has_errors(),
+            "diagnostics: {:?}",
+            result.diagnostics
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_loops_under_plugin_root_fail_cleanly() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"a","source":"a"}]}"#,
+            &["a"],
+        );
+        config.settings.dereference = true;
+        let root = config.plugin_root_abs.clone();
+        // A two-link cycle.
+        std::os::unix::fs::symlink(root.join("y"), root.join("x")).unwrap();
+        std::os::unix::fs::symlink(root.join("x"), root.join("y")).unwrap();
+
+        let err = find_orphaned_dirs(&config).unwrap_err();
+        assert!(matches!(err, SoukError::ScanAborted(_)), "{err}");
+        assert!(err.to_string().contains("symlink loop"), "{err}");
+
+        // A link back to pluginRoot itself.
+        std::fs::remove_file(root.join("x")).unwrap();
+        std::fs::remove_file(root.join("y")).unwrap();
+        std::os::unix::fs::symlink(&root, root.join("self")).unwrap();
+
+        let err = find_orphaned_dirs(&config).unwrap_err();
+        assert!(err.to_string().contains("leads back to"), "{err}");
+        let result = validate_marketplace(&config, false);
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .any(|d| d.is_error() && d.message.contains("symlink loop")),
+            "diagnostics: {:?}",
+            result.diagnostics
+        );
+
+        // Without --dereference the links are not followed at all.
+        config.settings.dereference = false;
+        assert!(find_orphaned_dirs(&config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn scan_stops_at_max_entries() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"a","source":"a"}]}"#,
+            &["a", "b", "c"],
+        );
+        config.settings.max_scan_entries = Some(3);
+        assert_eq!(find_orphaned_dirs(&config).unwrap().len(), 2);
+
+        config.settings.max_scan_entries = Some(2);
+        let err = find_orphaned_dirs(&config).unwrap_err();
+        assert!(err.to_string().contains("more than 2 entries"), "{err}");
+        let result = validate_marketplace(&config, true);
+        assert!(result.has_errors(), "diagnostics: {:?}", result.diagnostics);
+    }
+
+    #[test]
+    fn directory_matching_plugin_name_passes() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"a","source":"a"}]}"#,
+            &["a"],
+        );
+        assert!(check_directory_names(&config).diagnostics.is_empty());
+    }
+
+    #[test]
+    fn directory_differing_from_plugin_name_warns() {
+        let tmp = TempDir::new().unwrap();
+        let config = setup_marketplace(
+            &tmp,
+            r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"a","source":"a"}]}"#,
+            &["a"],
+        );
+        std::fs::write(
+            tmp.path().join("plugins/a/.claude-plugin/plugin.json"),
+            r#"{"name":"alpha","version":"1.0.0","description":"test"}"#,
+        )
+        .unwrap();
+
+        let result = validate_marketplace(&config, true);
+        assert!(!result.has_errors(), "{:?}", result.diagnostics);
+        assert_eq!(result.warning_count(), 1);
+        let warning = &result.diagnostics[0];
+        assert!(
+            warning.message.contains("rename the directory to alpha"),
+            "{}",
+            warning.message
+        );
+        assert_eq!(warning.field.as_deref(), Some("plugins[0].source"));
+        assert_eq!(result.outcome(true), crate::error::CommandOutcome::Failed);
+    }
 }
@@ -1,8 +1,17 @@
+pub mod components;
+pub mod dependencies;
 pub mod extends;
+pub mod formatting;
 pub mod marketplace;
 pub mod plugin;
 
+pub use components::{validate_components, validate_manifest_references, validate_skill};
+pub use dependencies::{
+    check_dependency_cycles, check_dependency_resolution, dependency_tree, DependencyNode,
+    DependencyStatus,
+};
 pub use extends::validate_extends_plugin;
-pub use marketplace::find_orphaned_dirs;
-pub use marketplace::validate_marketplace;
-pub use plugin::validate_plugin;
+pub use formatting::{canonical_json, check_formatting};
+pub use marketplace::{directory_counts, find_orphaned_dirs, find_stale_entries, DirectoryCounts};
+pub use marketplace::{validate_marketplace, validate_marketplace_with_jobs};
+pub use plugin::{check_plugin_name, check_readme_title, validate_plugin, validate_plugin_with};
@@ -1,8 +1,14 @@
-use std::path::Path;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
 
+use crate::config::SoukConfig;
 use crate::error::{ValidationDiagnostic, ValidationResult};
-use crate::types::plugin::PluginManifest;
+use crate::types::plugin::{read_manifest_text, PluginManifest, SUPPORTED_SCHEMA_VERSION};
+use crate::validation::components::{validate_components, validate_manifest_references};
 use crate::validation::extends::validate_extends_plugin;
+use crate::validation::formatting::check_formatting;
 
 /// Validates a plugin directory.
 ///
@@ -12,8 +18,28 @@ use crate::validation::extends::validate_extends_plugin;
 /// - The `.claude-plugin/plugin.json` file exists and is valid JSON
 /// - Required fields (`name`, `version`, `description`) are present and non-null
 /// - The `version` field is valid semver
+/// - A declared `schemaVersion` is not newer than this build understands
+///   (reported as a warning)
 /// - If an `extends-plugin.json` exists, it is also validated
+/// - Any `agents/`, `commands/`, and `hooks/` components are well-formed;
+///   see [`validate_components`]
+/// - Paths declared in plugin.json's `commands`, `agents`, and `hooks`
+///   exist; see [`validate_manifest_references`]
+///
+/// Uses the default settings; see [`validate_plugin_with`] to apply a
+/// project's `souk.toml`.
 pub fn validate_plugin(plugin_path: &Path) -> ValidationResult {
+    validate_plugin_with(plugin_path, &SoukConfig::default())
+}
+
+/// Validates a plugin directory using project `settings`.
+///
+/// Performs every check of [`validate_plugin`], and additionally requires
+/// each field in `settings.required_fields` to be present and non-null and,
+/// with `settings.plugin_schema`, checks plugin.json against that JSON
+/// Schema. Schema violations use the JSON pointer of the offending value as
+/// their `field`.
+pub fn validate_plugin_with(plugin_path: &Path, settings: &SoukConfig) -> ValidationResult {
     let mut result = ValidationResult::new();
 
     if !plugin_path.is_dir() {
@@ -43,13 +69,10 @@ pub fn validate_plugin(plugin_path: &Path) -> ValidationResult {
         return result;
     }
 
-    let content = match std::fs::read_to_string(&plugin_json_path) {
+    let content = match read_manifest_text(&plugin_json_path) {
         Ok(c) => c,
         Err(e) => {
-            result.push(
-                ValidationDiagnostic::error(format!("Cannot read plugin.json: {e}"))
-                    .with_path(&plugin_json_path),
-            );
+            result.push(ValidationDiagnostic::error(e).with_path(&plugin_json_path));
             return result;
         }
     };
@@ -65,6 +88,10 @@ pub fn validate_plugin(plugin_path: &Path) -> ValidationResult {
         }
     };
 
+    if settings.check_formatting {
+        result.merge(check_formatting(&plugin_json_path));
+    }
+
     if manifest.name_str().is_none() {
         result.push(
             ValidationDiagnostic::error("Missing or null required field: name")
@@ -90,6 +117,28 @@ pub fn validate_plugin(plugin_path: &Path) -> ValidationResult {
         );
     }
 
+    let fields: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&content).unwrap_or_default();
+    for field in settings.extra_required_fields() {
+        if fields.get(field).is_none_or(|v| v.is_null()) {
+            result.push(
+                ValidationDiagnostic::error(format!("Missing or null required field: {field}"))
+                    .with_path(&plugin_json_path)
+                    .with_field(field),
+            );
+        }
+    }
+
+    if let Some(schema_path) = &settings.plugin_schema {
+        check_schema(
+            &mut result,
+            &settings.compiled_schema,
+            schema_path,
+            &content,
+            &plugin_json_path,
+        );
+    }
+
     if let Some(v) = version_str {
         if semver::Version::parse(v).is_err() {
             result.push(
@@ -100,12 +149,231 @@ pub fn validate_plugin(plugin_path: &Path) -> ValidationResult {
         }
     }
 
+    if manifest.schema_version.is_some() {
+        match manifest.schema_version_num() {
+            Some(declared) if declared > SUPPORTED_SCHEMA_VERSION => {
+                result.push(
+                    ValidationDiagnostic::warning(format!(
+                        "plugin.json declares schemaVersion {declared}, but this version of souk \
+                         only understands up to {SUPPORTED_SCHEMA_VERSION}. Upgrade souk to \
+                         validate it fully."
+                    ))
+                    .with_path(&plugin_json_path)
+                    .with_field("schemaVersion"),
+                );
+            }
+            Some(_) => {}
+            None => {
+                result.push(
+                    ValidationDiagnostic::warning(
+                        "Unrecognized schemaVersion: expected a non-negative integer",
+                    )
+                    .with_path(&plugin_json_path)
+                    .with_field("schemaVersion"),
+                );
+            }
+        }
+    }
+
     let extends_result = validate_extends_plugin(plugin_path);
     result.merge(extends_result);
+    result.merge(validate_components(plugin_path));
+    result.merge(validate_manifest_references(
+        plugin_path,
+        &plugin_json_path,
+        &fields,
+    ));
 
     result
 }
 
+/// A `plugin_schema`, compiled on first use.
+///
+/// The cache is shared by every clone of the [`SoukConfig`] it belongs to,
+/// so validating all of a marketplace's plugins compiles the schema once
+/// and reports a schema that cannot be loaded once, not once per plugin.
+#[derive(Clone, Default)]
+pub(crate) struct CompiledSchema(Arc<SchemaCache>);
+
+#[derive(Default)]
+struct SchemaCache {
+    /// The schema path and its validator, or why it could not be loaded.
+    loaded: OnceLock<(PathBuf, Result<Arc<jsonschema::Validator>, String>)>,
+    /// Whether a load failure has been reported.
+    reported: AtomicBool,
+}
+
+impl CompiledSchema {
+    /// The validator for the schema at `path`, compiling it on first use.
+    /// A path other than the cached one is compiled afresh.
+    fn get(&self, path: &Path) -> Result<Arc<jsonschema::Validator>, String> {
+        let (cached_path, validator) = self.0.loaded.get_or_init(|| (path.into(), compile(path)));
+        if cached_path == path {
+            validator.clone()
+        } else {
+            compile(path)
+        }
+    }
+
+    /// Whether a load failure should be reported: true the first time only.
+    fn report_failure(&self) -> bool {
+        !self.0.reported.swap(true, Ordering::Relaxed)
+    }
+}
+
+impl fmt::Debug for CompiledSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CompiledSchema")
+    }
+}
+
+/// Settings compare equal whatever their schema cache holds.
+impl PartialEq for CompiledSchema {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for CompiledSchema {}
+
+fn compile(path: &Path) -> Result<Arc<jsonschema::Validator>, String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| serde_json::from_str(&s).map_err(|e| e.to_string()))
+        .and_then(|s| jsonschema::validator_for(&s).map_err(|e| e.to_string()))
+        .map(Arc::new)
+}
+
+/// Validate plugin.json `content` against the JSON Schema at `schema_path`,
+/// pushing one error per violation.
+///
+/// The schema is compiled once per `schema` cache, and a schema that cannot
+/// be loaded is reported by the first plugin checked against it only.
+fn check_schema(
+    result: &mut ValidationResult,
+    schema: &CompiledSchema,
+    schema_path: &Path,
+    content: &str,
+    plugin_json_path: &Path,
+) {
+    let validator = match schema.get(schema_path) {
+        Ok(v) => v,
+        Err(e) => {
+            if schema.report_failure() {
+                result.push(
+                    ValidationDiagnostic::error(format!("Cannot load plugin schema: {e}"))
+                        .with_path(schema_path),
+                );
+            }
+            return;
+        }
+    };
+
+    let Ok(instance) = serde_json::from_str::<serde_json::Value>(content) else {
+        return;
+    };
+    for error in validator.iter_errors(&instance) {
+        let pointer = error.instance_path.to_string();
+        let mut diagnostic = ValidationDiagnostic::error(format!("Schema violation: {error}"))
+            .with_path(plugin_json_path);
+        if !pointer.is_empty() {
+            diagnostic = diagnostic.with_field(pointer);
+        }
+        result.push(diagnostic);
+    }
+}
+
+/// Checks that `name` can name a plugin and its directory under pluginRoot.
+///
+/// A name must be non-empty and must not contain a path separator or `..`,
+/// so joining it to pluginRoot can never point outside it.
+///
+/// # Errors
+///
+/// Returns a description of the problem, suitable for following the name
+/// in a message.
+pub fn check_plugin_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        Err("must not be empty".to_string())
+    } else if name.contains(['/', '\\']) {
+        Err("must not contain path separators".to_string())
+    } else if name == "." || name.contains("..") {
+        Err("must not contain '..' or be '.'".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Warns when a plugin's README title disagrees with its plugin.json.
+///
+/// Reads the first `# ` heading in `README.md` and compares it loosely
+/// (ignoring case, whitespace, `-`, and `_`) against the manifest `name` and
+/// `description`. The title matches if it equals, contains, or is contained
+/// in either. A missing README, a README with no H1, or an unreadable
+/// plugin.json produces no diagnostics; [`validate_plugin`] reports the
+/// latter.
+pub fn check_readme_title(plugin_path: &Path) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    let readme_path = plugin_path.join("README.md");
+    let Some(title) = std::fs::read_to_string(&readme_path)
+        .ok()
+        .and_then(|readme| first_h1(&readme).map(str::to_string))
+    else {
+        return result;
+    };
+    let manifest: Option<PluginManifest> =
+        read_manifest_text(&plugin_path.join(".claude-plugin").join("plugin.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+    let Some(manifest) = manifest else {
+        return result;
+    };
+
+    let title_key = loose_key(&title);
+    let matches = [manifest.name_str(), manifest.description_str()]
+        .into_iter()
+        .flatten()
+        .map(loose_key)
+        .any(|key| !key.is_empty() && (title_key.contains(&key) || key.contains(&title_key)));
+
+    if !matches && !title_key.is_empty() {
+        result.push(
+            ValidationDiagnostic::warning(format!(
+                "README title \"{title}\" does not match plugin.json name \"{}\"",
+                manifest.name_str().unwrap_or_default()
+            ))
+            .with_path(&readme_path),
+        );
+    }
+
+    result
+}
+
+/// The text of the first ATX level-1 heading in `markdown`, outside code fences.
+fn first_h1(markdown: &str) -> Option<&str> {
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            if let Some(title) = trimmed.strip_prefix("# ") {
+                return Some(title.trim().trim_end_matches('#').trim_end());
+            }
+        }
+    }
+    None
+}
+
+/// Lowercase `text` with whitespace, `-`, and `_` removed.
+fn loose_key(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-' && *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,6 +442,27 @@ mod tests {
         assert!(result.has_errors());
     }
 
+    #[test]
+    fn non_utf8_plugin_json() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = tmp.path().join("binary");
+        let claude = plugin.join(".claude-plugin");
+        std::fs::create_dir_all(&claude).unwrap();
+        std::fs::write(claude.join("plugin.json"), b"{\"name\": \"\xff\xfe\"}").unwrap();
+
+        let result = validate_plugin(&plugin);
+        assert_eq!(result.error_count(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(
+            diagnostic.message,
+            "plugin.json is not valid UTF-8 (invalid byte at offset 10)"
+        );
+        assert_eq!(
+            diagnostic.path.as_deref(),
+            Some(claude.join("plugin.json").as_path())
+        );
+    }
+
     #[test]
     fn missing_required_fields() {
         let tmp = TempDir::new().unwrap();
@@ -235,4 +524,205 @@ mod tests {
         let result = validate_plugin(&plugin);
         assert!(!result.has_errors());
     }
+
+    #[test]
+    fn future_schema_version_warns() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        std::fs::write(
+            plugin.join(".claude-plugin").join("plugin.json"),
+            r#"{"name": "good-plugin", "version": "1.0.0", "description": "d", "schemaVersion": 99}"#,
+        )
+        .unwrap();
+        let result = validate_plugin(&plugin);
+        assert!(!result.has_errors());
+        assert_eq!(result.warning_count(), 1);
+        assert!(result.diagnostics[0].message.contains("Upgrade souk"));
+        assert_eq!(
+            result.diagnostics[0].field.as_deref(),
+            Some("schemaVersion")
+        );
+    }
+
+    #[test]
+    fn known_schema_version_passes() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        std::fs::write(
+            plugin.join(".claude-plugin").join("plugin.json"),
+            format!(
+                r#"{{"name": "good-plugin", "version": "1.0.0", "description": "d", "schemaVersion": {SUPPORTED_SCHEMA_VERSION}}}"#
+            ),
+        )
+        .unwrap();
+        let result = validate_plugin(&plugin);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    }
+
+    fn license_required() -> SoukConfig {
+        SoukConfig {
+            required_fields: vec!["name".into(), "license".into()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn configured_required_field_missing_fails() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+
+        let result = validate_plugin_with(&plugin, &license_required());
+
+        assert_eq!(result.error_count(), 1, "{:?}", result.diagnostics);
+        assert_eq!(
+            result.diagnostics[0].message,
+            "Missing or null required field: license"
+        );
+        assert_eq!(result.diagnostics[0].field.as_deref(), Some("license"));
+    }
+
+    #[test]
+    fn configured_required_field_present_passes() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        std::fs::write(
+            plugin.join(".claude-plugin").join("plugin.json"),
+            r#"{"name": "good-plugin", "version": "1.0.0", "description": "d", "license": "MIT"}"#,
+        )
+        .unwrap();
+
+        let result = validate_plugin_with(&plugin, &license_required());
+        assert!(!result.has_errors(), "{:?}", result.diagnostics);
+    }
+
+    #[test]
+    fn configured_required_field_null_fails() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        std::fs::write(
+            plugin.join(".claude-plugin").join("plugin.json"),
+            r#"{"name": "good-plugin", "version": "1.0.0", "description": "d", "license": null}"#,
+        )
+        .unwrap();
+
+        let result = validate_plugin_with(&plugin, &license_required());
+        assert_eq!(result.error_count(), 1);
+    }
+
+    #[test]
+    fn default_settings_only_require_builtin_fields() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        let result = validate_plugin_with(&plugin, &SoukConfig::default());
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn readme_title_matching_name_passes() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        std::fs::write(plugin.join("README.md"), "# Good Plugin\n\nDocs.").unwrap();
+
+        let result = check_readme_title(&plugin);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    }
+
+    #[test]
+    fn readme_title_mismatch_warns() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        std::fs::write(
+            plugin.join("README.md"),
+            "```\n# not a heading\n```\n# Unrelated Tool\n",
+        )
+        .unwrap();
+
+        let result = check_readme_title(&plugin);
+        assert!(!result.has_errors());
+        assert_eq!(result.warning_count(), 1);
+        assert!(result.diagnostics[0].message.contains("Unrelated Tool"));
+    }
+
+    fn with_schema(tmp: &TempDir, schema: &str) -> SoukConfig {
+        let path = tmp.path().join("plugin.schema.json");
+        std::fs::write(&path, schema).unwrap();
+        SoukConfig {
+            plugin_schema: Some(path),
+            ..SoukConfig::default()
+        }
+    }
+
+    const LICENSE_SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["license"],
+        "properties": {"version": {"type": "string", "pattern": "^1\\."}}
+    }"#;
+
+    #[test]
+    fn custom_schema_passes() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        std::fs::write(
+            plugin.join(".claude-plugin").join("plugin.json"),
+            r#"{"name": "good-plugin", "version": "1.0.0", "description": "A good plugin", "license": "MIT"}"#,
+        )
+        .unwrap();
+
+        let result = validate_plugin_with(&plugin, &with_schema(&tmp, LICENSE_SCHEMA));
+        assert!(!result.has_errors(), "{:?}", result.diagnostics);
+    }
+
+    #[test]
+    fn custom_schema_violations_report_pointer() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        std::fs::write(
+            plugin.join(".claude-plugin").join("plugin.json"),
+            r#"{"name": "good-plugin", "version": "2.0.0", "description": "A good plugin"}"#,
+        )
+        .unwrap();
+
+        let result = validate_plugin_with(&plugin, &with_schema(&tmp, LICENSE_SCHEMA));
+        assert_eq!(result.error_count(), 2, "{:?}", result.diagnostics);
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|d| d.message.contains("license") && d.field.is_none()));
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|d| d.field.as_deref() == Some("/version")));
+    }
+
+    #[test]
+    fn unreadable_schema_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        let settings = SoukConfig {
+            plugin_schema: Some(tmp.path().join("missing.json")),
+            ..SoukConfig::default()
+        };
+
+        let result = validate_plugin_with(&plugin, &settings);
+        assert!(result.diagnostics[0]
+            .message
+            .contains("Cannot load plugin schema"));
+
+        // Reported once per run, not once per plugin
+        let result = validate_plugin_with(&plugin, &settings.clone());
+        assert!(!result.has_errors(), "{:?}", result.diagnostics);
+    }
+
+    #[test]
+    fn schema_is_compiled_once_per_settings() {
+        let tmp = TempDir::new().unwrap();
+        let plugin = make_valid_plugin(&tmp);
+        let settings = with_schema(&tmp, LICENSE_SCHEMA);
+
+        assert!(validate_plugin_with(&plugin, &settings).has_errors());
+        // Replacing the file does not affect a run that already compiled it
+        std::fs::write(tmp.path().join("plugin.schema.json"), "{}").unwrap();
+        assert!(validate_plugin_with(&plugin, &settings).has_errors());
+        assert!(!validate_plugin_with(&plugin, &with_schema(&tmp, "{}")).has_errors());
+    }
 }
@@ -5,12 +5,73 @@
 //!
 //! Version strings are parsed with the [`semver`] crate to ensure correctness.
 //! Pre-release and build metadata are stripped on bump, following standard
-//! semver increment semantics.
+//! semver increment semantics; [`retain_prerelease`] re-applies the original
+//! pre-release for callers that want `1.2.3-beta.1` to become `1.2.4-beta.1`.
+//! [`bump_prerelease`] and [`finalize_prerelease`] cut and release
+//! pre-releases such as `1.2.0-rc.1`, and [`VersionBump`] names any of these
+//! changes for `souk update`.
 
 use std::collections::HashSet;
+use std::fmt;
+
+use semver::{BuildMetadata, Prerelease, Version};
 
 use crate::SoukError;
 
+/// A version change `souk update` applies to plugin.json.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionBump {
+    /// See [`bump_major`].
+    Major,
+    /// See [`bump_minor`].
+    Minor,
+    /// See [`bump_patch`].
+    Patch,
+    /// Start or advance a pre-release with this label; see [`bump_prerelease`].
+    Prerelease(String),
+    /// Drop the pre-release; see [`finalize_prerelease`].
+    Release,
+}
+
+impl VersionBump {
+    /// Applies the bump to `version`.
+    ///
+    /// With `keep_prerelease`, a major, minor, or patch bump keeps the
+    /// original pre-release (see [`retain_prerelease`]); it has no effect on
+    /// the other bumps.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `version` is not valid semver, or as described
+    /// on the underlying function.
+    pub fn apply(&self, version: &str, keep_prerelease: bool) -> Result<String, SoukError> {
+        let bumped = match self {
+            VersionBump::Major => bump_major(version)?,
+            VersionBump::Minor => bump_minor(version)?,
+            VersionBump::Patch => bump_patch(version)?,
+            VersionBump::Prerelease(label) => return bump_prerelease(version, label),
+            VersionBump::Release => return finalize_prerelease(version),
+        };
+        if keep_prerelease {
+            retain_prerelease(version, &bumped)
+        } else {
+            Ok(bumped)
+        }
+    }
+}
+
+impl fmt::Display for VersionBump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionBump::Major => f.write_str("major"),
+            VersionBump::Minor => f.write_str("minor"),
+            VersionBump::Patch => f.write_str("patch"),
+            VersionBump::Prerelease(label) => write!(f, "prerelease ({label})"),
+            VersionBump::Release => f.write_str("release"),
+        }
+    }
+}
+
 /// Bumps the major component of a semver version string.
 ///
 /// The minor and patch components are reset to zero and any pre-release /
@@ -79,6 +140,110 @@ pub fn bump_patch(version: &str) -> Result<String, SoukError> {
     Ok(bumped.to_string())
 }
 
+/// Re-applies the pre-release of `original` to an already-bumped version.
+///
+/// Use with [`bump_major`], [`bump_minor`], or [`bump_patch`] to keep a
+/// pre-release tag across a bump. Build metadata is still dropped. If
+/// `original` has no pre-release, `bumped` is returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use souk_core::version::{bump_patch, retain_prerelease};
+/// let v = "1.2.3-beta.1";
+/// assert_eq!(retain_prerelease(v, &bump_patch(v).unwrap()).unwrap(), "1.2.4-beta.1");
+/// ```
+///
+/// # Errors
+///
+/// Returns [`SoukError::Semver`] if either string is not a valid semver version.
+pub fn retain_prerelease(original: &str, bumped: &str) -> Result<String, SoukError> {
+    let original = semver::Version::parse(original)?;
+    let mut bumped = semver::Version::parse(bumped)?;
+    bumped.pre = original.pre;
+    bumped.build = semver::BuildMetadata::EMPTY;
+    Ok(bumped.to_string())
+}
+
+/// Starts or advances a pre-release labelled `label`.
+///
+/// A release gets `<label>.1` without changing its major, minor, or patch.
+/// A pre-release that already starts with `label` has its last numeric
+/// identifier incremented, or `.1` appended if it has none; any other
+/// pre-release switches to `<label>.1`. Build metadata is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use souk_core::version::bump_prerelease;
+/// assert_eq!(bump_prerelease("1.2.0", "rc").unwrap(), "1.2.0-rc.1");
+/// assert_eq!(bump_prerelease("1.2.0-rc.1", "rc").unwrap(), "1.2.0-rc.2");
+/// assert_eq!(bump_prerelease("1.2.0-beta.3", "rc").unwrap(), "1.2.0-rc.1");
+/// ```
+///
+/// # Errors
+///
+/// Returns [`SoukError::Semver`] if `version` is not valid semver or
+/// `label` is not a valid pre-release, and [`SoukError::Other`] if `label`
+/// is empty or the new pre-release would sort before the current one (e.g.
+/// `rc.1` to `beta.1`).
+pub fn bump_prerelease(version: &str, label: &str) -> Result<String, SoukError> {
+    let mut current = Version::parse(version)?;
+    current.build = BuildMetadata::EMPTY;
+    if label.is_empty() {
+        return Err(SoukError::Other(
+            "Pre-release label cannot be empty".to_string(),
+        ));
+    }
+    Prerelease::new(label)?;
+
+    let existing = current.pre.as_str();
+    let next = match existing
+        .strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix('.'))
+    {
+        Some(rest) => {
+            let mut identifiers: Vec<String> = rest.split('.').map(str::to_string).collect();
+            match identifiers.last().and_then(|last| last.parse::<u64>().ok()) {
+                Some(n) => *identifiers.last_mut().unwrap() = (n + 1).to_string(),
+                None => identifiers.push("1".to_string()),
+            }
+            format!("{label}.{}", identifiers.join("."))
+        }
+        None => format!("{label}.1"),
+    };
+
+    let mut bumped = Version::new(current.major, current.minor, current.patch);
+    bumped.pre = Prerelease::new(&next)?;
+    if !current.pre.is_empty() && bumped <= current {
+        return Err(SoukError::Other(format!(
+            "Pre-release {bumped} would not come after {current}"
+        )));
+    }
+    Ok(bumped.to_string())
+}
+
+/// Releases a pre-release by dropping its pre-release and build metadata.
+///
+/// # Examples
+///
+/// ```
+/// # use souk_core::version::finalize_prerelease;
+/// assert_eq!(finalize_prerelease("1.2.0-rc.2").unwrap(), "1.2.0");
+/// ```
+///
+/// # Errors
+///
+/// Returns [`SoukError::Semver`] if `version` is not valid semver, and
+/// [`SoukError::Other`] if it is not a pre-release.
+pub fn finalize_prerelease(version: &str) -> Result<String, SoukError> {
+    let v = Version::parse(version)?;
+    if v.pre.is_empty() {
+        return Err(SoukError::Other(format!("{version} is not a pre-release")));
+    }
+    Ok(Version::new(v.major, v.minor, v.patch).to_string())
+}
+
 /// Generates a unique name by appending a numeric suffix if `base` already
 /// exists in `existing`.
 ///
@@ -225,6 +390,162 @@ mod tests {
         assert_eq!(bump_patch("1.0.0-alpha+build.1").unwrap(), "1.0.1");
     }
 
+    // -----------------------------------------------------------------------
+    // retain_prerelease
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn retain_prerelease_major() {
+        let v = "1.2.3-beta.1";
+        assert_eq!(bump_major(v).unwrap(), "2.0.0");
+        assert_eq!(
+            retain_prerelease(v, &bump_major(v).unwrap()).unwrap(),
+            "2.0.0-beta.1"
+        );
+    }
+
+    #[test]
+    fn retain_prerelease_minor() {
+        let v = "2.0.0-rc.1";
+        assert_eq!(bump_minor(v).unwrap(), "2.1.0");
+        assert_eq!(
+            retain_prerelease(v, &bump_minor(v).unwrap()).unwrap(),
+            "2.1.0-rc.1"
+        );
+    }
+
+    #[test]
+    fn retain_prerelease_patch() {
+        let v = "3.1.4-alpha";
+        assert_eq!(bump_patch(v).unwrap(), "3.1.5");
+        assert_eq!(
+            retain_prerelease(v, &bump_patch(v).unwrap()).unwrap(),
+            "3.1.5-alpha"
+        );
+    }
+
+    #[test]
+    fn retain_prerelease_drops_build_metadata() {
+        let v = "1.0.0-beta+build.7";
+        assert_eq!(
+            retain_prerelease(v, &bump_patch(v).unwrap()).unwrap(),
+            "1.0.1-beta"
+        );
+    }
+
+    #[test]
+    fn retain_prerelease_without_prerelease_is_plain_bump() {
+        assert_eq!(retain_prerelease("1.2.3", "1.2.4").unwrap(), "1.2.4");
+    }
+
+    #[test]
+    fn retain_prerelease_invalid() {
+        assert!(retain_prerelease("nope", "1.0.0").is_err());
+    }
+
+    // -----------------------------------------------------------------------
+    // bump_prerelease
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn bump_prerelease_starts_from_release() {
+        assert_eq!(bump_prerelease("1.2.0", "rc").unwrap(), "1.2.0-rc.1");
+    }
+
+    #[test]
+    fn bump_prerelease_increments_same_label() {
+        assert_eq!(bump_prerelease("1.2.0-rc.1", "rc").unwrap(), "1.2.0-rc.2");
+        assert_eq!(bump_prerelease("1.2.0-rc.9", "rc").unwrap(), "1.2.0-rc.10");
+    }
+
+    #[test]
+    fn bump_prerelease_increments_last_of_multi_part_identifiers() {
+        assert_eq!(
+            bump_prerelease("1.0.0-rc.1.9", "rc").unwrap(),
+            "1.0.0-rc.1.10"
+        );
+        assert_eq!(
+            bump_prerelease("1.0.0-alpha.beta.1", "alpha.beta").unwrap(),
+            "1.0.0-alpha.beta.2"
+        );
+        assert_eq!(
+            bump_prerelease("1.0.0-alpha.1.x", "alpha").unwrap(),
+            "1.0.0-alpha.1.x.1"
+        );
+    }
+
+    #[test]
+    fn bump_prerelease_numbers_a_bare_label() {
+        assert_eq!(bump_prerelease("1.0.0-rc", "rc").unwrap(), "1.0.0-rc.1");
+    }
+
+    #[test]
+    fn bump_prerelease_switches_to_later_label() {
+        assert_eq!(bump_prerelease("1.2.0-beta.3", "rc").unwrap(), "1.2.0-rc.1");
+    }
+
+    #[test]
+    fn bump_prerelease_refuses_to_go_backwards() {
+        let err = bump_prerelease("1.2.0-rc.1", "beta").unwrap_err();
+        assert!(err.to_string().contains("would not come after"), "{err}");
+        // "r" is a prefix of "rc" but not the same label
+        assert!(bump_prerelease("1.2.0-rc.1", "r").is_err());
+    }
+
+    #[test]
+    fn bump_prerelease_drops_build_metadata() {
+        assert_eq!(
+            bump_prerelease("1.2.0-rc.1+build.5", "rc").unwrap(),
+            "1.2.0-rc.2"
+        );
+    }
+
+    #[test]
+    fn bump_prerelease_rejects_invalid_labels() {
+        assert!(bump_prerelease("1.2.0", "").is_err());
+        assert!(bump_prerelease("1.2.0", "rc!").is_err());
+        assert!(bump_prerelease("1.2.0", "rc..1").is_err());
+        assert!(bump_prerelease("nope", "rc").is_err());
+    }
+
+    // -----------------------------------------------------------------------
+    // finalize_prerelease
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn finalize_prerelease_drops_prerelease_and_build() {
+        assert_eq!(finalize_prerelease("1.2.0-rc.2").unwrap(), "1.2.0");
+        assert_eq!(finalize_prerelease("1.2.0-rc.2+build.7").unwrap(), "1.2.0");
+    }
+
+    #[test]
+    fn finalize_prerelease_requires_a_prerelease() {
+        let err = finalize_prerelease("1.2.0").unwrap_err();
+        assert!(err.to_string().contains("not a pre-release"), "{err}");
+    }
+
+    // -----------------------------------------------------------------------
+    // VersionBump
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn version_bump_applies_each_kind() {
+        let v = "1.2.3-beta.1";
+        assert_eq!(VersionBump::Major.apply(v, false).unwrap(), "2.0.0");
+        assert_eq!(VersionBump::Patch.apply(v, true).unwrap(), "1.2.4-beta.1");
+        assert_eq!(
+            VersionBump::Prerelease("beta".into())
+                .apply(v, true)
+                .unwrap(),
+            "1.2.3-beta.2"
+        );
+        assert_eq!(VersionBump::Release.apply(v, false).unwrap(), "1.2.3");
+        assert_eq!(
+            VersionBump::Prerelease("rc".into()).to_string(),
+            "prerelease (rc)"
+        );
+    }
+
     // -----------------------------------------------------------------------
     // generate_unique_name
     // -----------------------------------------------------------------------
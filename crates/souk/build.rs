@@ -0,0 +1,31 @@
+//! Records the git commit souk is built from, for `souk version`.
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=SOUK_GIT_COMMIT");
+    if std::env::var_os("SOUK_GIT_COMMIT").is_some() {
+        return;
+    }
+    let git_dir = Command::new("git")
+        .args(["rev-parse", "--git-dir"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+    // Source archives (such as a published crate) have no repository.
+    let Some(git_dir) = git_dir else {
+        return;
+    };
+    println!("cargo:rerun-if-changed={git_dir}/HEAD");
+    println!("cargo:rerun-if-changed={git_dir}/refs");
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+    if let Some(commit) = commit {
+        println!("cargo:rustc-env=SOUK_GIT_COMMIT={commit}");
+    }
+}
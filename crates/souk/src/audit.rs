@@ -0,0 +1,39 @@
+//! Recording mutating commands in souk.toml's `audit_log`.
+
+use std::path::Path;
+
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::ops::audit::{record_audit, record_init_audit, AuditEntry};
+
+use crate::output::Reporter;
+
+/// Append `entry` to the audit log, if one is configured.
+///
+/// The command has already finished by the time this runs, so a log that
+/// cannot be written is only a warning.
+pub fn record(config: &MarketplaceConfig, entry: AuditEntry, reporter: &mut Reporter) {
+    if let Err(e) = record_audit(config, entry) {
+        reporter.warning(&format!("Cannot write audit log: {e}"));
+    }
+}
+
+/// Append `entry` for `souk init` at `project_root`, if the souk.toml
+/// there configures an audit log.
+pub fn record_init(project_root: &Path, entry: AuditEntry, reporter: &mut Reporter) {
+    if let Err(e) = record_init_audit(project_root, entry) {
+        reporter.warning(&format!("Cannot write audit log: {e}"));
+    }
+}
+
+/// An audit entry for `command` on `plugins` with the given outcome.
+pub fn entry<T, E: std::fmt::Display>(
+    command: &str,
+    plugins: Vec<String>,
+    result: &Result<T, E>,
+) -> AuditEntry {
+    let entry = AuditEntry::new(command, plugins);
+    match result {
+        Ok(_) => entry,
+        Err(e) => entry.failed(e),
+    }
+}
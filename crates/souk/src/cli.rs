@@ -1,4 +1,11 @@
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::time::Duration;
+
 use clap::{Parser, Subcommand, ValueEnum};
+use souk_core::ops::prune::parse_duration;
+use souk_core::ops::update::UpdateFields;
+use souk_core::review::{ModelMap, ReportFormat};
 
 #[derive(Parser, Debug)]
 #[command(name = "souk", version, about = "Plugin marketplace management CLI")]
@@ -14,28 +21,97 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub quiet: bool,
 
-    /// Color mode
+    /// Color mode; auto honors NO_COLOR and CLICOLOR_FORCE
     #[arg(long, global = true, default_value = "auto")]
     pub color: ColorMode,
 
+    /// Disable color (same as --color never)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Treat validation warnings as errors
+    #[arg(long, global = true)]
+    pub strict: bool,
+
+    /// Print only failures and summaries, hiding per-item success lines
+    #[arg(long, global = true)]
+    pub only_failures: bool,
+
     /// Path to marketplace.json (overrides auto-discovery)
     #[arg(long, global = true)]
     pub marketplace: Option<String>,
+
+    /// Run as if souk was started in DIR, like `make -C`
+    #[arg(short = 'C', long, global = true, value_name = "DIR")]
+    pub chdir: Option<String>,
 }
 
-#[derive(Debug, Clone, ValueEnum)]
+/// Arguments to the hidden `souk __complete` command, which completion
+/// scripts call to list the marketplace's plugin names.
+///
+/// It is parsed separately from [`Cli`] so it never appears in help or in
+/// generated completion scripts.
+#[derive(Parser, Debug)]
+#[command(name = "souk __complete")]
+pub struct CompleteArgs {
+    /// Path to marketplace.json (overrides auto-discovery)
+    #[arg(long)]
+    pub marketplace: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum ColorMode {
     Auto,
     Always,
     Never,
 }
 
+impl ColorMode {
+    /// Whether to force color on or off, given the values of the
+    /// `NO_COLOR` and `CLICOLOR_FORCE` environment variables.
+    ///
+    /// `Auto` turns color off when `NO_COLOR` is set and non-empty, and on
+    /// when `CLICOLOR_FORCE` is set to anything but `0`, with `NO_COLOR`
+    /// winning if both are. Otherwise it returns `None`, leaving color to
+    /// whether stdout is a terminal.
+    pub fn color_override(
+        self,
+        no_color: Option<&OsStr>,
+        clicolor_force: Option<&OsStr>,
+    ) -> Option<bool> {
+        match self {
+            ColorMode::Always => Some(true),
+            ColorMode::Never => Some(false),
+            ColorMode::Auto if no_color.is_some_and(|v| !v.is_empty()) => Some(false),
+            ColorMode::Auto if clicolor_force.is_some_and(|v| v != "0") => Some(true),
+            ColorMode::Auto => None,
+        }
+    }
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Validate plugins or marketplace
     Validate {
         #[command(subcommand)]
         target: ValidateTarget,
+
+        /// JSON Schema each plugin.json must also satisfy (overrides souk.toml plugin_schema)
+        #[arg(long, global = true)]
+        schema: Option<PathBuf>,
+
+        /// Output format; sarif and junit print one report of every diagnostic
+        #[arg(long, global = true, value_enum, default_value = "text")]
+        format: ValidateFormat,
+
+        /// JUnit testcases: one per plugin, or also one per skill, checking
+        /// each SKILL.md's frontmatter
+        #[arg(long, global = true, value_enum, default_value = "plugin")]
+        junit_granularity: JunitGranularity,
+
+        /// Warn when marketplace.json or plugin.json is not formatted as souk writes it
+        #[arg(long, global = true)]
+        check_formatting: bool,
     },
 
     /// Add plugins to the marketplace
@@ -43,9 +119,19 @@ pub enum Commands {
         /// Plugin paths to add
         plugins: Vec<String>,
 
-        /// Conflict resolution strategy
-        #[arg(long, value_enum, default_value = "abort")]
-        on_conflict: ConflictStrategy,
+        /// Read a JSON add request from stdin, e.g.
+        /// {"plugins": ["./a"], "on_conflict": "rename", "dry_run": false, "no_copy": false}
+        #[arg(long, visible_alias = "from-stdin", conflicts_with = "plugins")]
+        stdin: bool,
+
+        /// Install the named plugins from the index at this URL (as written
+        /// by `souk export`), fetching each one's git or archive source
+        #[arg(long, value_name = "INDEX_URL", conflicts_with = "stdin")]
+        from_remote: Option<String>,
+
+        /// Conflict resolution strategy [default: souk.toml on_conflict, else abort]
+        #[arg(long, value_enum)]
+        on_conflict: Option<ConflictStrategy>,
 
         /// Preview changes without executing
         #[arg(long)]
@@ -54,6 +140,22 @@ pub enum Commands {
         /// Don't copy external plugins to pluginRoot
         #[arg(long)]
         no_copy: bool,
+
+        /// Allow recording an absolute, machine-specific source with --no-copy
+        #[arg(long)]
+        confirm_external: bool,
+
+        /// Answer yes to confirmation prompts
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Skip re-validating the marketplace after writing
+        #[arg(long)]
+        no_final_validation: bool,
+
+        /// On a rollback, reload the marketplace and retry up to N times
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        retry: u32,
     },
 
     /// Remove plugins from the marketplace
@@ -68,6 +170,109 @@ pub enum Commands {
         /// Allow deleting plugin directories outside pluginRoot
         #[arg(long, requires = "delete")]
         allow_external_delete: bool,
+
+        /// Skip re-validating the marketplace after writing
+        #[arg(long)]
+        no_final_validation: bool,
+
+        /// On a rollback, reload the marketplace and retry up to N times
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        retry: u32,
+    },
+
+    /// Rename a plugin, its directory, and dependencies on it
+    Rename {
+        /// Current plugin name
+        old: String,
+
+        /// New plugin name
+        new: String,
+
+        /// On a rollback, reload the marketplace and retry up to N times
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        retry: u32,
+    },
+
+    /// Archive plugins, keeping their files but hiding them from the active list
+    Archive {
+        /// Plugin names to archive
+        plugins: Vec<String>,
+
+        /// Also move plugin directories into .souk/archive/
+        #[arg(long)]
+        move_files: bool,
+
+        /// On a rollback, reload the marketplace and retry up to N times
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        retry: u32,
+    },
+
+    /// List plugins in the marketplace
+    List {
+        /// Include archived plugins
+        #[arg(long)]
+        include_archived: bool,
+
+        /// Only list plugins with this tag (case-insensitive)
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Sort by this field instead of marketplace order
+        #[arg(long, value_enum)]
+        sort: Option<ListSort>,
+
+        /// Columns to show, comma-separated [default: name,source,tags]
+        #[arg(long, value_enum, value_delimiter = ',')]
+        columns: Option<Vec<ListColumn>>,
+
+        /// Show every column (name, source, tags, version, skills)
+        #[arg(long, conflicts_with = "columns")]
+        wide: bool,
+
+        /// Show at most N plugins, after filtering and sorting
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Skip the first M plugins, after filtering and sorting
+        #[arg(long, value_name = "M")]
+        offset: Option<usize>,
+
+        /// Print only the number of matching plugins
+        #[arg(long, conflicts_with_all = ["limit", "offset"])]
+        count: bool,
+    },
+
+    /// Search plugin names, tags, and sources, best match first
+    Search {
+        /// Text to look for; small typos are tolerated
+        query: String,
+
+        /// Search the index at this http(s):// or file:// URL (as written by
+        /// `souk export`) instead of the local marketplace
+        #[arg(long, value_name = "URL")]
+        remote: Option<String>,
+
+        /// Show at most N matches
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Skip the first M matches
+        #[arg(long, value_name = "M")]
+        offset: Option<usize>,
+
+        /// Print only the number of matches
+        #[arg(long, conflicts_with_all = ["limit", "offset"])]
+        count: bool,
+    },
+
+    /// Show everything souk resolves for a plugin
+    Info {
+        /// Plugin name or path
+        plugin: String,
+
+        /// Also show the plugin's transitive dependency tree
+        #[arg(long)]
+        tree: bool,
     },
 
     /// Update plugin metadata and bump version
@@ -75,6 +280,10 @@ pub enum Commands {
         /// Plugin names to update
         plugins: Vec<String>,
 
+        /// Update every plugin in the marketplace
+        #[arg(long, conflicts_with = "plugins")]
+        all: bool,
+
         /// Bump major version
         #[arg(long, group = "bump")]
         major: bool,
@@ -86,12 +295,60 @@ pub enum Commands {
         /// Bump patch version
         #[arg(long, group = "bump")]
         patch: bool,
+
+        /// Start or advance a pre-release (1.2.0 -> 1.2.0-rc.1 -> 1.2.0-rc.2)
+        #[arg(long, value_name = "LABEL", group = "bump")]
+        prerelease: Option<String>,
+
+        /// Release a pre-release (1.2.0-rc.2 -> 1.2.0)
+        #[arg(long, group = "bump")]
+        release: bool,
+
+        /// Keep the pre-release tag when bumping (1.2.3-beta.1 -> 1.2.4-beta.1)
+        #[arg(long, requires = "bump", conflicts_with_all = ["prerelease", "release"])]
+        keep_prerelease: bool,
+
+        /// Prepend a dated entry with this message to each bumped plugin's
+        /// CHANGELOG.md
+        #[arg(long, value_name = "MESSAGE", requires = "bump")]
+        changelog: Option<String>,
+
+        /// Entry fields to refresh from plugin.json (default: name,tags)
+        #[arg(long, value_parser = UpdateFields::parse)]
+        fields: Option<UpdateFields>,
+
+        /// Record the commit each git-sourced entry's origin now points at,
+        /// leaving its other fields and the plugin's files unchanged
+        #[arg(long, conflicts_with_all = ["bump", "fields", "bump_dep", "check"])]
+        refresh_origin_sha: bool,
+
+        /// Point the plugins' extends-plugin.json constraint on this
+        /// dependency at its current version instead of refreshing metadata
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["bump", "fields", "keep_prerelease"])]
+        bump_dep: Option<String>,
+
+        /// Report entries out of sync with plugin.json and exit 1 if any are,
+        /// without writing
+        #[arg(long, conflicts_with_all = ["bump", "bump_dep", "keep_prerelease", "no_final_validation", "retry"])]
+        check: bool,
+
+        /// Skip re-validating the marketplace after writing
+        #[arg(long)]
+        no_final_validation: bool,
+
+        /// On a rollback, reload the marketplace and retry up to N times
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        retry: u32,
     },
 
     /// AI-powered review
     Review {
         #[command(subcommand)]
-        target: ReviewTarget,
+        target: Box<ReviewTarget>,
+
+        /// Rubric file appended to the review prompt (overrides souk.toml review_rubric)
+        #[arg(long, global = true)]
+        rubric: Option<PathBuf>,
     },
 
     /// CI hook management
@@ -109,6 +366,10 @@ pub enum Commands {
         /// Custom plugin root directory name
         #[arg(long, default_value = "./plugins")]
         plugin_root: String,
+
+        /// Verify an existing scaffold instead of creating one
+        #[arg(long)]
+        check: bool,
     },
 
     /// Remove orphaned plugin directories not listed in marketplace.json
@@ -116,8 +377,81 @@ pub enum Commands {
         /// Actually delete orphaned directories (default: dry-run)
         #[arg(long)]
         apply: bool,
+        /// Only prune orphans whose newest file is older than this (e.g. 7d, 12h)
+        #[arg(long, value_parser = parse_duration)]
+        older_than: Option<Duration>,
+        /// Follow symlinks in pluginRoot, removing orphaned links (never their targets)
+        #[arg(long)]
+        dereference: bool,
+        /// Abort with an error if pluginRoot holds more than N entries
+        #[arg(long, value_name = "N")]
+        max_scan_entries: Option<usize>,
+        /// Also remove marketplace entries whose plugin directory is missing
+        #[arg(long)]
+        stale_entries: bool,
+        /// On a rollback of --stale-entries, reload the marketplace and retry up to N times
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 0,
+            requires = "stale_entries"
+        )]
+        retry: u32,
+    },
+
+    /// Add unlisted plugin directories and drop entries whose directory is gone
+    Sync {
+        /// Print the plan without changing marketplace.json
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip re-validating the marketplace after writing
+        #[arg(long)]
+        no_final_validation: bool,
+
+        /// On a rollback, reload the marketplace and retry up to N times
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        retry: u32,
+    },
+
+    /// Reformat marketplace.json and plugin.json files as souk writes them
+    Fix {
+        /// List the files that would change without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show drift between marketplace.json and the plugin directories
+    Diff,
+
+    /// Summarize plugin, skill, tag, and dependency counts
+    Stats,
+
+    /// Export the marketplace and its plugins as a single artifact
+    Export {
+        /// Output format
+        #[arg(long, value_enum, default_value = "json")]
+        format: ExportFormat,
+
+        /// Write to this file instead of stdout
+        #[arg(long)]
+        output: Option<String>,
     },
 
+    /// Exercise init, add, validate, review, remove, and prune in a temp dir
+    Selftest,
+
+    /// Check the environment and marketplace for common setup problems
+    Doctor {
+        /// Apply the safe fixes, or only those listed (comma-separated)
+        #[arg(long, value_enum, value_delimiter = ',', num_args = 0.., require_equals = true)]
+        fix: Option<Vec<DoctorFix>>,
+    },
+
+    /// Show the version and supported providers, hook managers, and CI
+    /// providers (with --json, for tooling)
+    Version,
+
     /// Generate shell completions
     Completions {
         /// Shell to generate completions for
@@ -131,12 +465,36 @@ pub enum ValidateTarget {
     Plugin {
         /// Plugin names or paths (omit for all)
         plugins: Vec<String>,
+
+        /// Warn when the README title does not match plugin.json
+        #[arg(long)]
+        check_readme_title: bool,
     },
     /// Validate the marketplace
     Marketplace {
         /// Skip validating individual plugins
         #[arg(long)]
         skip_plugins: bool,
+
+        /// Group diagnostics in the output
+        #[arg(long, value_enum)]
+        group_by: Option<GroupBy>,
+
+        /// Error on sources that are absolute paths or escape pluginRoot
+        #[arg(long)]
+        no_external_sources: bool,
+
+        /// Follow symlinks in pluginRoot when checking for orphaned directories
+        #[arg(long)]
+        dereference: bool,
+
+        /// Abort with an error if pluginRoot holds more than N entries
+        #[arg(long, value_name = "N")]
+        max_scan_entries: Option<usize>,
+
+        /// Validate every marketplace under the current directory (skips .git and node_modules)
+        #[arg(long, conflicts_with = "group_by")]
+        recursive: bool,
     },
 }
 
@@ -148,10 +506,53 @@ pub enum ReviewTarget {
         plugin: String,
         #[arg(long)]
         output_dir: Option<String>,
+        /// Report files to write to --output-dir: markdown, json, or both
+        /// [default: souk.toml report_format, else both]
+        #[arg(long, value_parser = ReportFormat::parse)]
+        format: Option<ReportFormat>,
         #[arg(long)]
         provider: Option<String>,
+        /// Provider to retry the prompt with once if --provider fails
+        #[arg(long)]
+        fallback_provider: Option<String>,
         #[arg(long)]
         model: Option<String>,
+        /// Per-scope models, e.g. "skill=gpt-4o-mini,marketplace=gpt-4o"
+        #[arg(long, value_parser = ModelMap::parse)]
+        model_map: Option<ModelMap>,
+        /// Maximum provider requests per minute
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        rate_limit: Option<u32>,
+        /// Per-request timeout, e.g. "90s" or "5m" (default: 120s)
+        #[arg(long, value_parser = parse_duration)]
+        timeout: Option<Duration>,
+        /// Check the provider accepts its credentials before building prompts
+        #[arg(long)]
+        check_auth: bool,
+        /// Reuse reviews cached in this directory when the prompt and inputs are unchanged
+        #[arg(long)]
+        cache_dir: Option<String>,
+        /// Print the effective review cache key
+        #[arg(long)]
+        verbose: bool,
+        /// Send only plugin.json to the model, skipping README, skills, and extends
+        #[arg(long)]
+        manifest_only: bool,
+        /// Wrap review text to this many columns (default: terminal width)
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..), conflicts_with = "no_wrap")]
+        wrap: Option<u16>,
+        /// Print review text without wrapping
+        #[arg(long)]
+        no_wrap: bool,
+        /// Print the assembled prompt instead of calling the provider
+        #[arg(long)]
+        print_prompt: bool,
+        /// Fail unless the review's overall rating is at least N (1-10)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=10), conflicts_with = "print_prompt")]
+        min_rating: Option<u8>,
+        /// Post the review as a comment on the pull request (GitHub Actions only)
+        #[arg(long, conflicts_with = "print_prompt")]
+        github_comment: bool,
     },
     /// Review skills in a plugin
     Skill {
@@ -161,21 +562,100 @@ pub enum ReviewTarget {
         skills: Vec<String>,
         #[arg(long)]
         all: bool,
+        /// Maximum number of skills reviewed concurrently [default: souk.toml jobs, else 4]
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: Option<u16>,
         #[arg(long)]
         output_dir: Option<String>,
+        /// Report files to write to --output-dir: markdown, json, or both
+        /// [default: souk.toml report_format, else both]
+        #[arg(long, value_parser = ReportFormat::parse)]
+        format: Option<ReportFormat>,
         #[arg(long)]
         provider: Option<String>,
+        /// Provider to retry the prompt with once if --provider fails
+        #[arg(long)]
+        fallback_provider: Option<String>,
         #[arg(long)]
         model: Option<String>,
+        /// Per-scope models, e.g. "skill=gpt-4o-mini,marketplace=gpt-4o"
+        #[arg(long, value_parser = ModelMap::parse)]
+        model_map: Option<ModelMap>,
+        /// Maximum provider requests per minute
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        rate_limit: Option<u32>,
+        /// Per-request timeout, e.g. "90s" or "5m" (default: 120s)
+        #[arg(long, value_parser = parse_duration)]
+        timeout: Option<Duration>,
+        /// Check the provider accepts its credentials before building prompts
+        #[arg(long)]
+        check_auth: bool,
+        /// Wrap review text to this many columns (default: terminal width)
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..), conflicts_with = "no_wrap")]
+        wrap: Option<u16>,
+        /// Print review text without wrapping
+        #[arg(long)]
+        no_wrap: bool,
+        /// Print the assembled prompt instead of calling the provider
+        #[arg(long)]
+        print_prompt: bool,
+        /// Fail unless the review's overall rating is at least N (1-10)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=10), conflicts_with = "print_prompt")]
+        min_rating: Option<u8>,
     },
     /// Review the entire marketplace
     Marketplace {
+        /// Request and print only the overall rating and a 3-bullet summary
+        #[arg(long)]
+        summary_only: bool,
+        /// Also review every plugin listed in the marketplace
+        #[arg(long, conflicts_with = "print_prompt")]
+        recursive: bool,
+        /// Maximum number of plugins reviewed concurrently with --recursive
+        /// [default: souk.toml jobs, else 4]
+        #[arg(long, requires = "recursive", value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: Option<u16>,
+        /// Reuse plugin reviews cached in this directory with --recursive
+        #[arg(long, requires = "recursive")]
+        cache_dir: Option<String>,
         #[arg(long)]
         output_dir: Option<String>,
+        /// Report files to write to --output-dir: markdown, json, or both
+        /// [default: souk.toml report_format, else both]
+        #[arg(long, value_parser = ReportFormat::parse)]
+        format: Option<ReportFormat>,
         #[arg(long)]
         provider: Option<String>,
+        /// Provider to retry the prompt with once if --provider fails
+        #[arg(long)]
+        fallback_provider: Option<String>,
         #[arg(long)]
         model: Option<String>,
+        /// Per-scope models, e.g. "plugin=gpt-4o-mini,marketplace=gpt-4o";
+        /// plugins reviewed with --recursive use the plugin model
+        #[arg(long, value_parser = ModelMap::parse)]
+        model_map: Option<ModelMap>,
+        /// Maximum provider requests per minute
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        rate_limit: Option<u32>,
+        /// Per-request timeout, e.g. "90s" or "5m" (default: 120s)
+        #[arg(long, value_parser = parse_duration)]
+        timeout: Option<Duration>,
+        /// Check the provider accepts its credentials before building prompts
+        #[arg(long)]
+        check_auth: bool,
+        /// Wrap review text to this many columns (default: terminal width)
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..), conflicts_with = "no_wrap")]
+        wrap: Option<u16>,
+        /// Print review text without wrapping
+        #[arg(long)]
+        no_wrap: bool,
+        /// Print the assembled prompt instead of calling the provider
+        #[arg(long)]
+        print_prompt: bool,
+        /// Fail unless the review's overall rating is at least N (1-10)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=10), conflicts_with = "print_prompt")]
+        min_rating: Option<u8>,
     },
 }
 
@@ -185,6 +665,10 @@ pub enum CiAction {
     Run {
         #[command(subcommand)]
         hook: CiHook,
+
+        /// Output format; sarif and junit print one report of every diagnostic
+        #[arg(long, global = true, value_enum, default_value = "text")]
+        format: ValidateFormat,
     },
     /// Install CI integration
     Install {
@@ -198,7 +682,11 @@ pub enum CiHook {
     /// Run pre-commit validation
     PreCommit,
     /// Run pre-push validation
-    PrePush,
+    PrePush {
+        /// Report how long validation took and how many threads it used
+        #[arg(long)]
+        verbose: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -242,3 +730,90 @@ pub enum ConflictStrategy {
     Replace,
     Rename,
 }
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum GroupBy {
+    Plugin,
+}
+
+/// Output formats of `souk validate` beyond the global `--json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ValidateFormat {
+    /// Human-readable output (or souk's JSON with --json)
+    Text,
+    /// SARIF 2.1.0, for GitHub code scanning
+    Sarif,
+    /// JUnit XML, for CI test reporters
+    Junit,
+}
+
+/// What each testcase of `souk validate --format junit` covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum JunitGranularity {
+    /// One testcase per plugin
+    Plugin,
+    /// One testcase per plugin, plus one per skill
+    Skill,
+}
+
+/// Remediations `souk doctor --fix` can apply. None deletes anything the
+/// user wrote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DoctorFix {
+    /// Create a missing pluginRoot directory
+    PluginRoot,
+    /// Remove backups left by an interrupted command
+    Backups,
+    /// Remove staging directories left in the system temp directory
+    TempDirs,
+    /// Remove marketplace entries whose plugin directory is missing
+    StaleEntries,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListSort {
+    Name,
+    Source,
+}
+
+/// Columns of `souk list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListColumn {
+    Name,
+    Source,
+    Tags,
+    /// plugin.json `version`, read from disk
+    Version,
+    /// Number of skills, counted on disk
+    Skills,
+}
+
+impl ListColumn {
+    /// Columns shown without `--columns`.
+    pub const DEFAULT: [ListColumn; 3] = [ListColumn::Name, ListColumn::Source, ListColumn::Tags];
+
+    /// Every column, shown with `--wide`.
+    pub const ALL: [ListColumn; 5] = [
+        ListColumn::Name,
+        ListColumn::Source,
+        ListColumn::Tags,
+        ListColumn::Version,
+        ListColumn::Skills,
+    ];
+
+    /// The column's JSON key.
+    pub fn key(self) -> &'static str {
+        match self {
+            ListColumn::Name => "name",
+            ListColumn::Source => "source",
+            ListColumn::Tags => "tags",
+            ListColumn::Version => "version",
+            ListColumn::Skills => "skills",
+        }
+    }
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+}
@@ -1,31 +1,125 @@
 //! Handler for the `souk add` CLI command.
 
+use std::io::{IsTerminal, Read};
+
+use clap::ValueEnum;
+
 use crate::cli::ConflictStrategy;
-use crate::output::Reporter;
+use crate::output::{OutputMode, Reporter};
 use souk_core::discovery::MarketplaceConfig;
-use souk_core::ops::add::{execute_add, plan_add, ConflictResolution};
+use souk_core::ops::add::{execute_add, plan_add, AddPlan, AddRequest, ConflictResolution};
+use souk_core::ops::retry_on_rollback;
+use souk_core::remote::{fetch_index, index_cache_dir};
+use souk_core::resolution::expand_plugin_patterns;
+use souk_core::ExitStatus;
+
+/// Flags controlling how `souk add` executes its plan.
+pub struct AddOptions {
+    /// Preview changes without executing.
+    pub dry_run: bool,
+    /// Reference external plugins by absolute path instead of copying them.
+    pub no_copy: bool,
+    /// Re-validate the marketplace after writing.
+    pub validate_after: bool,
+    /// Allow recording machine-specific absolute sources without prompting.
+    pub confirm_external: bool,
+    /// Times to reload the marketplace, re-plan, and retry after a rollback.
+    pub retries: u32,
+}
 
 /// Run the add command, adding plugins to the marketplace.
 ///
-/// Returns `true` on success, `false` on failure.
+/// Wildcard arguments such as `lint-*` select directories under pluginRoot
+/// by name; see [`expand_plugin_patterns`]. Name conflicts are resolved
+/// with `on_conflict` (`--on-conflict`), else souk.toml's `on_conflict`,
+/// else by aborting.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
 pub fn run_add(
     plugins: &[String],
-    on_conflict: &ConflictStrategy,
-    dry_run: bool,
-    no_copy: bool,
+    on_conflict: Option<&ConflictStrategy>,
+    options: &AddOptions,
     config: &MarketplaceConfig,
     reporter: &mut Reporter,
-) -> bool {
+) -> ExitStatus {
     if plugins.is_empty() {
         reporter.error("At least one plugin argument is required");
-        return false;
+        return ExitStatus::Usage;
     }
 
+    let plugins = match expand_plugin_patterns(plugins, &plugin_root_dirs(config)) {
+        Ok(p) => p,
+        Err(e) => {
+            reporter.error(&format!("{e}"));
+            return ExitStatus::Usage;
+        }
+    };
+    add_plugins(&plugins, on_conflict, options, config, reporter)
+}
+
+/// Run `souk add --from-remote`, installing the plugins called `names` from
+/// the index at `url` through the git and archive pipelines of [`run_add`].
+///
+/// Every name must be listed in the index with a git or archive URL as its
+/// source.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_add_from_remote(
+    url: &str,
+    names: &[String],
+    on_conflict: Option<&ConflictStrategy>,
+    options: &AddOptions,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    if names.is_empty() {
+        reporter.error("At least one plugin name is required with --from-remote");
+        return ExitStatus::Usage;
+    }
+    let index = match fetch_index(url, &index_cache_dir()) {
+        Ok(index) => index,
+        Err(e) => {
+            reporter.error(&format!("Add failed: {e}"));
+            return e.exit_status();
+        }
+    };
+    let sources: Result<Vec<String>, _> = names
+        .iter()
+        .map(|name| index.installable_source(name).map(str::to_string))
+        .collect();
+    match sources {
+        Ok(sources) => add_plugins(&sources, on_conflict, options, config, reporter),
+        Err(e) => {
+            reporter.error(&format!("Add failed: {e}"));
+            ExitStatus::Usage
+        }
+    }
+}
+
+/// Plan and execute adding `plugins`, already expanded from any patterns.
+fn add_plugins(
+    plugins: &[String],
+    on_conflict: Option<&ConflictStrategy>,
+    options: &AddOptions,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let AddOptions {
+        dry_run,
+        no_copy,
+        validate_after,
+        retries,
+        ..
+    } = *options;
+
     let strategy = match on_conflict {
-        ConflictStrategy::Abort => "abort",
-        ConflictStrategy::Skip => "skip",
-        ConflictStrategy::Replace => "replace",
-        ConflictStrategy::Rename => "rename",
+        Some(ConflictStrategy::Abort) => "abort",
+        Some(ConflictStrategy::Skip) => "skip",
+        Some(ConflictStrategy::Replace) => "replace",
+        Some(ConflictStrategy::Rename) => "rename",
+        None => config.settings.on_conflict.as_deref().unwrap_or("abort"),
     };
 
     reporter.section("Pre-flight Validation");
@@ -34,13 +128,13 @@ pub fn run_add(
         Ok(p) => p,
         Err(e) => {
             reporter.error(&format!("{e}"));
-            return false;
+            return e.exit_status();
         }
     };
 
     if plan.actions.is_empty() {
         reporter.warning("No plugins to add");
-        return true;
+        return ExitStatus::Success;
     }
 
     // Report plan
@@ -67,11 +161,51 @@ pub fn run_add(
         reporter.info(&format!("{}: {status}", action.plugin_name));
     }
 
+    for warning in &plan.warnings {
+        reporter.warning(&format!("Recommended but missing: {warning}"));
+    }
+
+    for action in plan.absolute_sources() {
+        reporter.warning(&format!(
+            "{} will be recorded with absolute source {}, which only resolves on this machine",
+            action.plugin_name, action.source
+        ));
+    }
+    let confirm_external = options.confirm_external || confirm_absolute_sources(&plan, reporter);
+
     if dry_run {
         reporter.section("Dry Run");
+    } else if !validate_after {
+        reporter.warning("Skipping final marketplace validation; integrity is not re-checked");
     }
 
-    match execute_add(&plan, config, dry_run) {
+    let mut attempts = 0;
+    let result = retry_on_rollback(
+        retries,
+        config,
+        |attempt, e| reporter.retrying(attempt, retries, e),
+        |current| {
+            attempts += 1;
+            if attempts == 1 {
+                return execute_add(&plan, current, dry_run, validate_after, confirm_external);
+            }
+            let plan = plan_add(plugins, current, strategy, no_copy)?;
+            execute_add(&plan, current, dry_run, validate_after, confirm_external)
+        },
+    );
+    if !dry_run {
+        let plugins = match &result {
+            Ok(added) => added.clone(),
+            Err(_) => plan.actions.iter().map(|a| a.plugin_name.clone()).collect(),
+        };
+        crate::audit::record(
+            config,
+            crate::audit::entry("add", plugins, &result),
+            reporter,
+        );
+    }
+
+    match result {
         Ok(added) => {
             if dry_run {
                 for name in &added {
@@ -85,16 +219,93 @@ pub fn run_add(
                 for name in &added {
                     reporter.success(&format!("Added: {name}"));
                 }
-                reporter.success(&format!(
+                reporter.summary(&format!(
                     "Successfully added {} plugin(s) to marketplace",
                     added.len()
                 ));
             }
-            true
+            ExitStatus::Success
         }
         Err(e) => {
             reporter.error(&format!("Add failed: {e}"));
-            false
+            e.exit_status()
+        }
+    }
+}
+
+/// Run the add command with plugins and settings read from a JSON
+/// [`AddRequest`] on stdin.
+///
+/// Fields set in the request override the matching command-line flags;
+/// `dry_run` and `no_copy` can only be switched on, not off.
+pub fn run_add_stdin(
+    on_conflict: Option<&ConflictStrategy>,
+    mut options: AddOptions,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let mut input = String::new();
+    if let Err(e) = std::io::stdin().read_to_string(&mut input) {
+        reporter.error(&format!("Cannot read add request from stdin: {e}"));
+        return ExitStatus::Internal;
+    }
+    let request = match AddRequest::from_json(&input) {
+        Ok(r) => r,
+        Err(e) => {
+            reporter.error(&format!("{e}"));
+            return ExitStatus::Usage;
         }
+    };
+
+    let on_conflict = match request.on_conflict.as_deref() {
+        Some(name) => match ConflictStrategy::from_str(name, false) {
+            Ok(strategy) => Some(strategy),
+            Err(e) => {
+                reporter.error(&format!("Invalid conflict strategy: {e}"));
+                return ExitStatus::Usage;
+            }
+        },
+        None => on_conflict.cloned(),
+    };
+    options.dry_run |= request.dry_run;
+    options.no_copy |= request.no_copy;
+
+    run_add(
+        &request.plugins,
+        on_conflict.as_ref(),
+        &options,
+        config,
+        reporter,
+    )
+}
+
+/// Names of the directories directly under pluginRoot, sorted.
+fn plugin_root_dirs(config: &MarketplaceConfig) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(&config.plugin_root_abs)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Ask interactively whether to record absolute sources.
+///
+/// Only prompts in human mode on a terminal; otherwise returns `false` so
+/// `--confirm-external` (or `--yes`) is required.
+fn confirm_absolute_sources(plan: &AddPlan, reporter: &Reporter) -> bool {
+    if plan.absolute_sources().next().is_none()
+        || reporter.mode() != OutputMode::Human
+        || !std::io::stdin().is_terminal()
+    {
+        return false;
     }
+    dialoguer::Confirm::new()
+        .with_prompt("Record machine-specific absolute path(s) in marketplace.json?")
+        .default(false)
+        .interact()
+        .unwrap_or(false)
 }
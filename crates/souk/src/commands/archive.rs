@@ -0,0 +1,65 @@
+//! Handler for the `souk archive` CLI command.
+
+use crate::output::Reporter;
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::ops::archive::archive_plugins;
+use souk_core::ops::retry_on_rollback;
+use souk_core::ExitStatus;
+
+/// Run the archive command, moving plugins to the marketplace's archived list.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_archive(
+    plugins: &[String],
+    move_files: bool,
+    retries: u32,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    if plugins.is_empty() {
+        reporter.error("At least one plugin name is required");
+        return ExitStatus::Usage;
+    }
+
+    reporter.section("Archiving Plugins");
+
+    let result = retry_on_rollback(
+        retries,
+        config,
+        |attempt, e| reporter.retrying(attempt, retries, e),
+        |current| archive_plugins(plugins, move_files, current),
+    );
+    let archived = match &result {
+        Ok(result) => result.archived.clone(),
+        Err(_) => plugins.to_vec(),
+    };
+    crate::audit::record(
+        config,
+        crate::audit::entry("archive", archived, &result),
+        reporter,
+    );
+
+    match result {
+        Ok(result) => {
+            for name in &result.archived {
+                reporter.success(&format!("Archived: {name}"));
+            }
+            for (from, to) in &result.moved {
+                reporter.info(&format!(
+                    "Moved {} -> {}",
+                    config.display_path(from),
+                    config.display_path(to)
+                ));
+            }
+            for warn in &result.warnings {
+                reporter.warning(warn);
+            }
+            ExitStatus::Success
+        }
+        Err(e) => {
+            reporter.error(&format!("Archive failed: {e}"));
+            e.exit_status()
+        }
+    }
+}
@@ -5,70 +5,125 @@
 //! - `souk ci install hooks [--native|--lefthook|--husky|...]`
 //! - `souk ci install workflows [--github|--circleci|--gitlab|...]`
 
-use std::env;
-use std::path::PathBuf;
+use std::time::Instant;
 
 use souk_core::ci::install_hooks::{detect_hook_manager, install_hooks, HookManager};
 use souk_core::ci::install_workflows::{detect_ci_provider, install_workflow, CiProvider};
-use souk_core::discovery::{discover_marketplace, load_marketplace_config, MarketplaceConfig};
+use souk_core::ci::{detect_changed_plugins, is_marketplace_staged};
+use souk_core::ExitStatus;
 
-use crate::cli::CiInstallTarget;
+use crate::cli::{CiInstallTarget, ValidateFormat};
+use crate::commands::validate::{emit_report, ReportScope};
 use crate::output::Reporter;
 
 /// Run pre-commit validation.
 ///
 /// Detects plugins with staged changes and validates only those.
 /// If marketplace.json is staged, validates marketplace structure too.
-pub fn run_pre_commit(marketplace_override: Option<&str>, reporter: &mut Reporter) -> bool {
-    let config = match load_config_required(marketplace_override, reporter) {
-        Some(c) => c,
-        None => return false,
+/// A SARIF or JUnit `format` replaces the usual output with a report.
+pub fn run_pre_commit(
+    strict: bool,
+    format: ValidateFormat,
+    marketplace_override: Option<&str>,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let config = match crate::load_config_required(marketplace_override, reporter) {
+        Ok(c) => c,
+        Err(status) => return status,
     };
 
     reporter.section("Pre-commit validation");
 
     let result = souk_core::ci::run_pre_commit(&config);
-    reporter.report_validation(&result);
+    if format != ValidateFormat::Text {
+        let plugins = detect_changed_plugins(&config).unwrap_or_default();
+        let marketplace_staged = is_marketplace_staged(&config).unwrap_or(false);
+        let scope = ReportScope {
+            root: &config.project_root,
+            marketplace: marketplace_staged.then_some(config.marketplace_path.as_path()),
+            plugins: &plugins,
+            skills: &[],
+        };
+        emit_report(format, &result, &scope, reporter);
+        return result.outcome(strict).into();
+    }
+    reporter.report_validation(&result, Some(&config));
 
-    if result.has_errors() {
+    if !result.outcome(strict).is_success() {
         reporter.error("Pre-commit validation failed");
-        false
+        ExitStatus::Findings
     } else {
         reporter.success("Pre-commit validation passed");
-        true
+        ExitStatus::Success
     }
 }
 
 /// Run pre-push validation.
 ///
-/// Performs full marketplace validation including all plugins.
-pub fn run_pre_push(marketplace_override: Option<&str>, reporter: &mut Reporter) -> bool {
-    let config = match load_config_required(marketplace_override, reporter) {
-        Some(c) => c,
-        None => return false,
+/// Performs full marketplace validation including all plugins, validating
+/// plugins in parallel. With `verbose`, reports the elapsed time. A SARIF
+/// or JUnit `format` replaces the usual output with a report.
+pub fn run_pre_push(
+    strict: bool,
+    verbose: bool,
+    format: ValidateFormat,
+    marketplace_override: Option<&str>,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let config = match crate::load_config_required(marketplace_override, reporter) {
+        Ok(c) => c,
+        Err(status) => return status,
     };
 
     reporter.section("Pre-push validation");
 
+    let started = Instant::now();
     let result = souk_core::ci::run_pre_push(&config);
-    reporter.report_validation(&result);
+    if format != ValidateFormat::Text {
+        let plugins: Vec<String> = config
+            .marketplace
+            .plugins
+            .iter()
+            .map(|p| p.name.clone())
+            .collect();
+        let scope = ReportScope {
+            root: &config.project_root,
+            marketplace: Some(&config.marketplace_path),
+            plugins: &plugins,
+            skills: &[],
+        };
+        emit_report(format, &result, &scope, reporter);
+        return result.outcome(strict).into();
+    }
+    reporter.report_validation(&result, Some(&config));
+    if verbose {
+        reporter.info(&format!(
+            "Validated {} plugin(s) on {} thread(s) in {:.2}s",
+            config.marketplace.plugins.len(),
+            souk_core::ci::pre_push_jobs(),
+            started.elapsed().as_secs_f64()
+        ));
+    }
 
-    if result.has_errors() {
+    if !result.outcome(strict).is_success() {
         reporter.error("Pre-push validation failed. Use 'git push --no-verify' to skip.");
-        false
+        ExitStatus::Findings
     } else {
         reporter.success("Pre-push validation passed");
-        true
+        ExitStatus::Success
     }
 }
 
 /// Install CI integration (hooks or workflows).
-pub fn run_ci_install(target: &CiInstallTarget, reporter: &mut Reporter) -> bool {
-    let cwd = match env::current_dir() {
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_ci_install(target: &CiInstallTarget, reporter: &mut Reporter) -> ExitStatus {
+    let cwd = match crate::cwd::current_dir() {
         Ok(c) => c,
         Err(e) => {
             reporter.error(&format!("Cannot get current directory: {e}"));
-            return false;
+            return ExitStatus::Internal;
         }
     };
 
@@ -112,11 +167,11 @@ pub fn run_ci_install(target: &CiInstallTarget, reporter: &mut Reporter) -> bool
             match install_hooks(&cwd, &manager) {
                 Ok(msg) => {
                     reporter.success(&msg);
-                    true
+                    ExitStatus::Success
                 }
                 Err(e) => {
                     reporter.error(&format!("Failed to install hooks: {e}"));
-                    false
+                    e.exit_status()
                 }
             }
         }
@@ -159,44 +214,13 @@ pub fn run_ci_install(target: &CiInstallTarget, reporter: &mut Reporter) -> bool
             match install_workflow(&cwd, &provider) {
                 Ok(msg) => {
                     reporter.success(&msg);
-                    true
+                    ExitStatus::Success
                 }
                 Err(e) => {
                     reporter.error(&format!("Failed to install workflow: {e}"));
-                    false
+                    e.exit_status()
                 }
             }
         }
     }
 }
-
-fn load_config_required(
-    marketplace_override: Option<&str>,
-    reporter: &mut Reporter,
-) -> Option<MarketplaceConfig> {
-    let mp_path = if let Some(path) = marketplace_override {
-        PathBuf::from(path)
-    } else {
-        let cwd = match std::env::current_dir() {
-            Ok(c) => c,
-            Err(e) => {
-                reporter.error(&format!("Cannot get current directory: {e}"));
-                return None;
-            }
-        };
-        match discover_marketplace(&cwd) {
-            Ok(p) => p,
-            Err(e) => {
-                reporter.error(&format!("{e}"));
-                return None;
-            }
-        }
-    };
-    match load_marketplace_config(&mp_path) {
-        Ok(c) => Some(c),
-        Err(e) => {
-            reporter.error(&format!("Failed to load marketplace: {e}"));
-            None
-        }
-    }
-}
@@ -0,0 +1,132 @@
+//! Handlers for `souk completions` and the hidden `souk __complete`.
+//!
+//! clap's generated scripts only know the static command line. For the
+//! commands that take plugin names (`remove`, `archive`, and `update`), the
+//! bash, zsh, and fish scripts are extended to call `souk __complete`, which
+//! prints the current marketplace's plugin names.
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::ExitStatus;
+
+use crate::cli::Cli;
+
+/// The hidden command completion scripts call for plugin names. `main`
+/// dispatches it before parsing [`Cli`].
+pub const COMPLETE_COMMAND: &str = "__complete";
+
+/// Subcommands whose positional arguments are plugin names.
+const PLUGIN_NAME_COMMANDS: &str = "remove archive update";
+
+/// Run the completions command, printing a completion script for `shell`.
+pub fn run_completions(shell: Shell) {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), "souk", &mut script);
+    let script = String::from_utf8_lossy(&script);
+    print!("{}", with_plugin_names(shell, &script));
+}
+
+/// Run the hidden `souk __complete` command, printing one active plugin
+/// name per line.
+pub fn run_complete(config: &MarketplaceConfig) -> ExitStatus {
+    for entry in &config.marketplace.plugins {
+        println!("{}", entry.name);
+    }
+    ExitStatus::Success
+}
+
+/// Extend clap's static `script` for `shell` to complete plugin names.
+/// Shells without plugin-name support get the script unchanged.
+fn with_plugin_names(shell: Shell, script: &str) -> String {
+    match shell {
+        Shell::Bash => format!(
+            "{script}
+_souk_with_plugins() {{
+    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\" cmd=\"\" word skip=\"\"
+    for word in \"${{COMP_WORDS[@]:1:COMP_CWORD-1}}\"; do
+        if [[ -n \"$skip\" ]]; then skip=\"\"; continue; fi
+        case \"$word\" in
+            --marketplace|--color) skip=1 ;;
+            -*) ;;
+            *) cmd=\"$word\"; break ;;
+        esac
+    done
+    if [[ \" {PLUGIN_NAME_COMMANDS} \" == *\" $cmd \"* && \"$cur\" != -* ]]; then
+        COMPREPLY=( $(compgen -W \"$(souk __complete 2>/dev/null)\" -- \"$cur\") )
+        [[ ${{#COMPREPLY[@]}} -gt 0 ]] && return 0
+    fi
+    _souk \"$@\"
+}}
+complete -F _souk_with_plugins -o bashdefault -o default souk
+"
+        ),
+        Shell::Zsh => {
+            let helper = "_souk_plugin_names() {
+    local -a plugins
+    plugins=(${(f)\"$(souk __complete 2>/dev/null)\"})
+    _describe -t plugins 'plugin' plugins
+}
+";
+            let body: String = script
+                .lines()
+                .map(|line| {
+                    if PLUGIN_NAME_COMMANDS
+                        .split_whitespace()
+                        .any(|cmd| line.contains(&format!("::plugins -- Plugin names to {cmd}:")))
+                    {
+                        line.replace(":_default'", ":_souk_plugin_names'")
+                    } else {
+                        line.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            // Keep `#compdef` on the first line so the file still autoloads.
+            match body.split_once('\n') {
+                Some((first, rest)) => format!("{first}\n\n{helper}{rest}\n"),
+                None => format!("{body}\n{helper}"),
+            }
+        }
+        Shell::Fish => {
+            format!(
+                "{script}complete -c souk -n \"__fish_seen_subcommand_from {PLUGIN_NAME_COMMANDS}\" \
+                 -f -a \"(souk __complete 2>/dev/null)\"\n"
+            )
+        }
+        _ => script.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(shell: Shell) -> String {
+        let mut script = Vec::new();
+        clap_complete::generate(shell, &mut Cli::command(), "souk", &mut script);
+        with_plugin_names(shell, &String::from_utf8(script).unwrap())
+    }
+
+    #[test]
+    fn zsh_completes_plugin_names_for_remove_archive_update() {
+        let script = generate(Shell::Zsh);
+        assert!(script.starts_with("#compdef souk\n"));
+        assert!(script.contains("_souk_plugin_names() {"));
+        for cmd in ["remove", "archive", "update"] {
+            assert!(
+                script.contains(&format!("Plugin names to {cmd}:_souk_plugin_names'")),
+                "{cmd}"
+            );
+        }
+        assert!(script.contains("Plugin paths to add:_default'"));
+    }
+
+    #[test]
+    fn bash_and_fish_call_hidden_command() {
+        assert!(generate(Shell::Bash).contains("$(souk __complete 2>/dev/null)"));
+        assert!(generate(Shell::Fish).contains(
+            "__fish_seen_subcommand_from remove archive update\" -f -a \"(souk __complete"
+        ));
+    }
+}
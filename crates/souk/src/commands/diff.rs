@@ -0,0 +1,62 @@
+//! Handler for the `souk diff` CLI command.
+
+use souk_core::diff::diff_marketplace;
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::ExitStatus;
+
+use crate::output::{OutputMode, Reporter};
+
+/// Run the diff command, reporting drift between marketplace.json and disk.
+///
+/// JSON mode emits the [`souk_core::diff::MarketplaceDiff`] document
+/// directly so CI can fail on any non-empty category.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_diff(config: &MarketplaceConfig, reporter: &mut Reporter) -> ExitStatus {
+    let diff = match diff_marketplace(config) {
+        Ok(d) => d,
+        Err(e) => {
+            reporter.error(&format!("Diff failed: {e}"));
+            return e.exit_status();
+        }
+    };
+
+    if reporter.mode() == OutputMode::Json {
+        match serde_json::to_value(&diff) {
+            Ok(doc) => reporter.set_json_document(doc),
+            Err(e) => {
+                reporter.error(&format!("Diff failed: {e}"));
+                return ExitStatus::Internal;
+            }
+        }
+        return ExitStatus::Success;
+    }
+
+    if diff.is_empty() {
+        reporter.success("Marketplace is in sync with the filesystem.");
+        return ExitStatus::Success;
+    }
+
+    for name in &diff.orphaned_dirs {
+        reporter.warning(&format!("Directory not in marketplace: {name}"));
+    }
+    for name in &diff.missing_dirs {
+        reporter.warning(&format!("Entry has no directory: {name}"));
+    }
+    for drift in &diff.name_drift {
+        reporter.warning(&format!(
+            "Name drift: marketplace has '{}', plugin.json has '{}'",
+            drift.entry, drift.manifest
+        ));
+    }
+    for drift in &diff.tag_drift {
+        reporter.warning(&format!(
+            "Tag drift in '{}': marketplace has [{}], plugin.json has [{}]",
+            drift.name,
+            drift.entry.join(", "),
+            drift.manifest.join(", ")
+        ));
+    }
+    ExitStatus::Success
+}
@@ -0,0 +1,488 @@
+//! Handler for `souk doctor`.
+//!
+//! Checks the environment and the marketplace for the setup problems that
+//! most often trip up new contributors: no marketplace found, `git` missing,
+//! no review provider configured, a missing pluginRoot, and orphaned plugin
+//! directories. Only problems that stop other commands (no readable
+//! marketplace, a missing pluginRoot, or an invalid souk.toml) are failures;
+//! the rest are warnings.
+//!
+//! With `--fix`, doctor also applies the remediations that cannot lose user
+//! data, each only when its check found a problem: creating a missing
+//! pluginRoot, removing backups and temp directories left behind by an
+//! interrupted command, and removing marketplace entries whose plugin
+//! directory is gone. Orphaned directories are never touched; see
+//! `souk prune`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde_json::json;
+use souk_core::discovery::{discover_marketplace, load_marketplace_config, MarketplaceConfig};
+use souk_core::ops::add::find_stale_temp_dirs;
+use souk_core::ops::atomic::{find_stale_backups, STALE_BACKUP_AGE};
+use souk_core::ops::prune::prune_stale_entries;
+use souk_core::resolution::resolve_source;
+use souk_core::types::Marketplace;
+use souk_core::validation::{find_orphaned_dirs, find_stale_entries};
+use souk_core::ExitStatus;
+
+use crate::cli::DoctorFix;
+use crate::output::{OutputMode, Reporter};
+
+/// Environment variables that configure a review provider, by provider.
+const PROVIDER_ENV_VARS: [(&str, &str); 5] = [
+    ("anthropic", "ANTHROPIC_API_KEY"),
+    ("openai", "OPENAI_API_KEY"),
+    ("azure", "AZURE_OPENAI_API_KEY"),
+    ("gemini", "GEMINI_API_KEY"),
+    ("ollama", "OLLAMA_HOST"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Warn => "warn",
+            Status::Fail => "fail",
+        }
+    }
+}
+
+/// The outcome of one doctor check.
+struct Check {
+    name: &'static str,
+    status: Status,
+    message: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: Status, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// The fixes `souk doctor` was asked to apply: none without `--fix`, all
+/// with a bare `--fix`, else those listed.
+struct Fixes<'a>(Option<&'a [DoctorFix]>);
+
+impl Fixes<'_> {
+    fn enabled(&self, fix: DoctorFix) -> bool {
+        self.0
+            .is_some_and(|list| list.is_empty() || list.contains(&fix))
+    }
+}
+
+/// Run the `souk doctor` command.
+///
+/// Prints each check as a pass, warning, or failure, followed by each fix
+/// applied; in JSON mode, prints `{ok, checks: [{name, status, message}],
+/// fixed: [..]}`. `fix` selects the remediations to apply, as described in
+/// the module docs. Returns [`ExitStatus::Findings`] only when a check
+/// fails.
+pub fn run_doctor(
+    marketplace_override: Option<&str>,
+    fix: Option<&[DoctorFix]>,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let fixes = Fixes(fix);
+    let mut checks = Vec::new();
+    let mut fixed = Vec::new();
+    let marketplace = check_marketplace(marketplace_override, &mut checks);
+    checks.push(check_git());
+    checks.push(check_providers());
+    if let Some((path, marketplace)) = &marketplace {
+        if let Some(created) = check_plugin_root(path, marketplace, &fixes, &mut checks, &mut fixed)
+        {
+            match load_marketplace_config(path) {
+                Ok(config) => {
+                    checks.push(check_orphans(&config));
+                    checks.push(check_stale_entries(
+                        &config, &fixes, created, &mut fixed, reporter,
+                    ));
+                    checks.push(check_backups(&config, &fixes, &mut fixed));
+                }
+                Err(e) => checks.push(Check::new("settings", Status::Fail, e.to_string())),
+            }
+        }
+    }
+    checks.push(check_temp_dirs(&fixes, &mut fixed));
+
+    let ok = checks.iter().all(|c| c.status != Status::Fail);
+    if reporter.mode() == OutputMode::Json {
+        let checks: Vec<_> = checks
+            .iter()
+            .map(|c| {
+                json!({
+                    "name": c.name,
+                    "status": c.status.as_str(),
+                    "message": c.message,
+                })
+            })
+            .collect();
+        reporter.set_json_document(json!({ "ok": ok, "checks": checks, "fixed": fixed }));
+        return if ok {
+            ExitStatus::Success
+        } else {
+            ExitStatus::Findings
+        };
+    }
+
+    for check in &checks {
+        let line = format!("{}: {}", check.name, check.message);
+        match check.status {
+            Status::Pass => reporter.success(&line),
+            Status::Warn => reporter.warning(&line),
+            Status::Fail => reporter.error(&line),
+        }
+    }
+    if !fixed.is_empty() {
+        reporter.section("Fixed");
+        for action in &fixed {
+            reporter.success(action);
+        }
+    }
+    if ok {
+        ExitStatus::Success
+    } else {
+        ExitStatus::Findings
+    }
+}
+
+/// Discover and parse marketplace.json, recording the result in `checks`.
+fn check_marketplace(
+    marketplace_override: Option<&str>,
+    checks: &mut Vec<Check>,
+) -> Option<(PathBuf, Marketplace)> {
+    let path = match marketplace_override {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => crate::cwd::current_dir()
+            .map_err(|e| e.to_string())
+            .and_then(|cwd| discover_marketplace(&cwd).map_err(|e| e.to_string())),
+    };
+    let parsed = path.and_then(|path| {
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        serde_json::from_str::<Marketplace>(&content)
+            .map(|marketplace| (path.clone(), marketplace))
+            .map_err(|e| format!("invalid {}: {e}", path.display()))
+    });
+    match parsed {
+        Ok((path, marketplace)) => {
+            checks.push(Check::new(
+                "marketplace",
+                Status::Pass,
+                format!("found {}", path.display()),
+            ));
+            Some((path, marketplace))
+        }
+        Err(e) => {
+            checks.push(Check::new("marketplace", Status::Fail, e));
+            None
+        }
+    }
+}
+
+fn check_git() -> Check {
+    match Command::new("git").arg("--version").output() {
+        Ok(output) if output.status.success() => Check::new(
+            "git",
+            Status::Pass,
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ),
+        _ => Check::new(
+            "git",
+            Status::Warn,
+            "git not found on PATH; git plugin sources and hook installation need it",
+        ),
+    }
+}
+
+/// Report which provider variables are set, never their values.
+fn check_providers() -> Check {
+    let configured: Vec<String> = PROVIDER_ENV_VARS
+        .iter()
+        .filter(|(_, var)| std::env::var_os(var).is_some_and(|v| !v.is_empty()))
+        .map(|(provider, var)| format!("{provider} ({var})"))
+        .collect();
+    if configured.is_empty() {
+        let vars: Vec<&str> = PROVIDER_ENV_VARS.iter().map(|(_, var)| *var).collect();
+        Check::new(
+            "providers",
+            Status::Warn,
+            format!(
+                "no review provider configured; set one of {}",
+                vars.join(", ")
+            ),
+        )
+    } else {
+        Check::new("providers", Status::Pass, configured.join(", "))
+    }
+}
+
+/// Check that pluginRoot exists, creating it with the `plugin-root` fix.
+///
+/// Returns `None` if it does not exist, else whether it was just created.
+fn check_plugin_root(
+    path: &Path,
+    marketplace: &Marketplace,
+    fixes: &Fixes,
+    checks: &mut Vec<Check>,
+    fixed: &mut Vec<String>,
+) -> Option<bool> {
+    let project_root = path
+        .parent()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new("."));
+    let root = project_root.join(marketplace.normalized_plugin_root());
+    if root.is_dir() {
+        checks.push(Check::new(
+            "pluginRoot",
+            Status::Pass,
+            format!("{} exists", root.display()),
+        ));
+        return Some(false);
+    }
+    if !fixes.enabled(DoctorFix::PluginRoot) {
+        checks.push(Check::new(
+            "pluginRoot",
+            Status::Fail,
+            format!("{} does not exist (see souk doctor --fix)", root.display()),
+        ));
+        return None;
+    }
+    match std::fs::create_dir_all(&root) {
+        Ok(()) => {
+            fixed.push(format!("Created pluginRoot {}", root.display()));
+            checks.push(Check::new(
+                "pluginRoot",
+                Status::Pass,
+                format!("{} created", root.display()),
+            ));
+            Some(true)
+        }
+        Err(e) => {
+            checks.push(Check::new(
+                "pluginRoot",
+                Status::Fail,
+                format!("cannot create {}: {e}", root.display()),
+            ));
+            None
+        }
+    }
+}
+
+fn check_orphans(config: &MarketplaceConfig) -> Check {
+    match find_orphaned_dirs(config) {
+        Ok(orphans) if orphans.is_empty() => {
+            Check::new("orphans", Status::Pass, "no orphaned plugin directories")
+        }
+        Ok(orphans) => Check::new(
+            "orphans",
+            Status::Warn,
+            format!(
+                "{} orphaned plugin directory(ies) in pluginRoot (see souk prune)",
+                orphans.len()
+            ),
+        ),
+        Err(e) => Check::new(
+            "orphans",
+            Status::Warn,
+            format!("cannot scan pluginRoot: {e}"),
+        ),
+    }
+}
+
+/// Check for marketplace entries whose plugin directory is missing,
+/// removing them with the `stale-entries` fix through the same validated
+/// write as `souk prune --stale-entries`.
+///
+/// The `stale-entries` fix is not applied in a run that created pluginRoot:
+/// every entry is stale against an empty directory, most likely because
+/// pluginRoot points at the wrong place.
+fn check_stale_entries(
+    config: &MarketplaceConfig,
+    fixes: &Fixes,
+    root_created: bool,
+    fixed: &mut Vec<String>,
+    reporter: &mut Reporter,
+) -> Check {
+    let stale = match find_stale_entries(config) {
+        Ok(stale) => stale.len(),
+        Err(e) => {
+            return Check::new(
+                "entries",
+                Status::Warn,
+                format!("cannot scan pluginRoot: {e}"),
+            )
+        }
+    };
+    if stale == 0 {
+        return Check::new(
+            "entries",
+            Status::Pass,
+            "every entry has a plugin directory",
+        );
+    }
+    if root_created {
+        return Check::new(
+            "entries",
+            Status::Warn,
+            format!(
+                "{stale} marketplace entry(ies) without a plugin directory; pluginRoot was just \
+                 created, so check that it is right before removing them"
+            ),
+        );
+    }
+    if !fixes.enabled(DoctorFix::StaleEntries) {
+        return Check::new(
+            "entries",
+            Status::Warn,
+            format!(
+                "{stale} marketplace entry(ies) without a plugin directory (see souk doctor --fix)"
+            ),
+        );
+    }
+
+    let result = prune_stale_entries(true, config);
+    let names = result.as_ref().cloned().unwrap_or_default();
+    crate::audit::record(
+        config,
+        crate::audit::entry("doctor", names, &result),
+        reporter,
+    );
+    match result {
+        Ok(names) => {
+            fixed.extend(
+                names
+                    .iter()
+                    .map(|name| format!("Removed stale entry: {name}")),
+            );
+            Check::new(
+                "entries",
+                Status::Pass,
+                format!("removed {} stale entry(ies)", names.len()),
+            )
+        }
+        Err(e) => Check::new(
+            "entries",
+            Status::Warn,
+            format!("cannot remove stale entries: {e}"),
+        ),
+    }
+}
+
+/// Check for backups an interrupted command left next to marketplace.json
+/// or a plugin's files, removing them with the `backups` fix.
+fn check_backups(config: &MarketplaceConfig, fixes: &Fixes, fixed: &mut Vec<String>) -> Check {
+    let mut dirs: Vec<PathBuf> = config
+        .marketplace_path
+        .parent()
+        .map(Path::to_path_buf)
+        .into_iter()
+        .collect();
+    for plugin in &config.marketplace.plugins {
+        if let Ok(dir) = resolve_source(&plugin.source, config) {
+            dirs.push(dir.join(".claude-plugin"));
+            dirs.push(dir);
+        }
+    }
+
+    let mut backups = Vec::new();
+    for dir in &dirs {
+        match find_stale_backups(dir, STALE_BACKUP_AGE) {
+            Ok(found) => backups.extend(found),
+            Err(e) => {
+                return Check::new(
+                    "backups",
+                    Status::Warn,
+                    format!("cannot scan {}: {e}", dir.display()),
+                )
+            }
+        }
+    }
+    remove_leftovers(
+        "backups",
+        "stale backup(s)",
+        backups,
+        fixes.enabled(DoctorFix::Backups),
+        fixed,
+    )
+}
+
+/// Check the system temp directory for staging directories an interrupted
+/// `souk add` left behind, removing them with the `temp-dirs` fix.
+fn check_temp_dirs(fixes: &Fixes, fixed: &mut Vec<String>) -> Check {
+    match find_stale_temp_dirs(&std::env::temp_dir(), STALE_BACKUP_AGE) {
+        Ok(dirs) => remove_leftovers(
+            "tempDirs",
+            "stale temp directory(ies)",
+            dirs,
+            fixes.enabled(DoctorFix::TempDirs),
+            fixed,
+        ),
+        Err(e) => Check::new(
+            "tempDirs",
+            Status::Warn,
+            format!("cannot scan the temp directory: {e}"),
+        ),
+    }
+}
+
+/// Report `paths` left behind by souk as the `name` check, removing them
+/// when `apply` is set.
+fn remove_leftovers(
+    name: &'static str,
+    what: &str,
+    paths: Vec<PathBuf>,
+    apply: bool,
+    fixed: &mut Vec<String>,
+) -> Check {
+    if paths.is_empty() {
+        return Check::new(name, Status::Pass, format!("no {what}"));
+    }
+    if !apply {
+        return Check::new(
+            name,
+            Status::Warn,
+            format!("{} {what} (see souk doctor --fix)", paths.len()),
+        );
+    }
+
+    let mut failed = Vec::new();
+    for path in &paths {
+        let removed = if path.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        };
+        match removed {
+            Ok(()) => fixed.push(format!("Removed {}", path.display())),
+            Err(e) => failed.push(format!("{}: {e}", path.display())),
+        }
+    }
+    if failed.is_empty() {
+        Check::new(
+            name,
+            Status::Pass,
+            format!("removed {} {what}", paths.len()),
+        )
+    } else {
+        Check::new(
+            name,
+            Status::Warn,
+            format!("cannot remove {}", failed.join(", ")),
+        )
+    }
+}
@@ -0,0 +1,58 @@
+//! Handler for the `souk export` CLI command.
+
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::export::export_bundle;
+use souk_core::ExitStatus;
+
+use crate::cli::ExportFormat;
+use crate::output::Reporter;
+
+/// Run the export command, writing the marketplace bundle to `output` or stdout.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_export(
+    format: &ExportFormat,
+    output: Option<&str>,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let bundle = match export_bundle(config) {
+        Ok(b) => b,
+        Err(e) => {
+            reporter.error(&format!("Export failed: {e}"));
+            return e.exit_status();
+        }
+    };
+
+    let rendered = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&bundle),
+    };
+    let rendered = match rendered {
+        Ok(r) => r,
+        Err(e) => {
+            reporter.error(&format!("Export failed: {e}"));
+            return ExitStatus::Internal;
+        }
+    };
+
+    match output {
+        Some(path) => match std::fs::write(path, format!("{rendered}\n")) {
+            Ok(()) => {
+                reporter.summary(&format!(
+                    "Exported {} plugin(s) to {path}",
+                    bundle.plugins.len()
+                ));
+                ExitStatus::Success
+            }
+            Err(e) => {
+                reporter.error(&format!("Failed to write {path}: {e}"));
+                ExitStatus::Internal
+            }
+        },
+        None => {
+            println!("{rendered}");
+            ExitStatus::Success
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Handler for the `souk fix` CLI command.
+
+use crate::output::Reporter;
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::ops::fix::{fix_formatting, FixResult};
+use souk_core::resolution::resolve_source;
+use souk_core::ExitStatus;
+
+/// Run the fix command, rewriting marketplace.json and plugin.json files
+/// that `--check-formatting` would flag.
+///
+/// Unless `dry_run` is set, the run is recorded in the audit log with the
+/// plugins whose plugin.json was reformatted.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_fix(dry_run: bool, config: &MarketplaceConfig, reporter: &mut Reporter) -> ExitStatus {
+    let result = fix_formatting(config, dry_run);
+    if !dry_run {
+        let plugins = result
+            .as_ref()
+            .map(|result| fixed_plugins(result, config))
+            .unwrap_or_default();
+        crate::audit::record(
+            config,
+            crate::audit::entry("fix", plugins, &result),
+            reporter,
+        );
+    }
+
+    match result {
+        Ok(result) => {
+            for warn in &result.warnings {
+                reporter.warning(warn);
+            }
+            if result.fixed.is_empty() {
+                reporter.info("All files already use canonical formatting.");
+            } else if dry_run {
+                reporter.section("Fix (dry-run)");
+                for path in &result.fixed {
+                    reporter.info(&format!("Would reformat: {}", config.display_path(path)));
+                }
+            } else {
+                reporter.section("Fix");
+                for path in &result.fixed {
+                    reporter.success(&format!("Reformatted: {}", config.display_path(path)));
+                }
+            }
+            ExitStatus::Success
+        }
+        Err(e) => {
+            reporter.error(&format!("Fix failed: {e}"));
+            e.exit_status()
+        }
+    }
+}
+
+/// Names of the plugins, active or archived, whose plugin.json is among
+/// the files `result` reformatted.
+fn fixed_plugins(result: &FixResult, config: &MarketplaceConfig) -> Vec<String> {
+    config
+        .marketplace
+        .plugins
+        .iter()
+        .chain(&config.marketplace.archived)
+        .filter(|p| {
+            resolve_source(&p.source, config).is_ok_and(|dir| {
+                result
+                    .fixed
+                    .contains(&dir.join(".claude-plugin").join("plugin.json"))
+            })
+        })
+        .map(|p| p.name.clone())
+        .collect()
+}
@@ -0,0 +1,208 @@
+//! Handler for the `souk info` CLI command.
+
+use std::path::Path;
+
+use serde_json::json;
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::resolution::{
+    enumerate_skills, plugin_path_to_source, resolve_plugin, resolve_source,
+};
+use souk_core::types::plugin::read_manifest_text;
+use souk_core::types::PluginEntry;
+use souk_core::validation::{dependency_tree, DependencyNode, DependencyStatus};
+use souk_core::ExitStatus;
+
+use crate::output::{OutputMode, Reporter};
+
+/// Run the info command, showing everything souk resolves for one plugin.
+///
+/// `input` is a plugin name or path, resolved like every other command.
+/// A missing or unparseable plugin.json is reported alongside the other
+/// details rather than aborting, since `info` is mostly run on broken
+/// entries. The marketplace's own keywords and categories are shown too.
+/// Quiet mode prints just the resolved path, and JSON mode emits a single
+/// object, with the marketplace's under `marketplace`.
+///
+/// With `tree`, the plugin's dependencies are followed transitively through
+/// extends-plugin.json and shown with their constraints and whether each is
+/// satisfied, missing, unsatisfied, or a cycle. In JSON mode the tree is
+/// added under `dependencies`.
+///
+/// Returns [`ExitStatus::Success`], or the resolution error's status if
+/// the plugin cannot be resolved.
+pub fn run_info(
+    input: &str,
+    tree: bool,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let plugin_path = match resolve_plugin(input, Some(config)) {
+        Ok(p) => p,
+        Err(e) => {
+            reporter.error(&format!("Failed to resolve plugin: {e}"));
+            return e.exit_status();
+        }
+    };
+
+    let manifest_path = plugin_path.join(".claude-plugin").join("plugin.json");
+    let manifest = read_manifest_text(&manifest_path).and_then(|text| {
+        serde_json::from_str::<serde_json::Value>(&text)
+            .map_err(|e| format!("Invalid JSON in plugin.json: {e}"))
+    });
+    let skills = enumerate_skills(&plugin_path);
+    let (source, internal) = plugin_path_to_source(&plugin_path, config);
+    let entry = marketplace_entry(&plugin_path, config);
+    let dependencies = tree.then(|| {
+        let name = entry
+            .map(|(entry, _)| entry.name.clone())
+            .or_else(|| {
+                let manifest = manifest.as_ref().ok()?;
+                Some(manifest.get("name")?.as_str()?.to_string())
+            })
+            .or_else(|| Some(plugin_path.file_name()?.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        dependency_tree(&plugin_path, &name, config)
+    });
+
+    if reporter.mode() == OutputMode::Quiet {
+        println!("{}", plugin_path.display());
+        return ExitStatus::Success;
+    }
+    if reporter.mode() == OutputMode::Json {
+        let (manifest, manifest_error) = match manifest {
+            Ok(m) => (m, None),
+            Err(e) => (serde_json::Value::Null, Some(e)),
+        };
+        let mut doc = json!({
+            "path": plugin_path,
+            "source": source,
+            "internal": internal,
+            "manifest": manifest,
+            "manifest_error": manifest_error,
+            "entry": entry.map(|(entry, archived)| json!({
+                "name": entry.name,
+                "source": entry.source,
+                "tags": entry.tags,
+                "archived": archived,
+            })),
+            "skills": skills
+                .iter()
+                .map(|s| json!({"dir": s.dir_name, "name": s.display_name, "path": s.path}))
+                .collect::<Vec<_>>(),
+            "marketplace": {
+                "keywords": config.marketplace.keywords,
+                "categories": config.marketplace.categories,
+            },
+        });
+        if let Some(root) = &dependencies {
+            doc["dependencies"] = json!(root.dependencies);
+        }
+        reporter.set_json_document(doc);
+        return ExitStatus::Success;
+    }
+
+    let field = |value: Option<&serde_json::Value>| match value {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Null) | None => "-".to_string(),
+        Some(other) => other.to_string(),
+    };
+    let mut lines = Vec::new();
+    match &manifest {
+        Ok(m) => {
+            lines.push(("Name", field(m.get("name"))));
+            lines.push(("Version", field(m.get("version"))));
+            lines.push(("Description", field(m.get("description"))));
+        }
+        Err(e) => reporter.warning(&format!("{e} ({})", manifest_path.display())),
+    }
+    lines.push(("Path", plugin_path.display().to_string()));
+    let location = if internal { "internal" } else { "external" };
+    lines.push(("Source", format!("{source} ({location})")));
+    match entry {
+        Some((entry, archived)) => {
+            let status = if archived { " (archived)" } else { "" };
+            lines.push(("Entry", format!("{}{status}", entry.name)));
+            let tags = if entry.tags.is_empty() {
+                "-".to_string()
+            } else {
+                entry.tags.join(", ")
+            };
+            lines.push(("Tags", tags));
+        }
+        None => lines.push(("Entry", "not in marketplace".to_string())),
+    }
+    lines.push(("Skills", skills.len().to_string()));
+
+    for (label, value) in lines {
+        println!("{:<12} {value}", format!("{label}:"));
+    }
+    for skill in &skills {
+        if skill.display_name == skill.dir_name {
+            println!("  - {}", skill.dir_name);
+        } else {
+            println!("  - {} ({})", skill.dir_name, skill.display_name);
+        }
+    }
+    if let Some(root) = &dependencies {
+        if root.dependencies.is_empty() {
+            println!("{:<12} none", "Dependencies:");
+        } else {
+            println!("Dependencies:");
+            print_dependencies(&root.dependencies, 1);
+        }
+    }
+    let mp = &config.marketplace;
+    for (label, values) in [
+        ("Marketplace keywords", &mp.keywords),
+        ("Marketplace categories", &mp.categories),
+    ] {
+        if !values.is_empty() {
+            println!("{label}: {}", values.join(", "));
+        }
+    }
+    ExitStatus::Success
+}
+
+/// Print `nodes` and their children, two spaces deeper per level, as
+/// `- name constraint (optional): status`.
+fn print_dependencies(nodes: &[DependencyNode], depth: usize) {
+    for node in nodes {
+        let constraint = node
+            .constraint
+            .as_deref()
+            .map(|c| format!(" {c}"))
+            .unwrap_or_default();
+        let optional = if node.optional { " (optional)" } else { "" };
+        let version = node.version.as_deref().unwrap_or("unknown version");
+        let status = match node.status {
+            DependencyStatus::Satisfied => format!("satisfied ({version})"),
+            DependencyStatus::Unsatisfied => format!("unsatisfied (found {version})"),
+            DependencyStatus::Missing => "missing".to_string(),
+            DependencyStatus::Cycle => "cycle".to_string(),
+        };
+        println!(
+            "{}- {}{constraint}{optional}: {status}",
+            "  ".repeat(depth),
+            node.name
+        );
+        print_dependencies(&node.dependencies, depth + 1);
+    }
+}
+
+/// The active or archived entry whose source resolves to `plugin_path`,
+/// with whether it is archived.
+fn marketplace_entry<'a>(
+    plugin_path: &Path,
+    config: &'a MarketplaceConfig,
+) -> Option<(&'a PluginEntry, bool)> {
+    config
+        .marketplace
+        .listed_plugins(true)
+        .into_iter()
+        .find(|(entry, _)| {
+            resolve_source(&entry.source, config)
+                .ok()
+                .and_then(|p| p.canonicalize().ok())
+                .is_some_and(|p| p == plugin_path)
+        })
+}
@@ -2,34 +2,65 @@
 
 use std::path::Path;
 
-use souk_core::ops::init::scaffold_marketplace;
+use souk_core::ops::init::{check_scaffold, scaffold_marketplace};
+use souk_core::{ExitStatus, SoukError};
 
 use crate::output::Reporter;
 
 /// Run the init command, scaffolding a new marketplace at `target_path`.
 ///
-/// Returns `true` on success, `false` on failure.
-pub fn run_init(target_path: &str, plugin_root: &str, reporter: &mut Reporter) -> bool {
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_init(target_path: &str, plugin_root: &str, reporter: &mut Reporter) -> ExitStatus {
     let path = Path::new(target_path);
 
-    match scaffold_marketplace(path, plugin_root) {
+    let result = scaffold_marketplace(path, plugin_root);
+    crate::audit::record_init(
+        path,
+        crate::audit::entry("init", Vec::new(), &result),
+        reporter,
+    );
+
+    match result {
         Ok(()) => {
             reporter.success(&format!("Marketplace initialized at {}", path.display()));
             reporter.info(&format!(
                 "Created .claude-plugin/marketplace.json with pluginRoot: {plugin_root}"
             ));
-            true
+            ExitStatus::Success
+        }
+        Err(e) => {
+            match &e {
+                SoukError::MarketplaceAlreadyExists(mp_path) => reporter.error(&format!(
+                    "Marketplace already exists at {}",
+                    mp_path.display()
+                )),
+                _ => reporter.error(&format!("Failed to initialize marketplace: {e}")),
+            }
+            e.exit_status()
         }
-        Err(souk_core::SoukError::MarketplaceAlreadyExists(mp_path)) => {
-            reporter.error(&format!(
-                "Marketplace already exists at {}",
-                mp_path.display()
+    }
+}
+
+/// Run `souk init --check`, verifying the scaffold at `target_path` is intact.
+///
+/// Returns [`ExitStatus::Success`] if the scaffold is intact, else the
+/// status of the error that failed the check.
+pub fn run_init_check(target_path: &str, reporter: &mut Reporter) -> ExitStatus {
+    let path = Path::new(target_path);
+
+    match check_scaffold(path) {
+        Ok(config) => {
+            reporter.success(&format!(
+                "Marketplace scaffold intact at {}",
+                config.project_root.display()
             ));
-            false
+            reporter.info(&format!("pluginRoot: {}", config.marketplace.plugin_root()));
+            ExitStatus::Success
         }
         Err(e) => {
-            reporter.error(&format!("Failed to initialize marketplace: {e}"));
-            false
+            reporter.error(&format!("Marketplace scaffold check failed: {e}"));
+            e.exit_status()
         }
     }
 }
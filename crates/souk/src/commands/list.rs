@@ -0,0 +1,368 @@
+//! Handler for the `souk list` CLI command.
+
+use serde_json::json;
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::resolution::{enumerate_skills, resolve_source};
+use souk_core::types::plugin::read_manifest_text;
+use souk_core::types::{PluginEntry, PluginManifest};
+use souk_core::ExitStatus;
+
+use crate::cli::{ListColumn, ListSort};
+use crate::output::{OutputMode, Reporter};
+
+/// Which plugins `souk list` shows, and in what order.
+pub struct ListOptions<'a> {
+    /// Include archived entries after the active ones.
+    pub include_archived: bool,
+    /// Only show entries with this tag, compared case-insensitively.
+    pub tag: Option<&'a str>,
+    /// Sort by name or source; marketplace order when `None`.
+    pub sort: Option<ListSort>,
+    /// Columns to show, in order.
+    pub columns: &'a [ListColumn],
+    /// The window of the filtered, sorted plugins to show.
+    pub page: Page,
+    /// Print only the number of plugins selected, before paging.
+    pub count: bool,
+}
+
+/// An `--offset`/`--limit` window over `souk list` or `souk search` results.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Page {
+    /// Results skipped before the first one shown.
+    pub offset: Option<usize>,
+    /// Most results shown; all the rest when `None`.
+    pub limit: Option<usize>,
+}
+
+impl Page {
+    /// Whether `--offset` or `--limit` was given.
+    pub fn is_set(&self) -> bool {
+        self.offset.is_some() || self.limit.is_some()
+    }
+
+    /// The part of `items` inside the window.
+    pub fn slice<'a, T>(&self, items: &'a [T]) -> &'a [T] {
+        let start = self.offset.unwrap_or(0).min(items.len());
+        let end = self.limit.map_or(items.len(), |limit| {
+            start.saturating_add(limit).min(items.len())
+        });
+        &items[start..end]
+    }
+
+    /// The JSON document for `items` shown out of `total` results,
+    /// `{total, offset, limit, items}`, with an `offset` of 0 and a `null`
+    /// `limit` when they were not given.
+    pub fn json(&self, total: usize, items: Vec<serde_json::Value>) -> serde_json::Value {
+        json!({
+            "total": total,
+            "offset": self.offset.unwrap_or(0),
+            "limit": self.limit,
+            "items": items,
+        })
+    }
+
+    /// Note in human mode how much of `total` results a window showed.
+    pub fn report(&self, shown: usize, total: usize, reporter: &mut Reporter) {
+        if self.is_set() && shown < total {
+            let first = if shown == 0 {
+                0
+            } else {
+                self.offset.unwrap_or(0) + 1
+            };
+            let last = self.offset.unwrap_or(0) + shown;
+            reporter.info(&format!("Showing {first}-{last} of {total}"));
+        }
+    }
+}
+
+/// Print the number of results for `--count`: a bare number, or `{total}`
+/// in JSON mode.
+pub fn print_count(total: usize, reporter: &mut Reporter) {
+    if reporter.mode() == OutputMode::Json {
+        reporter.set_json_document(json!({ "total": total }));
+    } else {
+        println!("{total}");
+    }
+}
+
+/// Run the list command, printing the marketplace's plugins.
+///
+/// Human mode prints the marketplace's keywords and categories, if any,
+/// then a table of the selected columns. Quiet mode prints just names, and
+/// JSON mode emits `{total, offset, limit, items}` (see [`Page::json`]),
+/// where each item has the selected columns plus `archived`. The version and skills columns are read from each plugin's
+/// directory; a plugin whose version cannot be read shows `-` (`null` in
+/// JSON).
+///
+/// `options.page` selects a window of the filtered, sorted plugins, and
+/// `total` counts them all. With `options.count`, only the number of
+/// selected plugins is printed.
+pub fn run_list(
+    options: &ListOptions,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let listed = select(
+        config.marketplace.listed_plugins(options.include_archived),
+        options.tag,
+        options.sort,
+    );
+    if options.count {
+        print_count(listed.len(), reporter);
+        return ExitStatus::Success;
+    }
+    let total = listed.len();
+    let listed = options.page.slice(&listed);
+
+    match reporter.mode() {
+        OutputMode::Human => {
+            let mp = &config.marketplace;
+            for (label, values) in [("Keywords", &mp.keywords), ("Categories", &mp.categories)] {
+                if !values.is_empty() {
+                    println!("{label}: {}", values.join(", "));
+                }
+            }
+            let rows: Vec<_> = listed
+                .iter()
+                .map(|(entry, archived)| {
+                    let cells = options
+                        .columns
+                        .iter()
+                        .map(|&column| cell(&column_value(entry, column, config)))
+                        .collect();
+                    (cells, *archived)
+                })
+                .collect();
+            for line in table(&rows) {
+                println!("{line}");
+            }
+            options.page.report(listed.len(), total, reporter);
+            if listed.is_empty() && mp.plugins.is_empty() {
+                reporter.empty_marketplace_hint();
+            }
+        }
+        OutputMode::Quiet => {
+            for (entry, _) in listed {
+                println!("{}", entry.name);
+            }
+        }
+        OutputMode::Json => {
+            let entries: Vec<_> = listed
+                .iter()
+                .map(|(entry, archived)| {
+                    let mut object = serde_json::Map::new();
+                    for &column in options.columns {
+                        object.insert(
+                            column.key().to_string(),
+                            column_value(entry, column, config),
+                        );
+                    }
+                    object.insert("archived".to_string(), json!(archived));
+                    serde_json::Value::Object(object)
+                })
+                .collect();
+            reporter.set_json_document(options.page.json(total, entries));
+        }
+    }
+    ExitStatus::Success
+}
+
+/// Filter `listed` to entries carrying `tag` and order them by `sort`.
+///
+/// Sorting is stable, so entries with equal keys keep marketplace order.
+fn select<'a>(
+    listed: Vec<(&'a PluginEntry, bool)>,
+    tag: Option<&str>,
+    sort: Option<ListSort>,
+) -> Vec<(&'a PluginEntry, bool)> {
+    let mut listed: Vec<_> = listed
+        .into_iter()
+        .filter(|(entry, _)| {
+            tag.is_none_or(|tag| entry.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+        })
+        .collect();
+    match sort {
+        Some(ListSort::Name) => listed.sort_by_key(|(entry, _)| entry.name.to_lowercase()),
+        Some(ListSort::Source) => listed.sort_by(|(a, _), (b, _)| a.source.cmp(&b.source)),
+        None => {}
+    }
+    listed
+}
+
+/// The value of `column` for `entry`, reading the plugin's directory for
+/// the version and skills columns.
+fn column_value(
+    entry: &PluginEntry,
+    column: ListColumn,
+    config: &MarketplaceConfig,
+) -> serde_json::Value {
+    match column {
+        ListColumn::Name => json!(entry.name),
+        ListColumn::Source => json!(entry.source),
+        ListColumn::Tags => json!(entry.tags),
+        ListColumn::Version => {
+            let version = resolve_source(&entry.source, config).ok().and_then(|path| {
+                let text = read_manifest_text(&path.join(".claude-plugin").join("plugin.json"));
+                let manifest: PluginManifest = serde_json::from_str(&text.ok()?).ok()?;
+                manifest.version_str().map(str::to_string)
+            });
+            json!(version)
+        }
+        ListColumn::Skills => match resolve_source(&entry.source, config) {
+            Ok(path) => json!(enumerate_skills(&path).len()),
+            Err(_) => serde_json::Value::Null,
+        },
+    }
+}
+
+/// A column value as table text: tags comma-separated, `-` when unknown.
+fn cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .collect::<Vec<_>>()
+            .join(", "),
+        serde_json::Value::Null => "-".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Lines of the human-mode table, with every column but the last padded to
+/// line up, then an `(archived)` marker. Trailing empty cells are dropped.
+fn table(rows: &[(Vec<String>, bool)]) -> Vec<String> {
+    let columns = rows.iter().map(|(cells, _)| cells.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .map(|(cells, _)| cells.get(i).map_or(0, String::len))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    rows.iter()
+        .map(|(cells, archived)| {
+            let mut line: Vec<String> = cells
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    if i + 1 == cells.len() {
+                        cell.clone()
+                    } else {
+                        format!("{cell:width$}", width = widths[i])
+                    }
+                })
+                .collect();
+            while line.last().is_some_and(|cell| cell.is_empty()) {
+                line.pop();
+            }
+            if *archived {
+                line.push("(archived)".to_string());
+            }
+            line.join("  ").trim_end().to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, source: &str, tags: &[&str]) -> PluginEntry {
+        PluginEntry {
+            name: name.to_string(),
+            source: source.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            origin: None,
+            sha: None,
+        }
+    }
+
+    fn names(listed: &[(&PluginEntry, bool)]) -> Vec<String> {
+        listed.iter().map(|(e, _)| e.name.clone()).collect()
+    }
+
+    #[test]
+    fn filters_by_tag_case_insensitively() {
+        let plugins = [
+            entry("gamma", "c", &["Git"]),
+            entry("alpha", "a", &["dev"]),
+            entry("beta", "b", &["dev", "git"]),
+        ];
+        let listed = plugins.iter().map(|p| (p, false)).collect();
+        assert_eq!(names(&select(listed, Some("git"), None)), ["gamma", "beta"]);
+    }
+
+    #[test]
+    fn sorts_by_name_or_source() {
+        let plugins = [
+            entry("Gamma", "a-src", &[]),
+            entry("alpha", "c-src", &[]),
+            entry("beta", "b-src", &[]),
+        ];
+        let listed = || plugins.iter().map(|p| (p, false)).collect();
+        assert_eq!(
+            names(&select(listed(), None, None)),
+            ["Gamma", "alpha", "beta"]
+        );
+        assert_eq!(
+            names(&select(listed(), None, Some(ListSort::Name))),
+            ["alpha", "beta", "Gamma"]
+        );
+        assert_eq!(
+            names(&select(listed(), None, Some(ListSort::Source))),
+            ["Gamma", "beta", "alpha"]
+        );
+    }
+
+    #[test]
+    fn page_slices_after_offset() {
+        let items = [1, 2, 3, 4, 5];
+        let page = |offset, limit| Page { offset, limit }.slice(&items).to_vec();
+        assert_eq!(page(None, None), [1, 2, 3, 4, 5]);
+        assert_eq!(page(None, Some(2)), [1, 2]);
+        assert_eq!(page(Some(3), None), [4, 5]);
+        assert_eq!(page(Some(1), Some(2)), [2, 3]);
+        assert_eq!(page(Some(4), Some(10)), [5]);
+        assert!(page(Some(9), Some(2)).is_empty());
+        assert!(page(None, Some(0)).is_empty());
+    }
+
+    #[test]
+    fn page_json_always_wraps_items() {
+        let items = vec![json!("a")];
+        assert_eq!(
+            Page::default().json(1, items.clone()),
+            json!({"total": 1, "offset": 0, "limit": null, "items": ["a"]})
+        );
+        assert_eq!(
+            Page {
+                offset: Some(2),
+                limit: None
+            }
+            .json(3, items),
+            json!({"total": 3, "offset": 2, "limit": null, "items": ["a"]})
+        );
+    }
+
+    fn row(cells: &[&str], archived: bool) -> (Vec<String>, bool) {
+        (cells.iter().map(|c| c.to_string()).collect(), archived)
+    }
+
+    #[test]
+    fn table_aligns_columns() {
+        let rows = [
+            row(&["a", "plugins/a", "x, y"], false),
+            row(&["long-name", "b", ""], true),
+        ];
+        assert_eq!(
+            table(&rows),
+            [
+                "a          plugins/a  x, y",
+                "long-name  b          (archived)",
+            ]
+        );
+    }
+}
@@ -1,8 +1,22 @@
 pub mod add;
+pub mod archive;
 pub mod ci;
+pub mod completions;
+pub mod diff;
+pub mod doctor;
+pub mod export;
+pub mod fix;
+pub mod info;
 pub mod init;
+pub mod list;
 pub mod prune;
 pub mod remove;
+pub mod rename;
 pub mod review;
+pub mod search;
+pub mod selftest;
+pub mod stats;
+pub mod sync;
 pub mod update;
 pub mod validate;
+pub mod version;
@@ -1,22 +1,109 @@
 //! Handler for the `souk prune` CLI command.
 
+use std::time::Duration;
+
 use crate::output::Reporter;
 use souk_core::discovery::MarketplaceConfig;
-use souk_core::ops::prune::prune_plugins;
+use souk_core::ops::audit::AuditEntry;
+use souk_core::ops::prune::{prune_plugins, prune_stale_entries};
+use souk_core::ops::retry_on_rollback;
+use souk_core::ExitStatus;
+
+/// Flags controlling what `souk prune` cleans up.
+pub struct PruneOptions {
+    /// Delete and remove instead of only listing.
+    pub apply: bool,
+    /// Keep orphans modified more recently than this.
+    pub older_than: Option<Duration>,
+    /// Also remove marketplace entries whose directory is missing.
+    pub stale_entries: bool,
+    /// How many times to retry removing stale entries after a rollback.
+    pub retries: u32,
+}
 
 /// Run the prune command, removing orphaned plugin directories.
 ///
-/// Returns `true` on success, `false` on failure.
-pub fn run_prune(apply: bool, config: &MarketplaceConfig, reporter: &mut Reporter) -> bool {
-    match prune_plugins(apply, config) {
+/// With `older_than`, orphans modified more recently than that are kept.
+/// With `stale_entries`, marketplace entries pointing at missing
+/// directories are removed first, in one validated write, retried up to
+/// `retries` times if it is rolled back.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_prune(
+    options: &PruneOptions,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let PruneOptions {
+        apply,
+        older_than,
+        stale_entries,
+        retries,
+    } = *options;
+
+    let stale = if stale_entries {
+        let result = retry_on_rollback(
+            retries,
+            config,
+            |attempt, e| reporter.retrying(attempt, retries, e),
+            |current| prune_stale_entries(apply, current),
+        );
+        match result {
+            Ok(stale) => stale,
+            Err(e) => {
+                if apply {
+                    let entry = AuditEntry::new("prune", Vec::new()).failed(&e);
+                    crate::audit::record(config, entry, reporter);
+                }
+                reporter.error(&format!("Prune failed: {e}"));
+                return e.exit_status();
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    let result = prune_plugins(apply, older_than, config);
+    if apply {
+        let mut pruned = stale.clone();
+        if let Ok(result) = &result {
+            pruned.extend(
+                result
+                    .deleted
+                    .iter()
+                    .filter_map(|path| path.file_name())
+                    .map(|name| name.to_string_lossy().to_string()),
+            );
+        }
+        crate::audit::record(
+            config,
+            crate::audit::entry("prune", pruned, &result),
+            reporter,
+        );
+    }
+
+    match result {
         Ok(result) => {
-            if result.orphaned.is_empty() {
-                reporter.info("No orphaned plugin directories found.");
-                return true;
+            for path in &result.recent {
+                let name = path.file_name().unwrap().to_string_lossy();
+                reporter.info(&format!("Keeping recently modified: {name}"));
+            }
+
+            if result.orphaned.is_empty() && stale.is_empty() {
+                if stale_entries {
+                    reporter.info("No orphaned plugin directories or stale entries found.");
+                } else {
+                    reporter.info("No orphaned plugin directories found.");
+                }
+                return ExitStatus::Success;
             }
 
             if apply {
                 reporter.section("Prune");
+                for name in &stale {
+                    reporter.success(&format!("Removed stale entry: {name}"));
+                }
                 for path in &result.deleted {
                     let name = path.file_name().unwrap().to_string_lossy();
                     reporter.success(&format!("Deleted: {name}"));
@@ -24,27 +111,46 @@ pub fn run_prune(apply: bool, config: &MarketplaceConfig, reporter: &mut Reporte
                 for warn in &result.warnings {
                     reporter.warning(warn);
                 }
-                reporter.success(&format!(
-                    "Successfully pruned {} orphaned plugin directory(ies).",
-                    result.deleted.len()
-                ));
+                if !stale.is_empty() {
+                    reporter.summary(&format!(
+                        "Successfully removed {} stale marketplace entry(ies).",
+                        stale.len()
+                    ));
+                }
+                if !result.orphaned.is_empty() {
+                    reporter.summary(&format!(
+                        "Successfully pruned {} orphaned plugin directory(ies).",
+                        result.deleted.len()
+                    ));
+                }
             } else {
                 reporter.section("Prune (dry-run)");
+                for name in &stale {
+                    reporter.info(&format!("Would remove stale entry: {name}"));
+                }
                 for path in &result.orphaned {
                     let name = path.file_name().unwrap().to_string_lossy();
                     reporter.info(&format!("Would delete: {name}"));
                 }
-                reporter.info(&format!(
-                    "Found {} orphaned plugin directory(ies). Run with --apply to delete.",
-                    result.orphaned.len()
-                ));
+                if !stale.is_empty() {
+                    reporter.info(&format!(
+                        "Found {} stale marketplace entry(ies). Run with --apply to remove.",
+                        stale.len()
+                    ));
+                }
+                if !result.orphaned.is_empty() {
+                    reporter.info(&format!(
+                        "Found {} orphaned plugin directory(ies). Run with --apply to delete.",
+                        result.orphaned.len()
+                    ));
+                }
             }
 
-            true
+            ExitStatus::Success
         }
         Err(e) => {
             reporter.error(&format!("Prune failed: {e}"));
-            false
+            e.exit_status()
         }
     }
 }
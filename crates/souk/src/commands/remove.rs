@@ -3,25 +3,76 @@
 use crate::output::Reporter;
 use souk_core::discovery::MarketplaceConfig;
 use souk_core::ops::remove::remove_plugins;
+use souk_core::ops::retry_on_rollback;
+use souk_core::resolution::expand_plugin_patterns;
+use souk_core::ExitStatus;
 
 /// Run the remove command, removing plugins from the marketplace.
 ///
-/// Returns `true` on success, `false` on failure.
+/// Wildcard arguments such as `lint-*` select plugins by marketplace name;
+/// see [`expand_plugin_patterns`].
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
 pub fn run_remove(
     plugins: &[String],
     delete: bool,
     allow_external_delete: bool,
+    validate_after: bool,
+    retries: u32,
     config: &MarketplaceConfig,
     reporter: &mut Reporter,
-) -> bool {
+) -> ExitStatus {
     if plugins.is_empty() {
         reporter.error("At least one plugin name is required");
-        return false;
+        return ExitStatus::Usage;
     }
 
+    let names: Vec<String> = config
+        .marketplace
+        .plugins
+        .iter()
+        .map(|p| p.name.clone())
+        .collect();
+    let plugins = match expand_plugin_patterns(plugins, &names) {
+        Ok(p) => p,
+        Err(e) => {
+            reporter.error(&format!("{e}"));
+            return ExitStatus::Usage;
+        }
+    };
+
     reporter.section("Removing Plugins");
 
-    match remove_plugins(plugins, delete, allow_external_delete, config) {
+    if !validate_after {
+        reporter.warning("Skipping final marketplace validation; integrity is not re-checked");
+    }
+
+    let result = retry_on_rollback(
+        retries,
+        config,
+        |attempt, e| reporter.retrying(attempt, retries, e),
+        |current| {
+            remove_plugins(
+                &plugins,
+                delete,
+                allow_external_delete,
+                validate_after,
+                current,
+            )
+        },
+    );
+    let removed = match &result {
+        Ok(result) => result.removed.clone(),
+        Err(_) => plugins.clone(),
+    };
+    crate::audit::record(
+        config,
+        crate::audit::entry("remove", removed, &result),
+        reporter,
+    );
+
+    match result {
         Ok(result) => {
             if result.removed.is_empty() {
                 reporter.info("No plugins removed");
@@ -37,16 +88,16 @@ pub fn run_remove(
                 for warn in &result.warnings {
                     reporter.warning(warn);
                 }
-                reporter.success(&format!(
+                reporter.summary(&format!(
                     "Successfully removed {} plugin(s) from marketplace",
                     result.removed.len()
                 ));
             }
-            true
+            ExitStatus::Success
         }
         Err(e) => {
             reporter.error(&format!("Remove failed: {e}"));
-            false
+            e.exit_status()
         }
     }
 }
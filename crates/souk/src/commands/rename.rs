@@ -0,0 +1,58 @@
+//! Handler for the `souk rename` CLI command.
+
+use crate::output::Reporter;
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::ops::rename::rename_plugin;
+use souk_core::ops::retry_on_rollback;
+use souk_core::ExitStatus;
+
+/// Run the rename command, renaming a plugin and everything that refers to it.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_rename(
+    old: &str,
+    new: &str,
+    retries: u32,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    reporter.section("Renaming Plugin");
+
+    let result = retry_on_rollback(
+        retries,
+        config,
+        |attempt, e| reporter.retrying(attempt, retries, e),
+        |current| rename_plugin(old, new, current),
+    );
+    crate::audit::record(
+        config,
+        crate::audit::entry(
+            "rename",
+            vec![old.to_string(), new.trim().to_string()],
+            &result,
+        ),
+        reporter,
+    );
+
+    match result {
+        Ok(result) => {
+            if let Some((from, to)) = &result.moved {
+                reporter.info(&format!(
+                    "Moved {} -> {}",
+                    config.display_path(from),
+                    config.display_path(to)
+                ));
+            }
+            for name in &result.dependents {
+                reporter.info(&format!("Updated dependency in: {name}"));
+            }
+            reporter.success(&format!("Renamed: {old} -> {}", new.trim()));
+            ExitStatus::Success
+        }
+        Err(e) => {
+            reporter.error(&format!("Rename failed: {e}"));
+            e.exit_status()
+        }
+    }
+}
@@ -1,41 +1,132 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
 
+use souk_core::config::SoukConfig;
 use souk_core::discovery::{discover_marketplace, load_marketplace_config, MarketplaceConfig};
+use souk_core::error::SoukError;
 use souk_core::resolution::resolve_plugin;
-use souk_core::review::{detect_provider, review_plugin};
+use souk_core::review::{
+    detect_provider, post_comment, preflight, read_marketplace_review_prompt,
+    read_plugin_review_prompt, read_skill_review_prompts, review_marketplace,
+    review_marketplace_plugins, review_plugin, review_skills, FallbackProvider, LlmProvider,
+    PluginReviewOutcome, PullRequest, RateLimiter, ReportFormat, ReportOutput, ReviewCache,
+    ReviewReport,
+};
+use souk_core::ExitStatus;
 
 use crate::output::{OutputMode, Reporter};
 
+/// Skills reviewed at once when neither `--jobs` nor souk.toml sets it.
+const DEFAULT_JOBS: usize = 4;
+
+/// Provider selection shared by the review commands.
+pub struct ProviderOptions<'a> {
+    /// Provider override (`--provider`); souk.toml `provider` or
+    /// auto-detected when `None`.
+    pub name: Option<&'a str>,
+    /// Provider to retry the prompt with once when `name` fails
+    /// (`--fallback-provider`).
+    pub fallback: Option<&'a str>,
+    /// Model override for this review; souk.toml `model` or the provider
+    /// default when `None`.
+    pub model: Option<&'a str>,
+    /// Maximum provider requests per minute.
+    pub rate_limit: Option<u32>,
+    /// Per-request timeout; the provider default when `None`.
+    pub timeout: Option<Duration>,
+    /// Check the provider's credentials before any prompt is built
+    /// (`--check-auth`).
+    pub check_auth: bool,
+}
+
+impl<'a> ProviderOptions<'a> {
+    /// Fill the provider and model from souk.toml where no flag set them.
+    fn with_defaults<'b>(&'b self, settings: Option<&'b SoukConfig>) -> ProviderOptions<'b> {
+        ProviderOptions {
+            name: self
+                .name
+                .or_else(|| settings.and_then(|s| s.provider.as_deref())),
+            fallback: self.fallback,
+            model: self
+                .model
+                .or_else(|| settings.and_then(|s| s.model.as_deref())),
+            rate_limit: self.rate_limit,
+            timeout: self.timeout,
+            check_auth: self.check_auth,
+        }
+    }
+}
+
+/// Options specific to `souk review plugin`.
+pub struct PluginReviewOptions<'a> {
+    /// Report files written to the output directory (`--format`); souk.toml
+    /// `report_format` when `None`.
+    pub format: Option<ReportFormat>,
+    /// Reuse reviews cached in this directory (`--cache-dir`).
+    pub cache_dir: Option<&'a str>,
+    /// Print the effective cache key (`--verbose`).
+    pub verbose: bool,
+    /// Send plugin.json alone (`--manifest-only`).
+    pub manifest_only: bool,
+    /// Print the prompt instead of sending it (`--print-prompt`).
+    pub print_prompt: bool,
+    /// Rubric file (`--rubric`); souk.toml `review_rubric` when `None`.
+    pub rubric: Option<&'a Path>,
+    /// Fail when the parsed rating is below this (`--min-rating`).
+    pub min_rating: Option<u8>,
+    /// Post the review as a pull request comment (`--github-comment`).
+    pub github_comment: bool,
+}
+
 /// Run the `souk review plugin` command.
 ///
 /// Resolves the plugin, detects an LLM provider, sends the review prompt,
-/// and optionally saves the report to `output_dir`.
+/// and optionally saves the report to `output_dir`. With a cache directory,
+/// an earlier review of the same prompt and inputs is reused; `verbose`
+/// prints the effective cache key. A rubric from `--rubric` or souk.toml is
+/// appended to the prompt. With `print_prompt`, the prompt is printed and no
+/// provider is contacted. With `github_comment`, the review is also posted
+/// to the pull request of the GitHub Actions run, if there is one.
+///
+/// Returns [`ExitStatus::Findings`] when the review is rated below
+/// `--min-rating`, or the status of the error that stopped the review.
 pub fn run_review_plugin(
     plugin_input: &str,
     output_dir: Option<&str>,
-    provider_name: Option<&str>,
-    model: Option<&str>,
+    provider: &ProviderOptions,
+    options: &PluginReviewOptions,
     marketplace_override: Option<&str>,
     reporter: &mut Reporter,
-) -> bool {
+) -> ExitStatus {
     // Resolve plugin path
     let config = load_config(marketplace_override);
     let plugin_path = match resolve_plugin(plugin_input, config.as_ref()) {
         Ok(p) => p,
         Err(e) => {
             reporter.error(&format!("Failed to resolve plugin: {e}"));
-            return false;
+            return e.exit_status();
         }
     };
 
-    // Detect LLM provider
-    let provider = match detect_provider(provider_name, model) {
-        Ok(p) => p,
-        Err(e) => {
-            reporter.error(&format!("{e}"));
-            return false;
-        }
+    let Some(rubric) = load_rubric(options.rubric, config.as_ref(), reporter) else {
+        return ExitStatus::Internal;
     };
+    let settings = config.as_ref().map(|c| &c.settings);
+    let provider = provider.with_defaults(settings);
+
+    if options.print_prompt {
+        return emit_prompt(
+            read_plugin_review_prompt(&plugin_path, options.manifest_only, rubric.as_deref()),
+            reporter,
+        );
+    }
+
+    let provider = match build_provider(&provider, reporter) {
+        Ok(provider) => provider,
+        Err(status) => return status,
+    };
+    let primary = provider.name().to_string();
 
     reporter.info(&format!(
         "Reviewing plugin with {} ({})",
@@ -44,27 +135,591 @@ pub fn run_review_plugin(
     ));
 
     let output_path = output_dir.map(PathBuf::from);
-    match review_plugin(&plugin_path, provider.as_ref(), output_path.as_deref()) {
+    let output = output_path.as_deref().map(|dir| ReportOutput {
+        dir,
+        format: report_format(options.format, settings),
+    });
+    let cache = options.cache_dir.map(ReviewCache::new);
+    match review_plugin(
+        &plugin_path,
+        provider.as_ref(),
+        output,
+        cache.as_ref(),
+        options.manifest_only,
+        rubric.as_deref(),
+    ) {
         Ok(report) => {
+            note_fallback(&primary, &report.provider_name, reporter);
+            if options.verbose {
+                if let Some(key) = &report.cache_key {
+                    reporter.info(&format!("Review cache key: {key}"));
+                }
+            }
+            if report.cached {
+                reporter.info("Using cached review");
+            }
+            reporter.info(&format!("Request parameters: {}", report.parameters()));
             reporter.success(&format!("Plugin review complete: {}", report.plugin_name));
             if output_path.is_some() {
                 reporter.info("Review report saved");
             }
-            // Display the review text
-            match reporter.mode() {
-                OutputMode::Human => println!("\n{}", report.review_text),
-                OutputMode::Json => {
-                    reporter.info(&report.review_text);
+            reporter.review_text(&report.review_text);
+            let posted = if options.github_comment {
+                comment_on_pull_request(&report, reporter)
+            } else {
+                ExitStatus::Success
+            };
+            let rated = check_min_rating(
+                &report.plugin_name,
+                report.rating,
+                options.min_rating,
+                reporter,
+            );
+            if posted.is_success() {
+                rated
+            } else {
+                posted
+            }
+        }
+        Err(e) => {
+            reporter.error(&format!("Review failed: {e}"));
+            e.exit_status()
+        }
+    }
+}
+
+/// Options specific to `souk review skill`.
+pub struct SkillReviewOptions<'a> {
+    /// Report files written to the output directory (`--format`); souk.toml
+    /// `report_format` when `None`.
+    pub format: Option<ReportFormat>,
+    /// Skill names; comma-separated values are split.
+    pub skills: &'a [String],
+    /// Review every skill (`--all`).
+    pub all: bool,
+    /// Maximum skills reviewed at once (`--jobs`); souk.toml `jobs`, else
+    /// 4, when `None`.
+    pub jobs: Option<usize>,
+    /// Print the prompts instead of sending them (`--print-prompt`).
+    pub print_prompt: bool,
+    /// Rubric file (`--rubric`); souk.toml `review_rubric` when `None`.
+    pub rubric: Option<&'a Path>,
+    /// Fail when the parsed rating is below this (`--min-rating`).
+    pub min_rating: Option<u8>,
+}
+
+/// Options specific to `souk review marketplace`.
+pub struct MarketplaceReviewOptions<'a> {
+    /// Report files written to the output directory (`--format`); souk.toml
+    /// `report_format` when `None`.
+    pub format: Option<ReportFormat>,
+    /// Request only a rating and short summary (`--summary-only`).
+    pub summary_only: bool,
+    /// Also review every listed plugin (`--recursive`).
+    pub recursive: bool,
+    /// Maximum plugins reviewed at once with `recursive` (`--jobs`);
+    /// souk.toml `jobs`, else 4, when `None`.
+    pub jobs: Option<usize>,
+    /// Model override for the plugin reviews of `recursive`: the
+    /// `--model-map` plugin model, else `--model`.
+    pub plugin_model: Option<&'a str>,
+    /// Reuse plugin reviews cached in this directory (`--cache-dir`).
+    pub cache_dir: Option<&'a str>,
+    /// Print the prompt instead of sending it (`--print-prompt`).
+    pub print_prompt: bool,
+    /// Rubric file (`--rubric`); souk.toml `review_rubric` when `None`.
+    pub rubric: Option<&'a Path>,
+    /// Fail when the parsed rating is below this (`--min-rating`).
+    pub min_rating: Option<u8>,
+}
+
+/// Run the `souk review skill` command.
+///
+/// Resolves the plugin, detects an LLM provider, and reviews the selected
+/// skills up to `options.jobs` at a time, printing each review in skill
+/// order. Reports are saved to `output_dir` when given, and a rubric is
+/// appended to each prompt. With `options.print_prompt`, the prompts are
+/// printed and no provider is contacted.
+///
+/// Returns [`ExitStatus::Findings`] when any skill is rated below
+/// `--min-rating`, or the status of the error that stopped the review.
+pub fn run_review_skill(
+    plugin_input: &str,
+    output_dir: Option<&str>,
+    provider: &ProviderOptions,
+    options: &SkillReviewOptions,
+    marketplace_override: Option<&str>,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let config = load_config(marketplace_override);
+    let plugin_path = match resolve_plugin(plugin_input, config.as_ref()) {
+        Ok(p) => p,
+        Err(e) => {
+            reporter.error(&format!("Failed to resolve plugin: {e}"));
+            return e.exit_status();
+        }
+    };
+
+    let skill_names: Vec<String> = options
+        .skills
+        .iter()
+        .flat_map(|s| s.split(','))
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect();
+
+    let Some(rubric) = load_rubric(options.rubric, config.as_ref(), reporter) else {
+        return ExitStatus::Internal;
+    };
+    let settings = config.as_ref().map(|c| &c.settings);
+    let provider = provider.with_defaults(settings);
+
+    if options.print_prompt {
+        let prompts =
+            read_skill_review_prompts(&plugin_path, &skill_names, options.all, rubric.as_deref());
+        return emit_prompt(
+            prompts.map(|prompts| {
+                prompts
+                    .into_iter()
+                    .map(|(_, prompt)| prompt)
+                    .collect::<Vec<_>>()
+                    .join("\n---\n\n")
+            }),
+            reporter,
+        );
+    }
+
+    let provider = match build_provider(&provider, reporter) {
+        Ok(provider) => provider,
+        Err(status) => return status,
+    };
+    let primary = provider.name().to_string();
+
+    reporter.info(&format!(
+        "Reviewing skills with {} ({})",
+        provider.name(),
+        provider.model()
+    ));
+
+    let output_path = output_dir.map(PathBuf::from);
+    let output = output_path.as_deref().map(|dir| ReportOutput {
+        dir,
+        format: report_format(options.format, settings),
+    });
+    match review_skills(
+        &plugin_path,
+        &skill_names,
+        options.all,
+        provider.as_ref(),
+        output,
+        options
+            .jobs
+            .or_else(|| settings.and_then(|s| s.jobs).map(usize::from))
+            .unwrap_or(DEFAULT_JOBS),
+        rubric.as_deref(),
+    ) {
+        Ok(reports) => {
+            if let Some(report) = reports.first() {
+                note_fallback(&primary, &report.provider_name, reporter);
+            }
+            for report in &reports {
+                reporter.section(&format!("Skill: {}", report.skill_name));
+                reporter.review_text(&report.review_text);
+            }
+            reporter.summary(&format!(
+                "Skill review complete: {} skill(s)",
+                reports.len()
+            ));
+            if output_path.is_some() {
+                reporter.info("Review reports saved");
+            }
+            // Check every skill so each one below the threshold is reported.
+            let mut status = ExitStatus::Success;
+            for report in &reports {
+                let rated = check_min_rating(
+                    &report.skill_name,
+                    report.rating,
+                    options.min_rating,
+                    reporter,
+                );
+                if !rated.is_success() {
+                    status = rated;
                 }
-                OutputMode::Quiet => {}
             }
-            true
+            status
+        }
+        Err(e) => {
+            reporter.error(&format!("Review failed: {e}"));
+            e.exit_status()
+        }
+    }
+}
+
+/// Run the `souk review marketplace` command.
+///
+/// Sends every plugin manifest along with marketplace.json to the detected
+/// provider and saves the report to `output_dir` when given. With
+/// `summary_only`, only a rating and short summary are requested. A rubric is
+/// appended to the prompt. With `print_prompt`, the prompt is printed and no
+/// provider is contacted.
+///
+/// With `recursive`, every listed plugin is then reviewed as well (see
+/// [`review_plugins`]).
+///
+/// Returns [`ExitStatus::Findings`] when a review is rated below
+/// `--min-rating`, or the status of the error that stopped a review.
+pub fn run_review_marketplace(
+    config: &MarketplaceConfig,
+    output_dir: Option<&str>,
+    provider: &ProviderOptions,
+    options: &MarketplaceReviewOptions,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let Some(rubric) = load_rubric(options.rubric, Some(config), reporter) else {
+        return ExitStatus::Internal;
+    };
+    let settings = Some(&config.settings);
+    let provider = provider.with_defaults(settings);
+
+    if options.print_prompt {
+        return emit_prompt(
+            read_marketplace_review_prompt(config, options.summary_only, rubric.as_deref()),
+            reporter,
+        );
+    }
+
+    let provider_options = provider;
+    let provider = match build_provider(&provider_options, reporter) {
+        Ok(provider) => provider,
+        Err(status) => return status,
+    };
+    let primary = provider.name().to_string();
+
+    // Plugins reviewed with --recursive use the plugin-scope model, which
+    // needs its own provider when it differs from the marketplace model.
+    let plugin_model = options.plugin_model.or(config.settings.model.as_deref());
+    let plugin_provider = if options.recursive && plugin_model != provider_options.model {
+        let plugin_options = ProviderOptions {
+            model: plugin_model,
+            check_auth: false,
+            ..provider_options
+        };
+        match build_provider(&plugin_options, reporter) {
+            Ok(provider) => Some(provider),
+            Err(status) => return status,
+        }
+    } else {
+        None
+    };
+
+    reporter.info(&format!(
+        "Reviewing marketplace with {} ({})",
+        provider.name(),
+        provider.model()
+    ));
+
+    let output_path = output_dir.map(PathBuf::from);
+    let output = output_path.as_deref().map(|dir| ReportOutput {
+        dir,
+        format: report_format(options.format, settings),
+    });
+    match review_marketplace(
+        config,
+        provider.as_ref(),
+        output,
+        options.summary_only,
+        rubric.as_deref(),
+    ) {
+        Ok(report) => {
+            note_fallback(&primary, &report.provider_name, reporter);
+            reporter.success("Marketplace review complete");
+            if output_path.is_some() {
+                reporter.info("Review report saved");
+            }
+            reporter.review_text(&report.review_text);
+            let rated =
+                check_min_rating("marketplace", report.rating, options.min_rating, reporter);
+            if !options.recursive {
+                return rated;
+            }
+            let output = output_path.as_deref().map(|dir| ReportOutput {
+                dir,
+                format: report_format(options.format, settings),
+            });
+            let plugins = review_plugins(
+                config,
+                plugin_provider.as_deref().unwrap_or(provider.as_ref()),
+                output,
+                rubric.as_deref(),
+                options,
+                reporter,
+            );
+            if plugins.is_success() {
+                rated
+            } else {
+                plugins
+            }
         }
         Err(e) => {
             reporter.error(&format!("Review failed: {e}"));
-            false
+            e.exit_status()
+        }
+    }
+}
+
+/// Review every plugin in the marketplace, `options.jobs` at a time,
+/// reusing reviews cached in `options.cache_dir`.
+///
+/// In human mode each review is printed as soon as it completes; otherwise
+/// the reviews are reported in plugin-name order afterwards. Either way a
+/// summary ordered by plugin name follows. A failed plugin review is
+/// reported without stopping the others, and its error's status takes
+/// precedence over a rating below `options.min_rating`.
+fn review_plugins(
+    config: &MarketplaceConfig,
+    provider: &dyn LlmProvider,
+    output: Option<ReportOutput>,
+    rubric: Option<&str>,
+    options: &MarketplaceReviewOptions,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let jobs = options
+        .jobs
+        .or_else(|| config.settings.jobs.map(usize::from))
+        .unwrap_or(DEFAULT_JOBS);
+    let min_rating = options.min_rating;
+    let cache = options.cache_dir.map(ReviewCache::new);
+    let live = reporter.mode() == OutputMode::Human;
+    let shared = Mutex::new(&mut *reporter);
+    let outcomes = review_marketplace_plugins(
+        config,
+        provider,
+        output,
+        jobs,
+        rubric,
+        cache.as_ref(),
+        &|outcome| {
+            if live {
+                let mut reporter = shared.lock().unwrap_or_else(|e| e.into_inner());
+                report_plugin_review(outcome, &mut reporter);
+            }
+        },
+    );
+    if !live {
+        for outcome in &outcomes {
+            report_plugin_review(outcome, reporter);
+        }
+    }
+
+    reporter.section("Plugin review summary");
+    let mut status = ExitStatus::Success;
+    for outcome in &outcomes {
+        match &outcome.result {
+            Ok(report) => {
+                match report.rating {
+                    Some(rating) => {
+                        reporter.success(&format!("{}: rated {rating}/10", outcome.plugin_name))
+                    }
+                    None => reporter.success(&format!("{}: reviewed", outcome.plugin_name)),
+                }
+                let rated =
+                    check_min_rating(&outcome.plugin_name, report.rating, min_rating, reporter);
+                if status.is_success() {
+                    status = rated;
+                }
+            }
+            Err(e) => {
+                reporter.error(&format!("{}: review failed: {e}", outcome.plugin_name));
+                status = e.exit_status();
+            }
+        }
+    }
+    status
+}
+
+/// Print one plugin's review, or why it failed.
+fn report_plugin_review(outcome: &PluginReviewOutcome, reporter: &mut Reporter) {
+    match &outcome.result {
+        Ok(report) => {
+            reporter.section(&format!("Plugin: {}", outcome.plugin_name));
+            reporter.review_text(&report.review_text);
+        }
+        Err(e) => reporter.error(&format!("Review of {} failed: {e}", outcome.plugin_name)),
+    }
+}
+
+/// Print an assembled review prompt to stdout, or report why it could not be
+/// built.
+fn emit_prompt(prompt: Result<String, SoukError>, reporter: &mut Reporter) -> ExitStatus {
+    match prompt {
+        Ok(prompt) => {
+            print!("{prompt}");
+            ExitStatus::Success
+        }
+        Err(e) => {
+            reporter.error(&format!("Failed to build prompt: {e}"));
+            e.exit_status()
+        }
+    }
+}
+
+/// Enforce `--min-rating` for the review of `subject`. A review with no
+/// recognisable rating fails, since the threshold cannot be checked.
+///
+/// Returns [`ExitStatus::Findings`] when the check fails.
+fn check_min_rating(
+    subject: &str,
+    rating: Option<u8>,
+    min_rating: Option<u8>,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let Some(min) = min_rating else {
+        return ExitStatus::Success;
+    };
+    match rating {
+        Some(rating) if rating >= min => {
+            reporter.info(&format!("{subject}: rated {rating}/10 (minimum {min})"));
+            ExitStatus::Success
+        }
+        Some(rating) => {
+            reporter.error(&format!(
+                "{subject}: rated {rating}/10, below the minimum of {min}"
+            ));
+            ExitStatus::Findings
         }
+        None => {
+            reporter.error(&format!(
+                "{subject}: no overall rating found in the review; cannot check --min-rating"
+            ));
+            ExitStatus::Findings
+        }
+    }
+}
+
+/// Read the review rubric from `path`, falling back to souk.toml's
+/// `review_rubric`. Returns `Some(None)` when no rubric is configured and
+/// `None` after reporting a read failure.
+fn load_rubric(
+    path: Option<&Path>,
+    config: Option<&MarketplaceConfig>,
+    reporter: &mut Reporter,
+) -> Option<Option<String>> {
+    let Some(path) = path.or_else(|| config.and_then(|c| c.settings.review_rubric.as_deref()))
+    else {
+        return Some(None);
+    };
+    match std::fs::read_to_string(path) {
+        Ok(rubric) => Some(Some(rubric)),
+        Err(e) => {
+            reporter.error(&format!(
+                "Cannot read review rubric {}: {e}",
+                path.display()
+            ));
+            None
+        }
+    }
+}
+
+/// The report format from `--format`, else souk.toml, else both.
+fn report_format(format: Option<ReportFormat>, settings: Option<&SoukConfig>) -> ReportFormat {
+    format
+        .or_else(|| settings.and_then(|s| s.report_format))
+        .unwrap_or_default()
+}
+
+/// Post `report` to the pull request of the current GitHub Actions run.
+///
+/// Outside a pull request workflow this only warns, so the same command
+/// works locally; a comment GitHub rejects is an error.
+fn comment_on_pull_request(report: &ReviewReport, reporter: &mut Reporter) -> ExitStatus {
+    let pr = match PullRequest::from_env(|name| std::env::var(name).ok()) {
+        Ok(pr) => pr,
+        Err(e) => {
+            reporter.warning(&format!("Not posting a GitHub comment: {e}"));
+            return ExitStatus::Success;
+        }
+    };
+    match post_comment(&pr, report) {
+        Ok(()) => {
+            reporter.success(&format!("Posted review to {}#{}", pr.repository, pr.number));
+            ExitStatus::Success
+        }
+        Err(e) => {
+            reporter.error(&format!("{e}"));
+            e.exit_status()
+        }
+    }
+}
+
+/// Warn when the review was produced by the fallback provider rather than
+/// `primary`.
+fn note_fallback(primary: &str, used: &str, reporter: &mut Reporter) {
+    if used != primary {
+        reporter.warning(&format!(
+            "{primary} failed; review produced by fallback provider {used}"
+        ));
+    }
+}
+
+/// Detect the LLM provider, wrapping it with the optional fallback provider
+/// and requests-per-minute limit, and check its credentials when asked.
+fn build_provider(
+    options: &ProviderOptions,
+    reporter: &mut Reporter,
+) -> Result<Box<dyn LlmProvider>, ExitStatus> {
+    let mut provider = match detect_provider(options.name, options.model, options.timeout) {
+        Ok(p) => p,
+        Err(e) => {
+            reporter.error(&format!("{e}"));
+            return Err(e.exit_status());
+        }
+    };
+    if let Some(name) = options.fallback {
+        if name == provider.name() {
+            reporter.error(&format!(
+                "Fallback provider {name} is the same as the primary provider"
+            ));
+            return Err(ExitStatus::Usage);
+        }
+        // The primary's model override is provider-specific, so the fallback
+        // uses its own default model.
+        let fallback = match detect_provider(Some(name), None, options.timeout) {
+            Ok(p) => p,
+            Err(e) => {
+                reporter.error(&format!("Fallback provider {name}: {e}"));
+                return Err(e.exit_status());
+            }
+        };
+        provider = Box::new(FallbackProvider::new(provider, fallback));
+    }
+    let provider: Box<dyn LlmProvider> = match options.rate_limit {
+        Some(rpm) => Box::new(RateLimiter::new(provider, rpm)),
+        None => provider,
+    };
+    if options.check_auth {
+        if let Err(e) = preflight(provider.as_ref()) {
+            reporter.error(&format!("{e}"));
+            return Err(e.exit_status());
+        }
+        reporter.info(&format!(
+            "{} accepted the configured credentials",
+            provider.name()
+        ));
+    }
+    Ok(provider)
+}
+
+/// Resolve the column width for printed review text.
+///
+/// `--no-wrap` disables wrapping, `--wrap N` forces a width, and otherwise
+/// the terminal width is used when stdout is a terminal.
+pub fn wrap_width(wrap: Option<u16>, no_wrap: bool) -> Option<usize> {
+    if no_wrap {
+        return None;
     }
+    wrap.or_else(|| terminal_size::terminal_size().map(|(w, _)| w.0))
+        .map(usize::from)
 }
 
 /// Try to load marketplace config (non-fatal if not found).
@@ -72,7 +727,7 @@ fn load_config(marketplace_override: Option<&str>) -> Option<MarketplaceConfig>
     let mp_path = if let Some(path) = marketplace_override {
         PathBuf::from(path)
     } else {
-        let cwd = std::env::current_dir().ok()?;
+        let cwd = crate::cwd::current_dir().ok()?;
         discover_marketplace(&cwd).ok()?
     };
     load_marketplace_config(&mp_path).ok()
@@ -0,0 +1,356 @@
+//! Handler for the `souk search` CLI command.
+
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::json;
+use souk_core::remote::{fetch_index, index_cache_dir};
+use souk_core::types::PluginEntry;
+use souk_core::ExitStatus;
+
+use crate::commands::list::{print_count, Page};
+use crate::output::{OutputMode, Reporter};
+
+/// The part of a marketplace entry a query matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Field {
+    Name,
+    Tag,
+    Source,
+}
+
+/// A plugin matching a search query, with its best-scoring field.
+#[derive(Debug)]
+pub struct SearchHit<'a> {
+    pub entry: &'a PluginEntry,
+    /// Match quality from 1 to 100; higher is better.
+    pub score: u32,
+    pub field: Field,
+    /// The matched name, tag, or source.
+    pub value: &'a str,
+    /// Byte range of the query within `value`, or `None` for a fuzzy match.
+    pub span: Option<(usize, usize)>,
+}
+
+/// Run the search command, printing the `plugins` that match `query`, best
+/// first.
+///
+/// Human mode prints a `name  source  tags` table with the matched text
+/// highlighted. Quiet mode prints just names, and JSON mode emits
+/// `{total, offset, limit, items}` (see [`Page::json`]), where each item has
+/// its `score` and matched `field`.
+///
+/// `page` and `count` work as for `souk list` (see [`Page`]), over the
+/// ranked matches.
+pub fn run_search(
+    query: &str,
+    page: Page,
+    count: bool,
+    plugins: &[PluginEntry],
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let hits = search(plugins, query);
+    if count {
+        print_count(hits.len(), reporter);
+        return ExitStatus::Success;
+    }
+    let total = hits.len();
+    let hits = page.slice(&hits);
+
+    match reporter.mode() {
+        OutputMode::Human => {
+            if hits.is_empty() {
+                reporter.info(&format!("No plugins match '{query}'"));
+            }
+            for line in table(hits) {
+                println!("{line}");
+            }
+            page.report(hits.len(), total, reporter);
+        }
+        OutputMode::Quiet => {
+            for hit in hits {
+                println!("{}", hit.entry.name);
+            }
+        }
+        OutputMode::Json => {
+            let entries: Vec<_> = hits
+                .iter()
+                .map(|hit| {
+                    json!({
+                        "name": hit.entry.name,
+                        "source": hit.entry.source,
+                        "tags": hit.entry.tags,
+                        "score": hit.score,
+                        "field": hit.field,
+                        "matched": hit.value,
+                    })
+                })
+                .collect();
+            reporter.set_json_document(page.json(total, entries));
+        }
+    }
+    ExitStatus::Success
+}
+
+/// Run `souk search --remote`, searching the index at `url` exactly as
+/// [`run_search`] searches the local marketplace.
+///
+/// The index is cached briefly on disk; see [`fetch_index`].
+pub fn run_remote_search(
+    query: &str,
+    url: &str,
+    page: Page,
+    count: bool,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    match fetch_index(url, &index_cache_dir()) {
+        Ok(index) => run_search(query, page, count, &index.entries(), reporter),
+        Err(e) => {
+            reporter.error(&format!("Search failed: {e}"));
+            e.exit_status()
+        }
+    }
+}
+
+/// Rank `plugins` against `query`, best match first.
+///
+/// Names, tags, and sources are compared case-insensitively. An exact match
+/// scores 100, a prefix 90, and a substring 80. Failing those, a value or
+/// any of its `-`, `_`, `.`, or `/` separated words within a small edit
+/// distance of the query is a fuzzy match: 60 for one typo, 45 for two.
+/// Queries under four characters allow no typos, and under eight only one.
+///
+/// Each plugin is ranked by its best field; ties prefer names over tags over
+/// sources, then sort by name. Plugins that match nothing are omitted, as is
+/// everything for a blank query.
+pub fn search<'a>(plugins: &'a [PluginEntry], query: &str) -> Vec<SearchHit<'a>> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<_> = plugins
+        .iter()
+        .filter_map(|entry| {
+            let fields = std::iter::once((Field::Name, entry.name.as_str()))
+                .chain(entry.tags.iter().map(|t| (Field::Tag, t.as_str())))
+                .chain(std::iter::once((Field::Source, entry.source.as_str())));
+            fields
+                .filter_map(|(field, value)| {
+                    score(value, &query).map(|(score, span)| SearchHit {
+                        entry,
+                        score,
+                        field,
+                        value,
+                        span,
+                    })
+                })
+                .min_by(|a, b| b.score.cmp(&a.score).then(a.field.cmp(&b.field)))
+        })
+        .collect();
+
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.field.cmp(&b.field))
+            .then_with(|| {
+                a.entry
+                    .name
+                    .to_lowercase()
+                    .cmp(&b.entry.name.to_lowercase())
+            })
+    });
+    hits
+}
+
+/// Score one `value` against a lowercase `query`, with the matched span.
+fn score(value: &str, query: &str) -> Option<(u32, Option<(usize, usize)>)> {
+    let lower = value.to_lowercase();
+    if let Some(start) = lower.find(query) {
+        let score = if lower == query {
+            100
+        } else if start == 0 {
+            90
+        } else {
+            80
+        };
+        // Lowercasing can change byte offsets outside ASCII.
+        let span = (lower.len() == value.len()).then_some((start, start + query.len()));
+        return Some((score, span));
+    }
+
+    let max_typos = match query.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    let distance = std::iter::once(lower.as_str())
+        .chain(lower.split(['-', '_', '.', '/']))
+        .map(|word| levenshtein(word, query))
+        .min()?;
+    match distance {
+        1 if max_typos >= 1 => Some((60, None)),
+        2 if max_typos >= 2 => Some((45, None)),
+        _ => None,
+    }
+}
+
+/// The number of single-character insertions, deletions, or substitutions
+/// needed to turn `a` into `b`.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Lines of the human-mode table, padded like `souk list`, with the matched
+/// text of each hit highlighted. A fuzzy match highlights the whole value.
+fn table(hits: &[SearchHit]) -> Vec<String> {
+    let name_width = hits.iter().map(|h| h.entry.name.len()).max().unwrap_or(0);
+    let source_width = hits.iter().map(|h| h.entry.source.len()).max().unwrap_or(0);
+    hits.iter()
+        .map(|hit| {
+            let name = highlight(&hit.entry.name, hit, Field::Name);
+            let source = highlight(&hit.entry.source, hit, Field::Source);
+            let mut cells = vec![
+                format!("{name}{}", pad(&hit.entry.name, name_width)),
+                format!("{source}{}", pad(&hit.entry.source, source_width)),
+            ];
+            if !hit.entry.tags.is_empty() {
+                let tags: Vec<_> = hit
+                    .entry
+                    .tags
+                    .iter()
+                    .map(|t| highlight(t, hit, Field::Tag))
+                    .collect();
+                cells.push(tags.join(", "));
+            }
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Spaces that pad `text` out to `width` columns.
+fn pad(text: &str, width: usize) -> String {
+    " ".repeat(width.saturating_sub(text.len()))
+}
+
+/// `text` with the matched span emphasised, if `hit` matched it as `field`.
+fn highlight(text: &str, hit: &SearchHit, field: Field) -> String {
+    if hit.field != field || text != hit.value {
+        return text.to_string();
+    }
+    match hit.span {
+        Some((start, end)) => format!(
+            "{}{}{}",
+            &text[..start],
+            text[start..end].bold().yellow(),
+            &text[end..]
+        ),
+        None => text.bold().yellow().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, source: &str, tags: &[&str]) -> PluginEntry {
+        PluginEntry {
+            name: name.to_string(),
+            source: source.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            origin: None,
+            sha: None,
+        }
+    }
+
+    fn ranked(hits: &[SearchHit]) -> Vec<(String, u32, Field)> {
+        hits.iter()
+            .map(|h| (h.entry.name.clone(), h.score, h.field))
+            .collect()
+    }
+
+    #[test]
+    fn levenshtein_distances() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("lint", "lint"), 0);
+        assert_eq!(levenshtein("lnit", "lint"), 2);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("formater", "formatter"), 1);
+    }
+
+    #[test]
+    fn ranks_exact_then_prefix_then_substring() {
+        let plugins = [
+            entry("git-lint", "git-lint", &[]),
+            entry("lint", "lint", &[]),
+            entry("Linter", "linter", &[]),
+        ];
+        assert_eq!(
+            ranked(&search(&plugins, "LINT")),
+            [
+                ("lint".to_string(), 100, Field::Name),
+                ("Linter".to_string(), 90, Field::Name),
+                ("git-lint".to_string(), 80, Field::Name),
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_tags_and_sources() {
+        let plugins = [
+            entry("alpha", "vendor/alpha", &["Deploy"]),
+            entry("beta", "deploy-tools", &[]),
+        ];
+        let hits = search(&plugins, "deploy");
+        assert_eq!(
+            ranked(&hits),
+            [
+                ("alpha".to_string(), 100, Field::Tag),
+                ("beta".to_string(), 90, Field::Source),
+            ]
+        );
+        assert_eq!(hits[0].value, "Deploy");
+        assert_eq!(hits[1].span, Some((0, 6)));
+    }
+
+    #[test]
+    fn fuzzy_matches_tolerate_typos() {
+        let plugins = [
+            entry("code-formatter", "fmt", &[]),
+            entry("commit-helper", "commit", &[]),
+            entry("docs", "docs", &[]),
+        ];
+        assert_eq!(
+            ranked(&search(&plugins, "formater")),
+            [("code-formatter".to_string(), 60, Field::Name)]
+        );
+        assert_eq!(
+            ranked(&search(&plugins, "comit")),
+            [("commit-helper".to_string(), 60, Field::Name)]
+        );
+        // Short queries must match exactly.
+        assert!(search(&plugins, "dox").is_empty());
+        assert!(search(&plugins, "  ").is_empty());
+    }
+
+    #[test]
+    fn span_covers_matched_text() {
+        let plugins = [entry("git-lint", "src", &[]), entry("İnspect", "s", &[])];
+        assert_eq!(search(&plugins, "lint")[0].span, Some((4, 8)));
+        // Lowercasing "İ" adds a byte, so the whole name is highlighted
+        // rather than a shifted span.
+        let hits = search(&plugins, "nspect");
+        assert_eq!((hits[0].score, hits[0].span), (80, None));
+    }
+}
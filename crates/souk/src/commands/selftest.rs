@@ -0,0 +1,150 @@
+//! Handler for the `souk selftest` CLI command.
+//!
+//! Runs the core marketplace lifecycle against a throwaway directory so an
+//! installation can be verified without network access or API keys.
+
+use std::path::Path;
+
+use souk_core::discovery::{load_marketplace_config, MarketplaceConfig};
+use souk_core::ops::add::{execute_add, plan_add};
+use souk_core::ops::init::scaffold_marketplace;
+use souk_core::ops::prune::prune_plugins;
+use souk_core::ops::remove::remove_plugins;
+use souk_core::review::{review_plugin, MockProvider, ReportFormat, ReportOutput};
+use souk_core::validation::validate_marketplace;
+use souk_core::ExitStatus;
+use tempfile::TempDir;
+
+use crate::output::Reporter;
+
+const PLUGIN_NAME: &str = "selftest-plugin";
+
+type Step<'a> = (&'static str, &'a dyn Fn() -> Result<(), String>);
+
+/// Run every self-test step in order, stopping at the first failure.
+///
+/// Returns [`ExitStatus::Success`] if all steps pass. A failing step means
+/// souk itself is broken, so it returns [`ExitStatus::Internal`].
+pub fn run_selftest(reporter: &mut Reporter) -> ExitStatus {
+    let tmp = match TempDir::new() {
+        Ok(t) => t,
+        Err(e) => {
+            reporter.error(&format!("Cannot create temp directory: {e}"));
+            return ExitStatus::Internal;
+        }
+    };
+    let root = tmp.path().join("marketplace");
+    let plugin_src = tmp.path().join(PLUGIN_NAME);
+
+    reporter.section("Self-test");
+
+    let steps: [Step; 7] = [
+        ("Scaffold marketplace", &|| {
+            scaffold_marketplace(&root, "./plugins").map_err(|e| e.to_string())
+        }),
+        ("Scaffold example plugin", &|| {
+            scaffold_plugin(&plugin_src).map_err(|e| e.to_string())
+        }),
+        ("Add plugin", &|| {
+            let config = load(&root)?;
+            let inputs = [plugin_src.to_string_lossy().to_string()];
+            let plan = plan_add(&inputs, &config, "abort", false).map_err(|e| e.to_string())?;
+            let added =
+                execute_add(&plan, &config, false, true, false).map_err(|e| e.to_string())?;
+            expect(
+                added == [PLUGIN_NAME],
+                format!("expected {PLUGIN_NAME}, added {added:?}"),
+            )
+        }),
+        ("Validate marketplace", &|| {
+            let result = validate_marketplace(&load(&root)?, false);
+            expect(
+                !result.has_errors(),
+                format!("{} validation error(s)", result.error_count()),
+            )
+        }),
+        ("Review plugin with mock provider", &|| {
+            let config = load(&root)?;
+            let provider = MockProvider::new("Self-test review. Rating: 10/10");
+            let reviews = tmp.path().join("reviews");
+            let report = review_plugin(
+                &config.plugin_root_abs.join(PLUGIN_NAME),
+                &provider,
+                Some(ReportOutput {
+                    dir: &reviews,
+                    format: ReportFormat::Markdown,
+                }),
+                None,
+                false,
+                None,
+            )
+            .map_err(|e| e.to_string())?;
+            expect(
+                reviews
+                    .join(format!("{}-review-report.md", report.plugin_name))
+                    .is_file(),
+                "review report was not written".to_string(),
+            )
+        }),
+        ("Remove plugin", &|| {
+            let config = load(&root)?;
+            let result = remove_plugins(&[PLUGIN_NAME.to_string()], false, false, true, &config)
+                .map_err(|e| e.to_string())?;
+            expect(
+                result.removed == [PLUGIN_NAME],
+                format!("expected {PLUGIN_NAME}, removed {:?}", result.removed),
+            )
+        }),
+        ("Prune orphaned directory", &|| {
+            let result = prune_plugins(true, None, &load(&root)?).map_err(|e| e.to_string())?;
+            expect(
+                result.deleted.len() == 1,
+                format!("expected 1 deleted directory, got {}", result.deleted.len()),
+            )
+        }),
+    ];
+
+    let total = steps.len();
+    for (i, (name, step)) in steps.iter().enumerate() {
+        if let Err(e) = step() {
+            reporter.error(&format!("[{}/{total}] {name}: {e}", i + 1));
+            reporter.error("Self-test failed");
+            return ExitStatus::Internal;
+        }
+        reporter.success(&format!("[{}/{total}] {name}", i + 1));
+    }
+
+    reporter.summary("Self-test passed");
+    ExitStatus::Success
+}
+
+fn load(root: &Path) -> Result<MarketplaceConfig, String> {
+    load_marketplace_config(&root.join(".claude-plugin").join("marketplace.json"))
+        .map_err(|e| e.to_string())
+}
+
+fn expect(condition: bool, message: String) -> Result<(), String> {
+    if condition {
+        Ok(())
+    } else {
+        Err(message)
+    }
+}
+
+/// Write a minimal plugin with one skill to `dir`.
+fn scaffold_plugin(dir: &Path) -> std::io::Result<()> {
+    let claude = dir.join(".claude-plugin");
+    std::fs::create_dir_all(&claude)?;
+    std::fs::write(
+        claude.join("plugin.json"),
+        format!(
+            "{{\n  \"name\": \"{PLUGIN_NAME}\",\n  \"version\": \"0.1.0\",\n  \"description\": \"Plugin generated by souk selftest\"\n}}\n"
+        ),
+    )?;
+    let skill = dir.join("skills").join("hello");
+    std::fs::create_dir_all(&skill)?;
+    std::fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: hello\ndescription: Says hello\n---\n\n# Hello\n",
+    )
+}
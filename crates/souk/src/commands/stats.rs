@@ -0,0 +1,64 @@
+//! Handler for the `souk stats` CLI command.
+
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::stats::compute_stats;
+use souk_core::ExitStatus;
+
+use crate::output::{OutputMode, Reporter};
+
+/// Run the stats command, summarizing the marketplace's plugins.
+///
+/// JSON mode emits the [`souk_core::stats::MarketplaceStats`] document
+/// directly; human mode shows the marketplace's keywords and categories and
+/// lists tags from most to least used.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_stats(config: &MarketplaceConfig, reporter: &mut Reporter) -> ExitStatus {
+    let stats = compute_stats(config);
+
+    if reporter.mode() == OutputMode::Json {
+        match serde_json::to_value(&stats) {
+            Ok(doc) => reporter.set_json_document(doc),
+            Err(e) => {
+                reporter.error(&format!("Stats failed: {e}"));
+                return ExitStatus::Internal;
+            }
+        }
+        return ExitStatus::Success;
+    }
+
+    println!(
+        "{:<14} {} ({} internal, {} external)",
+        "Plugins:", stats.plugins, stats.internal, stats.external
+    );
+    println!("{:<14} {}", "Archived:", stats.archived);
+    println!("{:<14} {}", "Skills:", stats.skills);
+    println!(
+        "{:<14} {} plugin(s) declare dependencies",
+        "Dependencies:", stats.with_dependencies
+    );
+    for (label, values) in [
+        ("Keywords:", &stats.keywords),
+        ("Categories:", &stats.categories),
+    ] {
+        if values.is_empty() {
+            println!("{label:<14} none");
+        } else {
+            println!("{label:<14} {}", values.join(", "));
+        }
+    }
+    if stats.tags.is_empty() {
+        println!("{:<14} none", "Tags:");
+        return ExitStatus::Success;
+    }
+    println!("Tags:");
+    let mut tags: Vec<_> = stats.tags.iter().collect();
+    // Most used first; BTreeMap order breaks ties by name.
+    tags.sort_by(|a, b| b.1.cmp(a.1));
+    let width = tags.iter().map(|(tag, _)| tag.len()).max().unwrap_or(0);
+    for (tag, count) in tags {
+        println!("  {tag:<width$}  {count}");
+    }
+    ExitStatus::Success
+}
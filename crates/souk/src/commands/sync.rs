@@ -0,0 +1,98 @@
+//! Handler for the `souk sync` CLI command.
+
+use crate::output::Reporter;
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::ops::retry_on_rollback;
+use souk_core::ops::sync::{execute_sync, plan_sync};
+use souk_core::ExitStatus;
+
+/// Run the sync command, reconciling marketplace.json with pluginRoot.
+///
+/// If the write is rolled back, the sync is planned again against the
+/// reloaded marketplace and retried, up to `retries` times.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_sync(
+    dry_run: bool,
+    validate_after: bool,
+    retries: u32,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let plan = match plan_sync(config) {
+        Ok(plan) => plan,
+        Err(e) => {
+            reporter.error(&format!("Sync failed: {e}"));
+            return e.exit_status();
+        }
+    };
+
+    if plan.is_empty() {
+        reporter.success("Marketplace is in sync with the filesystem.");
+        return ExitStatus::Success;
+    }
+
+    reporter.section("Planning Operations");
+    for addition in &plan.additions {
+        reporter.info(&format!(
+            "ADD {} (source: {})",
+            addition.entry.name, addition.entry.source
+        ));
+    }
+    for name in &plan.removals {
+        reporter.info(&format!("REMOVE {name} (directory not found)"));
+    }
+
+    if dry_run {
+        reporter.warning("Dry run mode - no changes made");
+        return ExitStatus::Success;
+    }
+    if !validate_after {
+        reporter.warning("Skipping final marketplace validation; integrity is not re-checked");
+    }
+
+    // The first attempt runs the plan shown above; a retry re-plans
+    // against the reloaded marketplace.
+    let mut planned = Some(plan.clone());
+    let result = retry_on_rollback(
+        retries,
+        config,
+        |attempt, e| reporter.retrying(attempt, retries, e),
+        |current| {
+            let plan = match planned.take() {
+                Some(plan) => plan,
+                None => plan_sync(current)?,
+            };
+            execute_sync(&plan, current, validate_after).map(|()| plan)
+        },
+    );
+    let executed = result.as_ref().unwrap_or(&plan);
+    let plugins = executed
+        .additions
+        .iter()
+        .map(|a| a.entry.name.clone())
+        .chain(executed.removals.iter().cloned())
+        .collect();
+    crate::audit::record(
+        config,
+        crate::audit::entry("sync", plugins, &result),
+        reporter,
+    );
+
+    match result {
+        Ok(plan) => {
+            reporter.section("Summary");
+            reporter.summary(&format!(
+                "Synced marketplace: {} added, {} removed",
+                plan.additions.len(),
+                plan.removals.len()
+            ));
+            ExitStatus::Success
+        }
+        Err(e) => {
+            reporter.error(&format!("Sync failed: {e}"));
+            e.exit_status()
+        }
+    }
+}
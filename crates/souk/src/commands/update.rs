@@ -1,30 +1,221 @@
 //! Handler for the `souk update` CLI command.
 
-use crate::output::Reporter;
+use crate::output::{OutputMode, Reporter};
+use serde_json::json;
 use souk_core::discovery::MarketplaceConfig;
-use souk_core::ops::update::update_plugins;
+use souk_core::ops::retry_on_rollback;
+use souk_core::ops::update::{bump_dependency, check_drift, update_plugins, UpdateFields};
+use souk_core::resolution::expand_plugin_patterns;
+use souk_core::version::VersionBump;
+use souk_core::ExitStatus;
+
+/// The plugins a `souk update` run applies to, as given on the command line.
+pub struct PluginSelection {
+    /// Plugin names, which may be wildcards.
+    pub plugins: Vec<String>,
+    /// `--all`: every plugin in the marketplace.
+    pub all: bool,
+}
+
+/// Expand the selection against the marketplace.
+///
+/// Wildcard arguments are matched against marketplace names; an empty or
+/// unmatched selection is a usage error. `--all` on a marketplace without
+/// plugins has nothing to update, which is reported and yields
+/// `Err(ExitStatus::Success)`.
+fn expand_names(
+    selection: &PluginSelection,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> Result<Vec<String>, ExitStatus> {
+    let names: Vec<String> = config
+        .marketplace
+        .plugins
+        .iter()
+        .map(|p| p.name.clone())
+        .collect();
+    if selection.all {
+        if names.is_empty() {
+            reporter.info("No plugins in the marketplace; nothing to update");
+            return Err(ExitStatus::Success);
+        }
+        return Ok(names);
+    }
+    if selection.plugins.is_empty() {
+        reporter.error("At least one plugin name (or --all) is required");
+        return Err(ExitStatus::Usage);
+    }
+
+    expand_plugin_patterns(&selection.plugins, &names).map_err(|e| {
+        reporter.error(&format!("{e}"));
+        ExitStatus::Usage
+    })
+}
+
+/// Run `souk update --check`, reporting entries whose `fields` differ from
+/// plugin.json without writing anything.
+///
+/// In JSON mode, prints `{ok, checked, drifted: [{plugin, fields,
+/// marketplace, pluginJson}]}`. Returns [`ExitStatus::Findings`] if any
+/// entry has drifted.
+pub fn run_update_check(
+    selection: &PluginSelection,
+    fields: UpdateFields,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let plugins = match expand_names(selection, config, reporter) {
+        Ok(plugins) => plugins,
+        Err(status) => return status,
+    };
+    let drift = match check_drift(&plugins, fields, config) {
+        Ok(drift) => drift,
+        Err(e) => {
+            reporter.error(&format!("Check failed: {e}"));
+            return e.exit_status();
+        }
+    };
+
+    if reporter.mode() == OutputMode::Json {
+        let drifted: Vec<_> = drift
+            .iter()
+            .map(|d| {
+                json!({
+                    "plugin": d.current.name,
+                    "fields": d.fields(),
+                    "marketplace": d.current,
+                    "pluginJson": d.desired,
+                })
+            })
+            .collect();
+        reporter.set_json_document(json!({
+            "ok": drift.is_empty(),
+            "checked": plugins.len(),
+            "drifted": drifted,
+        }));
+        return if drift.is_empty() {
+            ExitStatus::Success
+        } else {
+            ExitStatus::Findings
+        };
+    }
+
+    for d in &drift {
+        for field in d.fields() {
+            let (current, desired) = match field {
+                "name" => (d.current.name.clone(), d.desired.name.clone()),
+                _ => (
+                    format!("[{}]", d.current.tags.join(", ")),
+                    format!("[{}]", d.desired.tags.join(", ")),
+                ),
+            };
+            reporter.error(&format!(
+                "{}: {field} out of sync (marketplace: {current}, plugin.json: {desired})",
+                d.current.name
+            ));
+        }
+    }
+    if drift.is_empty() {
+        reporter.summary(&format!("{} plugin(s) in sync", plugins.len()));
+        ExitStatus::Success
+    } else {
+        reporter.error(&format!(
+            "{} of {} plugin(s) out of sync; run `souk update` to refresh them",
+            drift.len(),
+            plugins.len()
+        ));
+        ExitStatus::Findings
+    }
+}
+
+/// How `souk update` changes the selected plugins.
+pub struct UpdateOptions<'a> {
+    /// Version bump to apply to each plugin.json, if any.
+    pub bump: Option<&'a VersionBump>,
+    /// Message recorded in each bumped plugin's CHANGELOG.md.
+    pub changelog: Option<&'a str>,
+    /// Keep the pre-release tag on a major, minor, or patch bump.
+    pub keep_prerelease: bool,
+    /// Marketplace-entry fields to refresh from plugin.json.
+    pub fields: UpdateFields,
+    /// Re-validate the marketplace after writing.
+    pub validate_after: bool,
+    /// Times to reload the marketplace and retry after a rollback.
+    pub retries: u32,
+}
 
 /// Run the update command, refreshing plugin metadata and optionally bumping versions.
 ///
-/// Returns `true` on success, `false` on failure.
+/// Only the marketplace-entry fields selected by `fields` are refreshed.
+/// Wildcard arguments such as `lint-*` select plugins by marketplace name;
+/// see [`expand_plugin_patterns`].
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
 pub fn run_update(
-    plugins: &[String],
-    bump_type: Option<&str>,
+    selection: &PluginSelection,
+    options: &UpdateOptions,
     config: &MarketplaceConfig,
     reporter: &mut Reporter,
-) -> bool {
-    if plugins.is_empty() {
-        reporter.error("At least one plugin name is required");
-        return false;
-    }
+) -> ExitStatus {
+    let plugins = match expand_names(selection, config, reporter) {
+        Ok(plugins) => plugins,
+        Err(status) => return status,
+    };
+    let &UpdateOptions {
+        bump,
+        changelog,
+        keep_prerelease,
+        fields,
+        validate_after,
+        retries,
+    } = options;
 
     reporter.section("Updating Plugins");
 
-    if let Some(bump) = bump_type {
-        reporter.info(&format!("Version bump: {bump}"));
+    if let Some(bump) = bump {
+        if keep_prerelease {
+            reporter.info(&format!("Version bump: {bump} (keeping pre-release)"));
+        } else {
+            reporter.info(&format!("Version bump: {bump}"));
+        }
+    }
+
+    if fields.sha {
+        reporter.info("Refreshing the recorded commit of each git origin");
+    }
+
+    if !validate_after {
+        reporter.warning("Skipping final marketplace validation; integrity is not re-checked");
     }
 
-    match update_plugins(plugins, bump_type, config) {
+    let result = retry_on_rollback(
+        retries,
+        config,
+        |attempt, e| reporter.retrying(attempt, retries, e),
+        |current| {
+            update_plugins(
+                &plugins,
+                bump,
+                changelog,
+                keep_prerelease,
+                fields,
+                validate_after,
+                current,
+            )
+        },
+    );
+    let updated = match &result {
+        Ok(updated) => updated.clone(),
+        Err(_) => plugins.clone(),
+    };
+    crate::audit::record(
+        config,
+        crate::audit::entry("update", updated, &result),
+        reporter,
+    );
+
+    match result {
         Ok(updated) => {
             if updated.is_empty() {
                 reporter.info("No plugins updated");
@@ -33,13 +224,85 @@ pub fn run_update(
                 for name in &updated {
                     reporter.success(&format!("Updated: {name}"));
                 }
-                reporter.success(&format!("Successfully updated {} plugin(s)", updated.len()));
+                reporter.summary(&format!("Successfully updated {} plugin(s)", updated.len()));
+            }
+            ExitStatus::Success
+        }
+        Err(e) => {
+            reporter.error(&format!("Update failed: {e}"));
+            e.exit_status()
+        }
+    }
+}
+
+/// Run `souk update <plugins> --bump-dep <dependency>`, pointing each
+/// plugin's constraint on `dependency` at the dependency's current version.
+///
+/// Returns [`ExitStatus::Success`], or the status of the error that
+/// stopped the command.
+pub fn run_bump_dependency(
+    selection: &PluginSelection,
+    dependency: &str,
+    validate_after: bool,
+    retries: u32,
+    config: &MarketplaceConfig,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let plugins = match expand_names(selection, config, reporter) {
+        Ok(plugins) => plugins,
+        Err(status) => return status,
+    };
+
+    reporter.section("Updating Dependency Constraints");
+
+    if !validate_after {
+        reporter.warning("Skipping final marketplace validation; integrity is not re-checked");
+    }
+
+    let result = retry_on_rollback(
+        retries,
+        config,
+        |attempt, e| reporter.retrying(attempt, retries, e),
+        |current| bump_dependency(&plugins, dependency, validate_after, current),
+    );
+    let bumped = match &result {
+        Ok(bumps) => {
+            let mut bumped: Vec<String> = bumps
+                .iter()
+                .filter(|b| !b.unchanged())
+                .map(|b| b.plugin.clone())
+                .collect();
+            bumped.dedup();
+            bumped
+        }
+        Err(_) => plugins.clone(),
+    };
+    crate::audit::record(
+        config,
+        crate::audit::entry("bump-dep", bumped, &result),
+        reporter,
+    );
+
+    match result {
+        Ok(bumps) => {
+            for bump in &bumps {
+                if bump.unchanged() {
+                    reporter.info(&format!(
+                        "{} ({}): {dependency} {} already matches",
+                        bump.plugin, bump.section, bump.to
+                    ));
+                } else {
+                    reporter.success(&format!(
+                        "{} ({}): {dependency} {} -> {}",
+                        bump.plugin, bump.section, bump.from, bump.to
+                    ));
+                }
             }
-            true
+            ExitStatus::Success
         }
         Err(e) => {
             reporter.error(&format!("Update failed: {e}"));
-            false
+            e.exit_status()
         }
     }
 }
@@ -1,30 +1,83 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use indicatif::{ProgressBar, ProgressStyle};
-use souk_core::discovery::{discover_marketplace, load_marketplace_config, MarketplaceConfig};
-use souk_core::resolution::{resolve_plugin, resolve_source};
-use souk_core::validation::{validate_marketplace, validate_plugin};
+use serde::Serialize;
+use souk_core::discovery::{
+    discover_all_marketplaces, discover_marketplace, load_marketplace_config, MarketplaceConfig,
+};
+use souk_core::report::{to_junit_xml, to_sarif, SkillCase};
+use souk_core::resolution::{enumerate_skills, resolve_plugin, resolve_source};
+use souk_core::validation::{
+    check_readme_title, directory_counts, validate_marketplace, validate_plugin_with,
+    validate_skill,
+};
+use souk_core::{ExitStatus, ValidationDiagnostic, ValidationResult};
+
+use crate::cli::{GroupBy, JunitGranularity, ValidateFormat};
+use crate::output::{diagnostic_message, OutputMode, Reporter};
+
+/// Machine-readable summary of a `souk validate plugin` run.
+#[derive(Debug, Serialize)]
+struct PluginRunSummary {
+    total: usize,
+    passed: usize,
+    failed: usize,
+    plugins: Vec<PluginOutcome>,
+}
 
-use crate::output::{OutputMode, Reporter};
+/// Validation outcome for a single plugin.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PluginOutcome {
+    name: String,
+    passed: bool,
+    error_count: usize,
+    warning_count: usize,
+}
+
+/// Flags of `souk validate plugin`.
+pub struct PluginValidateOptions {
+    /// Warn when the README title does not match plugin.json.
+    pub check_readme: bool,
+    /// Warn when plugin.json is not in canonical formatting.
+    pub check_formatting: bool,
+    /// Print a SARIF or JUnit report instead of the usual output.
+    pub format: ValidateFormat,
+    /// Add a JUnit testcase per skill.
+    pub junit_granularity: JunitGranularity,
+}
 
 pub fn run_validate_plugin(
+    options: &PluginValidateOptions,
     plugins: &[String],
+    schema: Option<PathBuf>,
+    strict: bool,
     marketplace_override: Option<&str>,
     reporter: &mut Reporter,
-) -> bool {
+) -> ExitStatus {
     let config = load_config(marketplace_override);
+    let mut settings = config
+        .as_ref()
+        .map(|c| c.settings.clone())
+        .unwrap_or_default();
+    if schema.is_some() {
+        settings.plugin_schema = schema;
+    }
+    settings.check_formatting |= options.check_formatting;
 
     let plugin_paths = collect_plugin_paths(plugins, config.as_ref(), reporter);
 
     if plugin_paths.is_empty() {
         reporter.error("No plugins found to validate");
-        return false;
+        return ExitStatus::Usage;
     }
 
     reporter.section(&format!("Validating {} plugin(s)", plugin_paths.len()));
 
     let mut success_count = 0;
     let mut failure_count = 0;
+    let mut outcomes = Vec::with_capacity(plugin_paths.len());
+    let mut all_diagnostics = ValidationResult::new();
 
     // Show a progress bar when validating multiple plugins in Human output mode
     let progress = if plugin_paths.len() > 1 && reporter.mode() == OutputMode::Human {
@@ -49,42 +102,58 @@ pub fn run_validate_plugin(
             pb.set_message(plugin_name.clone());
         }
 
-        let result = validate_plugin(path);
-
-        if result.has_errors() {
+        let mut result = validate_plugin_with(path, &settings);
+        if options.check_readme {
+            result.merge(check_readme_title(path));
+        }
+        if options.format != ValidateFormat::Text {
+            collect_for_report(&mut all_diagnostics, &result, path, &plugin_name);
+        } else if reporter.mode() == OutputMode::Json {
+            let mut tagged = ValidationResult {
+                diagnostics: result.diagnostics.clone(),
+            };
+            tagged.tag_plugin(&plugin_name);
+            all_diagnostics.merge(tagged);
+        }
+        let passed = result.outcome(strict).is_success();
+        outcomes.push(PluginOutcome {
+            name: plugin_name.clone(),
+            passed,
+            error_count: result.error_count(),
+            warning_count: result.warning_count(),
+        });
+
+        if !passed {
             failure_count += 1;
             if let Some(pb) = &progress {
                 // Print validation errors through the progress bar to avoid interleaving
                 for diagnostic in &result.diagnostics {
-                    let mut msg = diagnostic.message.clone();
-                    if let Some(p) = &diagnostic.path {
-                        msg = format!("{msg} ({})", p.display());
-                    }
+                    let msg = diagnostic_message(diagnostic, config.as_ref());
                     pb.println(format!("ERROR: {msg}"));
                 }
             } else {
-                reporter.report_validation(&result);
+                reporter.report_validation(&result, config.as_ref());
             }
         } else {
             success_count += 1;
-            if let Some(pb) = &progress {
-                pb.println(format!("✓ Plugin validated: {plugin_name}"));
-                if result.warning_count() > 0 {
-                    for diagnostic in &result.diagnostics {
-                        let mut msg = diagnostic.message.clone();
-                        if let Some(p) = &diagnostic.path {
-                            msg = format!("{msg} ({})", p.display());
+            // With --only-failures a passing plugin only counts toward the summary.
+            if !reporter.only_failures() {
+                if let Some(pb) = &progress {
+                    pb.println(format!("✓ Plugin validated: {plugin_name}"));
+                    if result.warning_count() > 0 {
+                        for diagnostic in &result.diagnostics {
+                            let msg = diagnostic_message(diagnostic, config.as_ref());
+                            pb.println(format!("WARNING: {msg}"));
                         }
-                        pb.println(format!("WARNING: {msg}"));
                     }
-                }
-            } else {
-                reporter.success_with_details(
-                    &format!("Plugin validated: {plugin_name}"),
-                    &format!("path: {}", path.display()),
-                );
-                if result.warning_count() > 0 {
-                    reporter.report_validation(&result);
+                } else {
+                    reporter.success_with_details(
+                        &format!("Plugin validated: {plugin_name}"),
+                        &format!("path: {}", path.display()),
+                    );
+                    if result.warning_count() > 0 {
+                        reporter.report_validation(&result, config.as_ref());
+                    }
                 }
             }
         }
@@ -104,40 +173,154 @@ pub fn run_validate_plugin(
         plugin_paths.len()
     ));
 
-    failure_count == 0
+    let root = match &config {
+        Some(config) => config.project_root.clone(),
+        None => canonical(crate::cwd::current_dir().unwrap_or_default()),
+    };
+    let names: Vec<String> = outcomes.iter().map(|o| o.name.clone()).collect();
+    let skills = junit_skills(
+        options.format,
+        options.junit_granularity,
+        plugin_paths.iter().cloned().zip(names.iter().cloned()),
+    );
+    let skills_passed = skills_pass(&skills, strict);
+    let scope = ReportScope {
+        root: &root,
+        marketplace: None,
+        plugins: &names,
+        skills: &skills,
+    };
+    let reported = emit_report(options.format, &all_diagnostics, &scope, reporter);
+    if !reported && reporter.mode() == OutputMode::Json {
+        let summary = PluginRunSummary {
+            total: plugin_paths.len(),
+            passed: success_count,
+            failed: failure_count,
+            plugins: outcomes,
+        };
+        let mut document = json_result(&all_diagnostics, failure_count == 0);
+        if let Ok(value) = serde_json::to_value(&summary) {
+            document.insert("summary".to_string(), value);
+        }
+        reporter.set_json_document(document.into());
+    }
+
+    if failure_count == 0 && skills_passed {
+        ExitStatus::Success
+    } else {
+        ExitStatus::Findings
+    }
+}
+
+/// Flags of `souk validate marketplace`.
+pub struct MarketplaceValidateOptions<'a> {
+    /// Only check the marketplace itself, not each plugin.
+    pub skip_plugins: bool,
+    /// How to group diagnostics in the output.
+    pub group_by: Option<&'a GroupBy>,
+    /// Error on sources that are absolute paths or escape pluginRoot.
+    pub no_external_sources: bool,
+    /// Follow symlinks in pluginRoot when looking for orphans.
+    pub dereference: bool,
+    /// Abort the scan of pluginRoot after this many entries.
+    pub max_scan_entries: Option<usize>,
+    /// Warn when marketplace.json or a plugin.json is not in canonical
+    /// formatting.
+    pub check_formatting: bool,
+    /// Print a SARIF or JUnit report instead; overrides `group_by`.
+    pub format: ValidateFormat,
+    /// Add a JUnit testcase per skill.
+    pub junit_granularity: JunitGranularity,
+    /// Validate every marketplace under the working directory instead of
+    /// the discovered one.
+    pub recursive: bool,
 }
 
 pub fn run_validate_marketplace(
-    skip_plugins: bool,
+    options: &MarketplaceValidateOptions,
+    schema: Option<PathBuf>,
+    strict: bool,
     marketplace_override: Option<&str>,
     reporter: &mut Reporter,
-) -> bool {
-    let config = match load_config_required(marketplace_override, reporter) {
-        Some(c) => c,
-        None => return false,
+) -> ExitStatus {
+    if options.recursive {
+        return run_validate_marketplaces_recursive(
+            options,
+            schema,
+            strict,
+            marketplace_override,
+            reporter,
+        );
+    }
+    let mut config = match crate::load_config_required(marketplace_override, reporter) {
+        Ok(c) => c,
+        Err(status) => return status,
     };
+    apply_options(&mut config, options, schema);
+
+    if options.format != ValidateFormat::Text {
+        let result = validate_marketplace(&config, options.skip_plugins);
+        let plugins: Vec<String> = if options.skip_plugins {
+            Vec::new()
+        } else {
+            config
+                .marketplace
+                .plugins
+                .iter()
+                .map(|p| p.name.clone())
+                .collect()
+        };
+        let skills = junit_skills(
+            options.format,
+            options.junit_granularity,
+            config.marketplace.plugins.iter().filter_map(|entry| {
+                let path = resolve_source(&entry.source, &config).ok()?;
+                (!options.skip_plugins).then(|| (path, entry.name.clone()))
+            }),
+        );
+        let scope = ReportScope {
+            root: &config.project_root,
+            marketplace: Some(&config.marketplace_path),
+            plugins: &plugins,
+            skills: &skills,
+        };
+        emit_report(options.format, &result, &scope, reporter);
+        if !skills_pass(&skills, strict) {
+            return ExitStatus::Findings;
+        }
+        return result.outcome(strict).into();
+    }
+
+    if let Some(GroupBy::Plugin) = options.group_by {
+        return run_validate_marketplace_grouped(&config, options.skip_plugins, strict, reporter);
+    }
 
     // Step 1: Validate marketplace structure (always skip plugins here, we handle them below)
     reporter.section("Validating marketplace");
 
     let result = validate_marketplace(&config, true);
-    reporter.report_validation(&result);
+    reporter.report_validation(&result, Some(&config));
 
-    let mut has_errors = result.has_errors();
+    let mut failed = !result.outcome(strict).is_success();
+    let mut all_diagnostics = result;
 
-    if has_errors {
+    if failed {
         reporter.error("Marketplace validation failed");
     } else {
         reporter.success(&format!(
             "Marketplace validated: {}",
-            config.marketplace_path.display()
+            config.display_path(&config.marketplace_path)
         ));
     }
 
     // Step 2: Check completeness is already included in the marketplace validation above
 
+    if config.marketplace.plugins.is_empty() {
+        reporter.empty_marketplace_hint();
+    }
+
     // Step 3: Validate individual plugins (unless skipped)
-    if !skip_plugins && config.plugin_root_abs.is_dir() {
+    if !options.skip_plugins && config.plugin_root_abs.is_dir() {
         let plugins = &config.marketplace.plugins;
         if !plugins.is_empty() {
             reporter.section(&format!("Validating {} plugin(s)", plugins.len()));
@@ -173,21 +356,19 @@ pub fn run_validate_marketplace(
                 }
 
                 if plugin_path.is_dir() {
-                    let plugin_result = validate_plugin(&plugin_path);
+                    let mut plugin_result = validate_plugin_with(&plugin_path, &config.settings);
+                    plugin_result.tag_plugin(&entry.name);
 
-                    if plugin_result.has_errors() {
+                    if !plugin_result.outcome(strict).is_success() {
                         failure_count += 1;
-                        has_errors = true;
+                        failed = true;
                         if let Some(pb) = &progress {
                             for diagnostic in &plugin_result.diagnostics {
-                                let mut msg = diagnostic.message.clone();
-                                if let Some(p) = &diagnostic.path {
-                                    msg = format!("{msg} ({})", p.display());
-                                }
+                                let msg = diagnostic_message(diagnostic, Some(&config));
                                 pb.println(format!("ERROR: {msg}"));
                             }
                         } else {
-                            reporter.report_validation(&plugin_result);
+                            reporter.report_validation(&plugin_result, Some(&config));
                         }
                     } else {
                         success_count += 1;
@@ -197,18 +378,24 @@ pub fn run_validate_marketplace(
                             reporter.success(&format!("Plugin validated: {plugin_name}"));
                         }
                     }
+                    all_diagnostics.merge(plugin_result);
                 } else {
                     failure_count += 1;
-                    has_errors = true;
+                    failed = true;
+                    all_diagnostics.push(
+                        ValidationDiagnostic::error("Plugin directory not found")
+                            .with_path(&plugin_path)
+                            .with_plugin(&entry.name),
+                    );
                     if let Some(pb) = &progress {
                         pb.println(format!(
                             "ERROR: Plugin directory not found: {}",
-                            plugin_path.display()
+                            config.display_path(&plugin_path)
                         ));
                     } else {
                         reporter.error(&format!(
                             "Plugin directory not found: {}",
-                            plugin_path.display()
+                            config.display_path(&plugin_path)
                         ));
                     }
                 }
@@ -229,57 +416,384 @@ pub fn run_validate_marketplace(
         }
     }
 
+    // An unreadable pluginRoot is already reported as a validation error
+    let counts = directory_counts(&config).ok();
+
+    if reporter.mode() == OutputMode::Json {
+        let mut document = json_result(&all_diagnostics, !failed);
+        if let Some(value) = counts.and_then(|c| serde_json::to_value(c).ok()) {
+            document.insert("counts".to_string(), value);
+        }
+        reporter.set_json_document(document.into());
+        return if !failed {
+            ExitStatus::Success
+        } else {
+            ExitStatus::Findings
+        };
+    }
+
     // Final summary
     reporter.section("Summary");
-    if has_errors {
+    if let Some(counts) = counts {
+        reporter.info(&counts.to_string());
+    }
+    if failed {
         reporter.error("Marketplace validation completed with errors");
-        false
+        ExitStatus::Findings
     } else {
-        reporter.success("Marketplace validation completed successfully");
-        true
+        reporter.summary("Marketplace validation completed successfully");
+        ExitStatus::Success
     }
 }
 
-fn load_config(marketplace_override: Option<&str>) -> Option<MarketplaceConfig> {
-    let mp_path = if let Some(path) = marketplace_override {
-        PathBuf::from(path)
-    } else {
-        let cwd = std::env::current_dir().ok()?;
-        discover_marketplace(&cwd).ok()?
-    };
-    load_marketplace_config(&mp_path).ok()
+/// Override souk.toml settings with the command-line flags.
+fn apply_options(
+    config: &mut MarketplaceConfig,
+    options: &MarketplaceValidateOptions,
+    schema: Option<PathBuf>,
+) {
+    if options.no_external_sources {
+        config.settings.allow_external_sources = false;
+    }
+    if options.dereference {
+        config.settings.dereference = true;
+    }
+    if options.max_scan_entries.is_some() {
+        config.settings.max_scan_entries = options.max_scan_entries;
+    }
+    if options.check_formatting {
+        config.settings.check_formatting = true;
+    }
+    if schema.is_some() {
+        config.settings.plugin_schema = schema;
+    }
+}
+
+/// Validation outcome for one marketplace of a `--recursive` run.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MarketplaceOutcome {
+    path: String,
+    passed: bool,
+    error_count: usize,
+    warning_count: usize,
 }
 
-fn load_config_required(
+/// Validate every marketplace under the working directory, then summarise.
+///
+/// A marketplace that cannot be loaded counts as failed without stopping
+/// the others. In JSON mode, prints the merged diagnostics with `ok` and a
+/// `marketplaces` list of per-marketplace outcomes.
+fn run_validate_marketplaces_recursive(
+    options: &MarketplaceValidateOptions,
+    schema: Option<PathBuf>,
+    strict: bool,
     marketplace_override: Option<&str>,
     reporter: &mut Reporter,
-) -> Option<MarketplaceConfig> {
-    let mp_path = if let Some(path) = marketplace_override {
-        PathBuf::from(path)
-    } else {
-        let cwd = match std::env::current_dir() {
-            Ok(c) => c,
+) -> ExitStatus {
+    if marketplace_override.is_some() {
+        reporter
+            .error("--recursive searches the working directory; use -C instead of --marketplace");
+        return ExitStatus::Usage;
+    }
+    if options.format != ValidateFormat::Text {
+        reporter.error("--recursive supports only text and JSON output");
+        return ExitStatus::Usage;
+    }
+    let root = match crate::cwd::current_dir() {
+        Ok(root) => root,
+        Err(e) => {
+            reporter.error(&format!("Cannot read the working directory: {e}"));
+            return ExitStatus::Internal;
+        }
+    };
+    let paths = match discover_all_marketplaces(&root) {
+        Ok(paths) => paths,
+        Err(e) => {
+            reporter.error(&format!("{e}"));
+            return e.exit_status();
+        }
+    };
+    if paths.is_empty() {
+        reporter.error(&format!("No marketplace found under {}", root.display()));
+        return ExitStatus::Usage;
+    }
+
+    let display = |path: &Path| match path.strip_prefix(&root) {
+        Ok(rel) => rel.display().to_string(),
+        Err(_) => path.display().to_string(),
+    };
+    let mut all_diagnostics = ValidationResult::new();
+    let mut outcomes = Vec::new();
+    for path in &paths {
+        let shown = display(path);
+        reporter.section(&format!("Validating {shown}"));
+        let result = match load_marketplace_config(path) {
+            Ok(mut config) => {
+                apply_options(&mut config, options, schema.clone());
+                let result = validate_marketplace(&config, options.skip_plugins);
+                reporter.report_validation(&result, Some(&config));
+                result
+            }
             Err(e) => {
-                reporter.error(&format!("Cannot get current directory: {e}"));
-                return None;
+                let mut result = ValidationResult::new();
+                result.push(ValidationDiagnostic::error(e.to_string()).with_path(path));
+                reporter.report_validation(&result, None);
+                result
             }
         };
-        match discover_marketplace(&cwd) {
-            Ok(p) => p,
+        let passed = result.outcome(strict).is_success();
+        if passed {
+            reporter.success(&format!("Marketplace validated: {shown}"));
+        } else {
+            reporter.error(&format!("Marketplace validation failed: {shown}"));
+        }
+        outcomes.push(MarketplaceOutcome {
+            path: shown,
+            passed,
+            error_count: result.error_count(),
+            warning_count: result.warning_count(),
+        });
+        all_diagnostics.merge(result);
+    }
+
+    let failed = outcomes.iter().filter(|o| !o.passed).count();
+    if reporter.mode() == OutputMode::Json {
+        let mut document = json_result(&all_diagnostics, failed == 0);
+        if let Ok(value) = serde_json::to_value(&outcomes) {
+            document.insert("marketplaces".to_string(), value);
+        }
+        reporter.set_json_document(document.into());
+        return if failed == 0 {
+            ExitStatus::Success
+        } else {
+            ExitStatus::Findings
+        };
+    }
+
+    reporter.section("Summary");
+    for outcome in &outcomes {
+        let line = format!(
+            "{}: {} error(s), {} warning(s)",
+            outcome.path, outcome.error_count, outcome.warning_count
+        );
+        if outcome.passed {
+            reporter.success(&line);
+        } else {
+            reporter.error(&line);
+        }
+    }
+    reporter.info(&format!(
+        "{} marketplace(s): {} passed, {failed} failed",
+        outcomes.len(),
+        outcomes.len() - failed
+    ));
+    if failed == 0 {
+        ExitStatus::Success
+    } else {
+        ExitStatus::Findings
+    }
+}
+
+/// Validate the marketplace and report diagnostics grouped by plugin.
+///
+/// In JSON mode the output is `{ "marketplace": [...], "plugins": { ... } }`.
+fn run_validate_marketplace_grouped(
+    config: &MarketplaceConfig,
+    skip_plugins: bool,
+    strict: bool,
+    reporter: &mut Reporter,
+) -> ExitStatus {
+    let result = validate_marketplace(config, skip_plugins);
+    let passed = result.outcome(strict).is_success();
+    let grouped = result.group_by_plugin();
+
+    if reporter.mode() == OutputMode::Json {
+        match serde_json::to_value(&grouped) {
+            Ok(doc) => reporter.set_json_document(doc),
             Err(e) => {
-                reporter.error(&format!("{e}"));
-                return None;
+                reporter.error(&format!("Failed to serialize diagnostics: {e}"));
+                return ExitStatus::Internal;
             }
         }
+        return if passed {
+            ExitStatus::Success
+        } else {
+            ExitStatus::Findings
+        };
+    }
+
+    let groups = std::iter::once(("marketplace".to_string(), &grouped.marketplace)).chain(
+        grouped
+            .plugins
+            .iter()
+            .map(|(name, diagnostics)| (format!("plugin {name}"), diagnostics)),
+    );
+    for (title, diagnostics) in groups {
+        reporter.section(&title);
+        for diagnostic in diagnostics {
+            let msg = diagnostic_message(diagnostic, Some(config));
+            if diagnostic.is_error() {
+                reporter.error(&msg);
+            } else {
+                reporter.warning(&msg);
+            }
+        }
+    }
+
+    if !passed {
+        reporter.error("Marketplace validation completed with errors");
+        ExitStatus::Findings
+    } else {
+        reporter.success("Marketplace validation completed successfully");
+        ExitStatus::Success
+    }
+}
+
+/// The `--json` document for a validation run: `{ok, error_count,
+/// warning_count, diagnostics}`, with `ok` set to `passed` so it reflects
+/// `--strict`.
+fn json_result(
+    result: &ValidationResult,
+    passed: bool,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut document = match serde_json::to_value(result) {
+        Ok(serde_json::Value::Object(document)) => document,
+        _ => serde_json::Map::new(),
     };
+    document.insert("ok".to_string(), passed.into());
+    document
+}
 
-    match load_marketplace_config(&mp_path) {
-        Ok(c) => Some(c),
-        Err(e) => {
-            reporter.error(&format!("Failed to load marketplace: {e}"));
-            None
+/// What a SARIF or JUnit report of a validation run covers.
+pub struct ReportScope<'a> {
+    /// Project root; report paths are relative to it.
+    pub root: &'a Path,
+    /// marketplace.json, if the marketplace itself was validated. SARIF
+    /// locates diagnostics without a path here.
+    pub marketplace: Option<&'a Path>,
+    /// Plugins that were validated, each a JUnit testcase even when clean.
+    pub plugins: &'a [String],
+    /// Skills validated for a skill-granularity JUnit report.
+    pub skills: &'a [SkillCase],
+}
+
+/// Validate every skill of `plugins`, given as `(path, name)`, when
+/// `format` is a skill-granularity JUnit report; otherwise nothing.
+fn junit_skills(
+    format: ValidateFormat,
+    granularity: JunitGranularity,
+    plugins: impl IntoIterator<Item = (PathBuf, String)>,
+) -> Vec<SkillCase> {
+    if format != ValidateFormat::Junit || granularity != JunitGranularity::Skill {
+        return Vec::new();
+    }
+    plugins
+        .into_iter()
+        .flat_map(|(path, plugin)| {
+            enumerate_skills(&path)
+                .into_iter()
+                .map(move |skill| SkillCase {
+                    plugin: plugin.clone(),
+                    skill: skill.dir_name,
+                    result: validate_skill(&skill.path),
+                })
+        })
+        .collect()
+}
+
+/// Whether every skill passed, counting warnings as failures when `strict`.
+fn skills_pass(skills: &[SkillCase], strict: bool) -> bool {
+    skills
+        .iter()
+        .all(|case| case.result.outcome(strict).is_success())
+}
+
+/// Set `result` as the whole output in `format`, returning `false` without
+/// doing anything for [`ValidateFormat::Text`].
+pub fn emit_report(
+    format: ValidateFormat,
+    result: &ValidationResult,
+    scope: &ReportScope,
+    reporter: &mut Reporter,
+) -> bool {
+    match format {
+        ValidateFormat::Text => return false,
+        ValidateFormat::Sarif => {
+            reporter.set_json_document(to_sarif(result, scope.root, scope.marketplace))
+        }
+        ValidateFormat::Junit => {
+            let relative = |result: &ValidationResult| {
+                let mut relative = ValidationResult::new();
+                for diagnostic in &result.diagnostics {
+                    let mut diagnostic = diagnostic.clone();
+                    if let Some(rel) = diagnostic
+                        .path
+                        .as_deref()
+                        .and_then(|p| p.strip_prefix(scope.root).ok())
+                    {
+                        diagnostic.path = Some(rel.to_path_buf());
+                    }
+                    relative.push(diagnostic);
+                }
+                relative
+            };
+            let skills: Vec<SkillCase> = scope
+                .skills
+                .iter()
+                .map(|case| SkillCase {
+                    plugin: case.plugin.clone(),
+                    skill: case.skill.clone(),
+                    result: relative(&case.result),
+                })
+                .collect();
+            reporter.set_document(to_junit_xml(
+                &relative(result),
+                scope.plugins,
+                scope.marketplace.is_some(),
+                &skills,
+            ));
         }
     }
+    true
+}
+
+/// Add a plugin's diagnostics to a report, locating those without a path
+/// at the plugin's plugin.json.
+///
+/// Plugin arguments may be relative or pass through symlinks, so paths are
+/// made canonical to line up with the report root.
+fn collect_for_report(
+    all: &mut ValidationResult,
+    result: &ValidationResult,
+    plugin_path: &Path,
+    plugin_name: &str,
+) {
+    let manifest = plugin_path.join(".claude-plugin").join("plugin.json");
+    for diagnostic in &result.diagnostics {
+        let mut diagnostic = diagnostic.clone();
+        let path = diagnostic.path.take().unwrap_or_else(|| manifest.clone());
+        diagnostic.path = Some(canonical(path));
+        all.push(diagnostic);
+    }
+    all.tag_plugin(plugin_name);
+}
+
+/// `path` made canonical, or at least absolute if it does not exist.
+fn canonical(path: PathBuf) -> PathBuf {
+    path.canonicalize()
+        .or_else(|_| std::path::absolute(&path))
+        .unwrap_or(path)
+}
+
+fn load_config(marketplace_override: Option<&str>) -> Option<MarketplaceConfig> {
+    let mp_path = if let Some(path) = marketplace_override {
+        PathBuf::from(path)
+    } else {
+        let cwd = crate::cwd::current_dir().ok()?;
+        discover_marketplace(&cwd).ok()?
+    };
+    load_marketplace_config(&mp_path).ok()
 }
 
 fn collect_plugin_paths(
@@ -0,0 +1,65 @@
+//! Handler for `souk version`.
+//!
+//! Prints the version along with what this build supports, so scripts can
+//! check for a capability with `souk version --json` instead of parsing help
+//! text or comparing version numbers.
+
+use serde_json::json;
+use souk_core::ci::install_hooks::HookManager;
+use souk_core::ci::install_workflows::CiProvider;
+use souk_core::review::PROVIDERS;
+use souk_core::ExitStatus;
+
+use crate::output::{OutputMode, Reporter};
+
+/// Capabilities newer than the first release, named so tooling can check
+/// for them before passing the matching flags.
+const FEATURES: [&str; 10] = [
+    "archive-sources",
+    "chdir",
+    "check-formatting",
+    "copy-ignore",
+    "fallback-provider",
+    "git-sources",
+    "github-comment",
+    "junit",
+    "review-cache",
+    "sarif",
+];
+
+/// Run the `souk version` command.
+///
+/// In JSON mode, prints `{version, commit, features, supported_providers,
+/// supported_hook_managers, supported_ci_providers}`; `commit` is `null`
+/// when souk was built outside a git checkout.
+pub fn run_version(reporter: &mut Reporter) -> ExitStatus {
+    let version = env!("CARGO_PKG_VERSION");
+    let commit = option_env!("SOUK_GIT_COMMIT");
+    let hook_managers: Vec<&str> = HookManager::ALL.iter().map(HookManager::name).collect();
+    let ci_providers: Vec<&str> = CiProvider::ALL.iter().map(CiProvider::name).collect();
+
+    if reporter.mode() == OutputMode::Json {
+        reporter.set_json_document(json!({
+            "version": version,
+            "commit": commit,
+            "features": FEATURES,
+            "supported_providers": PROVIDERS,
+            "supported_hook_managers": hook_managers,
+            "supported_ci_providers": ci_providers,
+        }));
+        return ExitStatus::Success;
+    }
+
+    match commit {
+        Some(commit) => println!("souk {version} ({commit})"),
+        None => println!("souk {version}"),
+    }
+    if reporter.mode() == OutputMode::Quiet {
+        return ExitStatus::Success;
+    }
+    println!("{:<16} {}", "Features:", FEATURES.join(", "));
+    println!("{:<16} {}", "Providers:", PROVIDERS.join(", "));
+    println!("{:<16} {}", "Hook managers:", hook_managers.join(", "));
+    println!("{:<16} {}", "CI providers:", ci_providers.join(", "));
+    ExitStatus::Success
+}
@@ -0,0 +1,57 @@
+//! The directory souk runs against.
+//!
+//! `--chdir <dir>` makes souk behave as if started in `dir`: marketplace
+//! discovery starts there and relative paths on the command line resolve
+//! against it. The process working directory itself is left alone.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static CHDIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Run against `dir` instead of the process working directory.
+///
+/// # Errors
+///
+/// Returns an error if `dir` does not exist or is not a directory.
+pub fn set(dir: &str) -> io::Result<()> {
+    let dir = Path::new(dir).canonicalize()?;
+    if !dir.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotADirectory,
+            format!("{} is not a directory", dir.display()),
+        ));
+    }
+    let _ = CHDIR.set(dir);
+    Ok(())
+}
+
+/// The effective working directory: the `--chdir` directory if given.
+pub fn current_dir() -> io::Result<PathBuf> {
+    match CHDIR.get() {
+        Some(dir) => Ok(dir.clone()),
+        None => std::env::current_dir(),
+    }
+}
+
+/// `path` resolved against the effective working directory.
+pub fn path(path: &str) -> PathBuf {
+    match CHDIR.get() {
+        Some(dir) => dir.join(path),
+        None => PathBuf::from(path),
+    }
+}
+
+/// A plugin argument, which may be a name or a path, with paths resolved
+/// against the effective working directory.
+///
+/// The argument is only rewritten when it names a directory there, so
+/// plugin names still reach [`resolve_plugin`](souk_core::resolution::resolve_plugin)
+/// unchanged.
+pub fn plugin_arg(input: &str) -> String {
+    match CHDIR.get() {
+        Some(dir) if dir.join(input).is_dir() => dir.join(input).to_string_lossy().into_owned(),
+        _ => input.to_string(),
+    }
+}
@@ -1,18 +1,52 @@
+mod audit;
 mod cli;
 mod commands;
+mod cwd;
 mod output;
 
 use std::path::PathBuf;
 
 use clap::Parser;
-use cli::{CiAction, CiHook, Cli, ColorMode, Commands, ReviewTarget, ValidateTarget};
+use cli::{
+    CiAction, CiHook, Cli, ColorMode, Commands, CompleteArgs, ListColumn, ReviewTarget,
+    ValidateFormat, ValidateTarget,
+};
+use commands::add::AddOptions;
+use commands::list::{ListOptions, Page};
+use commands::review::{
+    MarketplaceReviewOptions, PluginReviewOptions, ProviderOptions, SkillReviewOptions,
+};
+use commands::validate::{MarketplaceValidateOptions, PluginValidateOptions};
 use output::{OutputMode, Reporter};
 use souk_core::discovery::{discover_marketplace, load_marketplace_config, MarketplaceConfig};
+use souk_core::ops::update::UpdateFields;
+use souk_core::review::{ModelMap, ReviewScope};
+use souk_core::version::VersionBump;
+use souk_core::ExitStatus;
 
 fn main() {
+    if std::env::args().nth(1).as_deref() == Some(commands::completions::COMPLETE_COMMAND) {
+        let args = CompleteArgs::parse_from(std::env::args().skip(1));
+        let mut reporter = Reporter::new(OutputMode::Quiet);
+        let status = match load_config_required(args.marketplace.as_deref(), &mut reporter) {
+            Ok(config) => commands::completions::run_complete(&config),
+            Err(status) => status,
+        };
+        std::process::exit(status.code());
+    }
+
     let cli = Cli::parse();
 
-    let mode = if cli.json {
+    let report_format = match &cli.command {
+        Commands::Validate { format, .. } => *format,
+        Commands::Ci {
+            action: CiAction::Run { format, .. },
+        } => *format,
+        _ => ValidateFormat::Text,
+    };
+    // A SARIF or JUnit report replaces all other output, so run as in JSON
+    // mode, which prints nothing until the report.
+    let mode = if cli.json || report_format != ValidateFormat::Text {
         OutputMode::Json
     } else if cli.quiet {
         OutputMode::Quiet
@@ -20,162 +54,616 @@ fn main() {
         OutputMode::Human
     };
 
-    match cli.color {
-        ColorMode::Never => colored::control::set_override(false),
-        ColorMode::Always => colored::control::set_override(true),
-        ColorMode::Auto => {}
+    let color = if cli.no_color {
+        ColorMode::Never
+    } else {
+        cli.color
+    };
+    let no_color = std::env::var_os("NO_COLOR");
+    let clicolor_force = std::env::var_os("CLICOLOR_FORCE");
+    if let Some(on) = color.color_override(no_color.as_deref(), clicolor_force.as_deref()) {
+        colored::control::set_override(on);
     }
 
     let mut reporter = Reporter::new(mode);
-    let marketplace = cli.marketplace.as_deref();
+    reporter.set_only_failures(cli.only_failures);
+    if let Some(dir) = &cli.chdir {
+        if let Err(e) = cwd::set(dir) {
+            reporter.error(&format!("Cannot change to directory {dir}: {e}"));
+            reporter.finish();
+            std::process::exit(ExitStatus::Usage.code());
+        }
+    }
+    let marketplace = cli
+        .marketplace
+        .as_deref()
+        .map(|m| cwd::path(m).to_string_lossy().into_owned());
+    let marketplace = marketplace.as_deref();
 
-    let success = match cli.command {
-        Commands::Validate { target } => match target {
-            ValidateTarget::Plugin { plugins } => {
-                commands::validate::run_validate_plugin(&plugins, marketplace, &mut reporter)
-            }
-            ValidateTarget::Marketplace { skip_plugins } => {
-                commands::validate::run_validate_marketplace(
+    let status: ExitStatus = match cli.command {
+        Commands::Validate {
+            target,
+            schema,
+            format,
+            junit_granularity,
+            check_formatting,
+        } => {
+            let schema = schema.map(|s| cwd::path(&s.to_string_lossy()));
+            match target {
+                ValidateTarget::Plugin {
+                    plugins,
+                    check_readme_title,
+                } => commands::validate::run_validate_plugin(
+                    &PluginValidateOptions {
+                        check_readme: check_readme_title,
+                        check_formatting,
+                        format,
+                        junit_granularity,
+                    },
+                    &plugin_args(&plugins),
+                    schema,
+                    cli.strict,
+                    marketplace,
+                    &mut reporter,
+                ),
+                ValidateTarget::Marketplace {
                     skip_plugins,
+                    group_by,
+                    no_external_sources,
+                    dereference,
+                    max_scan_entries,
+                    recursive,
+                } => commands::validate::run_validate_marketplace(
+                    &MarketplaceValidateOptions {
+                        skip_plugins,
+                        group_by: group_by.as_ref(),
+                        no_external_sources,
+                        dereference,
+                        max_scan_entries,
+                        check_formatting,
+                        format,
+                        junit_granularity,
+                        recursive,
+                    },
+                    schema,
+                    cli.strict,
                     marketplace,
                     &mut reporter,
-                )
+                ),
+            }
+        }
+        Commands::Init {
+            path,
+            plugin_root,
+            check,
+        } => {
+            let target = cwd::path(path.as_deref().unwrap_or("."));
+            let target = target.to_string_lossy();
+            if check {
+                commands::init::run_init_check(&target, &mut reporter)
+            } else {
+                commands::init::run_init(&target, &plugin_root, &mut reporter)
             }
-        },
-        Commands::Init { path, plugin_root } => {
-            let target = path.as_deref().unwrap_or(".");
-            commands::init::run_init(target, &plugin_root, &mut reporter)
         }
         Commands::Add {
             plugins,
+            stdin,
+            from_remote,
             on_conflict,
             dry_run,
             no_copy,
+            confirm_external,
+            yes,
+            no_final_validation,
+            retry,
         } => match load_config_required(marketplace, &mut reporter) {
-            Some(config) => commands::add::run_add(
-                &plugins,
-                &on_conflict,
-                dry_run,
-                no_copy,
-                &config,
-                &mut reporter,
-            ),
-            None => false,
+            Ok(config) => {
+                let options = AddOptions {
+                    dry_run,
+                    no_copy,
+                    validate_after: !no_final_validation,
+                    confirm_external: confirm_external || yes,
+                    retries: retry,
+                };
+                if stdin {
+                    commands::add::run_add_stdin(
+                        on_conflict.as_ref(),
+                        options,
+                        &config,
+                        &mut reporter,
+                    )
+                } else if let Some(url) = from_remote {
+                    commands::add::run_add_from_remote(
+                        &url,
+                        &plugins,
+                        on_conflict.as_ref(),
+                        &options,
+                        &config,
+                        &mut reporter,
+                    )
+                } else {
+                    commands::add::run_add(
+                        &plugin_args(&plugins),
+                        on_conflict.as_ref(),
+                        &options,
+                        &config,
+                        &mut reporter,
+                    )
+                }
+            }
+            Err(status) => status,
         },
         Commands::Remove {
             plugins,
             delete,
             allow_external_delete,
+            no_final_validation,
+            retry,
         } => match load_config_required(marketplace, &mut reporter) {
-            Some(config) => commands::remove::run_remove(
+            Ok(config) => commands::remove::run_remove(
                 &plugins,
                 delete,
                 allow_external_delete,
+                !no_final_validation,
+                retry,
+                &config,
+                &mut reporter,
+            ),
+            Err(status) => status,
+        },
+        Commands::Rename { old, new, retry } => {
+            match load_config_required(marketplace, &mut reporter) {
+                Ok(config) => {
+                    commands::rename::run_rename(&old, &new, retry, &config, &mut reporter)
+                }
+                Err(status) => status,
+            }
+        }
+        Commands::Archive {
+            plugins,
+            move_files,
+            retry,
+        } => match load_config_required(marketplace, &mut reporter) {
+            Ok(config) => {
+                commands::archive::run_archive(&plugins, move_files, retry, &config, &mut reporter)
+            }
+            Err(status) => status,
+        },
+        Commands::List {
+            include_archived,
+            tag,
+            sort,
+            columns,
+            wide,
+            limit,
+            offset,
+            count,
+        } => match load_config_required(marketplace, &mut reporter) {
+            Ok(config) => commands::list::run_list(
+                &ListOptions {
+                    include_archived,
+                    tag: tag.as_deref(),
+                    sort,
+                    columns: match (wide, &columns) {
+                        (true, _) => &ListColumn::ALL,
+                        (false, Some(columns)) => columns,
+                        (false, None) => &ListColumn::DEFAULT,
+                    },
+                    page: Page { offset, limit },
+                    count,
+                },
+                &config,
+                &mut reporter,
+            ),
+            Err(status) => status,
+        },
+        Commands::Search {
+            query,
+            remote,
+            limit,
+            offset,
+            count,
+        } => {
+            let page = Page { offset, limit };
+            match remote {
+                Some(url) => {
+                    commands::search::run_remote_search(&query, &url, page, count, &mut reporter)
+                }
+                None => match load_config_required(marketplace, &mut reporter) {
+                    Ok(config) => commands::search::run_search(
+                        &query,
+                        page,
+                        count,
+                        &config.marketplace.plugins,
+                        &mut reporter,
+                    ),
+                    Err(status) => status,
+                },
+            }
+        }
+        Commands::Info { plugin, tree } => match load_config_required(marketplace, &mut reporter) {
+            Ok(config) => {
+                commands::info::run_info(&cwd::plugin_arg(&plugin), tree, &config, &mut reporter)
+            }
+            Err(status) => status,
+        },
+        Commands::Update {
+            plugins,
+            all,
+            fields,
+            check: true,
+            ..
+        } => match load_config_required(marketplace, &mut reporter) {
+            Ok(config) => commands::update::run_update_check(
+                &commands::update::PluginSelection { plugins, all },
+                fields.unwrap_or_default(),
+                &config,
+                &mut reporter,
+            ),
+            Err(status) => status,
+        },
+        Commands::Update {
+            plugins,
+            all,
+            bump_dep: Some(dependency),
+            no_final_validation,
+            retry,
+            ..
+        } => match load_config_required(marketplace, &mut reporter) {
+            Ok(config) => commands::update::run_bump_dependency(
+                &commands::update::PluginSelection { plugins, all },
+                &dependency,
+                !no_final_validation,
+                retry,
                 &config,
                 &mut reporter,
             ),
-            None => false,
+            Err(status) => status,
         },
         Commands::Update {
             plugins,
+            all,
             major,
             minor,
             patch,
+            prerelease,
+            release,
+            keep_prerelease,
+            changelog,
+            fields,
+            refresh_origin_sha,
+            no_final_validation,
+            retry,
+            ..
         } => {
-            let bump_type = if major {
-                Some("major")
+            let bump = if major {
+                Some(VersionBump::Major)
             } else if minor {
-                Some("minor")
+                Some(VersionBump::Minor)
             } else if patch {
-                Some("patch")
+                Some(VersionBump::Patch)
+            } else if release {
+                Some(VersionBump::Release)
             } else {
-                None
+                prerelease.map(VersionBump::Prerelease)
             };
             match load_config_required(marketplace, &mut reporter) {
-                Some(config) => {
-                    commands::update::run_update(&plugins, bump_type, &config, &mut reporter)
+                Ok(config) => commands::update::run_update(
+                    &commands::update::PluginSelection { plugins, all },
+                    &commands::update::UpdateOptions {
+                        bump: bump.as_ref(),
+                        changelog: changelog.as_deref(),
+                        keep_prerelease,
+                        fields: if refresh_origin_sha {
+                            UpdateFields::ORIGIN_SHA
+                        } else {
+                            fields.unwrap_or_default()
+                        },
+                        validate_after: !no_final_validation,
+                        retries: retry,
+                    },
+                    &config,
+                    &mut reporter,
+                ),
+                Err(status) => status,
+            }
+        }
+        Commands::Review { target, rubric } => {
+            let rubric = rubric.map(|r| cwd::path(&r.to_string_lossy()));
+            match *target {
+                ReviewTarget::Plugin {
+                    plugin,
+                    output_dir,
+                    format,
+                    provider,
+                    fallback_provider,
+                    model,
+                    model_map,
+                    rate_limit,
+                    timeout,
+                    check_auth,
+                    cache_dir,
+                    verbose,
+                    manifest_only,
+                    wrap,
+                    no_wrap,
+                    print_prompt,
+                    min_rating,
+                    github_comment,
+                } => {
+                    reporter.set_wrap_width(commands::review::wrap_width(wrap, no_wrap));
+                    let provider = ProviderOptions {
+                        name: provider.as_deref(),
+                        fallback: fallback_provider.as_deref(),
+                        model: model_for(model_map.as_ref(), ReviewScope::Plugin, model.as_deref()),
+                        rate_limit,
+                        timeout,
+                        check_auth,
+                    };
+                    commands::review::run_review_plugin(
+                        &cwd::plugin_arg(&plugin),
+                        path_arg(output_dir).as_deref(),
+                        &provider,
+                        &PluginReviewOptions {
+                            format,
+                            cache_dir: path_arg(cache_dir).as_deref(),
+                            verbose,
+                            manifest_only,
+                            print_prompt,
+                            rubric: rubric.as_deref(),
+                            min_rating,
+                            github_comment,
+                        },
+                        marketplace,
+                        &mut reporter,
+                    )
+                }
+                ReviewTarget::Marketplace {
+                    summary_only,
+                    recursive,
+                    jobs,
+                    cache_dir,
+                    output_dir,
+                    format,
+                    provider,
+                    fallback_provider,
+                    model,
+                    model_map,
+                    rate_limit,
+                    timeout,
+                    check_auth,
+                    wrap,
+                    no_wrap,
+                    print_prompt,
+                    min_rating,
+                } => {
+                    reporter.set_wrap_width(commands::review::wrap_width(wrap, no_wrap));
+                    let provider = ProviderOptions {
+                        name: provider.as_deref(),
+                        fallback: fallback_provider.as_deref(),
+                        model: model_for(
+                            model_map.as_ref(),
+                            ReviewScope::Marketplace,
+                            model.as_deref(),
+                        ),
+                        rate_limit,
+                        timeout,
+                        check_auth,
+                    };
+                    match load_config_required(marketplace, &mut reporter) {
+                        Ok(config) => commands::review::run_review_marketplace(
+                            &config,
+                            path_arg(output_dir).as_deref(),
+                            &provider,
+                            &MarketplaceReviewOptions {
+                                format,
+                                summary_only,
+                                recursive,
+                                jobs: jobs.map(usize::from),
+                                plugin_model: model_for(
+                                    model_map.as_ref(),
+                                    ReviewScope::Plugin,
+                                    model.as_deref(),
+                                ),
+                                cache_dir: path_arg(cache_dir).as_deref(),
+                                print_prompt,
+                                rubric: rubric.as_deref(),
+                                min_rating,
+                            },
+                            &mut reporter,
+                        ),
+                        Err(status) => status,
+                    }
+                }
+                ReviewTarget::Skill {
+                    plugin,
+                    skills,
+                    all,
+                    jobs,
+                    output_dir,
+                    format,
+                    provider,
+                    fallback_provider,
+                    model,
+                    model_map,
+                    rate_limit,
+                    timeout,
+                    check_auth,
+                    wrap,
+                    no_wrap,
+                    print_prompt,
+                    min_rating,
+                } => {
+                    reporter.set_wrap_width(commands::review::wrap_width(wrap, no_wrap));
+                    let provider = ProviderOptions {
+                        name: provider.as_deref(),
+                        fallback: fallback_provider.as_deref(),
+                        model: model_for(model_map.as_ref(), ReviewScope::Skill, model.as_deref()),
+                        rate_limit,
+                        timeout,
+                        check_auth,
+                    };
+                    commands::review::run_review_skill(
+                        &cwd::plugin_arg(&plugin),
+                        path_arg(output_dir).as_deref(),
+                        &provider,
+                        &SkillReviewOptions {
+                            format,
+                            skills: &skills,
+                            all,
+                            jobs: jobs.map(usize::from),
+                            print_prompt,
+                            rubric: rubric.as_deref(),
+                            min_rating,
+                        },
+                        marketplace,
+                        &mut reporter,
+                    )
                 }
-                None => false,
             }
         }
-        Commands::Review { target } => match target {
-            ReviewTarget::Plugin {
-                plugin,
-                output_dir,
-                provider,
-                model,
-            } => commands::review::run_review_plugin(
-                &plugin,
-                output_dir.as_deref(),
-                provider.as_deref(),
-                model.as_deref(),
-                marketplace,
+        Commands::Ci { action } => match action {
+            CiAction::Run { hook, format } => match hook {
+                CiHook::PreCommit => {
+                    commands::ci::run_pre_commit(cli.strict, format, marketplace, &mut reporter)
+                }
+                CiHook::PrePush { verbose } => commands::ci::run_pre_push(
+                    cli.strict,
+                    verbose,
+                    format,
+                    marketplace,
+                    &mut reporter,
+                ),
+            },
+            CiAction::Install { target } => commands::ci::run_ci_install(&target, &mut reporter),
+        },
+        Commands::Sync {
+            dry_run,
+            no_final_validation,
+            retry,
+        } => match load_config_required(marketplace, &mut reporter) {
+            Ok(config) => commands::sync::run_sync(
+                dry_run,
+                !no_final_validation,
+                retry,
+                &config,
                 &mut reporter,
             ),
-            _ => {
-                reporter.error("Review subcommand not yet implemented");
-                false
+            Err(status) => status,
+        },
+        Commands::Fix { dry_run } => match load_config_required(marketplace, &mut reporter) {
+            Ok(config) => commands::fix::run_fix(dry_run, &config, &mut reporter),
+            Err(status) => status,
+        },
+        Commands::Prune {
+            apply,
+            older_than,
+            dereference,
+            max_scan_entries,
+            stale_entries,
+            retry,
+        } => match load_config_required(marketplace, &mut reporter) {
+            Ok(mut config) => {
+                config.settings.dereference |= dereference;
+                if max_scan_entries.is_some() {
+                    config.settings.max_scan_entries = max_scan_entries;
+                }
+                let options = commands::prune::PruneOptions {
+                    apply,
+                    older_than,
+                    stale_entries,
+                    retries: retry,
+                };
+                commands::prune::run_prune(&options, &config, &mut reporter)
             }
+            Err(status) => status,
         },
-        Commands::Ci { action } => match action {
-            CiAction::Run { hook } => match hook {
-                CiHook::PreCommit => commands::ci::run_pre_commit(marketplace, &mut reporter),
-                CiHook::PrePush => commands::ci::run_pre_push(marketplace, &mut reporter),
-            },
-            CiAction::Install { target } => commands::ci::run_ci_install(&target, &mut reporter),
+        Commands::Diff => match load_config_required(marketplace, &mut reporter) {
+            Ok(config) => commands::diff::run_diff(&config, &mut reporter),
+            Err(status) => status,
         },
-        Commands::Prune { apply } => match load_config_required(marketplace, &mut reporter) {
-            Some(config) => commands::prune::run_prune(apply, &config, &mut reporter),
-            None => false,
+        Commands::Stats => match load_config_required(marketplace, &mut reporter) {
+            Ok(config) => commands::stats::run_stats(&config, &mut reporter),
+            Err(status) => status,
         },
+        Commands::Export { format, output } => {
+            match load_config_required(marketplace, &mut reporter) {
+                Ok(config) => commands::export::run_export(
+                    &format,
+                    path_arg(output).as_deref(),
+                    &config,
+                    &mut reporter,
+                ),
+                Err(status) => status,
+            }
+        }
+        Commands::Selftest => commands::selftest::run_selftest(&mut reporter),
+        Commands::Doctor { fix } => {
+            commands::doctor::run_doctor(marketplace, fix.as_deref(), &mut reporter)
+        }
+        Commands::Version => commands::version::run_version(&mut reporter),
         Commands::Completions { shell } => {
-            use clap::CommandFactory;
-            clap_complete::generate(
-                shell,
-                &mut cli::Cli::command(),
-                "souk",
-                &mut std::io::stdout(),
-            );
-            true
+            commands::completions::run_completions(shell);
+            ExitStatus::Success
         }
     };
 
     reporter.finish();
 
-    if !success {
-        std::process::exit(1);
+    if !status.is_success() {
+        std::process::exit(status.code());
     }
 }
 
-/// Loads the marketplace configuration, reporting an error if it cannot be found.
+/// Loads the marketplace configuration, reporting an error if it cannot be
+/// found or loaded and returning the exit status to fail with.
 fn load_config_required(
     marketplace_override: Option<&str>,
     reporter: &mut Reporter,
-) -> Option<MarketplaceConfig> {
+) -> Result<MarketplaceConfig, ExitStatus> {
     let mp_path = if let Some(path) = marketplace_override {
         PathBuf::from(path)
     } else {
-        let cwd = match std::env::current_dir() {
+        let cwd = match cwd::current_dir() {
             Ok(c) => c,
             Err(e) => {
                 reporter.error(&format!("Cannot get current directory: {e}"));
-                return None;
+                return Err(ExitStatus::Internal);
             }
         };
         match discover_marketplace(&cwd) {
             Ok(p) => p,
             Err(e) => {
                 reporter.error(&format!("{e}"));
-                return None;
+                return Err(e.exit_status());
             }
         }
     };
 
-    match load_marketplace_config(&mp_path) {
-        Ok(c) => Some(c),
-        Err(e) => {
-            reporter.error(&format!("Failed to load marketplace: {e}"));
-            None
-        }
+    load_marketplace_config(&mp_path).map_err(|e| {
+        reporter.error(&format!("Failed to load marketplace: {e}"));
+        e.exit_status()
+    })
+}
+
+/// Plugin arguments with paths resolved against `--chdir`; see [`cwd::plugin_arg`].
+fn plugin_args(plugins: &[String]) -> Vec<String> {
+    plugins.iter().map(|p| cwd::plugin_arg(p)).collect()
+}
+
+/// An optional path argument resolved against `--chdir`.
+fn path_arg(path: Option<String>) -> Option<String> {
+    path.map(|p| cwd::path(&p).to_string_lossy().into_owned())
+}
+
+/// Picks the review model for `scope`, preferring a `--model-map` entry over `--model`.
+fn model_for<'a>(
+    model_map: Option<&'a ModelMap>,
+    scope: ReviewScope,
+    model: Option<&'a str>,
+) -> Option<&'a str> {
+    match model_map {
+        Some(map) => map.model_for(scope, model),
+        None => model,
     }
 }
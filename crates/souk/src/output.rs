@@ -1,6 +1,7 @@
 use colored::*;
 use serde::Serialize;
-use souk_core::error::{Severity, ValidationResult};
+use souk_core::discovery::MarketplaceConfig;
+use souk_core::error::{Severity, ValidationDiagnostic, ValidationResult};
 
 /// Output mode for the CLI.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,6 +31,10 @@ pub struct JsonOutput {
 pub struct Reporter {
     mode: OutputMode,
     json_results: Vec<JsonResultEntry>,
+    json_document: Option<serde_json::Value>,
+    document: Option<String>,
+    wrap_width: Option<usize>,
+    only_failures: bool,
 }
 
 impl Reporter {
@@ -37,9 +42,44 @@ impl Reporter {
         Self {
             mode,
             json_results: Vec::new(),
+            json_document: None,
+            document: None,
+            wrap_width: None,
+            only_failures: false,
         }
     }
 
+    /// Replace the default `{"results": [...]}` JSON output with `document`.
+    pub fn set_json_document(&mut self, document: serde_json::Value) {
+        self.json_document = Some(document);
+    }
+
+    /// Replace all output with `document`, printed verbatim by
+    /// [`finish`](Self::finish) whatever the mode (e.g. a JUnit XML report).
+    pub fn set_document(&mut self, document: String) {
+        self.document = Some(document);
+    }
+
+    /// Set the column width used when printing review text in human mode.
+    ///
+    /// `None` prints the text unwrapped.
+    pub fn set_wrap_width(&mut self, width: Option<usize>) {
+        self.wrap_width = width;
+    }
+
+    /// Suppress success messages, leaving errors, warnings, info, and
+    /// [`summary`](Self::summary) lines to be reported.
+    pub fn set_only_failures(&mut self, only_failures: bool) {
+        self.only_failures = only_failures;
+    }
+
+    /// Whether per-item successes are hidden, either by
+    /// [`set_only_failures`](Self::set_only_failures) or by quiet mode.
+    /// Commands use this to skip detail they print alongside a success.
+    pub fn only_failures(&self) -> bool {
+        self.only_failures || self.mode == OutputMode::Quiet
+    }
+
     /// Returns the current output mode.
     pub fn mode(&self) -> OutputMode {
         self.mode
@@ -80,6 +120,29 @@ impl Reporter {
     }
 
     pub fn success(&mut self, message: &str) {
+        if self.only_failures {
+            return;
+        }
+        match self.mode {
+            OutputMode::Human => {
+                println!("{} {}", "✓".green(), message);
+            }
+            OutputMode::Json => {
+                self.json_results.push(JsonResultEntry {
+                    result_type: "success".to_string(),
+                    message: message.to_string(),
+                    details: None,
+                });
+            }
+            OutputMode::Quiet => {}
+        }
+    }
+
+    /// Report a command's final summary as a success.
+    ///
+    /// Unlike [`success`](Self::success), a summary is printed even with
+    /// [`set_only_failures`](Self::set_only_failures).
+    pub fn summary(&mut self, message: &str) {
         match self.mode {
             OutputMode::Human => {
                 println!("{} {}", "✓".green(), message);
@@ -96,6 +159,9 @@ impl Reporter {
     }
 
     pub fn success_with_details(&mut self, message: &str, details: &str) {
+        if self.only_failures {
+            return;
+        }
         match self.mode {
             OutputMode::Human => {
                 println!("{} {}", "✓".green(), message);
@@ -127,18 +193,52 @@ impl Reporter {
         }
     }
 
+    /// Warn that an operation was rolled back and is being retried, as
+    /// retry `attempt` of `retries`.
+    pub fn retrying(&mut self, attempt: u32, retries: u32, error: &dyn std::fmt::Display) {
+        self.warning(&format!("{error}; retrying ({attempt}/{retries})"));
+    }
+
+    /// Point a newcomer at how to add plugins to an empty marketplace.
+    ///
+    /// Human mode only: JSON output stays machine-readable and quiet output
+    /// stays empty.
+    pub fn empty_marketplace_hint(&self) {
+        if self.mode == OutputMode::Human {
+            println!(
+                "No plugins yet — add one with `souk add <path>`, or register \
+                 directories already in pluginRoot with `souk sync`"
+            );
+        }
+    }
+
+    /// Print LLM review text, wrapped to the configured width in human mode.
+    pub fn review_text(&mut self, text: &str) {
+        match self.mode {
+            OutputMode::Human => match self.wrap_width {
+                Some(width) => println!("\n{}", wrap_text(text, width)),
+                None => println!("\n{text}"),
+            },
+            OutputMode::Json => self.info(text),
+            OutputMode::Quiet => {}
+        }
+    }
+
     pub fn section(&mut self, title: &str) {
         if self.mode == OutputMode::Human {
             println!("{}", format!("=== {title} ===").cyan());
         }
     }
 
-    pub fn report_validation(&mut self, result: &ValidationResult) {
+    /// Report every diagnostic in `result`, showing paths relative to the
+    /// project root when a marketplace config is available.
+    pub fn report_validation(
+        &mut self,
+        result: &ValidationResult,
+        config: Option<&MarketplaceConfig>,
+    ) {
         for diagnostic in &result.diagnostics {
-            let mut msg = diagnostic.message.clone();
-            if let Some(path) = &diagnostic.path {
-                msg = format!("{msg} ({path})", path = path.display());
-            }
+            let msg = diagnostic_message(diagnostic, config);
             match diagnostic.severity {
                 Severity::Error => self.error(&msg),
                 Severity::Warning => self.warning(&msg),
@@ -147,7 +247,17 @@ impl Reporter {
     }
 
     pub fn finish(&self) {
+        if let Some(document) = &self.document {
+            print!("{document}");
+            return;
+        }
         if self.mode == OutputMode::Json {
+            if let Some(document) = &self.json_document {
+                if let Ok(json) = serde_json::to_string_pretty(document) {
+                    println!("{json}");
+                }
+                return;
+            }
             let output = JsonOutput {
                 results: self.json_results.clone(),
             };
@@ -157,3 +267,120 @@ impl Reporter {
         }
     }
 }
+
+/// Format a diagnostic's message with its path, if any.
+///
+/// Paths inside the project are shown relative to `config.project_root`.
+pub fn diagnostic_message(
+    diagnostic: &ValidationDiagnostic,
+    config: Option<&MarketplaceConfig>,
+) -> String {
+    match (&diagnostic.path, config) {
+        (Some(path), Some(config)) => {
+            format!("{} ({})", diagnostic.message, config.display_path(path))
+        }
+        (Some(path), None) => format!("{} ({})", diagnostic.message, path.display()),
+        (None, _) => diagnostic.message.clone(),
+    }
+}
+
+/// Word-wrap `text` so no line exceeds `width` columns where possible.
+///
+/// Existing line breaks are kept, continuation lines keep the original
+/// line's indentation, and fenced code blocks are left untouched. Words
+/// longer than `width` are placed on their own line rather than split.
+pub fn wrap_text(text: &str, width: usize) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            out.push(line.to_string());
+            continue;
+        }
+        if in_fence || line.chars().count() <= width {
+            out.push(line.to_string());
+            continue;
+        }
+
+        let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+        let indent_len = indent.chars().count();
+        let mut current = indent.clone();
+        let mut current_len = indent_len;
+
+        for word in line.split_whitespace() {
+            let word_len = word.chars().count();
+            if current_len > indent_len && current_len + 1 + word_len > width {
+                out.push(std::mem::replace(&mut current, indent.clone()));
+                current_len = indent_len;
+            }
+            if current_len > indent_len {
+                current.push(' ');
+                current_len += 1;
+            }
+            current.push_str(word);
+            current_len += word_len;
+        }
+        out.push(current);
+    }
+
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_long_line_to_width() {
+        let text = "The skill description is clear but the examples section could use more detail about edge cases.";
+        let wrapped = wrap_text(text, 30);
+        assert!(wrapped.lines().count() > 1);
+        for line in wrapped.lines() {
+            assert!(line.chars().count() <= 30, "line too long: {line:?}");
+        }
+        assert_eq!(
+            wrapped.split_whitespace().collect::<Vec<_>>(),
+            text.split_whitespace().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn keeps_indentation_and_short_lines() {
+        let text = "# Title\n\n  - one two three four five six";
+        let wrapped = wrap_text(text, 17);
+        assert_eq!(wrapped, "# Title\n\n  - one two three\n  four five six");
+    }
+
+    #[test]
+    fn leaves_code_fences_untouched() {
+        let long = "x".repeat(50);
+        let text = format!("```\nlet a = \"{long} {long}\";\n```");
+        assert_eq!(wrap_text(&text, 20), text);
+    }
+
+    #[test]
+    fn overlong_word_gets_its_own_line() {
+        let wrapped = wrap_text("a supercalifragilistic b", 10);
+        assert_eq!(wrapped, "a\nsupercalifragilistic\nb");
+    }
+
+    #[test]
+    fn only_failures_drops_successes() {
+        let mut reporter = Reporter::new(OutputMode::Json);
+        reporter.set_only_failures(true);
+        reporter.success("ok");
+        reporter.success_with_details("ok", "details");
+        reporter.error("bad");
+        reporter.info("1 passed, 1 failed");
+        reporter.summary("Successfully added 1 plugin(s)");
+        let types: Vec<_> = reporter
+            .json_results
+            .iter()
+            .map(|r| r.result_type.as_str())
+            .collect();
+        assert_eq!(types, ["error", "info", "success"]);
+        assert!(Reporter::new(OutputMode::Quiet).only_failures());
+    }
+}
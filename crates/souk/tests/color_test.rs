@@ -0,0 +1,67 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::path::PathBuf;
+
+fn good_plugin() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("tests")
+        .join("fixtures")
+        .join("valid-marketplace")
+        .join("plugins")
+        .join("good-plugin")
+}
+
+/// Stdout of `souk validate plugin` on a passing plugin, with the color
+/// environment cleared except for `env`.
+fn validate_stdout(args: &[&str], env: &[(&str, &str)]) -> String {
+    let mut cmd = cargo_bin_cmd!("souk");
+    cmd.env_remove("NO_COLOR")
+        .env_remove("CLICOLOR_FORCE")
+        .env_remove("CLICOLOR")
+        .args(["validate", "plugin", good_plugin().to_str().unwrap()])
+        .args(args);
+    for (key, value) in env {
+        cmd.env(key, value);
+    }
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn has_ansi(text: &str) -> bool {
+    text.contains("\x1b[")
+}
+
+#[test]
+fn clicolor_force_enables_color_under_auto() {
+    assert!(has_ansi(&validate_stdout(&[], &[("CLICOLOR_FORCE", "1")])));
+    assert!(!has_ansi(&validate_stdout(&[], &[("CLICOLOR_FORCE", "0")])));
+}
+
+#[test]
+fn no_color_disables_color_under_auto() {
+    let both = [("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")];
+    assert!(!has_ansi(&validate_stdout(&[], &both)));
+    // An empty NO_COLOR is ignored, per the convention.
+    let empty = [("NO_COLOR", ""), ("CLICOLOR_FORCE", "1")];
+    assert!(has_ansi(&validate_stdout(&[], &empty)));
+}
+
+#[test]
+fn explicit_color_flags_beat_the_environment() {
+    assert!(has_ansi(&validate_stdout(
+        &["--color", "always"],
+        &[("NO_COLOR", "1")]
+    )));
+    assert!(!has_ansi(&validate_stdout(
+        &["--no-color"],
+        &[("CLICOLOR_FORCE", "1")]
+    )));
+    assert!(!has_ansi(&validate_stdout(
+        &["--color", "never"],
+        &[("CLICOLOR_FORCE", "1")]
+    )));
+}
@@ -0,0 +1,47 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+use tempfile::TempDir;
+
+fn souk_cmd() -> assert_cmd::Command {
+    cargo_bin_cmd!("souk")
+}
+
+#[test]
+fn diff_json_reports_each_category() {
+    let tmp = TempDir::new().unwrap();
+    let claude_dir = tmp.path().join(".claude-plugin");
+    fs::create_dir_all(&claude_dir).unwrap();
+
+    for (dir, manifest) in [
+        ("renamed", r#"{"name":"new-name"}"#),
+        ("stray", r#"{"name":"stray"}"#),
+    ] {
+        let p = tmp.path().join("plugins").join(dir).join(".claude-plugin");
+        fs::create_dir_all(&p).unwrap();
+        fs::write(p.join("plugin.json"), manifest).unwrap();
+    }
+    fs::write(
+        claude_dir.join("marketplace.json"),
+        r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[
+            {"name":"renamed","source":"renamed"},
+            {"name":"ghost","source":"ghost"}
+        ]}"#,
+    )
+    .unwrap();
+    let mp_path = claude_dir.join("marketplace.json");
+
+    let output = souk_cmd()
+        .args(["diff", "--json", "--marketplace", mp_path.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(doc["orphaned_dirs"], serde_json::json!(["stray"]));
+    assert_eq!(doc["missing_dirs"], serde_json::json!(["ghost"]));
+    assert_eq!(
+        doc["name_drift"],
+        serde_json::json!([{"entry": "renamed", "manifest": "new-name"}])
+    );
+    assert_eq!(doc["tag_drift"], serde_json::json!([]));
+}
@@ -0,0 +1,181 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn souk_cmd() -> assert_cmd::Command {
+    let mut cmd = cargo_bin_cmd!("souk");
+    for var in [
+        "ANTHROPIC_API_KEY",
+        "OPENAI_API_KEY",
+        "AZURE_OPENAI_API_KEY",
+        "GEMINI_API_KEY",
+        "OLLAMA_HOST",
+    ] {
+        cmd.env_remove(var);
+    }
+    cmd
+}
+
+/// A marketplace listing "alpha", with an unlisted "stray" directory.
+fn setup(tmp: &TempDir) {
+    let claude_dir = tmp.path().join(".claude-plugin");
+    fs::create_dir_all(&claude_dir).unwrap();
+    fs::write(
+        claude_dir.join("marketplace.json"),
+        r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"alpha","source":"alpha"}]}"#,
+    )
+    .unwrap();
+    for name in ["alpha", "stray"] {
+        let p = tmp.path().join("plugins").join(name).join(".claude-plugin");
+        fs::create_dir_all(&p).unwrap();
+        fs::write(
+            p.join("plugin.json"),
+            format!(r#"{{"name":"{name}","version":"1.0.0","description":"test"}}"#),
+        )
+        .unwrap();
+    }
+}
+
+fn doctor_json(cmd: &mut assert_cmd::Command) -> (bool, serde_json::Value) {
+    let output = cmd.args(["doctor", "--json"]).output().unwrap();
+    (
+        output.status.success(),
+        serde_json::from_slice(&output.stdout).unwrap(),
+    )
+}
+
+fn check<'a>(json: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    json["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == name)
+        .unwrap_or_else(|| panic!("no {name} check in {json}"))
+}
+
+#[test]
+fn doctor_warns_without_failing() {
+    let tmp = TempDir::new().unwrap();
+    setup(&tmp);
+
+    let (ok, json) = doctor_json(souk_cmd().current_dir(tmp.path()));
+
+    assert!(ok, "{json}");
+    assert_eq!(json["ok"], true);
+    assert_eq!(check(&json, "marketplace")["status"], "pass");
+    assert_eq!(check(&json, "pluginRoot")["status"], "pass");
+    assert_eq!(check(&json, "providers")["status"], "warn");
+    let orphans = check(&json, "orphans");
+    assert_eq!(orphans["status"], "warn");
+    assert!(orphans["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("1 orphaned"));
+}
+
+#[test]
+fn doctor_lists_provider_variables_without_values() {
+    let tmp = TempDir::new().unwrap();
+    setup(&tmp);
+
+    souk_cmd()
+        .current_dir(tmp.path())
+        .env("GEMINI_API_KEY", "secret-value")
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("gemini (GEMINI_API_KEY)"))
+        .stdout(predicate::str::contains("secret-value").not());
+}
+
+#[test]
+fn doctor_fails_without_marketplace_or_plugin_root() {
+    let tmp = TempDir::new().unwrap();
+    let (ok, json) = doctor_json(souk_cmd().current_dir(tmp.path()));
+    assert!(!ok);
+    assert_eq!(check(&json, "marketplace")["status"], "fail");
+
+    setup(&tmp);
+    fs::remove_dir_all(tmp.path().join("plugins")).unwrap();
+    souk_cmd()
+        .current_dir(tmp.path())
+        .arg("doctor")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pluginRoot:"));
+}
+
+#[test]
+fn doctor_fix_creates_plugin_root_and_removes_stale_backups() {
+    let tmp = TempDir::new().unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    setup(&tmp);
+    fs::remove_dir_all(tmp.path().join("plugins")).unwrap();
+    let claude_dir = tmp.path().join(".claude-plugin");
+    // An hour-old guard backup, and user files that only look similar
+    let backup = claude_dir.join("marketplace.json.bak.1000.0.999999");
+    fs::write(&backup, "{}").unwrap();
+    fs::write(claude_dir.join("notes.bak"), "keep").unwrap();
+    fs::write(claude_dir.join("marketplace.json.bak.manual"), "keep").unwrap();
+
+    // Without --fix, nothing changes
+    let (ok, json) = doctor_json(
+        souk_cmd()
+            .current_dir(tmp.path())
+            .env("TMPDIR", temp_dir.path()),
+    );
+    assert!(!ok);
+    assert_eq!(check(&json, "pluginRoot")["status"], "fail");
+    assert!(!tmp.path().join("plugins").exists());
+    assert!(backup.exists());
+
+    let output = souk_cmd()
+        .current_dir(tmp.path())
+        .env("TMPDIR", temp_dir.path())
+        .args(["doctor", "--json", "--fix"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(check(&json, "pluginRoot")["status"], "pass");
+    assert_eq!(check(&json, "backups")["status"], "pass");
+    assert!(tmp.path().join("plugins").is_dir());
+    assert!(!backup.exists());
+    assert_eq!(json["fixed"].as_array().unwrap().len(), 2, "{json}");
+
+    // User data is untouched: the entry for alpha is kept, as are the
+    // look-alike files
+    assert_eq!(
+        fs::read_to_string(claude_dir.join("notes.bak")).unwrap(),
+        "keep"
+    );
+    assert!(claude_dir.join("marketplace.json.bak.manual").exists());
+    let marketplace = fs::read_to_string(claude_dir.join("marketplace.json")).unwrap();
+    assert!(marketplace.contains("\"alpha\""));
+}
+
+#[test]
+fn doctor_fix_applies_only_the_listed_fixes() {
+    let tmp = TempDir::new().unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    setup(&tmp);
+    fs::remove_dir_all(tmp.path().join("plugins/alpha")).unwrap();
+    let backup = tmp
+        .path()
+        .join(".claude-plugin/marketplace.json.bak.1000.0.999999");
+    fs::write(&backup, "{}").unwrap();
+
+    souk_cmd()
+        .current_dir(tmp.path())
+        .env("TMPDIR", temp_dir.path())
+        .args(["doctor", "--fix=stale-entries"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed stale entry: alpha"))
+        .stderr(predicate::str::contains("1 stale backup(s)"));
+    assert!(backup.exists());
+    let marketplace =
+        fs::read_to_string(tmp.path().join(".claude-plugin/marketplace.json")).unwrap();
+    assert!(!marketplace.contains("\"alpha\""));
+}
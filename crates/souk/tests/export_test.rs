@@ -0,0 +1,92 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn souk_cmd() -> assert_cmd::Command {
+    cargo_bin_cmd!("souk")
+}
+
+fn setup_marketplace(tmp: &TempDir) {
+    let claude_dir = tmp.path().join(".claude-plugin");
+    fs::create_dir_all(&claude_dir).unwrap();
+    let plugin_dir = tmp.path().join("plugins").join("alpha");
+    fs::create_dir_all(plugin_dir.join(".claude-plugin")).unwrap();
+    fs::write(
+        plugin_dir.join(".claude-plugin").join("plugin.json"),
+        r#"{"name":"alpha","version":"1.2.3","description":"test"}"#,
+    )
+    .unwrap();
+    let skill_dir = plugin_dir.join("skills").join("helper");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(skill_dir.join("SKILL.md"), "---\nname: Helper\n---\n").unwrap();
+    fs::write(
+        claude_dir.join("marketplace.json"),
+        r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"alpha","source":"alpha"}]}"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn export_json_to_stdout() {
+    let tmp = TempDir::new().unwrap();
+    setup_marketplace(&tmp);
+    let mp_path = tmp.path().join(".claude-plugin").join("marketplace.json");
+
+    let output = souk_cmd()
+        .args(["export", "--marketplace", mp_path.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(parsed["marketplace"]["version"], "0.1.0");
+    assert_eq!(parsed["plugins"][0]["manifest"]["version"], "1.2.3");
+    assert_eq!(parsed["plugins"][0]["skills"][0]["name"], "Helper");
+}
+
+#[test]
+fn export_json_to_file() {
+    let tmp = TempDir::new().unwrap();
+    setup_marketplace(&tmp);
+    let mp_path = tmp.path().join(".claude-plugin").join("marketplace.json");
+    let out = tmp.path().join("bundle.json");
+
+    souk_cmd()
+        .args([
+            "export",
+            "--format",
+            "json",
+            "--output",
+            out.to_str().unwrap(),
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Exported 1 plugin(s)"));
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+    assert_eq!(parsed["plugins"][0]["entry"]["name"], "alpha");
+}
+
+#[test]
+fn export_write_failure_exits_internal() {
+    let tmp = TempDir::new().unwrap();
+    setup_marketplace(&tmp);
+    let mp_path = tmp.path().join(".claude-plugin").join("marketplace.json");
+    let out = tmp.path().join("missing").join("bundle.json");
+
+    souk_cmd()
+        .args([
+            "export",
+            "--output",
+            out.to_str().unwrap(),
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Failed to write"));
+}
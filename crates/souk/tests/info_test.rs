@@ -0,0 +1,228 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn souk_cmd() -> assert_cmd::Command {
+    cargo_bin_cmd!("souk")
+}
+
+/// A marketplace with an internal "alpha" plugin holding one skill, an
+/// archived "beta" plugin, and an external plugin that is not listed.
+fn setup(tmp: &TempDir) -> std::path::PathBuf {
+    let claude_dir = tmp.path().join(".claude-plugin");
+    fs::create_dir_all(&claude_dir).unwrap();
+    for name in ["alpha", "beta"] {
+        let p = tmp.path().join("plugins").join(name).join(".claude-plugin");
+        fs::create_dir_all(&p).unwrap();
+        fs::write(
+            p.join("plugin.json"),
+            format!(r#"{{"name":"{name}","version":"1.2.0","description":"The {name} plugin"}}"#),
+        )
+        .unwrap();
+    }
+    let skill = tmp.path().join("plugins/alpha/skills/lint");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: Linter\n---\nLint things",
+    )
+    .unwrap();
+
+    let external = tmp.path().join("elsewhere/.claude-plugin");
+    fs::create_dir_all(&external).unwrap();
+    fs::write(external.join("plugin.json"), "{ broken").unwrap();
+
+    let mp_path = claude_dir.join("marketplace.json");
+    fs::write(
+        &mp_path,
+        r#"{"version":"0.1.0","pluginRoot":"./plugins",
+            "plugins":[{"name":"alpha","source":"alpha","tags":["lint","ci"]}],
+            "archived":[{"name":"beta","source":"beta"}],
+            "keywords":["devtools","git"],"categories":["productivity"]}"#,
+    )
+    .unwrap();
+    mp_path
+}
+
+#[test]
+fn info_shows_resolved_details() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup(&tmp);
+
+    souk_cmd()
+        .args(["info", "alpha", "--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Version:     1.2.0"))
+        .stdout(predicate::str::contains("Source:      alpha (internal)"))
+        .stdout(predicate::str::contains("Tags:        lint, ci"))
+        .stdout(predicate::str::contains(
+            "Skills:      1\n  - lint (Linter)",
+        ))
+        .stdout(predicate::str::contains(
+            "Marketplace keywords: devtools, git\nMarketplace categories: productivity",
+        ));
+
+    souk_cmd()
+        .args(["info", "beta", "--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Entry:       beta (archived)"));
+}
+
+#[test]
+fn info_json_is_a_single_object() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup(&tmp);
+
+    let output = souk_cmd()
+        .args([
+            "info",
+            "alpha",
+            "--json",
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(doc["manifest"]["version"], "1.2.0");
+    assert_eq!(doc["internal"], true);
+    assert_eq!(doc["entry"]["tags"], serde_json::json!(["lint", "ci"]));
+    assert_eq!(doc["entry"]["archived"], false);
+    assert_eq!(doc["skills"][0]["name"], "Linter");
+    assert!(doc["manifest_error"].is_null());
+    assert_eq!(
+        doc["marketplace"],
+        serde_json::json!({"keywords": ["devtools", "git"], "categories": ["productivity"]})
+    );
+}
+
+#[test]
+fn info_reports_broken_external_plugin() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup(&tmp);
+    let external = tmp.path().join("elsewhere");
+
+    let output = souk_cmd()
+        .args(["info", "--json", "--marketplace", mp_path.to_str().unwrap()])
+        .arg(&external)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(doc["internal"], false);
+    assert!(doc["entry"].is_null());
+    assert!(doc["manifest"].is_null());
+    assert!(doc["manifest_error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid JSON in plugin.json"));
+}
+
+#[test]
+fn info_unknown_plugin_fails() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup(&tmp);
+
+    souk_cmd()
+        .args(["info", "ghost", "--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Failed to resolve plugin"));
+}
+
+/// A marketplace where "app" depends on "lib", which depends on "core".
+/// With `core_listed` false, "core" is left out of the marketplace.
+fn setup_dependencies(tmp: &TempDir, core_listed: bool) -> std::path::PathBuf {
+    let claude_dir = tmp.path().join(".claude-plugin");
+    fs::create_dir_all(&claude_dir).unwrap();
+    let plugins = [
+        ("app", "1.0.0", Some(r#"{"dependencies":{"lib":"^1.0.0"}}"#)),
+        (
+            "lib",
+            "1.4.0",
+            Some(r#"{"dependencies":{"core":">=2.0.0"}}"#),
+        ),
+        ("core", "2.1.0", None),
+    ];
+    for (name, version, extends) in plugins {
+        let p = tmp.path().join("plugins").join(name).join(".claude-plugin");
+        fs::create_dir_all(&p).unwrap();
+        fs::write(
+            p.join("plugin.json"),
+            format!(r#"{{"name":"{name}","version":"{version}"}}"#),
+        )
+        .unwrap();
+        if let Some(extends) = extends {
+            fs::write(p.join("extends-plugin.json"), extends).unwrap();
+        }
+    }
+
+    let listed: Vec<_> = plugins
+        .iter()
+        .filter(|(name, _, _)| core_listed || *name != "core")
+        .map(|(name, _, _)| format!(r#"{{"name":"{name}","source":"{name}"}}"#))
+        .collect();
+    let mp_path = claude_dir.join("marketplace.json");
+    fs::write(
+        &mp_path,
+        format!(
+            r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{}]}}"#,
+            listed.join(",")
+        ),
+    )
+    .unwrap();
+    mp_path
+}
+
+#[test]
+fn info_tree_shows_transitive_dependencies() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_dependencies(&tmp, true);
+
+    souk_cmd()
+        .args([
+            "info",
+            "app",
+            "--tree",
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Dependencies:\n  - lib ^1.0.0: satisfied (1.4.0)\n    - core >=2.0.0: satisfied (2.1.0)\n",
+        ));
+}
+
+#[test]
+fn info_tree_json_flags_missing_transitive_dependency() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_dependencies(&tmp, false);
+
+    let output = souk_cmd()
+        .args([
+            "info",
+            "app",
+            "--tree",
+            "--json",
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let lib = &doc["dependencies"][0];
+    assert_eq!(lib["name"], "lib");
+    assert_eq!(lib["status"], "satisfied");
+    assert_eq!(lib["dependencies"][0]["name"], "core");
+    assert_eq!(lib["dependencies"][0]["constraint"], ">=2.0.0");
+    assert_eq!(lib["dependencies"][0]["status"], "missing");
+}
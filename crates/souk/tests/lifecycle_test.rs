@@ -153,5 +153,1115 @@ fn json_output_mode() {
     assert!(output.status.success());
     let stdout = String::from_utf8(output.stdout).unwrap();
     let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
-    assert!(parsed["results"].is_array());
+    assert_eq!(parsed["ok"], true);
+    assert!(parsed["diagnostics"].is_array());
+}
+
+#[test]
+fn init_check_intact_scaffold() {
+    let tmp = TempDir::new().unwrap();
+    let tmp_path = tmp.path().to_str().unwrap();
+
+    souk_cmd()
+        .args(["init", "--path", tmp_path])
+        .assert()
+        .success();
+
+    souk_cmd()
+        .args(["init", "--check", "--path", tmp_path])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("scaffold intact"));
+}
+
+#[test]
+fn init_check_missing_plugin_root() {
+    let tmp = TempDir::new().unwrap();
+    let tmp_path = tmp.path().to_str().unwrap();
+
+    souk_cmd()
+        .args(["init", "--path", tmp_path])
+        .assert()
+        .success();
+    fs::remove_dir_all(tmp.path().join("plugins")).unwrap();
+
+    souk_cmd()
+        .args(["init", "--check", "--path", tmp_path])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Plugin root directory not found"));
+
+    // --check must not recreate the scaffold
+    assert!(!tmp.path().join("plugins").exists());
+}
+
+#[test]
+fn selftest_passes_on_clean_environment() {
+    souk_cmd()
+        .arg("selftest")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Self-test passed"));
+}
+
+/// Create a marketplace listing one valid plugin per name; returns marketplace.json's path.
+fn setup_marketplace_with_plugins(tmp: &TempDir, names: &[&str]) -> std::path::PathBuf {
+    let claude_dir = tmp.path().join(".claude-plugin");
+    fs::create_dir_all(&claude_dir).unwrap();
+    let mut entries = Vec::new();
+    for name in names {
+        let p = tmp.path().join("plugins").join(name).join(".claude-plugin");
+        fs::create_dir_all(&p).unwrap();
+        fs::write(
+            p.join("plugin.json"),
+            format!(r#"{{"name":"{name}","version":"1.0.0","description":"test"}}"#),
+        )
+        .unwrap();
+        entries.push(format!(r#"{{"name":"{name}","source":"{name}"}}"#));
+    }
+    let mp_path = claude_dir.join("marketplace.json");
+    fs::write(
+        &mp_path,
+        format!(
+            r#"{{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{}]}}"#,
+            entries.join(",")
+        ),
+    )
+    .unwrap();
+    mp_path
+}
+
+#[test]
+fn archive_hides_plugin_from_default_list() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+    let mp = mp_path.to_str().unwrap();
+
+    souk_cmd()
+        .args(["archive", "alpha", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Archived: alpha"));
+
+    souk_cmd()
+        .args(["list", "--quiet", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout("beta\n");
+
+    souk_cmd()
+        .args(["list", "--include-archived", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("alpha  alpha  (archived)"));
+
+    let content = fs::read_to_string(&mp_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(json["archived"][0]["name"], "alpha");
+    assert_eq!(json["plugins"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn empty_marketplace_shows_onboarding_hint_in_human_mode_only() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &[]);
+    fs::create_dir_all(tmp.path().join("plugins")).unwrap();
+    let mp = mp_path.to_str().unwrap();
+
+    souk_cmd()
+        .args(["list", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No plugins yet — add one with `souk add <path>`",
+        ));
+
+    let output = souk_cmd()
+        .args(["--json", "list", "--marketplace", mp])
+        .output()
+        .unwrap();
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        doc,
+        serde_json::json!({"total": 0, "offset": 0, "limit": null, "items": []})
+    );
+
+    souk_cmd()
+        .args(["validate", "marketplace", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No plugins yet"));
+}
+
+#[test]
+fn list_columns_read_version_from_plugin_json() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha", "beta-long"]);
+    let mp = mp_path.to_str().unwrap();
+    fs::write(
+        tmp.path()
+            .join("plugins/beta-long/.claude-plugin/plugin.json"),
+        r#"{"name":"beta-long","version":"2.3.4","description":"test"}"#,
+    )
+    .unwrap();
+
+    souk_cmd()
+        .args(["list", "--columns", "name,version", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout("alpha      1.0.0\nbeta-long  2.3.4\n");
+
+    let output = souk_cmd()
+        .args(["--json", "list", "--wide", "--marketplace", mp])
+        .output()
+        .unwrap();
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(doc["total"], 2);
+    assert_eq!(
+        doc["items"][1],
+        serde_json::json!({
+            "name": "beta-long",
+            "source": "beta-long",
+            "tags": [],
+            "version": "2.3.4",
+            "skills": 0,
+            "archived": false,
+        })
+    );
+}
+
+#[test]
+fn add_reads_json_request_from_stdin() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+
+    // An external plugin whose name clashes with "alpha", plus a new one.
+    for (dir, name) in [("copy-of-alpha", "alpha"), ("gamma", "gamma")] {
+        let p = tmp.path().join("incoming").join(dir).join(".claude-plugin");
+        fs::create_dir_all(&p).unwrap();
+        fs::write(
+            p.join("plugin.json"),
+            format!(r#"{{"name":"{name}","version":"1.0.0","description":"test"}}"#),
+        )
+        .unwrap();
+    }
+
+    let request = serde_json::json!({
+        "plugins": [
+            tmp.path().join("incoming/copy-of-alpha"),
+            tmp.path().join("incoming/gamma"),
+        ],
+        "on_conflict": "rename",
+    });
+
+    souk_cmd()
+        .args(["add", "--stdin", "--marketplace", mp_path.to_str().unwrap()])
+        .write_stdin(request.to_string())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("alpha: RENAME -> alpha-2"));
+
+    let content = fs::read_to_string(&mp_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    let names: Vec<_> = json["plugins"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["alpha", "alpha-2", "gamma"]);
+}
+
+#[test]
+fn add_stdin_rejects_unknown_strategy() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+
+    souk_cmd()
+        .args(["add", "--stdin", "--marketplace", mp_path.to_str().unwrap()])
+        .write_stdin(r#"{"plugins":["alpha"],"on_conflict":"merge"}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid conflict strategy: merge"));
+}
+
+#[test]
+fn mutating_commands_exit_codes_distinguish_usage_from_internal_errors() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+
+    // An unknown plugin: a usage error
+    souk_cmd()
+        .args([
+            "remove",
+            "ghost",
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Plugin not found: ghost"));
+
+    // A plugin.json that cannot be read: an I/O error
+    let manifest = tmp.path().join("plugins/alpha/.claude-plugin/plugin.json");
+    fs::remove_file(&manifest).unwrap();
+    fs::create_dir(&manifest).unwrap();
+    souk_cmd()
+        .args(["update", "alpha", "--patch", "--marketplace"])
+        .arg(&mp_path)
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn list_and_search_limit_offset_and_count() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["lint-a", "lint-b", "lint-c", "docs"]);
+    let mp = mp_path.to_str().unwrap();
+
+    souk_cmd()
+        .args(["list", "--quiet", "--offset", "1", "--limit", "2"])
+        .args(["--marketplace", mp])
+        .assert()
+        .success()
+        .stdout("lint-b\nlint-c\n");
+
+    let output = souk_cmd()
+        .args(["search", "lint", "--json", "--limit", "1", "--offset", "1"])
+        .args(["--marketplace", mp])
+        .output()
+        .unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(parsed["total"], 3);
+    assert_eq!(parsed["offset"], 1);
+    assert_eq!(parsed["limit"], 1);
+    assert_eq!(parsed["items"][0]["name"], "lint-b");
+    assert_eq!(parsed["items"].as_array().unwrap().len(), 1);
+
+    souk_cmd()
+        .args(["list", "--count", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout("4\n");
+    souk_cmd()
+        .args(["search", "lint", "--count", "--json", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""total": 3"#));
+}
+
+#[test]
+fn search_remote_searches_an_exported_index() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["lint-a", "docs"]);
+    let index = tmp.path().join("index.json");
+    souk_cmd()
+        .args(["export", "--output", index.to_str().unwrap()])
+        .args(["--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    // Searched from elsewhere, with no local marketplace
+    let elsewhere = TempDir::new().unwrap();
+    let url = format!("file://{}", index.display());
+    souk_cmd()
+        .current_dir(elsewhere.path())
+        .env("TMPDIR", elsewhere.path())
+        .args(["search", "lint", "--quiet", "--remote", &url])
+        .assert()
+        .success()
+        .stdout("lint-a\n");
+    assert!(elsewhere.path().join("souk-index-cache").is_dir());
+
+    souk_cmd()
+        .current_dir(elsewhere.path())
+        .env("TMPDIR", elsewhere.path())
+        .args([
+            "search",
+            "lint",
+            "--remote",
+            "file:///nonexistent/index.json",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot read index"));
+}
+
+#[test]
+fn add_from_remote_installs_a_git_source_listed_in_an_index() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &[]);
+    fs::create_dir_all(tmp.path().join("plugins")).unwrap();
+    let mp = mp_path.to_str().unwrap();
+
+    // A plugin published as a git repository
+    let remote = TempDir::new().unwrap();
+    let repo = remote.path().join("linter");
+    fs::create_dir_all(repo.join(".claude-plugin")).unwrap();
+    fs::write(
+        repo.join(".claude-plugin/plugin.json"),
+        r#"{"name":"linter","version":"1.0.0","description":"test"}"#,
+    )
+    .unwrap();
+    for args in [
+        &["init", "--quiet"][..],
+        &["add", "."],
+        &["commit", "--quiet", "-m", "init"],
+    ] {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=souk", "-c", "user.email=souk@example.com"])
+            .args(args)
+            .current_dir(&repo)
+            .status()
+            .expect("git failed to run");
+        assert!(status.success());
+    }
+    let index = remote.path().join("index.json");
+    fs::write(
+        &index,
+        format!(
+            r#"{{"plugins":[
+                {{"entry":{{"name":"linter","source":"file://{}"}}}},
+                {{"entry":{{"name":"docs","source":"docs"}}}}]}}"#,
+            repo.display()
+        ),
+    )
+    .unwrap();
+    let url = format!("file://{}", index.display());
+
+    for (name, message) in [
+        ("missing", "Plugin not found: missing"),
+        ("docs", "not a git or archive URL"),
+    ] {
+        souk_cmd()
+            .env("TMPDIR", tmp.path())
+            .args(["add", name, "--from-remote", &url, "--marketplace", mp])
+            .assert()
+            .code(2)
+            .stderr(predicate::str::contains(message));
+    }
+
+    souk_cmd()
+        .env("TMPDIR", tmp.path())
+        .args(["add", "linter", "--from-remote", &url, "--marketplace", mp])
+        .assert()
+        .success();
+    assert!(tmp
+        .path()
+        .join("plugins/linter/.claude-plugin/plugin.json")
+        .is_file());
+    assert!(!tmp.path().join("plugins/linter/.git").exists());
+    let marketplace = fs::read_to_string(&mp_path).unwrap();
+    assert!(marketplace.contains(r#""name": "linter""#), "{marketplace}");
+}
+
+#[test]
+fn list_shows_marketplace_keywords_and_categories() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+    let mut json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&mp_path).unwrap()).unwrap();
+    json["keywords"] = serde_json::json!(["devtools", "git"]);
+    json["categories"] = serde_json::json!(["productivity"]);
+    fs::write(&mp_path, json.to_string()).unwrap();
+
+    souk_cmd()
+        .args(["list", "--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout("Keywords: devtools, git\nCategories: productivity\nalpha  alpha\n");
+}
+
+#[test]
+fn pre_push_verbose_reports_parallel_validation() {
+    let tmp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(tmp.path())
+        .status()
+        .expect("git init failed");
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha", "beta", "gamma", "delta"]);
+    fs::write(
+        tmp.path().join("plugins/gamma/.claude-plugin/plugin.json"),
+        "not valid json",
+    )
+    .unwrap();
+
+    souk_cmd()
+        .args(["ci", "run", "pre-push", "--verbose", "--marketplace"])
+        .arg(&mp_path)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Validated 4 plugin(s) on"))
+        .stderr(predicate::str::contains("gamma"))
+        .stderr(predicate::str::contains("Pre-push validation failed"));
+}
+
+#[test]
+fn pre_push_junit_reports_plugins_as_testcases() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha", "gamma"]);
+    fs::write(
+        tmp.path().join("plugins/gamma/.claude-plugin/plugin.json"),
+        "not valid json",
+    )
+    .unwrap();
+
+    souk_cmd()
+        .args([
+            "ci",
+            "run",
+            "pre-push",
+            "--format",
+            "junit",
+            "--marketplace",
+        ])
+        .arg(&mp_path)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            r#"<testsuite name="plugins" tests="2" failures="1">"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<testcase classname="plugins" name="alpha"/>"#,
+        ))
+        .stdout(predicate::str::contains("Pre-push validation failed").not());
+}
+
+#[test]
+fn hidden_complete_command_prints_plugin_names() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+
+    souk_cmd()
+        .args(["__complete", "--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout("alpha\nbeta\n");
+
+    souk_cmd()
+        .args(["--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("__complete").not());
+}
+
+#[test]
+fn on_conflict_flag_overrides_souk_toml() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+    let mp = mp_path.to_str().unwrap();
+    fs::write(tmp.path().join("souk.toml"), "on_conflict = \"skip\"\n").unwrap();
+
+    souk_cmd()
+        .args(["add", "alpha", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("alpha: SKIP"));
+
+    souk_cmd()
+        .args([
+            "add",
+            "alpha",
+            "--on-conflict",
+            "abort",
+            "--marketplace",
+            mp,
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+}
+
+#[test]
+fn list_filters_by_tag_and_sorts() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["gamma", "alpha", "beta"]);
+    let mut json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&mp_path).unwrap()).unwrap();
+    json["plugins"][0]["tags"] = serde_json::json!(["git"]);
+    json["plugins"][2]["tags"] = serde_json::json!(["dev", "Git"]);
+    fs::write(&mp_path, json.to_string()).unwrap();
+    let mp = mp_path.to_str().unwrap();
+
+    souk_cmd()
+        .args(["list", "--quiet", "--sort", "name", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout("alpha\nbeta\ngamma\n");
+
+    souk_cmd()
+        .args([
+            "list",
+            "--tag",
+            "git",
+            "--sort",
+            "name",
+            "--marketplace",
+            mp,
+        ])
+        .assert()
+        .success()
+        .stdout("beta   beta   dev, Git\ngamma  gamma  git\n");
+
+    souk_cmd()
+        .args(["list", "--json", "--tag", "dev", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""name": "beta""#))
+        .stdout(predicate::str::contains("gamma").not());
+}
+
+#[test]
+fn search_ranks_matches_with_scores() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["git-helper", "formatter", "docs"]);
+    let mp = mp_path.to_str().unwrap();
+
+    souk_cmd()
+        .args(["search", "FORMATER", "--quiet", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout("formatter\n");
+
+    let output = souk_cmd()
+        .args(["search", "git", "--json", "--marketplace", mp])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let hits: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(hits["total"], 1);
+    assert_eq!(hits["offset"], 0);
+    assert!(hits["limit"].is_null());
+    assert_eq!(
+        hits["items"],
+        serde_json::json!([{
+            "name": "git-helper",
+            "source": "git-helper",
+            "tags": [],
+            "score": 90,
+            "field": "name",
+            "matched": "git-helper",
+        }])
+    );
+}
+
+#[test]
+fn update_changelog_records_bump() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+    let mp = mp_path.to_str().unwrap();
+    let changelog = tmp.path().join("plugins/alpha/CHANGELOG.md");
+
+    souk_cmd()
+        .args([
+            "update",
+            "alpha",
+            "--changelog",
+            "Add search",
+            "--marketplace",
+            mp,
+        ])
+        .assert()
+        .failure();
+    assert!(!changelog.exists());
+
+    souk_cmd()
+        .args([
+            "update",
+            "alpha",
+            "--minor",
+            "--changelog",
+            "Add search",
+            "--marketplace",
+            mp,
+        ])
+        .assert()
+        .success();
+    let content = fs::read_to_string(&changelog).unwrap();
+    assert!(content.starts_with("# Changelog\n\n## 1.1.0 - "));
+    assert!(content.ends_with("\n- Add search\n"));
+}
+
+#[test]
+fn update_retry_gives_up_after_repeated_rollbacks() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+    // A duplicate entry fails the final validation every time
+    fs::write(
+        &mp_path,
+        r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[
+            {"name":"alpha","source":"alpha"},{"name":"alpha","source":"alpha"}]}"#,
+    )
+    .unwrap();
+
+    souk_cmd()
+        .args([
+            "update",
+            "alpha",
+            "--patch",
+            "--retry",
+            "2",
+            "--marketplace",
+        ])
+        .arg(&mp_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("retrying (1/2)"))
+        .stderr(predicate::str::contains("retrying (2/2)"))
+        .stderr(predicate::str::contains("retrying (3/2)").not());
+
+    let manifest =
+        fs::read_to_string(tmp.path().join("plugins/alpha/.claude-plugin/plugin.json")).unwrap();
+    assert!(manifest.contains(r#""version":"1.0.0""#));
+}
+
+#[test]
+fn update_all_refreshes_every_plugin() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+    let mp = mp_path.to_str().unwrap();
+    for name in ["alpha", "beta"] {
+        fs::write(
+            tmp.path()
+                .join("plugins")
+                .join(name)
+                .join(".claude-plugin/plugin.json"),
+            format!(
+                r#"{{"name":"{name}","version":"1.0.0","description":"test","keywords":["{name}-tag"]}}"#
+            ),
+        )
+        .unwrap();
+    }
+
+    souk_cmd()
+        .args(["update", "--all", "--minor", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Successfully updated 2 plugin(s)"));
+
+    let marketplace: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&mp_path).unwrap()).unwrap();
+    for (i, name) in ["alpha", "beta"].iter().enumerate() {
+        assert_eq!(
+            marketplace["plugins"][i]["tags"],
+            serde_json::json!([format!("{name}-tag")])
+        );
+        let manifest = fs::read_to_string(
+            tmp.path()
+                .join("plugins")
+                .join(name)
+                .join(".claude-plugin/plugin.json"),
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+        assert_eq!(json["version"], "1.1.0", "{name}");
+    }
+
+    souk_cmd()
+        .args(["update", "--all", "alpha", "--marketplace", mp])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn update_all_on_an_empty_marketplace_has_nothing_to_do() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &[]);
+    fs::create_dir_all(tmp.path().join("plugins")).unwrap();
+    let mp = mp_path.to_str().unwrap();
+
+    for extra in [&["--patch"][..], &["--check"], &["--bump-dep", "alpha"]] {
+        souk_cmd()
+            .args(["update", "--all", "--marketplace", mp])
+            .args(extra)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("nothing to update"));
+    }
+    souk_cmd()
+        .args(["update", "--marketplace", mp])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn update_cuts_and_releases_prerelease() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+    let mp = mp_path.to_str().unwrap();
+    let manifest = tmp.path().join("plugins/alpha/.claude-plugin/plugin.json");
+    let version = || {
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&manifest).unwrap()).unwrap();
+        json["version"].as_str().unwrap().to_string()
+    };
+
+    for expected in ["1.0.0-rc.1", "1.0.0-rc.2"] {
+        souk_cmd()
+            .args(["update", "alpha", "--prerelease", "rc", "--marketplace", mp])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Version bump: prerelease (rc)"));
+        assert_eq!(version(), expected);
+    }
+
+    souk_cmd()
+        .args(["update", "alpha", "--release", "--marketplace", mp])
+        .assert()
+        .success();
+    assert_eq!(version(), "1.0.0");
+
+    souk_cmd()
+        .args(["update", "alpha", "--release", "--marketplace", mp])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1.0.0 is not a pre-release"));
+    souk_cmd()
+        .args([
+            "update",
+            "alpha",
+            "--release",
+            "--patch",
+            "--marketplace",
+            mp,
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn update_check_reports_drift_without_writing() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+    let mp = mp_path.to_str().unwrap();
+
+    souk_cmd()
+        .args(["update", "--all", "--check", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 plugin(s) in sync"));
+
+    fs::write(
+        tmp.path().join("plugins/beta/.claude-plugin/plugin.json"),
+        r#"{"name":"beta","version":"1.0.0","description":"test","keywords":["new"]}"#,
+    )
+    .unwrap();
+    let before = fs::read_to_string(&mp_path).unwrap();
+
+    souk_cmd()
+        .args(["update", "--all", "--check", "--marketplace", mp])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "beta: tags out of sync (marketplace: [], plugin.json: [new])",
+        ))
+        .stderr(predicate::str::contains("1 of 2 plugin(s) out of sync"));
+
+    let output = souk_cmd()
+        .args(["--json", "update", "beta", "--check", "--marketplace", mp])
+        .output()
+        .unwrap();
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(doc["ok"], false);
+    assert_eq!(doc["drifted"][0]["fields"], serde_json::json!(["tags"]));
+    assert_eq!(
+        doc["drifted"][0]["pluginJson"]["tags"],
+        serde_json::json!(["new"])
+    );
+
+    assert_eq!(fs::read_to_string(&mp_path).unwrap(), before);
+    souk_cmd()
+        .args(["update", "beta", "--check", "--patch", "--marketplace", mp])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn plugin_arguments_accept_wildcards() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["lint-go", "lint-rust", "docs"]);
+    let mp = mp_path.to_str().unwrap();
+
+    souk_cmd()
+        .args(["update", "lint-*", "--patch", "--marketplace", mp])
+        .assert()
+        .success();
+    for (name, version) in [
+        ("lint-go", "1.0.1"),
+        ("lint-rust", "1.0.1"),
+        ("docs", "1.0.0"),
+    ] {
+        let manifest = fs::read_to_string(
+            tmp.path()
+                .join("plugins")
+                .join(name)
+                .join(".claude-plugin/plugin.json"),
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+        assert_eq!(json["version"], version, "{name}");
+    }
+
+    souk_cmd()
+        .args(["remove", "*-rust", "--marketplace", mp])
+        .assert()
+        .success();
+    souk_cmd()
+        .args(["list", "--quiet", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout("lint-go\ndocs\n");
+
+    // Unregistered directories under pluginRoot can be added by pattern.
+    souk_cmd()
+        .args(["add", "lint-r*", "--marketplace", mp])
+        .assert()
+        .success();
+
+    souk_cmd()
+        .args(["remove", "*-agent", "--marketplace", mp])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No plugins match pattern: *-agent",
+        ));
+}
+
+#[test]
+fn rename_updates_entry_directory_and_dependents() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["core", "app"]);
+    let mp = mp_path.to_str().unwrap();
+    let extends_path = tmp
+        .path()
+        .join("plugins/app/.claude-plugin/extends-plugin.json");
+    fs::write(&extends_path, r#"{"dependencies":{"core":"^1.0.0"}}"#).unwrap();
+
+    souk_cmd()
+        .args(["rename", "core", "base", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Updated dependency in: app"))
+        .stdout(predicate::str::contains("Renamed: core -> base"));
+
+    assert!(!tmp.path().join("plugins/core").exists());
+    let manifest =
+        fs::read_to_string(tmp.path().join("plugins/base/.claude-plugin/plugin.json")).unwrap();
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+    assert_eq!(manifest["name"], "base");
+
+    let extends: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&extends_path).unwrap()).unwrap();
+    assert_eq!(
+        extends["dependencies"],
+        serde_json::json!({"base": "^1.0.0"})
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&mp_path).unwrap()).unwrap();
+    assert_eq!(json["version"], "0.1.1");
+    assert_eq!(json["plugins"][0]["name"], "base");
+    assert_eq!(json["plugins"][0]["source"], "base");
+
+    souk_cmd()
+        .args(["rename", "base", "app", "--marketplace", mp])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Plugin already exists"));
+}
+
+#[test]
+fn update_bump_dep_follows_a_dependency_bump() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["core", "app"]);
+    let mp = mp_path.to_str().unwrap();
+    let extends_path = tmp
+        .path()
+        .join("plugins/app/.claude-plugin/extends-plugin.json");
+    fs::write(&extends_path, r#"{"dependencies":{"core":"^1.0.0"}}"#).unwrap();
+
+    souk_cmd()
+        .args(["update", "core", "--major", "--no-final-validation"])
+        .args(["--marketplace", mp])
+        .assert()
+        .success();
+    souk_cmd()
+        .args(["validate", "marketplace", "--marketplace", mp])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsatisfied dependency"));
+
+    souk_cmd()
+        .args(["update", "app", "--bump-dep", "core", "--marketplace", mp])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "app (dependencies): core ^1.0.0 -> ^2.0.0",
+        ));
+
+    let extends: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&extends_path).unwrap()).unwrap();
+    assert_eq!(extends["dependencies"]["core"], "^2.0.0");
+    souk_cmd()
+        .args(["validate", "marketplace", "--marketplace", mp])
+        .assert()
+        .success();
+
+    souk_cmd()
+        .args(["update", "app", "--bump-dep", "core", "--patch"])
+        .args(["--marketplace", mp])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn audit_log_records_add_with_version_transition() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha"]);
+    fs::write(
+        tmp.path().join("souk.toml"),
+        r#"audit_log = ".souk/audit.log""#,
+    )
+    .unwrap();
+    let p = tmp.path().join("plugins/beta/.claude-plugin");
+    fs::create_dir_all(&p).unwrap();
+    fs::write(
+        p.join("plugin.json"),
+        r#"{"name":"beta","version":"1.0.0","description":"test"}"#,
+    )
+    .unwrap();
+
+    souk_cmd()
+        .args(["add", "beta", "--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let log = fs::read_to_string(tmp.path().join(".souk/audit.log")).unwrap();
+    let lines: Vec<&str> = log.lines().collect();
+    assert_eq!(lines.len(), 1);
+    let entry: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(entry["command"], "add");
+    assert_eq!(entry["plugins"], serde_json::json!(["beta"]));
+    assert_eq!(entry["old_version"], "0.1.0");
+    assert_eq!(entry["new_version"], "0.1.1");
+    assert_eq!(entry["outcome"], "success");
+    assert!(entry["timestamp"].as_str().unwrap().ends_with('Z'));
+}
+
+#[test]
+fn audit_log_records_fix_with_reformatted_plugins() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha", "beta"]);
+    fs::write(
+        tmp.path().join("souk.toml"),
+        r#"audit_log = ".souk/audit.log""#,
+    )
+    .unwrap();
+    let mp = mp_path.to_str().unwrap();
+    let log_path = tmp.path().join(".souk/audit.log");
+    souk_cmd()
+        .args(["fix", "--marketplace", mp])
+        .assert()
+        .success();
+
+    // A dry run is not logged
+    fs::write(
+        tmp.path().join("plugins/beta/.claude-plugin/plugin.json"),
+        r#"{"name":"beta","version":"1.0.0","description":"test"}"#,
+    )
+    .unwrap();
+    souk_cmd()
+        .args(["fix", "--dry-run", "--marketplace", mp])
+        .assert()
+        .success();
+    assert_eq!(fs::read_to_string(&log_path).unwrap().lines().count(), 1);
+
+    souk_cmd()
+        .args(["fix", "--marketplace", mp])
+        .assert()
+        .success();
+    let log = fs::read_to_string(&log_path).unwrap();
+    let entries: Vec<serde_json::Value> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["command"], "fix");
+    assert_eq!(entries[0]["plugins"], serde_json::json!(["alpha", "beta"]));
+    assert_eq!(entries[1]["plugins"], serde_json::json!(["beta"]));
+    assert_eq!(entries[1]["outcome"], "success");
+}
+
+#[test]
+fn sync_reconciles_marketplace_with_plugin_root() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha", "gone"]);
+    fs::remove_dir_all(tmp.path().join("plugins/gone")).unwrap();
+    let p = tmp.path().join("plugins/beta/.claude-plugin");
+    fs::create_dir_all(&p).unwrap();
+    fs::write(
+        p.join("plugin.json"),
+        r#"{"name":"beta","version":"1.0.0","description":"test","keywords":["lint"]}"#,
+    )
+    .unwrap();
+    let before = fs::read_to_string(&mp_path).unwrap();
+
+    souk_cmd()
+        .args([
+            "sync",
+            "--dry-run",
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ADD beta"))
+        .stdout(predicate::str::contains("REMOVE gone"));
+    assert_eq!(fs::read_to_string(&mp_path).unwrap(), before);
+
+    souk_cmd()
+        .args(["sync", "--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 added, 1 removed"));
+
+    let mp: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&mp_path).unwrap()).unwrap();
+    assert_eq!(mp["version"], "0.1.1");
+    assert_eq!(
+        mp["plugins"],
+        serde_json::json!([
+            {"name": "alpha", "source": "alpha"},
+            {"name": "beta", "source": "beta", "tags": ["lint"]}
+        ])
+    );
+
+    souk_cmd()
+        .args(["sync", "--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("in sync"));
+}
+
+#[test]
+fn sync_retries_a_rolled_back_write() {
+    let tmp = TempDir::new().unwrap();
+    let mp_path = setup_marketplace_with_plugins(&tmp, &["alpha", "gone"]);
+    fs::remove_dir_all(tmp.path().join("plugins/gone")).unwrap();
+    // A duplicate entry fails final validation on every attempt
+    let duplicated = fs::read_to_string(&mp_path).unwrap().replace(
+        r#"{"name":"alpha","source":"alpha"}"#,
+        r#"{"name":"alpha","source":"alpha"},{"name":"alpha","source":"alpha"}"#,
+    );
+    fs::write(&mp_path, &duplicated).unwrap();
+
+    souk_cmd()
+        .args(["sync", "--retry", "2", "--marketplace"])
+        .arg(&mp_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("retrying (1/2)"))
+        .stderr(predicate::str::contains("retrying (2/2)"));
+    assert_eq!(fs::read_to_string(&mp_path).unwrap(), duplicated);
 }
@@ -80,6 +80,28 @@ fn prune_apply_deletes_orphans() {
     assert!(tmp.path().join("plugins").join("kept").exists());
 }
 
+#[test]
+fn prune_only_failures_keeps_the_summary() {
+    let tmp = TempDir::new().unwrap();
+    setup_marketplace(&tmp, &["kept"], &["kept", "orphan1"]);
+    let mp_path = tmp.path().join(".claude-plugin").join("marketplace.json");
+
+    souk_cmd()
+        .args([
+            "prune",
+            "--apply",
+            "--only-failures",
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deleted").not())
+        .stdout(predicate::str::contains(
+            "Successfully pruned 1 orphaned plugin directory(ies).",
+        ));
+}
+
 #[test]
 fn prune_nothing_to_do() {
     let tmp = TempDir::new().unwrap();
@@ -111,3 +133,115 @@ fn prune_json_output() {
         .stdout(predicate::str::contains("\"results\""))
         .stdout(predicate::str::contains("orphan"));
 }
+
+#[cfg(unix)]
+#[test]
+fn prune_follows_symlinks_only_with_dereference() {
+    let tmp = TempDir::new().unwrap();
+    setup_marketplace(&tmp, &["kept"], &["kept"]);
+    let mp_path = tmp.path().join(".claude-plugin").join("marketplace.json");
+    let target = tmp.path().join("scratch");
+    fs::create_dir_all(&target).unwrap();
+    std::os::unix::fs::symlink(&target, tmp.path().join("plugins").join("linked")).unwrap();
+
+    souk_cmd()
+        .args(["prune", "--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No orphaned plugin directories found",
+        ));
+
+    souk_cmd()
+        .args([
+            "prune",
+            "--dereference",
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("linked"))
+        .stdout(predicate::str::contains("1 orphaned"));
+    assert!(target.exists());
+}
+
+#[test]
+fn prune_stale_entries_dry_run_lists_both_kinds() {
+    let tmp = TempDir::new().unwrap();
+    setup_marketplace(&tmp, &["kept", "gone"], &["kept", "orphan1"]);
+    let mp_path = tmp.path().join(".claude-plugin").join("marketplace.json");
+    let before = fs::read_to_string(&mp_path).unwrap();
+
+    souk_cmd()
+        .args([
+            "prune",
+            "--stale-entries",
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would remove stale entry: gone"))
+        .stdout(predicate::str::contains("Would delete: orphan1"));
+
+    assert_eq!(fs::read_to_string(&mp_path).unwrap(), before);
+    assert!(tmp.path().join("plugins").join("orphan1").exists());
+}
+
+#[test]
+fn prune_stale_entries_apply_cleans_both_kinds() {
+    let tmp = TempDir::new().unwrap();
+    setup_marketplace(&tmp, &["kept", "gone"], &["kept", "orphan1"]);
+    let mp_path = tmp.path().join(".claude-plugin").join("marketplace.json");
+
+    souk_cmd()
+        .args([
+            "prune",
+            "--stale-entries",
+            "--apply",
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed stale entry: gone"))
+        .stdout(predicate::str::contains("Deleted: orphan1"));
+
+    let mp: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&mp_path).unwrap()).unwrap();
+    assert_eq!(mp["version"], "0.1.1");
+    assert_eq!(mp["plugins"].as_array().unwrap().len(), 1);
+    assert!(!tmp.path().join("plugins").join("orphan1").exists());
+}
+
+#[test]
+fn prune_stale_entries_retries_a_rolled_back_write() {
+    let tmp = TempDir::new().unwrap();
+    // The duplicate entry fails final validation on every attempt
+    setup_marketplace(&tmp, &["kept", "kept", "gone"], &["kept"]);
+    let mp_path = tmp.path().join(".claude-plugin").join("marketplace.json");
+    let before = fs::read_to_string(&mp_path).unwrap();
+
+    souk_cmd()
+        .args([
+            "prune",
+            "--stale-entries",
+            "--apply",
+            "--retry",
+            "1",
+            "--marketplace",
+            mp_path.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("retrying (1/1)"));
+    assert_eq!(fs::read_to_string(&mp_path).unwrap(), before);
+
+    // --retry only applies to the stale-entry write
+    souk_cmd()
+        .args(["prune", "--retry", "1", "--marketplace"])
+        .arg(&mp_path)
+        .assert()
+        .code(2);
+}
@@ -0,0 +1,350 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use std::path::PathBuf;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("tests")
+        .join("fixtures")
+}
+
+fn souk_cmd() -> assert_cmd::Command {
+    let mut cmd = cargo_bin_cmd!("souk");
+    for var in [
+        "ANTHROPIC_API_KEY",
+        "OPENAI_API_KEY",
+        "AZURE_OPENAI_API_KEY",
+        "GEMINI_API_KEY",
+        "OLLAMA_HOST",
+    ] {
+        cmd.env_remove(var);
+    }
+    cmd
+}
+
+#[test]
+fn print_prompt_needs_no_provider() {
+    let plugin = fixtures_dir()
+        .join("valid-marketplace")
+        .join("plugins")
+        .join("good-plugin");
+
+    souk_cmd()
+        .args([
+            "review",
+            "plugin",
+            plugin.to_str().unwrap(),
+            "--print-prompt",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## plugin.json"))
+        .stdout(predicate::str::contains("Overall Rating (1-10)"));
+}
+
+#[test]
+fn print_prompt_for_marketplace() {
+    let mp = fixtures_dir()
+        .join("valid-marketplace")
+        .join(".claude-plugin")
+        .join("marketplace.json");
+
+    souk_cmd()
+        .args([
+            "review",
+            "marketplace",
+            "--print-prompt",
+            "--marketplace",
+            mp.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## marketplace.json"));
+}
+
+#[test]
+fn rubric_is_appended_to_printed_prompt() {
+    let plugin = fixtures_dir()
+        .join("valid-marketplace")
+        .join("plugins")
+        .join("good-plugin");
+    let tmp = tempfile::TempDir::new().unwrap();
+    let rubric = tmp.path().join("RUBRIC.md");
+    std::fs::write(&rubric, "- Every command documents its flags\n").unwrap();
+
+    souk_cmd()
+        .args([
+            "review",
+            "plugin",
+            plugin.to_str().unwrap(),
+            "--print-prompt",
+        ])
+        .arg("--rubric")
+        .arg(&rubric)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Review Rubric"))
+        .stdout(predicate::str::contains(
+            "- Every command documents its flags",
+        ));
+}
+
+#[test]
+fn missing_rubric_is_an_error() {
+    let plugin = fixtures_dir()
+        .join("valid-marketplace")
+        .join("plugins")
+        .join("good-plugin");
+
+    souk_cmd()
+        .args([
+            "review",
+            "plugin",
+            plugin.to_str().unwrap(),
+            "--print-prompt",
+            "--rubric",
+            "/nonexistent/rubric.md",
+        ])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Cannot read review rubric"));
+}
+
+#[test]
+fn min_rating_must_be_between_one_and_ten() {
+    souk_cmd()
+        .args(["review", "plugin", "any", "--min-rating", "11"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--min-rating"));
+}
+
+#[test]
+fn github_comment_conflicts_with_print_prompt() {
+    souk_cmd()
+        .args([
+            "review",
+            "plugin",
+            "any",
+            "--github-comment",
+            "--print-prompt",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--github-comment"));
+}
+
+/// Create a one-plugin marketplace with the given souk.toml; returns the
+/// marketplace.json path.
+fn marketplace_with_settings(tmp: &tempfile::TempDir, souk_toml: &str) -> PathBuf {
+    let plugin = tmp.path().join("plugins/alpha/.claude-plugin");
+    std::fs::create_dir_all(&plugin).unwrap();
+    std::fs::write(
+        plugin.join("plugin.json"),
+        r#"{"name":"alpha","version":"1.0.0","description":"test"}"#,
+    )
+    .unwrap();
+    let claude = tmp.path().join(".claude-plugin");
+    std::fs::create_dir_all(&claude).unwrap();
+    let mp_path = claude.join("marketplace.json");
+    std::fs::write(
+        &mp_path,
+        r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"alpha","source":"alpha"}]}"#,
+    )
+    .unwrap();
+    std::fs::write(tmp.path().join("souk.toml"), souk_toml).unwrap();
+    mp_path
+}
+
+#[test]
+fn provider_flag_overrides_souk_toml() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mp_path = marketplace_with_settings(&tmp, "provider = \"from-config\"\njobs = 2\n");
+    let mp = mp_path.to_str().unwrap();
+
+    souk_cmd()
+        .args(["review", "plugin", "alpha", "--marketplace", mp])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown provider: from-config"));
+
+    souk_cmd()
+        .args(["review", "plugin", "alpha", "--marketplace", mp])
+        .args(["--provider", "from-flag"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown provider: from-flag"));
+}
+
+#[test]
+fn invalid_souk_toml_defaults_are_reported() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mp_path = marketplace_with_settings(&tmp, "on_conflict = \"merge\"\n");
+
+    souk_cmd()
+        .args(["list", "--marketplace", mp_path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid on_conflict in souk.toml"));
+}
+
+#[test]
+fn fallback_provider_needs_its_own_key() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mp_path = marketplace_with_settings(&tmp, "");
+
+    souk_cmd()
+        .env("ANTHROPIC_API_KEY", "test-key")
+        .args(["review", "plugin", "alpha", "--marketplace"])
+        .arg(&mp_path)
+        .args(["--provider", "anthropic", "--fallback-provider", "openai"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Fallback provider openai:"));
+
+    souk_cmd()
+        .env("ANTHROPIC_API_KEY", "test-key")
+        .args(["review", "plugin", "alpha", "--marketplace"])
+        .arg(&mp_path)
+        .args([
+            "--provider",
+            "anthropic",
+            "--fallback-provider",
+            "anthropic",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("same as the primary provider"));
+}
+
+#[test]
+fn marketplace_jobs_requires_recursive() {
+    souk_cmd()
+        .args(["review", "marketplace", "--jobs", "2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--recursive"));
+}
+
+#[test]
+fn check_auth_fails_before_reviewing() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mp_path = marketplace_with_settings(&tmp, "");
+
+    // Nothing listens on port 1, so the preflight fails without a prompt
+    // ever being sent.
+    souk_cmd()
+        .env("OLLAMA_HOST", "127.0.0.1:1")
+        .args(["review", "plugin", "alpha", "--marketplace"])
+        .arg(&mp_path)
+        .args(["--provider", "ollama", "--check-auth"])
+        .assert()
+        .code(3)
+        .stdout(predicate::str::contains("Reviewing plugin").not())
+        .stderr(predicate::str::contains("ollama auth check failed"));
+}
+
+/// A fake Ollama server on a free local port that answers every generate
+/// request with a fixed review; returns its address and the `model` of each
+/// request received.
+fn fake_ollama() -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let models = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = models.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            seen.lock()
+                .unwrap()
+                .push(request["model"].as_str().unwrap().to_string());
+            let response = r#"{"response":"Fine. Rating: 8/10"}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            )
+            .unwrap();
+        }
+    });
+    (addr, models)
+}
+
+#[test]
+fn recursive_review_uses_plugin_model_and_review_cache() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mp_path = marketplace_with_settings(&tmp, "");
+    let cache = tmp.path().join("cache");
+    let (addr, models) = fake_ollama();
+
+    for _ in 0..2 {
+        souk_cmd()
+            .env("OLLAMA_HOST", &addr)
+            .args([
+                "review",
+                "marketplace",
+                "--recursive",
+                "--provider",
+                "ollama",
+            ])
+            .args(["--model-map", "plugin=small,marketplace=big"])
+            .arg("--cache-dir")
+            .arg(&cache)
+            .arg("--marketplace")
+            .arg(&mp_path)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("alpha: rated 8/10"));
+    }
+
+    // The second run reviews the marketplace again but reuses alpha's review
+    assert_eq!(*models.lock().unwrap(), ["big", "small", "big"]);
+}
+
+#[test]
+fn plugin_review_json_reports_request_parameters() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mp_path = marketplace_with_settings(&tmp, "");
+    let (addr, _) = fake_ollama();
+
+    souk_cmd()
+        .env("OLLAMA_HOST", &addr)
+        .args([
+            "--json",
+            "review",
+            "plugin",
+            "alpha",
+            "--provider",
+            "ollama",
+        ])
+        .arg("--marketplace")
+        .arg(&mp_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Request parameters: max_tokens default, temperature default, prompt hash",
+        ));
+}
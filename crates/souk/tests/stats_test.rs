@@ -0,0 +1,81 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn souk_cmd() -> assert_cmd::Command {
+    cargo_bin_cmd!("souk")
+}
+
+fn setup(tmp: &TempDir) {
+    let claude_dir = tmp.path().join(".claude-plugin");
+    fs::create_dir_all(&claude_dir).unwrap();
+    fs::write(
+        claude_dir.join("marketplace.json"),
+        r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[
+            {"name":"alpha","source":"alpha","tags":["lint","docs"]},
+            {"name":"beta","source":"beta","tags":["lint"]}],
+            "keywords":["devtools"],"categories":["productivity","testing"]}"#,
+    )
+    .unwrap();
+    for name in ["alpha", "beta"] {
+        let p = tmp.path().join("plugins").join(name).join(".claude-plugin");
+        fs::create_dir_all(&p).unwrap();
+        fs::write(
+            p.join("plugin.json"),
+            format!(r#"{{"name":"{name}","version":"1.0.0","description":"test"}}"#),
+        )
+        .unwrap();
+    }
+    let skill = tmp.path().join("plugins/alpha/skills/hello");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: hello\ndescription: Says hello\n---\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn stats_json_is_a_single_object() {
+    let tmp = TempDir::new().unwrap();
+    setup(&tmp);
+
+    let output = souk_cmd()
+        .current_dir(tmp.path())
+        .args(["stats", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(json["plugins"], 2);
+    assert_eq!(json["internal"], 2);
+    assert_eq!(json["external"], 0);
+    assert_eq!(json["skills"], 1);
+    assert_eq!(json["tags"]["lint"], 2);
+    assert_eq!(json["tags"]["docs"], 1);
+    assert_eq!(json["keywords"], serde_json::json!(["devtools"]));
+    assert_eq!(
+        json["categories"],
+        serde_json::json!(["productivity", "testing"])
+    );
+}
+
+#[test]
+fn stats_lists_most_used_tags_first() {
+    let tmp = TempDir::new().unwrap();
+    setup(&tmp);
+
+    souk_cmd()
+        .current_dir(tmp.path())
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 (2 internal, 0 external)"))
+        .stdout(predicate::str::contains("Keywords:      devtools"))
+        .stdout(predicate::str::contains(
+            "Categories:    productivity, testing",
+        ))
+        .stdout(predicate::str::is_match(r"(?s)lint\s+2\n\s+docs\s+1").unwrap());
+}
@@ -77,7 +77,10 @@ fn validate_plugin_json_output() {
     assert!(output.status.success());
     let stdout = String::from_utf8(output.stdout).unwrap();
     let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
-    assert!(parsed["results"].is_array());
+    assert_eq!(parsed["ok"], true);
+    assert_eq!(parsed["error_count"], 0);
+    assert_eq!(parsed["warning_count"], 0);
+    assert_eq!(parsed["diagnostics"], serde_json::json!([]));
 }
 
 #[test]
@@ -87,3 +90,627 @@ fn validate_nonexistent_plugin() {
         .assert()
         .failure();
 }
+
+#[test]
+fn validate_marketplace_json_grouped_by_plugin() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let claude = tmp.path().join(".claude-plugin");
+    std::fs::create_dir_all(&claude).unwrap();
+    let bad = tmp
+        .path()
+        .join("plugins")
+        .join("bad")
+        .join(".claude-plugin");
+    std::fs::create_dir_all(&bad).unwrap();
+    std::fs::write(bad.join("plugin.json"), "not json").unwrap();
+    let mp = claude.join("marketplace.json");
+    std::fs::write(
+        &mp,
+        r#"{"version":"nope","pluginRoot":"./plugins","plugins":[{"name":"bad","source":"bad"}]}"#,
+    )
+    .unwrap();
+
+    let output = souk_cmd()
+        .args([
+            "validate",
+            "marketplace",
+            "--group-by",
+            "plugin",
+            "--json",
+            "--marketplace",
+            mp.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let marketplace = parsed["marketplace"].as_array().unwrap();
+    assert!(marketplace.iter().any(|d| d["message"]
+        .as_str()
+        .unwrap()
+        .contains("marketplace version")));
+    let bad_plugin = parsed["plugins"]["bad"].as_array().unwrap();
+    assert!(bad_plugin
+        .iter()
+        .any(|d| d["message"].as_str().unwrap().contains("Invalid JSON")));
+    assert!(parsed.get("results").is_none());
+}
+
+#[test]
+fn validate_plugin_json_summary_counts() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mut paths = Vec::new();
+    for (name, manifest) in [
+        (
+            "alpha",
+            r#"{"name":"alpha","version":"1.0.0","description":"ok"}"#,
+        ),
+        (
+            "beta",
+            r#"{"name":"beta","version":"1.0.0","description":"ok"}"#,
+        ),
+        ("gamma", r#"{"name":"gamma","version":"not-semver"}"#),
+    ] {
+        let dir = tmp.path().join(name);
+        std::fs::create_dir_all(dir.join(".claude-plugin")).unwrap();
+        std::fs::write(dir.join(".claude-plugin").join("plugin.json"), manifest).unwrap();
+        paths.push(dir.to_str().unwrap().to_string());
+    }
+
+    let mut args = vec!["validate".to_string(), "plugin".to_string()];
+    args.extend(paths);
+    args.push("--json".to_string());
+    let output = souk_cmd().args(&args).output().unwrap();
+
+    assert!(!output.status.success());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(parsed["ok"], false);
+    assert_eq!(parsed["error_count"], 2);
+    let diagnostics = parsed["diagnostics"].as_array().unwrap();
+    assert!(diagnostics.iter().all(|d| d["plugin"] == "gamma"));
+    let summary = &parsed["summary"];
+    assert_eq!(summary["total"], 3);
+    assert_eq!(summary["passed"], 2);
+    assert_eq!(summary["failed"], 1);
+    let gamma = summary["plugins"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["name"] == "gamma")
+        .unwrap();
+    assert_eq!(gamma["passed"], false);
+    assert_eq!(gamma["errorCount"], 2);
+    assert_eq!(gamma["warningCount"], 0);
+}
+
+#[test]
+fn validate_plugin_strict_fails_on_warnings() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let dir = tmp.path().join("future");
+    std::fs::create_dir_all(dir.join(".claude-plugin")).unwrap();
+    std::fs::write(
+        dir.join(".claude-plugin").join("plugin.json"),
+        r#"{"name":"future","version":"1.0.0","description":"ok","schemaVersion":999}"#,
+    )
+    .unwrap();
+    let path = dir.to_str().unwrap();
+
+    souk_cmd()
+        .args(["validate", "plugin", path])
+        .assert()
+        .success();
+    souk_cmd()
+        .args(["validate", "plugin", path, "--strict"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn validate_plugin_only_failures_hides_passing_plugins() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mut paths = Vec::new();
+    for (name, manifest) in [
+        (
+            "alpha",
+            r#"{"name":"alpha","version":"1.0.0","description":"ok"}"#,
+        ),
+        ("beta", r#"{"name":"beta","version":"not-semver"}"#),
+    ] {
+        let dir = tmp.path().join(name);
+        std::fs::create_dir_all(dir.join(".claude-plugin")).unwrap();
+        std::fs::write(dir.join(".claude-plugin").join("plugin.json"), manifest).unwrap();
+        paths.push(dir.to_str().unwrap().to_string());
+    }
+
+    souk_cmd()
+        .args(["validate", "plugin", &paths[0], "--only-failures"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Plugin validated").not())
+        .stdout(predicate::str::contains("1 plugin(s): 1 passed, 0 failed"));
+
+    let output = souk_cmd()
+        .args(["validate", "plugin", "--only-failures", "--json"])
+        .args(&paths)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let diagnostics = parsed["diagnostics"].as_array().unwrap();
+    assert!(diagnostics.iter().any(|d| d["severity"] == "error"));
+    assert_eq!(parsed["summary"]["passed"], 1);
+    assert_eq!(parsed["summary"]["failed"], 1);
+}
+
+#[test]
+fn chdir_discovers_marketplace_in_another_directory() {
+    let elsewhere = tempfile::TempDir::new().unwrap();
+    let project = fixtures_dir().join("valid-marketplace");
+
+    souk_cmd()
+        .current_dir(elsewhere.path())
+        .args(["validate", "marketplace", "--chdir"])
+        .arg(&project)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Marketplace validation completed successfully",
+        ));
+
+    // Relative plugin paths resolve against the --chdir directory too.
+    souk_cmd()
+        .current_dir(elsewhere.path())
+        .arg("-C")
+        .arg(&project)
+        .args(["validate", "plugin", "plugins/good-plugin"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Plugin validated: good-plugin"));
+
+    souk_cmd()
+        .current_dir(elsewhere.path())
+        .args(["validate", "marketplace"])
+        .assert()
+        .failure();
+
+    souk_cmd()
+        .args(["validate", "marketplace", "--chdir", "no/such/dir"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot change to directory"));
+}
+
+#[test]
+fn validate_marketplace_sarif_locates_diagnostics() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let claude = tmp.path().join(".claude-plugin");
+    std::fs::create_dir_all(&claude).unwrap();
+    let bad = tmp
+        .path()
+        .join("plugins")
+        .join("bad")
+        .join(".claude-plugin");
+    std::fs::create_dir_all(&bad).unwrap();
+    std::fs::write(bad.join("plugin.json"), "not json").unwrap();
+    let mp = claude.join("marketplace.json");
+    std::fs::write(
+        &mp,
+        r#"{"version":"nope","pluginRoot":"./plugins","plugins":[{"name":"bad","source":"bad"}]}"#,
+    )
+    .unwrap();
+
+    let output = souk_cmd()
+        .args(["validate", "marketplace", "--format", "sarif"])
+        .args(["--marketplace", mp.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    let uri = |r: &serde_json::Value| {
+        r["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+
+    let version = results
+        .iter()
+        .find(|r| r["ruleId"] == "souk/invalid-marketplace-version")
+        .expect("marketplace version result");
+    assert_eq!(version["level"], "error");
+    assert_eq!(uri(version), ".claude-plugin/marketplace.json");
+
+    let invalid_json = results
+        .iter()
+        .find(|r| r["ruleId"] == "souk/invalid-json-in-plugin-json")
+        .expect("plugin.json result");
+    assert_eq!(uri(invalid_json), "plugins/bad/.claude-plugin/plugin.json");
+}
+
+#[test]
+fn validate_plugin_sarif_uses_plugin_json_for_pathless_diagnostics() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let claude = tmp.path().join("my-plugin").join(".claude-plugin");
+    std::fs::create_dir_all(&claude).unwrap();
+    std::fs::write(claude.join("plugin.json"), r#"{"name":"my-plugin"}"#).unwrap();
+
+    let output = souk_cmd()
+        .current_dir(tmp.path())
+        .args(["validate", "plugin", "my-plugin", "--format", "sarif"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert!(!results.is_empty());
+    for result in results {
+        assert_eq!(result["properties"]["plugin"], "my-plugin");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "my-plugin/.claude-plugin/plugin.json"
+        );
+    }
+}
+
+#[test]
+fn validate_marketplace_junit_reports_each_plugin() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let claude = tmp.path().join(".claude-plugin");
+    std::fs::create_dir_all(&claude).unwrap();
+    for (name, manifest) in [
+        ("bad", "not json".to_string()),
+        (
+            "good",
+            r#"{"name":"good","version":"1.0.0","description":"fine"}"#.to_string(),
+        ),
+    ] {
+        let dir = tmp.path().join("plugins").join(name).join(".claude-plugin");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("plugin.json"), manifest).unwrap();
+    }
+    let mp = claude.join("marketplace.json");
+    std::fs::write(
+        &mp,
+        r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"bad","source":"bad"},{"name":"good","source":"good"}]}"#,
+    )
+    .unwrap();
+
+    let output = souk_cmd()
+        .args(["validate", "marketplace", "--format", "junit"])
+        .args(["--marketplace", mp.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let xml = String::from_utf8(output.stdout).unwrap();
+    assert!(xml.starts_with("<?xml"), "{xml}");
+    assert!(xml.contains(r#"<testsuites name="souk" tests="3" failures="1">"#));
+    assert!(xml.contains(r#"<testcase classname="marketplace" name="marketplace.json"/>"#));
+    assert!(xml.contains(r#"<testcase classname="plugins" name="good"/>"#));
+    assert!(xml.contains("(plugins/bad/.claude-plugin/plugin.json)"));
+}
+
+#[test]
+fn validate_junit_skill_granularity_reports_each_skill() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let plugin = tmp.path().join("tools");
+    std::fs::create_dir_all(plugin.join(".claude-plugin")).unwrap();
+    std::fs::write(
+        plugin.join(".claude-plugin/plugin.json"),
+        r#"{"name":"tools","version":"1.0.0","description":"fine"}"#,
+    )
+    .unwrap();
+    for (skill, content) in [
+        (
+            "format",
+            "---\nname: format\ndescription: Formats code\n---\n",
+        ),
+        ("lint", "---\nname: lint\n---\n"),
+    ] {
+        let dir = plugin.join("skills").join(skill);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("SKILL.md"), content).unwrap();
+    }
+
+    let output = souk_cmd()
+        .current_dir(tmp.path())
+        .args(["validate", "plugin", "tools", "--format", "junit"])
+        .args(["--junit-granularity", "skill"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let xml = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        xml.contains(r#"<testsuite name="skills" tests="2" failures="1">"#),
+        "{xml}"
+    );
+    assert!(xml.contains(r#"<testcase classname="skills" name="tools/format"/>"#));
+    assert!(xml.contains(r#"<testcase classname="skills" name="tools/lint">"#));
+    assert!(xml.contains("Missing description in frontmatter (tools/skills/lint/SKILL.md)"));
+
+    // The default granularity has no skill testcases
+    let output = souk_cmd()
+        .current_dir(tmp.path())
+        .args(["validate", "plugin", "tools", "--format", "junit"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(!String::from_utf8(output.stdout).unwrap().contains("skills"));
+}
+
+#[test]
+fn validate_marketplace_json_has_stable_layout() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let claude = tmp.path().join(".claude-plugin");
+    std::fs::create_dir_all(&claude).unwrap();
+    let bad = tmp
+        .path()
+        .join("plugins")
+        .join("bad")
+        .join(".claude-plugin");
+    std::fs::create_dir_all(&bad).unwrap();
+    std::fs::write(bad.join("plugin.json"), "not json").unwrap();
+    let mp = claude.join("marketplace.json");
+    std::fs::write(
+        &mp,
+        r#"{"version":"nope","pluginRoot":"./plugins","plugins":[{"name":"bad","source":"bad"}]}"#,
+    )
+    .unwrap();
+
+    let output = souk_cmd()
+        .args(["validate", "marketplace", "--json", "--marketplace"])
+        .arg(&mp)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(parsed["ok"], false);
+    let diagnostics = parsed["diagnostics"].as_array().unwrap();
+    assert_eq!(parsed["error_count"], diagnostics.len());
+    assert_eq!(parsed["warning_count"], 0);
+    for diagnostic in diagnostics {
+        for key in ["severity", "message", "path", "field"] {
+            assert!(diagnostic.get(key).is_some(), "{key} missing: {diagnostic}");
+        }
+    }
+    let invalid_json = diagnostics
+        .iter()
+        .find(|d| d["plugin"] == "bad")
+        .expect("plugin diagnostic");
+    assert!(invalid_json["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("plugin.json"));
+    assert!(diagnostics.iter().any(|d| d["message"]
+        .as_str()
+        .unwrap()
+        .contains("marketplace version")));
+}
+
+#[test]
+fn validate_check_formatting_flags_then_fix_canonicalizes() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let claude = tmp.path().join(".claude-plugin");
+    std::fs::create_dir_all(&claude).unwrap();
+    let plugin = tmp
+        .path()
+        .join("plugins")
+        .join("good")
+        .join(".claude-plugin");
+    std::fs::create_dir_all(&plugin).unwrap();
+    let canonical_plugin =
+        "{\n  \"name\": \"good\",\n  \"version\": \"1.0.0\",\n  \"description\": \"ok\"\n}\n";
+    std::fs::write(plugin.join("plugin.json"), canonical_plugin).unwrap();
+    let mp = claude.join("marketplace.json");
+    std::fs::write(
+        &mp,
+        r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"good","source":"good"}]}"#,
+    )
+    .unwrap();
+
+    // Off by default.
+    souk_cmd()
+        .args(["validate", "marketplace", "--strict", "--marketplace"])
+        .arg(&mp)
+        .assert()
+        .success();
+
+    souk_cmd()
+        .args([
+            "validate",
+            "marketplace",
+            "--check-formatting",
+            "--strict",
+            "--marketplace",
+        ])
+        .arg(&mp)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("souk fix"));
+
+    souk_cmd()
+        .args(["fix", "--marketplace"])
+        .arg(&mp)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Reformatted: .claude-plugin/marketplace.json",
+        ));
+
+    let content = std::fs::read_to_string(&mp).unwrap();
+    assert!(content.starts_with("{\n  \"version\": \"0.1.0\",\n"));
+    assert!(content.ends_with("}\n"));
+    assert_eq!(
+        std::fs::read_to_string(plugin.join("plugin.json")).unwrap(),
+        canonical_plugin
+    );
+
+    souk_cmd()
+        .args([
+            "validate",
+            "marketplace",
+            "--check-formatting",
+            "--strict",
+            "--marketplace",
+        ])
+        .arg(&mp)
+        .assert()
+        .success();
+}
+
+/// Write a one-plugin marketplace under `dir`; `plugin_json` is the
+/// plugin's manifest.
+fn write_marketplace(dir: &std::path::Path, plugin_json: &str) {
+    let plugin = dir.join("plugins").join("good").join(".claude-plugin");
+    std::fs::create_dir_all(&plugin).unwrap();
+    std::fs::write(plugin.join("plugin.json"), plugin_json).unwrap();
+    let claude = dir.join(".claude-plugin");
+    std::fs::create_dir_all(&claude).unwrap();
+    std::fs::write(
+        claude.join("marketplace.json"),
+        r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[{"name":"good","source":"good"}]}"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn validate_marketplace_recursive_summarises_each_marketplace() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    write_marketplace(
+        &tmp.path().join("packages/a"),
+        r#"{"name":"good","version":"1.0.0","description":"ok"}"#,
+    );
+    write_marketplace(&tmp.path().join("packages/b"), "not json");
+    write_marketplace(
+        &tmp.path().join("node_modules/dep"),
+        r#"{"name":"good","version":"1.0.0","description":"ok"}"#,
+    );
+
+    souk_cmd()
+        .arg("-C")
+        .arg(tmp.path())
+        .args(["validate", "marketplace", "--recursive"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Marketplace validated: packages/a/.claude-plugin/marketplace.json",
+        ))
+        .stdout(predicate::str::contains(
+            "2 marketplace(s): 1 passed, 1 failed",
+        ))
+        .stdout(predicate::str::contains("node_modules").not());
+
+    let output = souk_cmd()
+        .arg("-C")
+        .arg(tmp.path())
+        .args(["--json", "validate", "marketplace", "--recursive"])
+        .output()
+        .unwrap();
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(doc["ok"], false);
+    let marketplaces = doc["marketplaces"].as_array().unwrap();
+    assert_eq!(marketplaces.len(), 2);
+    assert_eq!(marketplaces[0]["passed"], true);
+    assert_eq!(marketplaces[1]["passed"], false);
+}
+
+#[test]
+fn validate_marketplace_summary_counts_orphans_and_missing_entries() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    write_marketplace(
+        tmp.path(),
+        r#"{"name":"good","version":"1.0.0","description":"ok"}"#,
+    );
+    std::fs::create_dir_all(tmp.path().join("plugins/orphan")).unwrap();
+    let mp = tmp.path().join(".claude-plugin/marketplace.json");
+    std::fs::write(
+        &mp,
+        r#"{"version":"0.1.0","pluginRoot":"./plugins","plugins":[
+            {"name":"good","source":"good"},{"name":"ghost","source":"ghost"}]}"#,
+    )
+    .unwrap();
+
+    souk_cmd()
+        .args(["validate", "marketplace", "--marketplace"])
+        .arg(&mp)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "2 entries, 2 plugin directories on disk, 1 orphaned, 1 missing",
+        ));
+
+    let output = souk_cmd()
+        .args(["validate", "marketplace", "--json", "--marketplace"])
+        .arg(&mp)
+        .output()
+        .unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        parsed["counts"],
+        serde_json::json!({"entries": 2, "directories": 2, "orphaned": 1, "missing": 1})
+    );
+}
+
+#[test]
+fn validate_marketplace_max_scan_entries_aborts_the_scan() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    write_marketplace(
+        tmp.path(),
+        r#"{"name":"good","version":"1.0.0","description":"ok"}"#,
+    );
+    std::fs::create_dir_all(tmp.path().join("plugins/orphan")).unwrap();
+    let mp = tmp.path().join(".claude-plugin/marketplace.json");
+
+    souk_cmd()
+        .args(["validate", "marketplace", "--max-scan-entries", "1"])
+        .arg("--marketplace")
+        .arg(&mp)
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Directory scan aborted: more than 1 entries",
+        ));
+
+    souk_cmd()
+        .args(["validate", "marketplace", "--max-scan-entries", "2"])
+        .arg("--marketplace")
+        .arg(&mp)
+        .assert()
+        .success();
+}
+
+#[test]
+fn validate_exit_codes_distinguish_findings_from_usage_and_io_errors() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    write_marketplace(tmp.path(), r#"{"name":"good","version":"1.0.0"}"#);
+    let mp = tmp.path().join(".claude-plugin/marketplace.json");
+
+    // Plugin missing its description: a validation finding
+    souk_cmd()
+        .args(["validate", "marketplace", "--marketplace"])
+        .arg(&mp)
+        .assert()
+        .code(1);
+
+    // No marketplace above an empty directory: a usage error
+    let empty = tempfile::TempDir::new().unwrap();
+    souk_cmd()
+        .current_dir(empty.path())
+        .args(["validate", "marketplace"])
+        .assert()
+        .code(2);
+
+    // An unreadable marketplace.json: an I/O error
+    souk_cmd()
+        .args(["validate", "marketplace", "--marketplace"])
+        .arg(tmp.path().join("missing.json"))
+        .assert()
+        .code(3);
+}
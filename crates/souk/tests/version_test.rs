@@ -0,0 +1,64 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+
+fn version_json() -> serde_json::Value {
+    let output = cargo_bin_cmd!("souk")
+        .args(["version", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn names(value: &serde_json::Value) -> Vec<&str> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn version_json_lists_hook_managers_and_ci_providers() {
+    let json = version_json();
+
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        names(&json["supported_hook_managers"]),
+        [
+            "native",
+            "lefthook",
+            "husky",
+            "overcommit",
+            "hk",
+            "simple-git-hooks"
+        ]
+    );
+    assert_eq!(
+        names(&json["supported_ci_providers"]),
+        [
+            "github",
+            "blacksmith",
+            "northflank",
+            "circleci",
+            "gitlab",
+            "buildkite"
+        ]
+    );
+    assert!(names(&json["supported_providers"]).contains(&"anthropic"));
+    assert!(json["features"].is_array());
+    assert!(json["commit"].is_string() || json["commit"].is_null());
+}
+
+#[test]
+fn version_prints_the_package_version() {
+    cargo_bin_cmd!("souk")
+        .arg("version")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(format!(
+            "souk {}",
+            env!("CARGO_PKG_VERSION")
+        )));
+}